@@ -6,18 +6,21 @@ use crate::utils::{FileUtils, LogUtils};
 use anyhow::Result;
 use clap::Parser;
 use std::path::PathBuf;
-use tailwind_rs_core::TailwindBuilder;
+use tailwind_rs_core::{CargoContent, ContentConfig, TailwindBuilder, TailwindConfig};
+use tailwind_rs_scanner::{ContentScanner, DynamicClass, ScanConfig};
 
 /// Build Tailwind CSS from Rust source files
 #[derive(Parser)]
 pub struct BuildCommand {
-    /// Source directory to scan for Rust files
-    #[arg(short, long, default_value = "src")]
-    pub source: PathBuf,
+    /// Source directory to scan for Rust files [default: the configuration's
+    /// `build.input`, relative to its directory, or `src`]
+    #[arg(short, long)]
+    pub source: Option<PathBuf>,
 
-    /// Output CSS file path
-    #[arg(short, long, default_value = "dist/styles.css")]
-    pub output: PathBuf,
+    /// Output CSS file path [default: the configuration's `build.output`,
+    /// or `dist/styles.css`]
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
 
     /// Configuration file path
     #[arg(short, long, default_value = "tailwind-rs.toml")]
@@ -38,6 +41,20 @@ pub struct BuildCommand {
     /// Verbose output
     #[arg(short, long)]
     pub verbose: bool,
+
+    /// Also scan the `src` of every workspace member, found with
+    /// `cargo metadata --offline`
+    #[arg(long)]
+    pub workspace: bool,
+
+    /// Also scan a dependency crate, such as a component library
+    #[arg(long = "dependency", value_name = "CRATE")]
+    pub dependencies: Vec<String>,
+
+    /// Fail when sources build class names at runtime, such as
+    /// `format!("bg-{}-500", color)`, instead of warning about them
+    #[arg(long)]
+    pub deny_dynamic_classes: bool,
 }
 
 impl BuildCommand {
@@ -45,34 +62,65 @@ impl BuildCommand {
     pub async fn execute(&self) -> Result<()> {
         LogUtils::info("Starting Tailwind CSS build...");
 
+        let config = FileUtils::load_config(self.config.as_deref())?;
+        let inputs = FileUtils::inputs(&config, self.config.as_deref(), self.source.as_deref())?;
+        let output = self
+            .output
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(&config.build.output));
+
         if self.verbose {
-            LogUtils::info(&format!("Source directory: {:?}", self.source));
-            LogUtils::info(&format!("Output file: {:?}", self.output));
+            let paths: Vec<_> = inputs.iter().map(|input| &input.path).collect();
+            LogUtils::info(&format!("Source directories: {:?}", paths));
+            LogUtils::info(&format!("Output file: {:?}", output));
         }
 
-        // Validate source directory exists
-        if !FileUtils::file_exists(&self.source) {
-            LogUtils::error(&format!(
-                "Source directory does not exist: {:?}",
-                self.source
-            ));
-            return Err(anyhow::anyhow!("Source directory not found"));
+        // Validate source directories exist
+        for input in &inputs {
+            if !FileUtils::file_exists(&input.path) {
+                LogUtils::error(&format!(
+                    "Source directory does not exist: {:?}",
+                    input.path
+                ));
+                return Err(anyhow::anyhow!("Source directory not found"));
+            }
         }
 
         // Ensure output directory exists
-        if let Some(output_dir) = self.output.parent() {
+        if let Some(output_dir) = output.parent() {
             FileUtils::ensure_dir(output_dir)?;
         }
 
-        // Find Rust files
-        let rust_files = FileUtils::find_rust_files(&self.source)?;
+        // Find the input files named by the content sources
+        let sources = config
+            .content
+            .sources(FileUtils::config_root(self.config.as_deref()))?;
+        let mut files = Vec::new();
+        for input in &inputs {
+            files.extend(input.files(&sources)?);
+        }
+        files.sort();
+        files.dedup();
         if self.verbose {
-            LogUtils::info(&format!("Found {} Rust files", rust_files.len()));
+            LogUtils::info(&format!("Found {} content files", files.len()));
+        }
+
+        // Classes completed at runtime never reach the generated CSS
+        let dynamic_classes = self.find_dynamic_classes(&config, &files).await?;
+        for class in &dynamic_classes {
+            LogUtils::warning(&class.to_string());
+        }
+        if self.deny_dynamic_classes && !dynamic_classes.is_empty() {
+            return Err(anyhow::anyhow!(
+                "Found {} dynamically constructed classes",
+                dynamic_classes.len()
+            ));
         }
 
-        let mut builder = TailwindBuilder::new()
-            .scan_source(&self.source)
-            .output_css(&self.output);
+        let mut builder = TailwindBuilder::new().output_css(&output);
+        for file in &files {
+            builder = builder.scan_source(file);
+        }
 
         if let Some(config_path) = &self.config {
             if FileUtils::file_exists(config_path) {
@@ -85,6 +133,13 @@ impl BuildCommand {
             }
         }
 
+        if self.workspace || !self.dependencies.is_empty() {
+            builder = builder.content(self.cargo_content(&config.content));
+            if self.verbose {
+                LogUtils::info("Scanning crates found with cargo metadata");
+            }
+        }
+
         if self.tree_shake {
             builder = builder.enable_tree_shaking();
             if self.verbose {
@@ -107,29 +162,72 @@ impl BuildCommand {
         }
 
         // Perform the build
-        let start_time = std::time::Instant::now();
-        builder.build()?;
-        let duration = start_time.elapsed();
-
-        // Get output file size
-        let output_size = if FileUtils::file_exists(&self.output) {
-            std::fs::metadata(&self.output)?.len()
-        } else {
-            0
-        };
+        let report = builder.build()?;
 
         LogUtils::success(&format!(
-            "Build completed successfully in {:.2}s ({} bytes)",
-            duration.as_secs_f64(),
-            output_size
+            "Build completed successfully in {:.2}s ({} rules, {} bytes)",
+            report.duration.as_secs_f64(),
+            report.rule_count,
+            report.css_size
         ));
 
+        if let Some(tree_shaking) = &report.tree_shaking {
+            LogUtils::info(&format!(
+                "Tree-shaking removed {} rules ({:.1}% smaller)",
+                tree_shaking.removed_classes.len(),
+                tree_shaking.reduction_percentage
+            ));
+        }
+
+        if let Some(source_map_path) = &report.source_map_path {
+            LogUtils::info(&format!("Source map written to: {:?}", source_map_path));
+        }
+
         if self.verbose {
-            LogUtils::info(&format!("Output written to: {:?}", self.output));
+            LogUtils::info(&format!(
+                "Found {} classes in {} files",
+                report.classes.len(),
+                report.files_scanned
+            ));
+            LogUtils::info(&format!("Output written to: {:?}", report.output_path));
         }
 
         Ok(())
     }
+
+    /// The configured content sources, with the crates selected on the
+    /// command line added to their `cargo` section
+    fn cargo_content(&self, content: &ContentConfig) -> ContentConfig {
+        let mut content = content.clone();
+        let cargo = content.cargo.get_or_insert_with(CargoContent::default);
+        cargo.workspace |= self.workspace;
+        for dependency in &self.dependencies {
+            if !cargo.dependencies.contains(dependency) {
+                cargo.dependencies.push(dependency.clone());
+            }
+        }
+        content
+    }
+
+    /// Find the class names the content files build at runtime
+    ///
+    /// JavaScript and TypeScript files are only checked with the
+    /// `tree-sitter` feature.
+    async fn find_dynamic_classes(
+        &self,
+        config: &TailwindConfig,
+        files: &[PathBuf],
+    ) -> Result<Vec<DynamicClass>> {
+        let scanner = ContentScanner::new(ScanConfig {
+            tailwind_config: Some(config.clone()),
+            ..ScanConfig::default()
+        })?;
+        let paths: Vec<String> = files
+            .iter()
+            .map(|file| file.to_string_lossy().into_owned())
+            .collect();
+        Ok(scanner.find_dynamic_classes(&paths).await?)
+    }
 }
 
 #[cfg(test)]
@@ -166,13 +264,16 @@ mod tests {
         .unwrap();
 
         let cmd = BuildCommand {
-            source: source_dir,
-            output: output_file.clone(),
+            source: Some(source_dir),
+            output: Some(output_file.clone()),
             config: None,
             tree_shake: true,
             minify: true,
             source_maps: false,
             verbose: false,
+            workspace: false,
+            dependencies: Vec::new(),
+            deny_dynamic_classes: false,
         };
 
         // This should not panic, even if the build fails
@@ -182,6 +283,80 @@ mod tests {
         assert!(result.is_ok() || result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_deny_dynamic_classes() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("src");
+        fs::create_dir_all(&source_dir).unwrap();
+        fs::write(
+            source_dir.join("badge.rs"),
+            "fn badge(tone: &str) -> String {\n    format!(\"bg-{}-500\", tone)\n}\n",
+        )
+        .unwrap();
+
+        let cmd = BuildCommand {
+            source: Some(source_dir.clone()),
+            output: Some(temp_dir.path().join("styles.css")),
+            config: None,
+            tree_shake: false,
+            minify: false,
+            source_maps: false,
+            verbose: false,
+            workspace: false,
+            dependencies: Vec::new(),
+            deny_dynamic_classes: true,
+        };
+
+        let files = vec![source_dir.join("badge.rs")];
+        let dynamic = cmd
+            .find_dynamic_classes(&TailwindConfig::default(), &files)
+            .await
+            .unwrap();
+        assert_eq!(dynamic.len(), 1);
+        assert_eq!(dynamic[0].template, "bg-{}-500");
+
+        let error = cmd.execute().await.unwrap_err();
+        assert!(error.to_string().contains("dynamically constructed"));
+    }
+
+    #[tokio::test]
+    async fn test_build_reads_config_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let source_dir = temp_dir.path().join("src");
+        let output_file = temp_dir.path().join("dist/styles.css");
+        fs::create_dir_all(&source_dir).unwrap();
+        fs::write(source_dir.join("app.rs"), r#"const APP: &str = "p-4";"#).unwrap();
+
+        // The layout `config init` used to write
+        let config_file = temp_dir.path().join("tailwind-rs.toml");
+        fs::write(
+            &config_file,
+            format!(
+                "[build]\nsource = {:?}\noutput = {:?}\ntree_shake = true\n\n[watch]\ndebounce = 300\npatterns = [\"**/*.rs\"]\n",
+                source_dir.to_string_lossy(),
+                output_file.to_string_lossy()
+            ),
+        )
+        .unwrap();
+
+        let cmd = BuildCommand {
+            source: None,
+            output: None,
+            config: Some(config_file),
+            tree_shake: false,
+            minify: false,
+            source_maps: false,
+            verbose: false,
+            workspace: false,
+            dependencies: Vec::new(),
+            deny_dynamic_classes: false,
+        };
+        cmd.execute().await.unwrap();
+
+        let css = fs::read_to_string(&output_file).unwrap();
+        assert!(css.contains(".p-4"));
+    }
+
     #[test]
     fn test_build_command_parsing() {
         use crate::Cli;
@@ -196,17 +371,29 @@ mod tests {
             "--tree-shake",
             "--minify",
             "--verbose",
+            "--workspace",
+            "--dependency",
+            "tailwind-rs-leptos",
+            "--deny-dynamic-classes",
         ];
 
         let cli = Cli::try_parse_from(args).unwrap();
 
         match cli.command {
             crate::Commands::Build(cmd) => {
-                assert_eq!(cmd.source, PathBuf::from("custom-src"));
-                assert_eq!(cmd.output, PathBuf::from("custom-output.css"));
+                assert_eq!(cmd.source, Some(PathBuf::from("custom-src")));
+                assert_eq!(cmd.output, Some(PathBuf::from("custom-output.css")));
                 assert!(cmd.tree_shake);
                 assert!(cmd.minify);
                 assert!(cmd.verbose);
+                assert!(cmd.deny_dynamic_classes);
+                assert!(cmd.workspace);
+                assert_eq!(cmd.dependencies, vec!["tailwind-rs-leptos"]);
+
+                let content = cmd.cargo_content(&ContentConfig::default());
+                let cargo = content.cargo.unwrap();
+                assert!(cargo.workspace);
+                assert_eq!(cargo.dependencies, vec!["tailwind-rs-leptos"]);
             }
             _ => panic!("Expected Build command"),
         }
@@ -5,8 +5,10 @@
 use crate::utils::{FileUtils, LogUtils};
 use anyhow::Result;
 use clap::Parser;
-use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use tailwind_rs_core::color_space::{generate_palette, ColorFormat, Oklch};
+use tailwind_rs_core::config::schema;
+use tailwind_rs_core::{DesignTokens, CONFIG_VERSION};
 
 /// Manage configuration
 #[derive(Parser)]
@@ -39,54 +41,45 @@ pub enum ConfigAction {
         #[arg(short, long, default_value = "tailwind-rs.toml")]
         file: PathBuf,
     },
-}
-
-/// Tailwind-rs configuration structure
-#[derive(Debug, Serialize, Deserialize)]
-pub struct TailwindRsConfig {
-    /// Build configuration
-    pub build: BuildConfig,
-    /// Optimization configuration
-    pub optimize: OptimizeConfig,
-    /// Watch configuration
-    pub watch: WatchConfig,
-}
-
-/// Build configuration
-#[derive(Debug, Serialize, Deserialize)]
-pub struct BuildConfig {
-    /// Source directory
-    pub source: String,
-    /// Output CSS file
-    pub output: String,
-    /// Enable tree-shaking
-    pub tree_shake: bool,
-    /// Enable minification
-    pub minify: bool,
-    /// Enable source maps
-    pub source_maps: bool,
-}
+    /// Print the JSON Schema of the configuration file, for editor completion
+    Schema {
+        /// Write the schema to a file instead of printing it
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Upgrade a configuration file written for an older version
+    Migrate {
+        /// Configuration file path
+        #[arg(short, long, default_value = "tailwind-rs.toml")]
+        file: PathBuf,
 
-/// Optimization configuration
-#[derive(Debug, Serialize, Deserialize)]
-pub struct OptimizeConfig {
-    /// Optimization level (1-3)
-    pub level: u8,
-    /// Remove unused classes
-    pub remove_unused: bool,
-    /// Minify CSS
-    pub minify: bool,
-    /// Generate source maps
-    pub source_maps: bool,
-}
+        /// Print the upgraded file instead of writing it
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Import design tokens (W3C DTCG JSON) and print the resulting theme
+    Tokens {
+        /// Design token file path
+        #[arg(default_value = "tokens.json")]
+        file: PathBuf,
 
-/// Watch configuration
-#[derive(Debug, Serialize, Deserialize)]
-pub struct WatchConfig {
-    /// Debounce delay in milliseconds
-    pub debounce: u64,
-    /// Watch patterns
-    pub patterns: Vec<String>,
+        /// Exit with an error if any token could not be imported
+        #[arg(long)]
+        strict: bool,
+    },
+    /// Generate a 50-950 color palette from a seed color
+    Palette {
+        /// Seed color (hex, rgb(), hsl(), oklch())
+        seed: String,
+
+        /// Palette name used in the variable names
+        #[arg(short, long, default_value = "brand")]
+        name: String,
+
+        /// Color format (hex, rgb, oklch)
+        #[arg(short, long, default_value = "oklch")]
+        format: String,
+    },
 }
 
 impl ConfigCommand {
@@ -96,6 +89,12 @@ impl ConfigCommand {
             ConfigAction::Init { file, default } => self.init_config(file, *default).await,
             ConfigAction::Validate { file } => self.validate_config(file).await,
             ConfigAction::Show { file } => self.show_config(file).await,
+            ConfigAction::Schema { output } => self.show_schema(output.as_ref()).await,
+            ConfigAction::Migrate { file, dry_run } => self.migrate_config(file, *dry_run).await,
+            ConfigAction::Tokens { file, strict } => self.show_tokens(file, *strict).await,
+            ConfigAction::Palette { seed, name, format } => {
+                self.show_palette(seed, name, format).await
+            }
         }
     }
 
@@ -120,22 +119,31 @@ impl ConfigCommand {
             Self::interactive_config()?
         };
 
-        let toml_content = toml::to_string_pretty(&config)?;
-        FileUtils::write_file(file, &toml_content)?;
+        FileUtils::write_file(file, &config)?;
 
         LogUtils::success(&format!("Configuration file created: {:?}", file));
         Ok(())
     }
 
-    /// Validate configuration file
+    /// Validate configuration file, loading it the way `build` does
     async fn validate_config(&self, file: &PathBuf) -> Result<()> {
         if !FileUtils::file_exists(file) {
             LogUtils::error(&format!("Configuration file does not exist: {:?}", file));
             return Err(anyhow::anyhow!("Configuration file not found"));
         }
 
-        let content = FileUtils::read_file(file)?;
-        let _config: TailwindRsConfig = toml::from_str(&content)?;
+        let config = FileUtils::load_config(Some(file))?;
+        config.validate()?;
+
+        if file.extension().is_none_or(|ext| ext != "css") {
+            let (_, migration) = schema::migrate_document(&FileUtils::read_file(file)?)?;
+            if migration.is_needed() {
+                LogUtils::warning(&format!(
+                    "Configuration file is written for version {}; run `tailwind-rs config migrate` to upgrade it to version {}",
+                    migration.from_version, CONFIG_VERSION
+                ));
+            }
+        }
 
         LogUtils::success(&format!("Configuration file is valid: {:?}", file));
         Ok(())
@@ -148,39 +156,132 @@ impl ConfigCommand {
             return Err(anyhow::anyhow!("Configuration file not found"));
         }
 
-        let content = FileUtils::read_file(file)?;
-        let config: TailwindRsConfig = toml::from_str(&content)?;
+        let config = FileUtils::load_config(Some(file))?;
 
         LogUtils::info("Current configuration:");
         println!("{:#?}", config);
         Ok(())
     }
 
-    /// Create default configuration
-    fn default_config() -> TailwindRsConfig {
-        TailwindRsConfig {
-            build: BuildConfig {
-                source: "src".to_string(),
-                output: "dist/styles.css".to_string(),
-                tree_shake: true,
-                minify: true,
-                source_maps: false,
-            },
-            optimize: OptimizeConfig {
-                level: 3,
-                remove_unused: true,
-                minify: true,
-                source_maps: false,
-            },
-            watch: WatchConfig {
-                debounce: 300,
-                patterns: vec!["**/*.rs".to_string()],
-            },
+    /// Print or write the JSON Schema of the configuration file
+    async fn show_schema(&self, output: Option<&PathBuf>) -> Result<()> {
+        let schema = serde_json::to_string_pretty(&schema::json_schema())?;
+        match output {
+            Some(output) => {
+                if let Some(parent) = output.parent() {
+                    FileUtils::ensure_dir(parent)?;
+                }
+                FileUtils::write_file(output, &schema)?;
+                LogUtils::success(&format!("Configuration schema written to: {:?}", output));
+            }
+            None => println!("{}", schema),
+        }
+        Ok(())
+    }
+
+    /// Upgrade a configuration file to the current version, keeping the
+    /// original next to it since comments are not preserved
+    async fn migrate_config(&self, file: &PathBuf, dry_run: bool) -> Result<()> {
+        if !FileUtils::file_exists(file) {
+            LogUtils::error(&format!("Configuration file does not exist: {:?}", file));
+            return Err(anyhow::anyhow!("Configuration file not found"));
+        }
+
+        let content = FileUtils::read_file(file)?;
+        let (migrated, migration) = schema::migrate_document(&content)?;
+        if !migration.is_needed() {
+            LogUtils::success(&format!(
+                "Configuration file is already at version {}: {:?}",
+                CONFIG_VERSION, file
+            ));
+            return Ok(());
+        }
+
+        for change in &migration.changes {
+            LogUtils::info(change);
         }
+        if dry_run {
+            print!("{}", migrated);
+            return Ok(());
+        }
+
+        let mut backup = file.clone().into_os_string();
+        backup.push(".bak");
+        FileUtils::write_file(&backup, &content)?;
+        FileUtils::write_file(file, &migrated)?;
+
+        LogUtils::success(&format!(
+            "Configuration file upgraded from version {} to {}: {:?} (original kept as {:?})",
+            migration.from_version, CONFIG_VERSION, file, backup
+        ));
+        Ok(())
+    }
+
+    /// Import design tokens and print the theme as an `@theme` block
+    async fn show_tokens(&self, file: &PathBuf, strict: bool) -> Result<()> {
+        if !FileUtils::file_exists(file) {
+            LogUtils::error(&format!("Design token file does not exist: {:?}", file));
+            return Err(anyhow::anyhow!("Design token file not found"));
+        }
+
+        let import = DesignTokens::from_file(file)?.import()?;
+        print!("{}", import.to_theme_css());
+
+        for token in &import.unsupported {
+            LogUtils::warning(&format!(
+                "Skipped token '{}' ({}): {}",
+                token.path,
+                token.token_type.as_deref().unwrap_or("no type"),
+                token.reason
+            ));
+        }
+        if strict && !import.unsupported.is_empty() {
+            return Err(anyhow::anyhow!(
+                "{} design tokens could not be imported",
+                import.unsupported.len()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Print a palette generated from a seed color as an `@theme` block
+    async fn show_palette(&self, seed: &str, name: &str, format: &str) -> Result<()> {
+        let seed: Oklch = seed.parse()?;
+        let format: ColorFormat = format.parse()?;
+
+        println!("@theme {{");
+        for (shade, color) in generate_palette(seed) {
+            println!("  --color-{}-{}: {};", name, shade, color.to_css(format));
+        }
+        println!("}}");
+        Ok(())
+    }
+
+    /// Create default configuration
+    fn default_config() -> String {
+        format!(
+            r#"version = {}
+
+[build]
+input = ["src"]
+output = "dist/styles.css"
+purge = true
+minify = true
+source_maps = false
+
+[optimize]
+level = 3
+minify = true
+
+[watch]
+debounce = 300
+"#,
+            CONFIG_VERSION
+        )
     }
 
     /// Create interactive configuration
-    fn interactive_config() -> Result<TailwindRsConfig> {
+    fn interactive_config() -> Result<String> {
         // For now, just return the default config
         // In a real implementation, this would prompt the user for input
         Ok(Self::default_config())
@@ -191,6 +292,7 @@ impl ConfigCommand {
 mod tests {
     use super::*;
     use std::fs;
+    use tailwind_rs_core::TailwindConfig;
     use tempfile::TempDir;
 
     #[test]
@@ -215,36 +317,20 @@ mod tests {
 
     #[test]
     fn test_default_config() {
-        let config = ConfigCommand::default_config();
+        let config = TailwindConfig::from_str(&ConfigCommand::default_config()).unwrap();
 
-        assert_eq!(config.build.source, "src");
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.build.input, vec!["src"]);
         assert_eq!(config.build.output, "dist/styles.css");
-        assert!(config.build.tree_shake);
+        assert!(config.build.purge);
         assert!(config.build.minify);
         assert!(!config.build.source_maps);
 
         assert_eq!(config.optimize.level, 3);
-        assert!(config.optimize.remove_unused);
         assert!(config.optimize.minify);
-        assert!(!config.optimize.source_maps);
 
         assert_eq!(config.watch.debounce, 300);
-        assert_eq!(config.watch.patterns, vec!["**/*.rs"]);
-    }
-
-    #[test]
-    fn test_config_serialization() {
-        let config = ConfigCommand::default_config();
-        let toml_content = toml::to_string_pretty(&config).unwrap();
-
-        // Should be able to deserialize it back
-        let deserialized: TailwindRsConfig = toml::from_str(&toml_content).unwrap();
-
-        assert_eq!(config.build.source, deserialized.build.source);
-        assert_eq!(config.build.output, deserialized.build.output);
-        assert_eq!(config.build.tree_shake, deserialized.build.tree_shake);
-        assert_eq!(config.build.minify, deserialized.build.minify);
-        assert_eq!(config.build.source_maps, deserialized.build.source_maps);
+        assert!(config.validate().is_ok());
     }
 
     #[tokio::test]
@@ -264,9 +350,9 @@ mod tests {
         // Check that the file was created
         assert!(config_file.exists());
 
-        // Check that it contains valid TOML
-        let content = fs::read_to_string(&config_file).unwrap();
-        let _config: TailwindRsConfig = toml::from_str(&content).unwrap();
+        // Check that it loads the way `build` loads it
+        let config = TailwindConfig::from_file(&config_file).unwrap();
+        assert_eq!(config.build.input, vec!["src"]);
     }
 
     #[tokio::test]
@@ -275,15 +361,120 @@ mod tests {
         let config_file = temp_dir.path().join("tailwind-rs.toml");
 
         // Create a valid config file
-        let config = ConfigCommand::default_config();
-        let toml_content = toml::to_string_pretty(&config).unwrap();
-        fs::write(&config_file, toml_content).unwrap();
+        fs::write(&config_file, ConfigCommand::default_config()).unwrap();
 
         let cmd = ConfigCommand {
-            action: ConfigAction::Validate { file: config_file },
+            action: ConfigAction::Validate {
+                file: config_file.clone(),
+            },
         };
 
         // Should not panic
         cmd.execute().await.unwrap();
+
+        // Settings the other commands would reject fail validation too
+        fs::write(&config_file, "[optimize]\nlevel = 7\n").unwrap();
+        assert!(cmd.execute().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_config_migrate() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("tailwind-rs.toml");
+        let legacy = "[build]\nsource = \"src\"\noutput = \"dist/styles.css\"\ntree_shake = true\nminify = true\nsource_maps = false\n\n[watch]\ndebounce = 300\npatterns = [\"**/*.rs\"]\n";
+        fs::write(&config_file, legacy).unwrap();
+
+        let migrate = |dry_run| ConfigCommand {
+            action: ConfigAction::Migrate {
+                file: config_file.clone(),
+                dry_run,
+            },
+        };
+        migrate(true).execute().await.unwrap();
+        assert_eq!(fs::read_to_string(&config_file).unwrap(), legacy);
+
+        migrate(false).execute().await.unwrap();
+        let migrated = fs::read_to_string(&config_file).unwrap();
+        assert!(migrated.starts_with(&format!("version = {}", CONFIG_VERSION)));
+        assert!(!migrated.contains("tree_shake"));
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("tailwind-rs.toml.bak")).unwrap(),
+            legacy
+        );
+        assert_eq!(
+            TailwindConfig::from_str(&migrated).unwrap(),
+            TailwindConfig::from_str(legacy).unwrap()
+        );
+
+        // Upgraded files are left alone
+        migrate(false).execute().await.unwrap();
+        assert_eq!(fs::read_to_string(&config_file).unwrap(), migrated);
+    }
+
+    #[tokio::test]
+    async fn test_config_schema() {
+        let temp_dir = TempDir::new().unwrap();
+        let schema_file = temp_dir.path().join("schema/tailwind-rs.json");
+
+        let cmd = ConfigCommand {
+            action: ConfigAction::Schema {
+                output: Some(schema_file.clone()),
+            },
+        };
+        cmd.execute().await.unwrap();
+
+        let schema: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&schema_file).unwrap()).unwrap();
+        assert_eq!(schema["properties"]["version"]["maximum"], CONFIG_VERSION);
+        assert!(schema["properties"]["optimize"]["properties"]["level"].is_object());
+    }
+
+    #[tokio::test]
+    async fn test_config_tokens() {
+        let temp_dir = TempDir::new().unwrap();
+        let tokens_file = temp_dir.path().join("tokens.json");
+        fs::write(
+            &tokens_file,
+            r##"{ "color": { "$type": "color", "brand": { "$value": "#0f766e" } },
+                  "typography": { "body": { "$type": "typography", "$value": {} } } }"##,
+        )
+        .unwrap();
+
+        let cmd = ConfigCommand {
+            action: ConfigAction::Tokens {
+                file: tokens_file.clone(),
+                strict: false,
+            },
+        };
+        cmd.execute().await.unwrap();
+
+        let strict = ConfigCommand {
+            action: ConfigAction::Tokens {
+                file: tokens_file,
+                strict: true,
+            },
+        };
+        assert!(strict.execute().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_config_palette() {
+        let cmd = ConfigCommand {
+            action: ConfigAction::Palette {
+                seed: "#0f766e".to_string(),
+                name: "brand".to_string(),
+                format: "hex".to_string(),
+            },
+        };
+        cmd.execute().await.unwrap();
+
+        let invalid = ConfigCommand {
+            action: ConfigAction::Palette {
+                seed: "not-a-color".to_string(),
+                name: "brand".to_string(),
+                format: "hex".to_string(),
+            },
+        };
+        assert!(invalid.execute().await.is_err());
     }
 }
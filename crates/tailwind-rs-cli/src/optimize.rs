@@ -6,32 +6,39 @@ use crate::utils::{FileUtils, LogUtils};
 use anyhow::Result;
 use clap::Parser;
 use std::path::PathBuf;
-use tailwind_rs_core::css_optimizer::CssOptimizer;
+use tailwind_rs_core::css_optimizer::{CssOptimizer, OptimizationConfig};
 
 /// Optimize CSS output
 #[derive(Parser)]
 pub struct OptimizeCommand {
-    /// Input CSS file path
-    #[arg(short, long, default_value = "dist/styles.css")]
-    pub input: PathBuf,
+    /// Input CSS file path [default: the configuration's `build.output`,
+    /// or `dist/styles.css`]
+    #[arg(short, long)]
+    pub input: Option<PathBuf>,
 
-    /// Output CSS file path
-    #[arg(short, long, default_value = "dist/styles.min.css")]
-    pub output: PathBuf,
+    /// Output CSS file path [default: the input with a `.min.css` extension
+    /// when minifying, or else `.optimized.css`]
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
 
-    /// Optimization level (1-3)
-    #[arg(short, long, default_value = "3")]
-    pub level: u8,
+    /// Configuration file path
+    #[arg(short, long, default_value = "tailwind-rs.toml")]
+    pub config: Option<PathBuf>,
 
-    /// Remove unused classes
+    /// Optimization level (1-3) [default: the configuration's
+    /// `optimize.level`, or 3]
+    #[arg(short, long)]
+    pub level: Option<u8>,
+
+    /// Remove unused classes (ignored: use `build --tree-shake`)
     #[arg(long)]
     pub remove_unused: bool,
 
-    /// Minify CSS
+    /// Minify CSS [default: the configuration's `optimize.minify`]
     #[arg(long)]
     pub minify: bool,
 
-    /// Generate source maps
+    /// Generate source maps (ignored: use `build --source-maps`)
     #[arg(long)]
     pub source_maps: bool,
 
@@ -45,51 +52,79 @@ impl OptimizeCommand {
     pub async fn execute(&self) -> Result<()> {
         LogUtils::info("Starting CSS optimization...");
 
+        let config = FileUtils::load_config(self.config.as_deref())?;
+        let input = self
+            .input
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(&config.build.output));
+        let level = self.level.unwrap_or(config.optimize.level);
+        let minify = self.minify || config.optimize.minify;
+        let output = self.output.clone().unwrap_or_else(|| {
+            let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+            let extension = if minify { "min.css" } else { "optimized.css" };
+            input.with_file_name(format!("{}.{}", stem, extension))
+        });
+
         if self.verbose {
-            LogUtils::info(&format!("Input file: {:?}", self.input));
-            LogUtils::info(&format!("Output file: {:?}", self.output));
-            LogUtils::info(&format!("Optimization level: {}", self.level));
+            LogUtils::info(&format!("Input file: {:?}", input));
+            LogUtils::info(&format!("Output file: {:?}", output));
+            LogUtils::info(&format!("Optimization level: {}", level));
+            LogUtils::info(&format!("Minify: {}", minify));
+        }
+        if !(1..=3).contains(&level) {
+            return Err(anyhow::anyhow!(
+                "Optimization level must be between 1 and 3, got {}",
+                level
+            ));
+        }
+        if self.remove_unused || self.source_maps {
+            LogUtils::warning(
+                "--remove-unused and --source-maps are ignored; use `tailwind-rs build --tree-shake --source-maps`",
+            );
         }
 
         // Validate input file exists
-        if !FileUtils::file_exists(&self.input) {
-            LogUtils::error(&format!("Input file does not exist: {:?}", self.input));
+        if !FileUtils::file_exists(&input) {
+            LogUtils::error(&format!("Input file does not exist: {:?}", input));
             return Err(anyhow::anyhow!("Input file not found"));
         }
 
         // Ensure output directory exists
-        if let Some(output_dir) = self.output.parent() {
+        if let Some(output_dir) = output.parent() {
             FileUtils::ensure_dir(output_dir)?;
         }
 
         // Get input file size
-        let input_size = std::fs::metadata(&self.input)?.len();
+        let input_size = std::fs::metadata(&input)?.len();
         if self.verbose {
             LogUtils::info(&format!("Input file size: {} bytes", input_size));
         }
 
-        let optimizer = CssOptimizer::new();
+        let optimizer = CssOptimizer::with_config(OptimizationConfig {
+            minify,
+            ..OptimizationConfig::for_level(level)
+        });
 
         // Read input CSS
-        let css_content = std::fs::read_to_string(&self.input)?;
+        let css_content = std::fs::read_to_string(&input)?;
 
         // Perform optimization
         let start_time = std::time::Instant::now();
         let optimized_css = optimizer.optimize_css(&css_content)?;
 
         // Write output
-        std::fs::write(&self.output, optimized_css)?;
+        std::fs::write(&output, optimized_css)?;
         let duration = start_time.elapsed();
 
         // Get output file size
-        let output_size = if FileUtils::file_exists(&self.output) {
-            std::fs::metadata(&self.output)?.len()
+        let output_size = if FileUtils::file_exists(&output) {
+            std::fs::metadata(&output)?.len()
         } else {
             0
         };
 
         let savings = if input_size > 0 {
-            ((input_size - output_size) as f64 / input_size as f64) * 100.0
+            ((input_size as f64 - output_size as f64) / input_size as f64) * 100.0
         } else {
             0.0
         };
@@ -102,7 +137,7 @@ impl OptimizeCommand {
         if self.verbose {
             LogUtils::info(&format!("Output file size: {} bytes", output_size));
             LogUtils::info(&format!("Size reduction: {:.1}%", savings));
-            LogUtils::info(&format!("Output written to: {:?}", self.output));
+            LogUtils::info(&format!("Output written to: {:?}", output));
         }
 
         Ok(())
@@ -138,9 +173,9 @@ mod tests {
 
         match cli.command {
             crate::Commands::Optimize(cmd) => {
-                assert_eq!(cmd.input, PathBuf::from("input.css"));
-                assert_eq!(cmd.output, PathBuf::from("output.min.css"));
-                assert_eq!(cmd.level, 2);
+                assert_eq!(cmd.input, Some(PathBuf::from("input.css")));
+                assert_eq!(cmd.output, Some(PathBuf::from("output.min.css")));
+                assert_eq!(cmd.level, Some(2));
                 assert!(cmd.remove_unused);
                 assert!(cmd.minify);
                 assert!(cmd.source_maps);
@@ -160,9 +195,10 @@ mod tests {
 
         match cli.command {
             crate::Commands::Optimize(cmd) => {
-                assert_eq!(cmd.input, PathBuf::from("dist/styles.css"));
-                assert_eq!(cmd.output, PathBuf::from("dist/styles.min.css"));
-                assert_eq!(cmd.level, 3);
+                assert_eq!(cmd.input, None);
+                assert_eq!(cmd.output, None);
+                assert_eq!(cmd.config, Some(PathBuf::from("tailwind-rs.toml")));
+                assert_eq!(cmd.level, None);
                 assert!(!cmd.remove_unused);
                 assert!(!cmd.minify);
                 assert!(!cmd.source_maps);
@@ -181,29 +217,56 @@ mod tests {
         // Create a test CSS file
         fs::write(
             &input_file,
-            r#"
-            .px-4 { padding-left: 1rem; padding-right: 1rem; }
-            .py-2 { padding-top: 0.5rem; padding-bottom: 0.5rem; }
-            .bg-blue-600 { background-color: #2563eb; }
-            .text-white { color: #ffffff; }
-        "#,
+            ".px-4 {\n  padding-left: 1rem;\n  padding-right: 1rem;\n}\n\
+             .text-white {\n  color: #ffffff;\n}\n",
         )
         .unwrap();
 
         let cmd = OptimizeCommand {
-            input: input_file,
-            output: output_file.clone(),
-            level: 2,
+            input: Some(input_file),
+            output: Some(output_file.clone()),
+            config: None,
+            level: Some(2),
             remove_unused: true,
             minify: true,
             source_maps: false,
             verbose: false,
         };
 
-        // This should not panic, even if the optimization fails
-        let result = cmd.execute().await;
-        // We don't assert success here because we don't have a full CSS optimizer setup
-        // In a real test, we would mock the CssOptimizer
-        assert!(result.is_ok() || result.is_err());
+        cmd.execute().await.unwrap();
+        let optimized = fs::read_to_string(&output_file).unwrap();
+        assert!(optimized.contains("padding-left:1rem;"));
+        assert!(optimized.contains(".text-white"));
+        assert!(!optimized.contains('\n'));
+    }
+
+    #[tokio::test]
+    async fn test_optimize_command_reads_config_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_file = temp_dir.path().join("tailwind-rs.toml");
+        let input_file = temp_dir.path().join("styles.css");
+        fs::write(&config_file, "[optimize]\nlevel = 1\nminify = true\n").unwrap();
+        fs::write(&input_file, ".p-4 {\n  padding: 1rem;\n}\n").unwrap();
+
+        let cmd = OptimizeCommand {
+            input: Some(input_file),
+            output: None,
+            config: Some(config_file),
+            level: None,
+            remove_unused: false,
+            minify: false,
+            source_maps: false,
+            verbose: false,
+        };
+        cmd.execute().await.unwrap();
+
+        let optimized = fs::read_to_string(temp_dir.path().join("styles.min.css")).unwrap();
+        assert!(optimized.contains("padding:1rem"));
+
+        let cmd = OptimizeCommand {
+            level: Some(4),
+            ..cmd
+        };
+        assert!(cmd.execute().await.is_err());
     }
 }
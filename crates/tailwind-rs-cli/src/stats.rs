@@ -2,21 +2,30 @@
 //!
 //! This module handles the stats command for showing build statistics and project information.
 
-use crate::utils::{FileUtils, LogUtils, StringUtils};
+use crate::utils::{FileUtils, LogUtils};
 use anyhow::Result;
 use clap::Parser;
-use std::path::PathBuf;
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use tailwind_rs_core::{BuildInput, TailwindConfig};
+use tailwind_rs_scanner::{ContentScanner, ScanConfig, UsageIndex};
 
 /// Show build statistics and project information
 #[derive(Parser)]
 pub struct StatsCommand {
-    /// Source directory to analyze
-    #[arg(short, long, default_value = "src")]
-    pub source: PathBuf,
+    /// Source directory to analyze [default: the configuration's
+    /// `build.input`, relative to its directory, or `src`]
+    #[arg(short, long)]
+    pub source: Option<PathBuf>,
+
+    /// Output CSS file to analyze [default: the configuration's
+    /// `build.output`, or `dist/styles.css`]
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
 
-    /// Output CSS file to analyze
-    #[arg(short, long, default_value = "dist/styles.css")]
-    pub output: PathBuf,
+    /// Configuration file path
+    #[arg(short, long, default_value = "tailwind-rs.toml")]
+    pub config: Option<PathBuf>,
 
     /// Show detailed statistics
     #[arg(long)]
@@ -25,6 +34,14 @@ pub struct StatsCommand {
     /// Verbose output
     #[arg(short, long)]
     pub verbose: bool,
+
+    /// List every usage of a class, with its file, line and enclosing item
+    #[arg(long, value_name = "CLASS")]
+    pub usages: Option<String>,
+
+    /// Report classes in the output CSS that no source file uses
+    #[arg(long)]
+    pub unused: bool,
 }
 
 impl StatsCommand {
@@ -32,16 +49,29 @@ impl StatsCommand {
     pub async fn execute(&self) -> Result<()> {
         LogUtils::info("Analyzing project statistics...");
 
+        let config = FileUtils::load_config(self.config.as_deref())?;
+        let inputs = FileUtils::inputs(&config, self.config.as_deref(), self.source.as_deref())?;
+        let output = self
+            .output
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(&config.build.output));
+
         if self.verbose {
-            LogUtils::info(&format!("Source directory: {:?}", self.source));
-            LogUtils::info(&format!("Output file: {:?}", self.output));
+            let paths: Vec<_> = inputs.iter().map(|input| &input.path).collect();
+            LogUtils::info(&format!("Source directories: {:?}", paths));
+            LogUtils::info(&format!("Output file: {:?}", output));
         }
 
         // Analyze source files
-        let source_stats = self.analyze_source()?;
+        let source_stats = self.analyze_source(&config, &inputs).await?;
+
+        if let Some(class) = &self.usages {
+            self.display_usages(&source_stats.index, class);
+            return Ok(());
+        }
 
         // Analyze output file
-        let output_stats = self.analyze_output()?;
+        let output_stats = self.analyze_output(&output)?;
 
         // Display statistics
         self.display_stats(&source_stats, &output_stats)?;
@@ -49,50 +79,63 @@ impl StatsCommand {
         Ok(())
     }
 
-    /// Analyze source directory
-    fn analyze_source(&self) -> Result<SourceStats> {
-        if !FileUtils::file_exists(&self.source) {
+    /// Analyze the input files named by the content sources, indexing where
+    /// each class is used
+    async fn analyze_source(
+        &self,
+        config: &TailwindConfig,
+        inputs: &[BuildInput],
+    ) -> Result<SourceStats> {
+        let sources = config
+            .content
+            .sources(FileUtils::config_root(self.config.as_deref()))?;
+        let mut paths = Vec::new();
+        for input in inputs.iter().filter(|input| input.path.exists()) {
+            paths.extend(
+                input
+                    .files(&sources)?
+                    .into_iter()
+                    .map(|file| file.to_string_lossy().into_owned()),
+            );
+        }
+        paths.sort();
+        paths.dedup();
+        if paths.is_empty() {
             return Ok(SourceStats::default());
         }
 
-        let rust_files = FileUtils::find_rust_files(&self.source)?;
-        let mut total_lines = 0;
-        let mut total_classes = 0;
-        let mut class_usage = std::collections::HashMap::new();
+        let scanner = ContentScanner::new(ScanConfig {
+            tailwind_config: Some(config.clone()),
+            ..ScanConfig::default()
+        })?;
+        let class_set = scanner.scan_paths(&paths).await?;
+        let index = class_set.usage_index();
 
-        for file in &rust_files {
-            if let Ok(content) = FileUtils::read_file(file) {
-                let lines = content.lines().count();
-                total_lines += lines;
-
-                // Count Tailwind classes (simple heuristic)
-                let classes = self.extract_classes(&content);
-                total_classes += classes.len();
-
-                for class in classes {
-                    *class_usage.entry(class).or_insert(0) += 1;
-                }
-            }
-        }
+        let total_lines = index
+            .files()
+            .filter_map(|file| FileUtils::read_file(file).ok())
+            .map(|content| content.lines().count())
+            .sum();
+        let total_classes = class_set.file_classes.values().map(Vec::len).sum();
 
         Ok(SourceStats {
-            rust_files: rust_files.len(),
+            files: index.len(),
             total_lines,
             total_classes,
-            class_usage,
+            index,
         })
     }
 
     /// Analyze output CSS file
-    fn analyze_output(&self) -> Result<OutputStats> {
-        if !FileUtils::file_exists(&self.output) {
+    fn analyze_output(&self, output: &Path) -> Result<OutputStats> {
+        if !FileUtils::file_exists(output) {
             return Ok(OutputStats::default());
         }
 
-        let content = FileUtils::read_file(&self.output)?;
+        let content = FileUtils::read_file(output)?;
         let size = content.len();
         let lines = content.lines().count();
-        let classes = self.count_css_classes(&content);
+        let classes = css_class_names(&content);
 
         Ok(OutputStats {
             size,
@@ -101,31 +144,22 @@ impl StatsCommand {
         })
     }
 
-    /// Extract Tailwind classes from Rust code
-    fn extract_classes(&self, content: &str) -> Vec<String> {
-        let mut classes = Vec::new();
-
-        // Simple regex-like extraction for common patterns
-        for line in content.lines() {
-            // Look for class strings in common patterns
-            if line.contains("class") || line.contains("classes") {
-                // Extract quoted strings that look like Tailwind classes
-                let words: Vec<&str> = line.split_whitespace().collect();
-                for word in words {
-                    if word.contains('-') && StringUtils::is_valid_css_class(word.trim_matches('"'))
-                    {
-                        classes.push(word.trim_matches('"').to_string());
-                    }
-                }
-            }
+    /// Display every usage of a class
+    fn display_usages(&self, index: &UsageIndex, class: &str) {
+        let usages = index.usages(class);
+        if usages.is_empty() {
+            LogUtils::warning(&format!("No usages of {}", class));
+            return;
         }
 
-        classes
-    }
-
-    /// Count CSS classes in output
-    fn count_css_classes(&self, content: &str) -> usize {
-        content.matches('.').count()
+        LogUtils::success(&format!("{} usages of {}", usages.len(), class));
+        for usage in usages {
+            let location = format!("{}:{}:{}", usage.path.display(), usage.line, usage.column);
+            match usage.item {
+                Some(item) => println!("  {} in {}", location, item),
+                None => println!("  {}", location),
+            }
+        }
     }
 
     /// Display statistics
@@ -135,18 +169,30 @@ impl StatsCommand {
 
         // Source statistics
         println!("📁 Source Analysis:");
-        println!("  Rust files: {}", source.rust_files);
+        println!("  Source files: {}", source.files);
         println!("  Total lines: {}", source.total_lines);
         println!("  Tailwind classes: {}", source.total_classes);
+        println!("  Unique classes: {}", source.index.classes().len());
 
-        if self.detailed && !source.class_usage.is_empty() {
+        if self.detailed && !source.index.is_empty() {
             println!("  Most used classes:");
-            let mut sorted_classes: Vec<_> = source.class_usage.iter().collect();
-            sorted_classes.sort_by(|a, b| b.1.cmp(a.1));
+            let mut sorted_classes: Vec<_> = source
+                .index
+                .classes()
+                .into_iter()
+                .map(|class| (class, source.index.usage_count(class)))
+                .collect();
+            sorted_classes.sort_by_key(|&(_, count)| std::cmp::Reverse(count));
 
             for (class, count) in sorted_classes.iter().take(10) {
                 println!("    {}: {} times", class, count);
             }
+
+            let used_once = source.index.used_once();
+            println!("  Classes used once: {}", used_once.len());
+            for class in used_once.iter().take(10) {
+                println!("    {}", class);
+            }
         }
 
         println!();
@@ -155,23 +201,32 @@ impl StatsCommand {
         println!("📄 Output Analysis:");
         println!("  File size: {} bytes", output.size);
         println!("  CSS lines: {}", output.lines);
-        println!("  CSS classes: {}", output.classes);
+        println!("  CSS classes: {}", output.classes.len());
 
         if output.size > 0 {
             let size_kb = output.size as f64 / 1024.0;
             println!("  Size: {:.2} KB", size_kb);
         }
 
+        if self.unused {
+            let unused = source
+                .index
+                .unused(output.classes.iter().map(String::as_str));
+            println!("  Unused classes: {}", unused.len());
+            for class in unused {
+                println!("    {}", class);
+            }
+        }
+
         println!();
 
         // Summary
-        if source.rust_files > 0 {
-            let avg_lines = source.total_lines / source.rust_files;
+        if let Some(avg_lines) = source.total_lines.checked_div(source.files) {
             println!("📊 Summary:");
             println!("  Average lines per file: {}", avg_lines);
             println!(
                 "  Classes per file: {:.1}",
-                source.total_classes as f64 / source.rust_files as f64
+                source.total_classes as f64 / source.files as f64
             );
         }
 
@@ -182,10 +237,10 @@ impl StatsCommand {
 /// Source file statistics
 #[derive(Debug, Default)]
 struct SourceStats {
-    rust_files: usize,
+    files: usize,
     total_lines: usize,
     total_classes: usize,
-    class_usage: std::collections::HashMap<String, usize>,
+    index: UsageIndex,
 }
 
 /// Output file statistics
@@ -193,7 +248,44 @@ struct SourceStats {
 struct OutputStats {
     size: usize,
     lines: usize,
-    classes: usize,
+    classes: BTreeSet<String>,
+}
+
+/// Unescaped class names of the class selectors in a stylesheet
+fn css_class_names(css: &str) -> BTreeSet<String> {
+    let mut classes = BTreeSet::new();
+    for (open, _) in css.match_indices('{') {
+        // The selector runs from the end of the previous rule or declaration
+        let before = &css[..open];
+        let start = before.rfind(['{', '}', ';']).map_or(0, |end| end + 1);
+        let selector = before[start..].trim();
+        if selector.starts_with('@') {
+            continue;
+        }
+
+        let mut chars = selector.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != '.' {
+                continue;
+            }
+            let mut class = String::new();
+            while let Some(&next) = chars.peek() {
+                if next == '\\' {
+                    chars.next();
+                    class.extend(chars.next());
+                } else if next.is_alphanumeric() || matches!(next, '-' | '_') {
+                    class.push(next);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            if class.starts_with(|c: char| !c.is_ascii_digit()) {
+                classes.insert(class);
+            }
+        }
+    }
+    classes
 }
 
 #[cfg(test)]
@@ -215,59 +307,92 @@ mod tests {
             "custom-output.css",
             "--detailed",
             "--verbose",
+            "--usages",
+            "p-4",
+            "--unused",
         ];
 
         let cli = Cli::try_parse_from(args).unwrap();
 
         match cli.command {
             crate::Commands::Stats(cmd) => {
-                assert_eq!(cmd.source, PathBuf::from("custom-src"));
-                assert_eq!(cmd.output, PathBuf::from("custom-output.css"));
+                assert_eq!(cmd.source, Some(PathBuf::from("custom-src")));
+                assert_eq!(cmd.output, Some(PathBuf::from("custom-output.css")));
                 assert!(cmd.detailed);
                 assert!(cmd.verbose);
+                assert_eq!(cmd.usages.as_deref(), Some("p-4"));
+                assert!(cmd.unused);
             }
             _ => panic!("Expected Stats command"),
         }
     }
 
     #[test]
-    fn test_extract_classes() {
-        let cmd = StatsCommand {
-            source: PathBuf::from("src"),
-            output: PathBuf::from("dist/styles.css"),
-            detailed: false,
-            verbose: false,
-        };
-
-        let content = r#"
-            let classes = "px-4 py-2 bg-blue-600 text-white";
-            let other = "hover:bg-blue-700 focus:ring-2";
+    fn test_css_class_names() {
+        let css = r#"
+            .px-4 { padding-left: 1rem; }
+            .hover\:bg-blue-600:hover, .w-1\/2 { width: .5rem; background: url(a.png); }
+            @media (min-width: 768px) { .md\:flex { display: flex; } }
         "#;
 
-        let classes = cmd.extract_classes(content);
-        assert!(classes.contains(&"px-4".to_string()));
-        assert!(classes.contains(&"py-2".to_string()));
-        assert!(classes.contains(&"bg-blue-600".to_string()));
+        let classes: Vec<String> = css_class_names(css).into_iter().collect();
+        assert_eq!(
+            classes,
+            vec!["hover:bg-blue-600", "md:flex", "px-4", "w-1/2"]
+        );
     }
 
-    #[test]
-    fn test_count_css_classes() {
+    #[tokio::test]
+    async fn test_analyze_source_indexes_usages() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("card.rs"),
+            "fn card() -> &'static str {\n    \"p-4 shadow\"\n}\nfn page() -> &'static str {\n    \"p-4\"\n}\n",
+        )
+        .unwrap();
+
         let cmd = StatsCommand {
-            source: PathBuf::from("src"),
-            output: PathBuf::from("dist/styles.css"),
+            source: Some(temp_dir.path().to_path_buf()),
+            output: Some(temp_dir.path().join("styles.css")),
+            config: None,
             detailed: false,
             verbose: false,
+            usages: Some("p-4".to_string()),
+            unused: false,
         };
+        let stats = cmd
+            .analyze_source(
+                &TailwindConfig::default(),
+                &[BuildInput::new(temp_dir.path())],
+            )
+            .await
+            .unwrap();
+
+        let items: Vec<_> = stats
+            .index
+            .usages("p-4")
+            .into_iter()
+            .map(|usage| (usage.line, usage.item))
+            .collect();
+        assert_eq!(
+            items,
+            vec![(2, Some("card".to_string())), (5, Some("page".to_string()))]
+        );
+        assert_eq!(stats.index.used_once(), vec!["shadow"]);
+        assert_eq!(stats.total_classes, 3);
+        assert!(cmd.execute().await.is_ok());
+    }
 
+    #[test]
+    fn test_count_css_classes() {
         let css = r#"
             .px-4 { padding: 1rem; }
             .py-2 { padding: 0.5rem; }
             .bg-blue-600 { background-color: #2563eb; }
         "#;
 
-        let count = cmd.count_css_classes(css);
-        // The CSS has 3 class selectors, but there might be additional dots in the content
-        assert!(count >= 3);
+        // Dots in declaration values aren't class selectors
+        assert_eq!(css_class_names(css).len(), 3);
     }
 
     #[tokio::test]
@@ -304,10 +429,13 @@ mod tests {
         .unwrap();
 
         let cmd = StatsCommand {
-            source: source_dir,
-            output: output_file,
+            source: Some(source_dir),
+            output: Some(output_file),
+            config: None,
             detailed: false,
             verbose: false,
+            usages: None,
+            unused: false,
         };
 
         // Should not panic
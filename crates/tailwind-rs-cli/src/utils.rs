@@ -4,6 +4,7 @@
 
 use anyhow::Result;
 use std::path::Path;
+use tailwind_rs_core::{BuildInput, TailwindConfig};
 use walkdir::WalkDir;
 
 /// Utility functions for file operations
@@ -26,6 +27,43 @@ impl FileUtils {
         Ok(rust_files)
     }
 
+    /// Load a configuration file, TOML, JSON or a Tailwind v4 CSS entry
+    /// file, or the defaults when there is none
+    ///
+    /// Files written for an older schema version are upgraded as they load.
+    pub fn load_config(config: Option<&Path>) -> Result<TailwindConfig> {
+        let Some(config) = config.filter(|config| config.exists()) else {
+            return Ok(TailwindConfig::new());
+        };
+        if config.extension().is_some_and(|ext| ext == "css") {
+            Ok(TailwindConfig::from_css_file(config)?)
+        } else {
+            Ok(TailwindConfig::from_file(config)?)
+        }
+    }
+
+    /// The directory a configuration file's paths are relative to
+    pub fn config_root(config: Option<&Path>) -> &Path {
+        config
+            .filter(|config| config.exists())
+            .and_then(Path::parent)
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(Path::new("."))
+    }
+
+    /// The inputs to scan: `source` when given on the command line, or else
+    /// the configuration's `build.input`, resolved against its directory
+    pub fn inputs(
+        config: &TailwindConfig,
+        config_path: Option<&Path>,
+        source: Option<&Path>,
+    ) -> Result<Vec<BuildInput>> {
+        match source {
+            Some(source) => Ok(vec![BuildInput::new(source)]),
+            None => Ok(config.build.inputs(Self::config_root(config_path))?),
+        }
+    }
+
     /// Check if a file exists
     pub fn file_exists<P: AsRef<Path>>(path: P) -> bool {
         path.as_ref().exists()
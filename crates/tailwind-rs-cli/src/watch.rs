@@ -6,21 +6,23 @@ use crate::utils::{FileUtils, LogUtils};
 use anyhow::Result;
 use clap::Parser;
 use notify::{Event, EventKind, RecursiveMode, Watcher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::mpsc;
 use std::time::Duration;
-use tailwind_rs_core::TailwindBuilder;
+use tailwind_rs_core::{BuildInput, ContentSources, TailwindBuilder};
 
 /// Watch for changes and rebuild automatically
 #[derive(Parser)]
 pub struct WatchCommand {
-    /// Source directory to watch
-    #[arg(short, long, default_value = "src")]
-    pub source: PathBuf,
+    /// Source directory to watch [default: the configuration's
+    /// `build.input`, relative to its directory, or `src`]
+    #[arg(short, long)]
+    pub source: Option<PathBuf>,
 
-    /// Output CSS file path
-    #[arg(short, long, default_value = "dist/styles.css")]
-    pub output: PathBuf,
+    /// Output CSS file path [default: the configuration's `build.output`,
+    /// or `dist/styles.css`]
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
 
     /// Configuration file path
     #[arg(short, long, default_value = "tailwind-rs.toml")]
@@ -34,9 +36,10 @@ pub struct WatchCommand {
     #[arg(long)]
     pub minify: bool,
 
-    /// Debounce delay in milliseconds
-    #[arg(long, default_value = "300")]
-    pub debounce: u64,
+    /// Debounce delay in milliseconds [default: the configuration's
+    /// `watch.debounce`, or 300]
+    #[arg(long)]
+    pub debounce: Option<u64>,
 
     /// Verbose output
     #[arg(short, long)]
@@ -48,34 +51,51 @@ impl WatchCommand {
     pub async fn execute(&self) -> Result<()> {
         LogUtils::info("Starting Tailwind CSS watch mode...");
 
+        let config = FileUtils::load_config(self.config.as_deref())?;
+        let inputs = FileUtils::inputs(&config, self.config.as_deref(), self.source.as_deref())?;
+        let output = self
+            .output
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(&config.build.output));
+        let debounce = self.debounce.unwrap_or(config.watch.debounce);
+
         if self.verbose {
-            LogUtils::info(&format!("Watching directory: {:?}", self.source));
-            LogUtils::info(&format!("Output file: {:?}", self.output));
-            LogUtils::info(&format!("Debounce delay: {}ms", self.debounce));
+            let paths: Vec<_> = inputs.iter().map(|input| &input.path).collect();
+            LogUtils::info(&format!("Watching directories: {:?}", paths));
+            LogUtils::info(&format!("Output file: {:?}", output));
+            LogUtils::info(&format!("Debounce delay: {}ms", debounce));
         }
 
-        // Validate source directory exists
-        if !FileUtils::file_exists(&self.source) {
-            LogUtils::error(&format!(
-                "Source directory does not exist: {:?}",
-                self.source
-            ));
-            return Err(anyhow::anyhow!("Source directory not found"));
+        // Validate source directories exist
+        for input in &inputs {
+            if !FileUtils::file_exists(&input.path) {
+                LogUtils::error(&format!(
+                    "Source directory does not exist: {:?}",
+                    input.path
+                ));
+                return Err(anyhow::anyhow!("Source directory not found"));
+            }
         }
 
         // Ensure output directory exists
-        if let Some(output_dir) = self.output.parent() {
+        if let Some(output_dir) = output.parent() {
             FileUtils::ensure_dir(output_dir)?;
         }
 
+        let sources = config
+            .content
+            .sources(FileUtils::config_root(self.config.as_deref()))?;
+
         // Create a channel to receive file system events
         let (tx, rx) = mpsc::channel();
 
         // Create a watcher
         let mut watcher = notify::recommended_watcher(tx)?;
 
-        // Watch the source directory
-        watcher.watch(&self.source, RecursiveMode::Recursive)?;
+        // Watch the source directories
+        for input in &inputs {
+            watcher.watch(&input.path, RecursiveMode::Recursive)?;
+        }
 
         LogUtils::success("Watcher started successfully");
         if self.verbose {
@@ -84,23 +104,23 @@ impl WatchCommand {
 
         // Initial build
         LogUtils::info("Performing initial build...");
-        self.build()?;
+        self.build(&inputs, &sources, &output)?;
 
         // Watch for changes
         loop {
             match rx.recv() {
                 Ok(event) => {
                     if let Ok(event) = event {
-                        if self.should_rebuild(&event) {
+                        if self.should_rebuild(&event, &sources) {
                             if self.verbose {
                                 LogUtils::info(&format!("File changed: {:?}", event.paths));
                             }
 
                             // Debounce the rebuild
-                            tokio::time::sleep(Duration::from_millis(self.debounce)).await;
+                            tokio::time::sleep(Duration::from_millis(debounce)).await;
 
                             // Rebuild
-                            if let Err(e) = self.build() {
+                            if let Err(e) = self.build(&inputs, &sources, &output) {
                                 LogUtils::error(&format!("Build error: {}", e));
                             }
                         }
@@ -117,26 +137,29 @@ impl WatchCommand {
     }
 
     /// Check if we should rebuild based on the event
-    fn should_rebuild(&self, event: &Event) -> bool {
+    fn should_rebuild(&self, event: &Event, sources: &ContentSources) -> bool {
         match event.kind {
             EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) => {
-                // Only rebuild for Rust files
-                event
-                    .paths
-                    .iter()
-                    .any(|path| path.extension().map(|ext| ext == "rs").unwrap_or(false))
+                // Only rebuild for files named by the content sources
+                event.paths.iter().any(|path| sources.includes_file(path))
             }
             _ => false,
         }
     }
 
-    /// Perform the actual build
-    fn build(&self) -> Result<()> {
-        let start_time = std::time::Instant::now();
+    /// Perform the actual build, scanning the input files found now
+    fn build(&self, inputs: &[BuildInput], sources: &ContentSources, output: &Path) -> Result<()> {
+        let mut files = Vec::new();
+        for input in inputs {
+            files.extend(input.files(sources)?);
+        }
+        files.sort();
+        files.dedup();
 
-        let mut builder = TailwindBuilder::new()
-            .scan_source(&self.source)
-            .output_css(&self.output);
+        let mut builder = TailwindBuilder::new().output_css(output);
+        for file in &files {
+            builder = builder.scan_source(file);
+        }
 
         if let Some(config_path) = &self.config {
             if FileUtils::file_exists(config_path) {
@@ -152,22 +175,14 @@ impl WatchCommand {
             builder = builder.enable_minification();
         }
 
-        builder.build()?;
-
-        let duration = start_time.elapsed();
-
-        // Get output file size
-        let output_size = if FileUtils::file_exists(&self.output) {
-            std::fs::metadata(&self.output)?.len()
-        } else {
-            0
-        };
+        let report = builder.build()?;
 
         if self.verbose {
             LogUtils::success(&format!(
-                "Rebuild completed in {:.2}s ({} bytes)",
-                duration.as_secs_f64(),
-                output_size
+                "Rebuild completed in {:.2}s ({} rules, {} bytes)",
+                report.duration.as_secs_f64(),
+                report.rule_count,
+                report.css_size
             ));
         }
 
@@ -201,11 +216,11 @@ mod tests {
 
         match cli.command {
             crate::Commands::Watch(cmd) => {
-                assert_eq!(cmd.source, PathBuf::from("custom-src"));
-                assert_eq!(cmd.output, PathBuf::from("custom-output.css"));
+                assert_eq!(cmd.source, Some(PathBuf::from("custom-src")));
+                assert_eq!(cmd.output, Some(PathBuf::from("custom-output.css")));
                 assert!(cmd.tree_shake);
                 assert!(cmd.minify);
-                assert_eq!(cmd.debounce, 500);
+                assert_eq!(cmd.debounce, Some(500));
                 assert!(cmd.verbose);
             }
             _ => panic!("Expected Watch command"),
@@ -215,14 +230,19 @@ mod tests {
     #[test]
     fn test_should_rebuild() {
         let cmd = WatchCommand {
-            source: PathBuf::from("src"),
-            output: PathBuf::from("dist/styles.css"),
+            source: None,
+            output: None,
             config: None,
             tree_shake: false,
             minify: false,
-            debounce: 300,
+            debounce: None,
             verbose: false,
         };
+        let sources = FileUtils::load_config(None)
+            .unwrap()
+            .content
+            .sources(FileUtils::config_root(None))
+            .unwrap();
 
         // Test with a Rust file
         let rust_event = Event {
@@ -233,9 +253,18 @@ mod tests {
             attrs: Default::default(),
         };
 
-        assert!(cmd.should_rebuild(&rust_event));
+        assert!(cmd.should_rebuild(&rust_event, &sources));
+
+        // Test with a template named by the default content sources
+        let template_event = Event {
+            kind: EventKind::Create(notify::event::CreateKind::File),
+            paths: vec![PathBuf::from("templates/index.html")],
+            attrs: Default::default(),
+        };
+
+        assert!(cmd.should_rebuild(&template_event, &sources));
 
-        // Test with a non-Rust file
+        // Test with a file outside the content sources
         let non_rust_event = Event {
             kind: EventKind::Modify(notify::event::ModifyKind::Data(
                 notify::event::DataChange::Content,
@@ -244,6 +273,6 @@ mod tests {
             attrs: Default::default(),
         };
 
-        assert!(!cmd.should_rebuild(&non_rust_event));
+        assert!(!cmd.should_rebuild(&non_rust_event, &sources));
     }
 }
@@ -3,6 +3,7 @@
 //! This module provides functionality to parse Rust source files and extract
 //! Tailwind class usage patterns from the abstract syntax tree.
 
+use crate::classes::split_classes;
 use crate::error::{Result, TailwindError};
 use std::collections::HashSet;
 use std::path::Path;
@@ -313,7 +314,7 @@ impl ClassVisitor {
             match method_name {
                 "class" => {
                     if let Ok(class_name) = self.extract_string_literal(arg) {
-                        self.classes.insert(class_name);
+                        self.classes.extend(split_classes(&class_name));
                     }
                 }
                 "padding" => {
@@ -350,7 +351,7 @@ impl ClassVisitor {
     fn extract_direct_class_call(&mut self, call: &syn::ExprCall) {
         if let Some(arg) = call.args.first() {
             if let Ok(class_name) = self.extract_string_literal(arg) {
-                self.classes.insert(class_name);
+                self.classes.extend(split_classes(&class_name));
             }
         }
     }
@@ -448,6 +449,7 @@ mod tests {
                     .class("px-4")
                     .class("py-2")
                     .class("bg-blue-500")
+                    .class("hover:(bg-blue-600 text-white)")
                     .build_string()
             }
         "#;
@@ -461,6 +463,8 @@ mod tests {
         assert!(parser.get_classes().contains("px-4"));
         assert!(parser.get_classes().contains("py-2"));
         assert!(parser.get_classes().contains("bg-blue-500"));
+        assert!(parser.get_classes().contains("hover:bg-blue-600"));
+        assert!(parser.get_classes().contains("hover:text-white"));
     }
 
     #[test]
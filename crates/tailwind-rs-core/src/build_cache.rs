@@ -0,0 +1,435 @@
+//! Persistent build cache
+//!
+//! Remembers what was extracted from each source file, keyed by path and
+//! content hash, and the CSS rule generated for each class, so a build of
+//! unchanged sources neither rescans files nor regenerates rules. Caches are
+//! stored as JSON, by default under `target/tailwind-rs/`, and are discarded
+//! whole when their [`CacheKey`] changes: a new scanner or generator version,
+//! or an edited configuration, starts from scratch.
+//!
+//! Files whose size and modification time match the cache are trusted
+//! without being read. Other files are hashed, so a fresh checkout with new
+//! modification times still hits the cache.
+
+use crate::css_generator::CssRule;
+use crate::error::Result;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::Metadata;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Version of the cache file layout
+const FORMAT_VERSION: u32 = 2;
+
+/// How recent a modification time can be and still be trusted; a write in
+/// the same timestamp tick as the caching would otherwise go unnoticed
+const MTIME_GRANULARITY: Duration = Duration::from_secs(2);
+
+/// Hash of file contents or configuration, for change detection
+///
+/// This is 64-bit FNV-1a, whose output is fixed by its specification, so
+/// saved hashes stay valid across Rust releases (unlike `std`'s hashers).
+pub fn content_hash(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    bytes.iter().fold(OFFSET_BASIS, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Identity of the code and configuration a cache was built with
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheKey {
+    /// Name and version of the extractor that produced the file data
+    pub scanner: String,
+    /// Version of the generator that produced the rules
+    pub generator: String,
+    /// Hash of the configuration the cached data depends on
+    pub config: u64,
+}
+
+impl CacheKey {
+    /// Create a key for an extractor and configuration hash, with this
+    /// crate's generator version
+    pub fn new(scanner: impl Into<String>, config: u64) -> Self {
+        Self {
+            scanner: scanner.into(),
+            generator: crate::VERSION.to_string(),
+            config,
+        }
+    }
+}
+
+/// The version of a file an entry is checked against: its content hash, size
+/// and modification time
+///
+/// Computing it hashes the contents and reads the file's metadata, which
+/// callers sharing a cache between threads can do before locking it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileVersion {
+    hash: u64,
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl FileVersion {
+    /// The version of `content`, read from the file at `path`
+    pub fn of(path: &Path, content: &str) -> Self {
+        Self::new(content, std::fs::metadata(path).ok().as_ref())
+    }
+
+    fn new(content: &str, metadata: Option<&Metadata>) -> Self {
+        Self {
+            hash: content_hash(content.as_bytes()),
+            len: content.len() as u64,
+            modified: metadata.and_then(trusted_mtime),
+        }
+    }
+}
+
+/// Cached data of one file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FileEntry<T> {
+    /// Hash of the contents the data was extracted from
+    hash: u64,
+    /// Size of the file when it was cached
+    len: u64,
+    /// Modification time, when it is old enough to be trusted
+    modified: Option<SystemTime>,
+    data: T,
+}
+
+/// Layout of a cache file
+#[derive(Serialize, Deserialize)]
+struct CacheFile<T> {
+    format: u32,
+    key: CacheKey,
+    files: HashMap<PathBuf, FileEntry<T>>,
+    rules: HashMap<String, CssRule>,
+}
+
+/// Borrowed layout of a cache file, for saving
+#[derive(Serialize)]
+struct CacheFileRef<'a, T> {
+    format: u32,
+    key: &'a CacheKey,
+    files: &'a HashMap<PathBuf, FileEntry<T>>,
+    rules: &'a HashMap<String, CssRule>,
+}
+
+/// Cache of per-file extraction results and generated rules
+#[derive(Debug)]
+pub struct BuildCache<T> {
+    /// Where the cache is saved, if it is persistent
+    path: Option<PathBuf>,
+    key: CacheKey,
+    files: HashMap<PathBuf, FileEntry<T>>,
+    rules: HashMap<String, CssRule>,
+    /// Files and rules looked up since the cache was loaded
+    used_files: HashSet<PathBuf>,
+    used_rules: HashSet<String>,
+    hits: usize,
+    misses: usize,
+    /// Whether there are changes to save
+    dirty: bool,
+}
+
+impl<T> BuildCache<T> {
+    /// Create an empty cache that is kept in memory only
+    pub fn new(key: CacheKey) -> Self {
+        Self {
+            path: None,
+            key,
+            files: HashMap::new(),
+            rules: HashMap::new(),
+            used_files: HashSet::new(),
+            used_rules: HashSet::new(),
+            hits: 0,
+            misses: 0,
+            dirty: false,
+        }
+    }
+
+    /// Directory caches of a project are kept in: `tailwind-rs` in the Cargo
+    /// target directory
+    pub fn default_dir(project_root: &Path) -> PathBuf {
+        let target = std::env::var_os("CARGO_TARGET_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("target"));
+        project_root.join(target).join("tailwind-rs")
+    }
+
+    /// The key the cache was built with
+    pub fn key(&self) -> &CacheKey {
+        &self.key
+    }
+
+    /// Number of cached files
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Whether no files are cached
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Number of file lookups answered from the cache
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Number of file lookups that needed extraction
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Data cached for a file, if it was extracted from `content`
+    pub fn get(&mut self, path: &Path, content: &str) -> Option<&T> {
+        self.get_version(path, FileVersion::of(path, content))
+    }
+
+    /// Data cached for a file, if it was extracted from this version of it
+    pub fn get_version(&mut self, path: &Path, version: FileVersion) -> Option<&T> {
+        self.used_files.insert(path.to_path_buf());
+        let Some(entry) = self
+            .files
+            .get_mut(path)
+            .filter(|entry| entry.hash == version.hash)
+        else {
+            self.misses += 1;
+            return None;
+        };
+        self.hits += 1;
+
+        // Remember the new modification time so the file needn't be read again
+        if entry.modified != version.modified {
+            entry.modified = version.modified;
+            entry.len = version.len;
+            self.dirty = true;
+        }
+        Some(&entry.data)
+    }
+
+    /// Cache data extracted from the contents of a file
+    pub fn insert(&mut self, path: &Path, content: &str, data: T) {
+        self.insert_version(path, FileVersion::of(path, content), data);
+    }
+
+    /// Cache data extracted from a version of a file
+    pub fn insert_version(&mut self, path: &Path, version: FileVersion, data: T) {
+        self.used_files.insert(path.to_path_buf());
+        let entry = FileEntry {
+            hash: version.hash,
+            len: version.len,
+            modified: version.modified,
+            data,
+        };
+        self.files.insert(path.to_path_buf(), entry);
+        self.dirty = true;
+    }
+
+    /// Data for a file, extracting it with `extract` if the file changed
+    pub fn get_or_extract(&mut self, path: &Path, extract: impl FnOnce(&str) -> T) -> Result<&T> {
+        let metadata = std::fs::metadata(path)?;
+        let unchanged = self.files.get(path).is_some_and(|entry| {
+            entry.len == metadata.len()
+                && entry.modified.is_some()
+                && entry.modified == metadata.modified().ok()
+        });
+        if unchanged {
+            self.hits += 1;
+            self.used_files.insert(path.to_path_buf());
+        } else {
+            let content = std::fs::read_to_string(path)?;
+            let version = FileVersion::new(&content, Some(&metadata));
+            if self.get_version(path, version).is_none() {
+                let data = extract(&content);
+                self.insert_version(path, version, data);
+            }
+        }
+        Ok(&self.files[path].data)
+    }
+
+    /// Rule cached for a class
+    pub fn rule(&mut self, class: &str) -> Option<&CssRule> {
+        let rule = self.rules.get(class)?;
+        self.used_rules.insert(class.to_string());
+        Some(rule)
+    }
+
+    /// Cache the rule generated for a class
+    pub fn insert_rule(&mut self, class: &str, rule: CssRule) {
+        self.used_rules.insert(class.to_string());
+        self.rules.insert(class.to_string(), rule);
+        self.dirty = true;
+    }
+
+    /// Remove every cached file and rule
+    pub fn clear(&mut self) {
+        self.files.clear();
+        self.rules.clear();
+        self.dirty = true;
+    }
+
+    /// Drop the files and rules that weren't looked up since the cache was
+    /// loaded, such as deleted files and classes no longer used
+    pub fn prune(&mut self) {
+        let (files, rules) = (self.files.len(), self.rules.len());
+        self.files.retain(|path, _| self.used_files.contains(path));
+        self.rules
+            .retain(|class, _| self.used_rules.contains(class));
+        self.dirty |= files != self.files.len() || rules != self.rules.len();
+    }
+}
+
+impl<T: DeserializeOwned> BuildCache<T> {
+    /// Load the cache saved at `path`
+    ///
+    /// A missing or unreadable cache, or one built with another key, gives an
+    /// empty cache that is saved to `path`.
+    pub fn load(path: impl Into<PathBuf>, key: CacheKey) -> Self {
+        let path = path.into();
+        let saved = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<CacheFile<T>>(&bytes).ok())
+            .filter(|saved| saved.format == FORMAT_VERSION && saved.key == key);
+
+        let mut cache = Self::new(key);
+        if let Some(saved) = saved {
+            cache.files = saved.files;
+            cache.rules = saved.rules;
+        }
+        cache.path = Some(path);
+        cache
+    }
+}
+
+impl<T: Serialize> BuildCache<T> {
+    /// Save the cache if it is persistent and changed
+    pub fn save(&mut self) -> Result<()> {
+        let Some(path) = self.path.as_ref().filter(|_| self.dirty) else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let file = CacheFileRef {
+            format: FORMAT_VERSION,
+            key: &self.key,
+            files: &self.files,
+            rules: &self.rules,
+        };
+        // Write a sibling file and rename it, so readers never see half a cache
+        let temporary = path.with_extension("json.tmp");
+        std::fs::write(&temporary, serde_json::to_vec(&file)?)?;
+        std::fs::rename(&temporary, path)?;
+        self.dirty = false;
+        Ok(())
+    }
+}
+
+/// Modification time of a file, unless it is too recent to be trusted
+fn trusted_mtime(metadata: &Metadata) -> Option<SystemTime> {
+    let modified = metadata.modified().ok()?;
+    let age = SystemTime::now().duration_since(modified).ok()?;
+    (age > MTIME_GRANULARITY).then_some(modified)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css_generator::CssProperty;
+
+    fn rule(selector: &str) -> CssRule {
+        CssRule {
+            selector: selector.to_string(),
+            properties: vec![CssProperty {
+                name: "padding".to_string(),
+                value: "1rem".to_string(),
+                important: false,
+            }],
+            media_query: None,
+            specificity: 10,
+        }
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let source = dir.join("app.rs");
+        std::fs::write(&source, r#"const A: &str = "p-4";"#).unwrap();
+        let cache_path = dir.join("cache.json");
+        let key = CacheKey::new("test", 1);
+
+        let mut cache = BuildCache::load(&cache_path, key.clone());
+        let data = cache
+            .get_or_extract(&source, |_| vec!["p-4".to_string()])
+            .unwrap();
+        assert_eq!(data, &["p-4"]);
+        cache.insert_rule("p-4", rule(".p-4"));
+        cache.save().unwrap();
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+
+        let mut cache = BuildCache::<Vec<String>>::load(&cache_path, key);
+        let data = cache
+            .get_or_extract(&source, |_| panic!("unchanged file was rescanned"))
+            .unwrap();
+        assert_eq!(data, &["p-4"]);
+        assert_eq!(cache.rule("p-4"), Some(&rule(".p-4")));
+        assert_eq!(cache.hits(), 1);
+
+        // A changed file is extracted again
+        std::fs::write(&source, r#"const A: &str = "m-2";"#).unwrap();
+        let data = cache
+            .get_or_extract(&source, |_| vec!["m-2".to_string()])
+            .unwrap();
+        assert_eq!(data, &["m-2"]);
+    }
+
+    #[test]
+    fn test_cache_key_invalidates() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let cache_path = dir.join("cache.json");
+
+        let mut cache = BuildCache::load(&cache_path, CacheKey::new("test", 1));
+        cache.insert(&dir.join("a.rs"), "p-4", vec!["p-4".to_string()]);
+        cache.save().unwrap();
+
+        let cache = BuildCache::<Vec<String>>::load(&cache_path, CacheKey::new("test", 1));
+        assert_eq!(cache.len(), 1);
+        let cache = BuildCache::<Vec<String>>::load(&cache_path, CacheKey::new("test", 2));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_content_hash_is_fnv1a() {
+        assert_eq!(content_hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(content_hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(content_hash(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn test_content_hash_and_prune() {
+        let mut cache = BuildCache::new(CacheKey::new("test", 0));
+        cache.insert(Path::new("a.rs"), "p-4", 1);
+        cache.insert(Path::new("b.rs"), "m-2", 2);
+        cache.insert_rule("p-4", rule(".p-4"));
+
+        let mut cache = BuildCache {
+            used_files: HashSet::new(),
+            used_rules: HashSet::new(),
+            ..cache
+        };
+        assert_eq!(cache.get(Path::new("a.rs"), "p-4"), Some(&1));
+        assert_eq!(cache.get(Path::new("a.rs"), "p-8"), None);
+        cache.prune();
+        assert_eq!(cache.len(), 1);
+        assert!(cache.rule("p-4").is_none());
+    }
+}
@@ -0,0 +1,273 @@
+//! Candidate extraction
+//!
+//! Splits any text into the tokens that could be utility classes, without
+//! knowing the language it is written in. Class strings are found wherever
+//! they appear (attributes, Rust constants, match arms, JSON), and the
+//! generator decides which candidates are real classes:
+//!
+//! ```rust
+//! use tailwind_rs_core::{extract_candidates, resolve_candidates, CssGenerator};
+//!
+//! let source = r#"const BUTTON: &str = "px-4 hover:(bg-blue-600 text-white)";"#;
+//! let generator = CssGenerator::new();
+//! let classes = resolve_candidates(&generator, extract_candidates(source));
+//! assert_eq!(classes, ["hover:bg-blue-600", "hover:text-white", "px-4"]);
+//! ```
+//!
+//! Tokens start after a boundary byte (whitespace, quotes, punctuation) and
+//! may contain variants (`md:hover:`), fractions and opacity modifiers (`/`),
+//! arbitrary values (`w-[calc(100%-1rem)]`, `content-['x']`), CSS variable
+//! shorthands (`bg-(--brand)`) and variant groups (`hover:(a b)`). Brackets
+//! and groups that don't close are rescanned from the next byte, so a class
+//! inside `["p-4", x]` is still found. Candidates may overlap and are meant to
+//! be over-inclusive; validation filters out the rest.
+
+use crate::classes::split_classes;
+use crate::css_generator::CssGenerator;
+use std::collections::{BTreeSet, HashMap};
+use std::ops::Range;
+
+/// A token of text that could be a utility class or variant group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Candidate<'a> {
+    /// The token text
+    pub value: &'a str,
+    /// Byte offset of the token in the text
+    pub start: usize,
+}
+
+impl Candidate<'_> {
+    /// Byte range of the token in the text
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.start + self.value.len()
+    }
+}
+
+/// Iterator over the candidates of a text
+#[derive(Debug, Clone)]
+pub struct Candidates<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+/// Split text into class candidates
+pub fn extract_candidates(text: &str) -> Candidates<'_> {
+    Candidates { text, pos: 0 }
+}
+
+/// Expand candidates and keep the classes the generator resolves
+///
+/// Returns each class once, in sorted order.
+pub fn resolve_candidates<'a>(
+    generator: &CssGenerator,
+    candidates: impl IntoIterator<Item = Candidate<'a>>,
+) -> Vec<String> {
+    CandidateResolver::new(generator).resolve(candidates)
+}
+
+/// Resolves the candidates of many files, checking each class only once
+#[derive(Debug)]
+pub struct CandidateResolver<'g> {
+    generator: &'g CssGenerator,
+    /// Whether each class checked so far resolves
+    resolved: HashMap<String, bool>,
+}
+
+impl<'g> CandidateResolver<'g> {
+    /// Create a resolver validating against a generator
+    pub fn new(generator: &'g CssGenerator) -> Self {
+        Self {
+            generator,
+            resolved: HashMap::new(),
+        }
+    }
+
+    /// Expand candidates and keep the classes the generator resolves
+    ///
+    /// Returns each class once, in sorted order.
+    pub fn resolve<'a>(
+        &mut self,
+        candidates: impl IntoIterator<Item = Candidate<'a>>,
+    ) -> Vec<String> {
+        let values: BTreeSet<&str> = candidates
+            .into_iter()
+            .map(|candidate| candidate.value)
+            .collect();
+        let classes: BTreeSet<String> = values.into_iter().flat_map(split_classes).collect();
+        classes
+            .into_iter()
+            .filter(|class| match self.resolved.get(class) {
+                Some(&resolved) => resolved,
+                None => {
+                    let resolved = self.generator.resolves_class(class);
+                    self.resolved.insert(class.clone(), resolved);
+                    resolved
+                }
+            })
+            .collect()
+    }
+}
+
+impl<'a> Iterator for Candidates<'a> {
+    type Item = Candidate<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.text.as_bytes();
+        while self.pos < bytes.len() {
+            let start = self.pos;
+            let at_boundary = start == 0 || !is_class_byte(bytes[start - 1]);
+            if !at_boundary || !is_start_byte(bytes[start]) {
+                self.pos += 1;
+                continue;
+            }
+
+            let Some(end) = token_end(bytes, start) else {
+                // An unclosed bracket or group; look for classes inside it
+                self.pos += 1;
+                continue;
+            };
+            let value = &self.text[start..end];
+            // Quotes only appear inside brackets, which may hide literals
+            self.pos = if value.contains(['"', '\'', '`']) {
+                start + 1
+            } else {
+                end.max(start + 1)
+            };
+            if value.bytes().any(|b| b.is_ascii_lowercase()) {
+                return Some(Candidate { value, start });
+            }
+        }
+        None
+    }
+}
+
+/// Bytes a class can start with
+fn is_start_byte(b: u8) -> bool {
+    b.is_ascii_lowercase() || b.is_ascii_digit() || matches!(b, b'-' | b'!' | b'[' | b'@' | b'*')
+}
+
+/// Bytes a class can contain outside brackets and groups
+fn is_class_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b':' | b'/' | b'.' | b'!' | b'@' | b'*')
+}
+
+/// End of the token starting at `start`, or `None` if it leaves a bracket or
+/// group open
+fn token_end(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut i = start;
+    let mut groups = 0usize;
+
+    while let Some(&b) = bytes.get(i) {
+        let after = |prefixes: &[u8]| i == start || prefixes.contains(&bytes[i - 1]);
+        match b {
+            // Arbitrary values and properties hold anything but whitespace
+            b'[' if after(b"-:[") => i = bracket_end(bytes, i)?,
+            // Variant groups and CSS variable shorthands
+            b'(' if i > start && after(b":-") => {
+                groups += 1;
+                i += 1;
+            }
+            b')' if groups > 0 => {
+                groups -= 1;
+                i += 1;
+            }
+            b if b.is_ascii_whitespace() && groups > 0 => i += 1,
+            b if is_class_byte(b) => i += 1,
+            _ => break,
+        }
+    }
+    if groups > 0 {
+        return None;
+    }
+
+    // Trailing punctuation belongs to the surrounding text
+    while i > start && matches!(bytes[i - 1], b':' | b'.' | b'/' | b'-') {
+        i -= 1;
+    }
+    Some(i)
+}
+
+/// Index after the `]` closing the bracket at `open`
+fn bracket_end(bytes: &[u8], open: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, &b) in bytes.iter().enumerate().skip(open) {
+        match b {
+            b'[' => depth += 1,
+            b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            b if b.is_ascii_whitespace() => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(text: &str) -> Vec<&str> {
+        extract_candidates(text)
+            .map(|candidate| candidate.value)
+            .collect()
+    }
+
+    #[test]
+    fn test_candidates_in_any_language() {
+        assert_eq!(
+            values(r#"<div class="p-4 md:hover:bg-blue-500/50">"#),
+            ["div", "class", "p-4", "md:hover:bg-blue-500/50"]
+        );
+        assert_eq!(
+            values(r#"Size::Large => "text-lg w-1/2","#),
+            ["text-lg", "w-1/2"]
+        );
+        assert_eq!(
+            values(r#"{"button": "rounded-md -mt-2 !font-bold"}"#),
+            ["button", "rounded-md", "-mt-2", "!font-bold"]
+        );
+    }
+
+    #[test]
+    fn test_candidates_with_brackets_and_groups() {
+        assert_eq!(
+            values("w-[calc(100%-1rem)] bg-(--brand) hover:(underline text-white)"),
+            [
+                "w-[calc(100%-1rem)]",
+                "bg-(--brand)",
+                "hover:(underline text-white)"
+            ]
+        );
+        assert_eq!(
+            values("content-['hi'] [mask-type:alpha]"),
+            ["content-['hi']", "hi", "[mask-type:alpha]"]
+        );
+        // Unclosed brackets and code brackets are scanned inside
+        assert_eq!(values(r#"vec!["p-4", "m-2"]"#), ["vec!", "p-4", "m-2"]);
+        assert_eq!(values("items[0] (flex"), ["items", "flex"]);
+    }
+
+    #[test]
+    fn test_candidate_ranges_and_trailing_punctuation() {
+        let text = "Use p-4. Then mt-2:";
+        let candidates: Vec<_> = extract_candidates(text).collect();
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].value, "p-4");
+        assert_eq!(&text[candidates[0].range()], "p-4");
+        assert_eq!(candidates[1].value, "mt-2");
+    }
+
+    #[test]
+    fn test_resolve_candidates() {
+        let generator = CssGenerator::new();
+        let text = r#"match size { Size::Small => "px-2 text-sm", _ => "flex hover:(underline)" }"#;
+        assert_eq!(
+            resolve_candidates(&generator, extract_candidates(text)),
+            ["flex", "hover:underline", "px-2", "text-sm"]
+        );
+    }
+}
@@ -23,6 +23,7 @@
 //! // Result: "bg-blue-500 text-white px-4 py-2 rounded-lg hover:bg-blue-600"
 //! ```
 
+use super::variant_groups::{has_variant_group, split_classes};
 use super::ClassSet;
 use crate::responsive::Breakpoint;
 
@@ -41,14 +42,24 @@ impl ClassBuilder {
     }
 
     /// Add a base class
+    ///
+    /// Whitespace-separated classes and variant groups
+    /// (`hover:(bg-blue-600 text-white)`) are expanded into individual classes.
     pub fn class(mut self, class: impl Into<String>) -> Self {
-        self.class_set.add_class(class);
+        let class = class.into();
+        if has_variant_group(&class) {
+            self.class_set.add_classes(split_classes(&class));
+        } else {
+            self.class_set.add_class(class);
+        }
         self
     }
 
     /// Add multiple base classes
     pub fn classes(mut self, classes: impl IntoIterator<Item = String>) -> Self {
-        self.class_set.add_classes(classes);
+        for class in classes {
+            self = self.class(class);
+        }
         self
     }
 
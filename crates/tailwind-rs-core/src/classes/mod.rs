@@ -1,13 +1,16 @@
 //! Class management system for tailwind-rs
 //!
 //! This module provides the core class management functionality,
-//! including ClassSet, ClassBuilder, and utility functions.
+//! including ClassSet, ClassBuilder, variant group expansion and utility
+//! functions.
 
 pub mod class_builder;
 pub mod class_set;
 pub mod utilities;
+pub mod variant_groups;
 
 // Re-export main types
 pub use class_builder::ClassBuilder;
 pub use class_set::ClassSet;
 pub use utilities::*;
+pub use variant_groups::{expand_variant_groups, split_classes};
@@ -0,0 +1,142 @@
+//! Variant group expansion
+//!
+//! Variant groups apply a variant prefix to several utilities at once and
+//! expand to canonical Tailwind classes before anything else sees them:
+//!
+//! ```rust
+//! use tailwind_rs_core::classes::expand_variant_groups;
+//!
+//! assert_eq!(
+//!     expand_variant_groups("p-4 hover:(bg-blue-600 text-white) md:(flex gap-4)"),
+//!     "p-4 hover:bg-blue-600 hover:text-white md:flex md:gap-4"
+//! );
+//! ```
+//!
+//! Groups nest (`md:(hover:(underline))` expands to `md:hover:underline`), and
+//! parentheses inside arbitrary values (`w-[calc(100%-1rem)]`) or CSS variable
+//! shorthands (`bg-(--brand)`) are left alone. Parsing is lenient: an
+//! unclosed group ends with the input and a stray `)` is dropped.
+
+/// Split a class string into individual classes, expanding variant groups
+pub fn split_classes(input: &str) -> Vec<String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut classes = Vec::new();
+    let mut pos = 0;
+    expand_group(&chars, &mut pos, "", false, &mut classes);
+    classes
+}
+
+/// Expand the variant groups of a class string, e.g. for server-side rendering
+pub fn expand_variant_groups(input: &str) -> String {
+    split_classes(input).join(" ")
+}
+
+/// Check whether a class string needs expansion (more than one class or a group)
+pub fn has_variant_group(input: &str) -> bool {
+    input.contains('(') || input.contains(char::is_whitespace)
+}
+
+/// Expand the classes of one group, prefixing each with `prefix`
+///
+/// Returns after the group's closing parenthesis when `nested`, or at the end
+/// of the input.
+fn expand_group(
+    chars: &[char],
+    pos: &mut usize,
+    prefix: &str,
+    nested: bool,
+    out: &mut Vec<String>,
+) {
+    while *pos < chars.len() {
+        let c = chars[*pos];
+        if c.is_whitespace() {
+            *pos += 1;
+            continue;
+        }
+        if c == ')' {
+            *pos += 1;
+            if nested {
+                return;
+            }
+            continue;
+        }
+
+        let mut token = String::new();
+        // Depth of brackets and of parentheses that don't open a group
+        let mut depth = 0usize;
+        while *pos < chars.len() {
+            let c = chars[*pos];
+            match c {
+                '(' if depth == 0 && (token.is_empty() || token.ends_with(':')) => {
+                    *pos += 1;
+                    let prefix = format!("{}{}", prefix, token);
+                    expand_group(chars, pos, &prefix, true, out);
+                    token.clear();
+                    break;
+                }
+                '[' | '(' => depth += 1,
+                ']' | ')' if depth > 0 => depth -= 1,
+                // Closes the enclosing group
+                ')' => break,
+                c if c.is_whitespace() && depth == 0 => break,
+                _ => {}
+            }
+            token.push(c);
+            *pos += 1;
+        }
+
+        if !token.is_empty() {
+            out.push(format!("{}{}", prefix, token));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_groups() {
+        assert_eq!(
+            split_classes("hover:(bg-blue-600 text-white) p-4"),
+            vec!["hover:bg-blue-600", "hover:text-white", "p-4"]
+        );
+        assert_eq!(
+            split_classes("md:(flex gap-4 hover:(underline text-red-500))"),
+            vec![
+                "md:flex",
+                "md:gap-4",
+                "md:hover:underline",
+                "md:hover:text-red-500"
+            ]
+        );
+        assert_eq!(
+            expand_variant_groups("  dark:md:( p-2 )\n m-1 "),
+            "dark:md:p-2 m-1"
+        );
+    }
+
+    #[test]
+    fn test_arbitrary_values_are_not_groups() {
+        assert_eq!(
+            split_classes("w-[calc(100%-1rem)] md:(w-[calc(50%-(2rem))] bg-(--brand))"),
+            vec![
+                "w-[calc(100%-1rem)]",
+                "md:w-[calc(50%-(2rem))]",
+                "md:bg-(--brand)"
+            ]
+        );
+        assert_eq!(
+            split_classes("[&:nth-child(3)]:(p-4 m-2)"),
+            vec!["[&:nth-child(3)]:p-4", "[&:nth-child(3)]:m-2"]
+        );
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        assert_eq!(split_classes("hover:(p-4"), vec!["hover:p-4"]);
+        assert_eq!(split_classes("p-4) m-2"), vec!["p-4", "m-2"]);
+        assert_eq!(split_classes("hover:()"), Vec::<String>::new());
+        assert!(split_classes("").is_empty());
+    }
+}
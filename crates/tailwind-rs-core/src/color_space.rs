@@ -0,0 +1,763 @@
+//! Color space conversions for tailwind-rs
+//!
+//! This module does the color math behind theme colors: parsing CSS colors
+//! (`#0f766e`, `rgb()`, `hsl()`, `oklab()`, `oklch()`), converting between
+//! sRGB and OKLCH, mapping out-of-gamut colors back into sRGB, generating
+//! 50–950 palettes from a single seed color, and formatting colors for the
+//! selected [`ColorFormat`].
+//!
+//! ## Example
+//!
+//! ```rust
+//! use tailwind_rs_core::color_space::{generate_palette, ColorFormat, Oklch};
+//!
+//! let seed: Oklch = "#0f766e".parse().unwrap();
+//! let palette = generate_palette(seed);
+//! assert_eq!(palette.len(), 11);
+//! assert_eq!(palette[0].0, 50);
+//! assert!(palette[0].1.to_css(ColorFormat::Oklch).starts_with("oklch("));
+//! ```
+
+use crate::error::{Result, TailwindError};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// How colors are written to the generated CSS
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ColorFormat {
+    /// `#0f766e` (`#0f766e80` with alpha)
+    Hex,
+    /// `rgb(15 118 110)` (`rgb(15 118 110 / 0.5)` with alpha)
+    Rgb,
+    /// `oklch(51.1% 0.096 186.391)`, as in Tailwind v4's default palette
+    Oklch,
+    /// OKLCH inside `@supports (color: oklch(0 0 0))`, with an sRGB hex fallback
+    OklchWithFallback,
+}
+
+impl ColorFormat {
+    /// The feature query guarding OKLCH values in [`ColorFormat::OklchWithFallback`]
+    pub const SUPPORTS_QUERY: &'static str = "(color: oklch(0 0 0))";
+}
+
+impl FromStr for ColorFormat {
+    type Err = TailwindError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "hex" => Ok(Self::Hex),
+            "rgb" => Ok(Self::Rgb),
+            "oklch" => Ok(Self::Oklch),
+            "oklch-with-fallback" => Ok(Self::OklchWithFallback),
+            _ => Err(TailwindError::validation(format!(
+                "Unknown color format '{}' (expected hex, rgb, oklch or oklch-with-fallback)",
+                s
+            ))),
+        }
+    }
+}
+
+/// Lightness of each palette shade, following Tailwind v4's default palette
+pub const PALETTE_LIGHTNESS: [(u16, f64); 11] = [
+    (50, 0.971),
+    (100, 0.936),
+    (200, 0.885),
+    (300, 0.808),
+    (400, 0.704),
+    (500, 0.637),
+    (600, 0.577),
+    (700, 0.505),
+    (800, 0.444),
+    (900, 0.396),
+    (950, 0.258),
+];
+
+/// Chroma of each shade relative to the most saturated one
+const PALETTE_CHROMA: [f64; 11] = [
+    0.053, 0.13, 0.25, 0.47, 0.78, 0.97, 1.0, 0.87, 0.72, 0.58, 0.38,
+];
+
+/// A gamma-encoded sRGB color, components in `0.0..=1.0`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Srgb {
+    pub r: f64,
+    pub g: f64,
+    pub b: f64,
+    pub alpha: f64,
+}
+
+/// An HSL color, hue in degrees and saturation/lightness in `0.0..=1.0`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hsl {
+    pub h: f64,
+    pub s: f64,
+    pub l: f64,
+    pub alpha: f64,
+}
+
+/// A color in the OKLab space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklab {
+    pub l: f64,
+    pub a: f64,
+    pub b: f64,
+    pub alpha: f64,
+}
+
+/// A color in the OKLCH space, hue in degrees
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    pub l: f64,
+    pub c: f64,
+    pub h: f64,
+    pub alpha: f64,
+}
+
+impl Srgb {
+    /// Create an opaque color from 8-bit channels
+    pub fn from_rgb8(r: u8, g: u8, b: u8) -> Self {
+        Self {
+            r: f64::from(r) / 255.0,
+            g: f64::from(g) / 255.0,
+            b: f64::from(b) / 255.0,
+            alpha: 1.0,
+        }
+    }
+
+    /// Parse `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let digits = hex.strip_prefix('#')?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |index: usize, width: usize| {
+            let value = u8::from_str_radix(&digits[index * width..(index + 1) * width], 16).ok()?;
+            Some(if width == 1 { value * 17 } else { value })
+        };
+
+        let width = match digits.len() {
+            3 | 4 => 1,
+            6 | 8 => 2,
+            _ => return None,
+        };
+        let mut color = Self::from_rgb8(channel(0, width)?, channel(1, width)?, channel(2, width)?);
+        if digits.len() == 4 || digits.len() == 8 {
+            color.alpha = f64::from(channel(3, width)?) / 255.0;
+        }
+        Some(color)
+    }
+
+    /// The 8-bit channels, clamped to the gamut
+    pub fn to_rgb8(&self) -> [u8; 3] {
+        [self.r, self.g, self.b].map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+
+    /// Format as `#rrggbb`, or `#rrggbbaa` when translucent
+    pub fn to_hex(&self) -> String {
+        let [r, g, b] = self.to_rgb8();
+        let mut hex = format!("#{:02x}{:02x}{:02x}", r, g, b);
+        if self.alpha < 1.0 {
+            hex.push_str(&format!(
+                "{:02x}",
+                (self.alpha.clamp(0.0, 1.0) * 255.0).round() as u8
+            ));
+        }
+        hex
+    }
+
+    /// The color with each channel clamped to the gamut
+    pub fn clamped(&self) -> Self {
+        Self {
+            r: self.r.clamp(0.0, 1.0),
+            g: self.g.clamp(0.0, 1.0),
+            b: self.b.clamp(0.0, 1.0),
+            alpha: self.alpha,
+        }
+    }
+
+    /// Check whether the color is inside the sRGB gamut
+    pub fn in_gamut(&self) -> bool {
+        const EPSILON: f64 = 1e-4;
+        [self.r, self.g, self.b]
+            .iter()
+            .all(|channel| (-EPSILON..=1.0 + EPSILON).contains(channel))
+    }
+
+    /// Convert to OKLab
+    pub fn to_oklab(&self) -> Oklab {
+        let [r, g, b] = [self.r, self.g, self.b].map(srgb_to_linear);
+
+        let l = (0.412_221_470_8 * r + 0.536_332_536_3 * g + 0.051_445_992_9 * b).cbrt();
+        let m = (0.211_903_498_2 * r + 0.680_699_545_1 * g + 0.107_396_956_6 * b).cbrt();
+        let s = (0.088_302_461_9 * r + 0.281_718_837_6 * g + 0.629_978_700_5 * b).cbrt();
+
+        Oklab {
+            l: 0.210_454_255_3 * l + 0.793_617_785_0 * m - 0.004_072_046_8 * s,
+            a: 1.977_998_495_1 * l - 2.428_592_205_0 * m + 0.450_593_709_9 * s,
+            b: 0.025_904_037_1 * l + 0.782_771_766_2 * m - 0.808_675_766_0 * s,
+            alpha: self.alpha,
+        }
+    }
+
+    /// Convert to HSL
+    pub fn to_hsl(&self) -> Hsl {
+        let max = self.r.max(self.g).max(self.b);
+        let min = self.r.min(self.g).min(self.b);
+        let l = (max + min) / 2.0;
+        let delta = max - min;
+        if delta == 0.0 {
+            return Hsl {
+                h: 0.0,
+                s: 0.0,
+                l,
+                alpha: self.alpha,
+            };
+        }
+
+        let s = delta / (1.0 - (2.0 * l - 1.0).abs());
+        let h = if max == self.r {
+            ((self.g - self.b) / delta).rem_euclid(6.0)
+        } else if max == self.g {
+            (self.b - self.r) / delta + 2.0
+        } else {
+            (self.r - self.g) / delta + 4.0
+        };
+        Hsl {
+            h: h * 60.0,
+            s,
+            l,
+            alpha: self.alpha,
+        }
+    }
+}
+
+impl Hsl {
+    /// Convert to sRGB
+    pub fn to_srgb(&self) -> Srgb {
+        let chroma = (1.0 - (2.0 * self.l - 1.0).abs()) * self.s;
+        let h = self.h.rem_euclid(360.0) / 60.0;
+        let x = chroma * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let m = self.l - chroma / 2.0;
+        Srgb {
+            r: r + m,
+            g: g + m,
+            b: b + m,
+            alpha: self.alpha,
+        }
+    }
+}
+
+impl Oklab {
+    /// Convert to sRGB (possibly out of gamut)
+    pub fn to_srgb(&self) -> Srgb {
+        let l = (self.l + 0.396_337_777_4 * self.a + 0.215_803_757_3 * self.b).powi(3);
+        let m = (self.l - 0.105_561_345_8 * self.a - 0.063_854_172_8 * self.b).powi(3);
+        let s = (self.l - 0.089_484_177_5 * self.a - 1.291_485_548_0 * self.b).powi(3);
+
+        Srgb {
+            r: linear_to_srgb(4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s),
+            g: linear_to_srgb(-1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s),
+            b: linear_to_srgb(-0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701_0 * s),
+            alpha: self.alpha,
+        }
+    }
+
+    /// Convert to OKLCH
+    pub fn to_oklch(&self) -> Oklch {
+        let c = self.a.hypot(self.b);
+        let h = if c < 1e-6 {
+            0.0
+        } else {
+            self.b.atan2(self.a).to_degrees().rem_euclid(360.0)
+        };
+        Oklch {
+            l: self.l,
+            c,
+            h,
+            alpha: self.alpha,
+        }
+    }
+}
+
+impl Oklch {
+    /// Create an opaque color
+    pub fn new(l: f64, c: f64, h: f64) -> Self {
+        Self {
+            l,
+            c,
+            h,
+            alpha: 1.0,
+        }
+    }
+
+    /// The same color with a different alpha
+    pub fn with_alpha(self, alpha: f64) -> Self {
+        Self {
+            alpha: alpha.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// Convert to OKLab
+    pub fn to_oklab(&self) -> Oklab {
+        let (sin, cos) = self.h.to_radians().sin_cos();
+        Oklab {
+            l: self.l,
+            a: self.c * cos,
+            b: self.c * sin,
+            alpha: self.alpha,
+        }
+    }
+
+    /// Convert to sRGB, reducing chroma until the color fits the gamut
+    ///
+    /// Lightness and hue are kept, so wide-gamut colors degrade to the
+    /// closest-looking sRGB color instead of shifting hue when clipped.
+    pub fn to_srgb(&self) -> Srgb {
+        let srgb = self.to_oklab().to_srgb();
+        if srgb.in_gamut() {
+            return srgb.clamped();
+        }
+
+        let (mut low, mut high) = (0.0, self.c);
+        for _ in 0..24 {
+            let mid = (low + high) / 2.0;
+            let candidate = Self { c: mid, ..*self };
+            if candidate.to_oklab().to_srgb().in_gamut() {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        Self { c: low, ..*self }.to_oklab().to_srgb().clamped()
+    }
+
+    /// Check whether the color is displayable in sRGB without gamut mapping
+    pub fn in_srgb_gamut(&self) -> bool {
+        self.to_oklab().to_srgb().in_gamut()
+    }
+
+    /// Format the color for a [`ColorFormat`]
+    ///
+    /// [`ColorFormat::OklchWithFallback`] formats as OKLCH; the sRGB
+    /// fallback is [`ColorFormat::Hex`].
+    pub fn to_css(&self, format: ColorFormat) -> String {
+        let alpha = || {
+            if self.alpha < 1.0 {
+                format!(" / {}", format_number(self.alpha, 3))
+            } else {
+                String::new()
+            }
+        };
+        match format {
+            ColorFormat::Hex => self.to_srgb().to_hex(),
+            ColorFormat::Rgb => {
+                let [r, g, b] = self.to_srgb().to_rgb8();
+                format!("rgb({} {} {}{})", r, g, b, alpha())
+            }
+            ColorFormat::Oklch | ColorFormat::OklchWithFallback => format!(
+                "oklch({}% {} {}{})",
+                format_number(self.l * 100.0, 1),
+                format_number(self.c, 3),
+                format_number(if self.c < 1e-4 { 0.0 } else { self.h }, 3),
+                alpha()
+            ),
+        }
+    }
+}
+
+impl From<Srgb> for Oklch {
+    fn from(color: Srgb) -> Self {
+        color.to_oklab().to_oklch()
+    }
+}
+
+impl FromStr for Oklch {
+    type Err = TailwindError;
+
+    /// Parse a hex, `rgb()`, `hsl()`, `oklab()` or `oklch()` color
+    fn from_str(s: &str) -> Result<Self> {
+        parse_color(s).ok_or_else(|| TailwindError::validation(format!("Invalid color: '{}'", s)))
+    }
+}
+
+impl fmt::Display for Oklch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_css(ColorFormat::Oklch))
+    }
+}
+
+/// Generate a 50–950 palette around a seed color
+///
+/// Shades follow the lightness ramp of Tailwind's default palette so
+/// palettes generated from different seeds line up. The seed itself replaces
+/// the shade closest in lightness, and chroma tapers towards both ends of
+/// the ramp, relative to the seed's chroma at that position.
+pub fn generate_palette(seed: Oklch) -> Vec<(u16, Oklch)> {
+    let anchor = PALETTE_LIGHTNESS
+        .iter()
+        .enumerate()
+        .min_by(|(_, (_, a)), (_, (_, b))| (a - seed.l).abs().total_cmp(&(b - seed.l).abs()))
+        .map(|(index, _)| index)
+        .unwrap_or(5);
+    let peak_chroma = seed.c / PALETTE_CHROMA[anchor];
+
+    PALETTE_LIGHTNESS
+        .iter()
+        .zip(PALETTE_CHROMA)
+        .enumerate()
+        .map(|(index, ((shade, lightness), chroma))| {
+            if index == anchor {
+                return (*shade, seed.with_alpha(1.0));
+            }
+            let color = Oklch::new(*lightness, peak_chroma * chroma, seed.h);
+            // Keep every shade displayable: reduce chroma into sRGB
+            (*shade, Oklch::from(color.to_srgb()))
+        })
+        .collect()
+}
+
+/// Apply an opacity (`0.0..=1.0`) to a CSS color value
+///
+/// Parsable colors get their alpha replaced; anything else (`var(--color-x)`,
+/// `currentColor`) is mixed with `transparent`, as Tailwind v4 does.
+pub fn apply_opacity(color: &str, opacity: f64, format: ColorFormat) -> String {
+    match parse_color(color) {
+        Some(parsed) => parsed.with_alpha(parsed.alpha * opacity).to_css(format),
+        None => format!(
+            "color-mix(in oklab, {} {}%, transparent)",
+            color,
+            format_number(opacity * 100.0, 2)
+        ),
+    }
+}
+
+/// Parse an opacity modifier (`50`, `[0.35]`, `[35%]`) to `0.0..=1.0`
+pub fn parse_opacity_modifier(modifier: &str) -> Option<f64> {
+    let opacity = match modifier
+        .strip_prefix('[')
+        .and_then(|inner| inner.strip_suffix(']'))
+    {
+        Some(inner) => match inner.strip_suffix('%') {
+            Some(percent) => percent.parse::<f64>().ok()? / 100.0,
+            None => inner.parse::<f64>().ok()?,
+        },
+        None => {
+            if !modifier.chars().all(|c| c.is_ascii_digit() || c == '.') {
+                return None;
+            }
+            modifier.parse::<f64>().ok()? / 100.0
+        }
+    };
+    (0.0..=1.0).contains(&opacity).then_some(opacity)
+}
+
+/// Rewrite the literal colors in a CSS value for a [`ColorFormat`]
+///
+/// Returns `None` when the value contains no literal colors.
+pub fn convert_colors(value: &str, format: ColorFormat) -> Option<String> {
+    const FUNCTIONS: [&str; 6] = ["rgba(", "rgb(", "hsla(", "hsl(", "oklab(", "oklch("];
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || matches!(c, '-' | '_');
+
+    let mut output = String::new();
+    let mut converted = false;
+    let mut index = 0;
+    while index < value.len() {
+        let rest = &value[index..];
+        let preceded_by_ident = value[..index].chars().next_back().is_some_and(is_ident);
+
+        let literal_len = if rest.starts_with('#') && !preceded_by_ident {
+            let digits = rest[1..]
+                .chars()
+                .take_while(char::is_ascii_hexdigit)
+                .count();
+            let boundary = !rest[1 + digits..].starts_with(is_ident);
+            (matches!(digits, 3 | 4 | 6 | 8) && boundary).then_some(1 + digits)
+        } else if !preceded_by_ident && FUNCTIONS.iter().any(|f| rest.starts_with(f)) {
+            rest.find(')')
+                .map(|end| end + 1)
+                .filter(|end| !rest[..*end].contains("var("))
+        } else {
+            None
+        };
+
+        match literal_len.and_then(|len| Some((len, parse_color(&rest[..len])?))) {
+            Some((len, color)) => {
+                output.push_str(&color.to_css(format));
+                converted = true;
+                index += len;
+            }
+            None => {
+                let ch = rest.chars().next().unwrap_or_default();
+                output.push(ch);
+                index += ch.len_utf8();
+            }
+        }
+    }
+
+    converted.then_some(output)
+}
+
+/// Parse any supported CSS color
+fn parse_color(input: &str) -> Option<Oklch> {
+    let input = input.trim();
+    if input.starts_with('#') {
+        return Srgb::from_hex(input).map(Oklch::from);
+    }
+
+    let (name, arguments) = input.strip_suffix(')')?.split_once('(')?;
+    let (channels, alpha) = parse_arguments(arguments)?;
+    let [first, second, third] = channels;
+    let alpha = match alpha {
+        Some(alpha) => number_or_percent(alpha, 1.0)?,
+        None => 1.0,
+    };
+
+    let color = match name.trim().to_ascii_lowercase().as_str() {
+        "rgb" | "rgba" => Oklch::from(Srgb {
+            r: number_or_percent(first, 255.0)? / 255.0,
+            g: number_or_percent(second, 255.0)? / 255.0,
+            b: number_or_percent(third, 255.0)? / 255.0,
+            alpha,
+        }),
+        "hsl" | "hsla" => Oklch::from(
+            Hsl {
+                h: hue(first)?,
+                s: number_or_percent(second, 100.0)? / 100.0,
+                l: number_or_percent(third, 100.0)? / 100.0,
+                alpha,
+            }
+            .to_srgb(),
+        ),
+        "oklab" => Oklab {
+            l: number_or_percent(first, 1.0)?,
+            a: number_or_percent(second, 0.4)?,
+            b: number_or_percent(third, 0.4)?,
+            alpha,
+        }
+        .to_oklch(),
+        "oklch" => Oklch {
+            l: number_or_percent(first, 1.0)?,
+            c: number_or_percent(second, 0.4)?,
+            h: hue(third)?,
+            alpha,
+        },
+        _ => return None,
+    };
+    Some(color)
+}
+
+/// Split `r, g, b, a` or `r g b / a` into channels and alpha
+fn parse_arguments(arguments: &str) -> Option<([&str; 3], Option<&str>)> {
+    let (channels, alpha) = match arguments.split_once('/') {
+        Some((channels, alpha)) => (channels, Some(alpha.trim())),
+        None => (arguments, None),
+    };
+    let parts: Vec<&str> = channels
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|part| !part.is_empty())
+        .collect();
+
+    match (parts.as_slice(), alpha) {
+        ([first, second, third], alpha) => Some(([first, second, third], alpha)),
+        ([first, second, third, alpha], None) => Some(([first, second, third], Some(alpha))),
+        _ => None,
+    }
+}
+
+/// Parse a number, or a percentage of `full_scale`
+fn number_or_percent(value: &str, full_scale: f64) -> Option<f64> {
+    let value = value.trim();
+    if value == "none" {
+        return Some(0.0);
+    }
+    match value.strip_suffix('%') {
+        Some(percent) => Some(percent.parse::<f64>().ok()? / 100.0 * full_scale),
+        None => value.parse().ok(),
+    }
+}
+
+/// Parse a hue in degrees (`250`, `250deg`, `0.5turn`)
+fn hue(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if value == "none" {
+        return Some(0.0);
+    }
+    if let Some(turns) = value.strip_suffix("turn") {
+        return Some(turns.parse::<f64>().ok()? * 360.0);
+    }
+    if let Some(radians) = value.strip_suffix("rad") {
+        return Some(radians.parse::<f64>().ok()?.to_degrees());
+    }
+    value.strip_suffix("deg").unwrap_or(value).parse().ok()
+}
+
+fn srgb_to_linear(channel: f64) -> f64 {
+    if channel.abs() <= 0.040_45 {
+        channel / 12.92
+    } else {
+        channel.signum() * ((channel.abs() + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(channel: f64) -> f64 {
+    if channel.abs() <= 0.003_130_8 {
+        channel * 12.92
+    } else {
+        channel.signum() * (1.055 * channel.abs().powf(1.0 / 2.4) - 0.055)
+    }
+}
+
+/// Format a number with at most `decimals` decimals and no trailing zeros
+fn format_number(number: f64, decimals: usize) -> String {
+    let formatted = format!("{:.*}", decimals, number);
+    let trimmed = if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &formatted
+    };
+    match trimmed {
+        "-0" => "0".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64, tolerance: f64) -> bool {
+        (a - b).abs() <= tolerance
+    }
+
+    #[test]
+    fn test_conversions_round_trip() {
+        // Tailwind v4's blue-500 is oklch(62.3% 0.214 259.815) ≈ #2b7fff
+        let blue: Oklch = "#3b82f6".parse().unwrap();
+        assert!(close(blue.l, 0.623, 0.005));
+        assert!(close(blue.c, 0.188, 0.005));
+        assert!(close(blue.h, 259.8, 1.0));
+        assert_eq!(blue.to_css(ColorFormat::Hex), "#3b82f6");
+        assert_eq!(blue.to_css(ColorFormat::Rgb), "rgb(59 130 246)");
+
+        for input in [
+            "rgb(59, 130, 246)",
+            "rgba(59 130 246 / 1)",
+            "hsl(217.2 91.2% 59.8%)",
+            "oklch(62.3% 0.188 259.8)",
+        ] {
+            let color: Oklch = input.parse().unwrap();
+            assert!(
+                close(color.to_oklab().l, blue.l, 0.01),
+                "{} -> {:?}",
+                input,
+                color
+            );
+            assert_eq!(color.to_css(ColorFormat::Hex).len(), 7, "{}", input);
+        }
+
+        let translucent: Oklch = "#0f766e80".parse().unwrap();
+        assert!(close(translucent.alpha, 0.502, 0.001));
+        assert_eq!(translucent.to_css(ColorFormat::Hex), "#0f766e80");
+        assert!("#12345".parse::<Oklch>().is_err());
+        assert!("rgb(1, 2)".parse::<Oklch>().is_err());
+    }
+
+    #[test]
+    fn test_gamut_mapping_keeps_hue() {
+        // Display P3 green, far outside sRGB
+        let wide = Oklch::new(0.85, 0.37, 142.0);
+        assert!(!wide.in_srgb_gamut());
+        let mapped = Oklch::from(wide.to_srgb());
+        assert!(mapped.in_srgb_gamut());
+        assert!(close(mapped.h, 142.0, 2.0));
+        assert!(close(mapped.l, 0.85, 0.02));
+    }
+
+    #[test]
+    fn test_palette_from_seed() {
+        let seed: Oklch = "#0f766e".parse().unwrap();
+        let palette = generate_palette(seed);
+        let shades: Vec<u16> = palette.iter().map(|(shade, _)| *shade).collect();
+        assert_eq!(
+            shades,
+            [50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950]
+        );
+
+        // The seed is kept exactly and lightness decreases monotonically
+        assert!(palette
+            .iter()
+            .any(|(_, color)| color.to_css(ColorFormat::Hex) == "#0f766e"));
+        assert!(palette.windows(2).all(|pair| pair[0].1.l > pair[1].1.l));
+        assert!(palette.iter().all(|(_, color)| color.in_srgb_gamut()));
+        assert!(palette
+            .iter()
+            .all(|(_, color)| color.c < 0.01 || close(color.h, seed.h, 2.0)));
+    }
+
+    #[test]
+    fn test_convert_colors_and_opacity() {
+        assert_eq!(
+            convert_colors("0 1px 3px 0 #000000", ColorFormat::Rgb).as_deref(),
+            Some("0 1px 3px 0 rgb(0 0 0)")
+        );
+        assert_eq!(
+            convert_colors("rgba(59, 130, 246, 0.5)", ColorFormat::Hex).as_deref(),
+            Some("#3b82f680")
+        );
+        assert!(convert_colors("var(--color-blue-500)", ColorFormat::Oklch).is_none());
+        assert!(convert_colors("url(#mask)", ColorFormat::Oklch).is_none());
+        assert!(convert_colors("rgb(var(--x))", ColorFormat::Oklch).is_none());
+
+        assert_eq!(parse_opacity_modifier("50"), Some(0.5));
+        assert_eq!(parse_opacity_modifier("[35%]"), Some(0.35));
+        assert_eq!(parse_opacity_modifier("[0.35]"), Some(0.35));
+        assert!(parse_opacity_modifier("150").is_none());
+        assert_eq!(
+            apply_opacity("#3b82f6", 0.5, ColorFormat::Rgb),
+            "rgb(59 130 246 / 0.5)"
+        );
+        assert_eq!(
+            apply_opacity("var(--color-blue-500)", 0.5, ColorFormat::Rgb),
+            "color-mix(in oklab, var(--color-blue-500) 50%, transparent)"
+        );
+    }
+
+    #[test]
+    fn test_generator_color_formats() {
+        use crate::css_generator::{CssGenerationConfig, CssGenerator};
+
+        let generator = |color_format| {
+            let mut generator = CssGenerator::with_config(CssGenerationConfig {
+                color_format: Some(color_format),
+                ..Default::default()
+            });
+            generator.add_class("bg-blue-500").unwrap();
+            generator.add_class("p-4").unwrap();
+            generator
+        };
+
+        let css = generator(ColorFormat::Oklch).generate_css();
+        assert!(css.contains("background-color: oklch(62.3% 0.188 259."));
+
+        let css = generator(ColorFormat::OklchWithFallback).generate_css();
+        let (fallback, modern) = css.split_once("@supports (color: oklch(0 0 0)) {").unwrap();
+        assert!(fallback.contains("background-color: #3b82f6;"));
+        assert!(fallback.contains("padding: 1rem;"));
+        assert!(modern.contains("background-color: oklch("));
+        assert!(!modern.contains("padding"));
+
+        let minified = generator(ColorFormat::OklchWithFallback).generate_minified_css();
+        assert!(minified.contains("@supports (color: oklch(0 0 0)){.bg-blue-500"));
+    }
+}
@@ -1,6 +1,11 @@
 //! Build configuration for tailwind-rs
 
+use super::content::ContentSources;
+use crate::color_space::ColorFormat;
+use crate::error::{self, TailwindError};
+use globset::{GlobBuilder, GlobMatcher};
 use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 
 /// Build configuration for tailwind-rs
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -21,6 +26,71 @@ pub struct BuildConfig {
     pub additional_css: Vec<String>,
     /// PostCSS plugins
     pub postcss_plugins: Vec<String>,
+    /// Cascade layers to emit (`theme`, `base`, `components`, `utilities`)
+    #[serde(default = "default_layers")]
+    pub layers: Vec<String>,
+    /// Reference theme tokens through CSS custom properties (`var(--color-blue-500)`)
+    #[serde(default)]
+    pub theme_variables: bool,
+    /// How colors are written (`hex`, `rgb`, `oklch`, `oklch-with-fallback`);
+    /// unset keeps the colors as generated
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color_format: Option<ColorFormat>,
+    /// Plain CSS of a CSS entry file, emitted after the generated rules
+    #[serde(skip)]
+    pub passthrough: String,
+}
+
+/// A `build.input` entry: the directory (or file) it names, and the glob
+/// files found there have to match
+#[derive(Debug, Clone)]
+pub struct BuildInput {
+    /// Directory or file scanned for the input
+    pub path: PathBuf,
+    filter: Option<GlobMatcher>,
+}
+
+impl PartialEq for BuildInput {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+            && self.filter.as_ref().map(GlobMatcher::glob)
+                == other.filter.as_ref().map(GlobMatcher::glob)
+    }
+}
+
+impl BuildInput {
+    /// An input taking every file under `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            filter: None,
+        }
+    }
+
+    /// Whether a file found under the input's path belongs to the input
+    pub fn matches(&self, file: &Path) -> bool {
+        match &self.filter {
+            Some(filter) => file
+                .strip_prefix(&self.path)
+                .is_ok_and(|relative| filter.is_match(relative)),
+            None => true,
+        }
+    }
+
+    /// The input's files among those the content sources name
+    pub fn files(&self, sources: &ContentSources) -> error::Result<Vec<PathBuf>> {
+        let mut files = sources.files(&self.path)?;
+        files.retain(|file| self.matches(file));
+        Ok(files)
+    }
+}
+
+/// The layers emitted when none are configured
+fn default_layers() -> Vec<String> {
+    super::css_config::DEFAULT_LAYERS
+        .iter()
+        .map(|layer| layer.to_string())
+        .collect()
 }
 
 impl BuildConfig {
@@ -35,6 +105,10 @@ impl BuildConfig {
             purge: true,
             additional_css: Vec::new(),
             postcss_plugins: Vec::new(),
+            layers: default_layers(),
+            theme_variables: false,
+            color_format: None,
+            passthrough: String::new(),
         }
     }
 
@@ -98,6 +172,60 @@ impl BuildConfig {
         self.postcss_plugins.push(plugin.into());
     }
 
+    /// The inputs, resolved against the project root (the directory of the
+    /// configuration file)
+    ///
+    /// Each input is scanned from its path up to the first glob component,
+    /// and the rest of a glob filters the files found there, so
+    /// `src/components/*.rs` scans the `.rs` files of `src/components` only.
+    pub fn inputs(&self, root: &Path) -> error::Result<Vec<BuildInput>> {
+        let mut inputs: Vec<BuildInput> = Vec::new();
+        for input in &self.input {
+            let is_glob = |component: &std::path::Component| {
+                component
+                    .as_os_str()
+                    .to_string_lossy()
+                    .contains(['*', '?', '[', '{'])
+            };
+            let components: Vec<_> = Path::new(input).components().collect();
+            let split = components
+                .iter()
+                .position(is_glob)
+                .unwrap_or(components.len());
+            let dir: PathBuf = components[..split].iter().collect();
+            let pattern: PathBuf = components[split..].iter().collect();
+
+            let path = if root == Path::new(".") || dir.is_absolute() {
+                dir
+            } else {
+                root.join(dir)
+            };
+            let filter = (split < components.len())
+                .then(|| {
+                    GlobBuilder::new(&pattern.to_string_lossy())
+                        .literal_separator(true)
+                        .build()
+                        .map(|glob| glob.compile_matcher())
+                        .map_err(|e| {
+                            TailwindError::config(format!("Invalid build input '{}': {}", input, e))
+                        })
+                })
+                .transpose()?;
+            let input = BuildInput {
+                path: if path.as_os_str().is_empty() {
+                    PathBuf::from(".")
+                } else {
+                    path
+                },
+                filter,
+            };
+            if !inputs.contains(&input) {
+                inputs.push(input);
+            }
+        }
+        Ok(inputs)
+    }
+
     /// Validate build configuration
     pub fn validate(&self) -> Result<(), String> {
         if self.output.is_empty() {
@@ -133,6 +261,10 @@ impl From<super::toml_config::BuildConfigToml> for BuildConfig {
             purge: toml_config.purge.unwrap_or(true),
             additional_css: toml_config.additional_css.unwrap_or_default(),
             postcss_plugins: toml_config.postcss_plugins.unwrap_or_default(),
+            layers: toml_config.layers.unwrap_or_else(default_layers),
+            theme_variables: toml_config.theme_variables.unwrap_or(false),
+            color_format: toml_config.color_format,
+            passthrough: String::new(),
         }
     }
 }
@@ -185,4 +317,46 @@ mod tests {
             .additional_css
             .contains(&"src/custom.css".to_string()));
     }
+
+    #[test]
+    fn test_inputs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/components")).unwrap();
+        for file in [
+            "src/app.rs",
+            "src/components/card.rs",
+            "src/components/card.html",
+        ] {
+            std::fs::write(root.join(file), "").unwrap();
+        }
+
+        let mut config = BuildConfig::new();
+        let inputs = config.inputs(root).unwrap();
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(inputs[0].path, root.join("src"));
+
+        config.input = vec![
+            "src/components/*.rs".to_string(),
+            "src/components/*.rs".to_string(),
+            "**/*.html".to_string(),
+        ];
+        let inputs = config.inputs(root).unwrap();
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(inputs[0].path, root.join("src/components"));
+        assert_eq!(inputs[1].path, root);
+
+        let sources = super::super::ContentConfig::default()
+            .sources(root)
+            .unwrap();
+        assert_eq!(
+            inputs[0].files(&sources).unwrap(),
+            vec![root.join("src/components/card.rs")]
+        );
+        assert_eq!(
+            inputs[1].files(&sources).unwrap(),
+            vec![root.join("src/components/card.html")]
+        );
+        assert!(BuildInput::new(root.join("src")).matches(&root.join("src/app.rs")));
+    }
 }
@@ -0,0 +1,282 @@
+//! Rust crates as content sources
+//!
+//! Components often live in crates of their own, such as other workspace
+//! members or `tailwind-rs-leptos`, whose class strings a build never sees
+//! when it only scans the application's `src`. The `[content.cargo]` section
+//! runs `cargo metadata --offline` to find those crates and scans their `src`
+//! directories too, matching the content globs relative to each crate:
+//!
+//! ```toml
+//! [content.cargo]
+//! workspace = true
+//! dependencies = ["tailwind-rs-leptos", "acme-components"]
+//! ```
+//!
+//! A crate can list its classes in its manifest instead, so published
+//! component crates don't need their sources scanned:
+//!
+//! ```toml
+//! [package.metadata.tailwind-rs]
+//! classes = ["px-4", "py-2", "bg-blue-600", "hover:bg-blue-700"]
+//! ```
+
+use crate::error::{Result, TailwindError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Crates whose sources are scanned along with the source paths
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CargoContent {
+    /// Scan every member of the workspace
+    #[serde(default)]
+    pub workspace: bool,
+    /// Names of dependencies, path or registry, to scan
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+    /// Manifest to read the workspace from, relative to the project root;
+    /// cargo looks for one from the root upwards when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest_path: Option<PathBuf>,
+}
+
+/// A crate selected as a content source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrateSource {
+    /// Package name
+    pub name: String,
+    /// Package version
+    pub version: String,
+    /// Directory holding the crate's `Cargo.toml`
+    pub root: PathBuf,
+    /// Classes from `[package.metadata.tailwind-rs]`, used instead of
+    /// scanning the crate's sources
+    pub classes: Option<Vec<String>>,
+}
+
+impl CrateSource {
+    /// The directory scanned for classes when the crate has no class manifest
+    pub fn source_dir(&self) -> PathBuf {
+        self.root.join("src")
+    }
+}
+
+/// The parts of `cargo metadata` output read here
+#[derive(Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+    workspace_members: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    id: String,
+    name: String,
+    version: String,
+    manifest_path: PathBuf,
+    #[serde(default)]
+    metadata: Option<PackageMetadata>,
+}
+
+#[derive(Deserialize)]
+struct PackageMetadata {
+    #[serde(rename = "tailwind-rs")]
+    tailwind_rs: Option<ClassManifest>,
+}
+
+#[derive(Deserialize)]
+struct ClassManifest {
+    #[serde(default)]
+    classes: Option<Vec<String>>,
+}
+
+impl CargoContent {
+    /// Whether any crate is selected
+    pub fn is_enabled(&self) -> bool {
+        self.workspace || !self.dependencies.is_empty()
+    }
+
+    /// Run `cargo metadata --offline` from `root` and select the crates
+    pub fn resolve(&self, root: &Path) -> Result<Vec<CrateSource>> {
+        if !self.is_enabled() {
+            return Ok(Vec::new());
+        }
+
+        let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+        let mut command = Command::new(cargo);
+        command
+            .args(["metadata", "--offline", "--format-version", "1"])
+            .current_dir(root);
+        if let Some(manifest_path) = &self.manifest_path {
+            command.arg("--manifest-path").arg(root.join(manifest_path));
+        }
+        let output = command
+            .output()
+            .map_err(|e| TailwindError::build(format!("Failed to run cargo metadata: {}", e)))?;
+        if !output.status.success() {
+            return Err(TailwindError::build(format!(
+                "cargo metadata failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        self.select(&String::from_utf8_lossy(&output.stdout))
+    }
+
+    /// Select the crates from the JSON output of `cargo metadata`, workspace
+    /// members first
+    ///
+    /// Every named dependency has to be in the dependency graph.
+    pub fn select(&self, metadata: &str) -> Result<Vec<CrateSource>> {
+        let metadata: Metadata = serde_json::from_str(metadata)
+            .map_err(|e| TailwindError::build(format!("Invalid cargo metadata: {}", e)))?;
+
+        let members: HashSet<&str> = metadata
+            .workspace_members
+            .iter()
+            .map(String::as_str)
+            .collect();
+        for name in &self.dependencies {
+            if !metadata
+                .packages
+                .iter()
+                .any(|package| &package.name == name)
+            {
+                return Err(TailwindError::config(format!(
+                    "Content crate '{}' is not a dependency of the workspace",
+                    name
+                )));
+            }
+        }
+
+        let mut crates: Vec<(bool, CrateSource)> = metadata
+            .packages
+            .into_iter()
+            .filter_map(|package| {
+                let member = members.contains(package.id.as_str());
+                let selected =
+                    (self.workspace && member) || self.dependencies.contains(&package.name);
+                selected.then(|| (member, package.into()))
+            })
+            .collect();
+        crates.sort_by(|(a_member, a), (b_member, b)| {
+            b_member
+                .cmp(a_member)
+                .then_with(|| a.name.cmp(&b.name))
+                .then_with(|| a.version.cmp(&b.version))
+        });
+        Ok(crates.into_iter().map(|(_, source)| source).collect())
+    }
+}
+
+impl From<Package> for CrateSource {
+    fn from(package: Package) -> Self {
+        let root = package
+            .manifest_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        Self {
+            name: package.name,
+            version: package.version,
+            root,
+            classes: package
+                .metadata
+                .and_then(|metadata| metadata.tailwind_rs)
+                .and_then(|manifest| manifest.classes),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METADATA: &str = r#"{
+        "packages": [
+            {
+                "id": "app 0.1.0 (path+file:///work/app)",
+                "name": "app",
+                "version": "0.1.0",
+                "manifest_path": "/work/app/Cargo.toml",
+                "metadata": null
+            },
+            {
+                "id": "registry+https://github.com/rust-lang/crates.io-index#acme-ui@1.2.0",
+                "name": "acme-ui",
+                "version": "1.2.0",
+                "manifest_path": "/registry/acme-ui-1.2.0/Cargo.toml",
+                "metadata": { "tailwind-rs": { "classes": ["px-4", "rounded-md"] } }
+            },
+            {
+                "id": "registry+https://github.com/rust-lang/crates.io-index#serde@1.0.0",
+                "name": "serde",
+                "version": "1.0.0",
+                "manifest_path": "/registry/serde-1.0.0/Cargo.toml",
+                "metadata": null
+            }
+        ],
+        "workspace_members": ["app 0.1.0 (path+file:///work/app)"]
+    }"#;
+
+    #[test]
+    fn test_select_crates() {
+        let cargo = CargoContent {
+            workspace: true,
+            dependencies: vec!["acme-ui".to_string()],
+            manifest_path: None,
+        };
+        let crates = cargo.select(METADATA).unwrap();
+
+        let names: Vec<&str> = crates.iter().map(|krate| krate.name.as_str()).collect();
+        assert_eq!(names, vec!["app", "acme-ui"]);
+        assert_eq!(crates[0].source_dir(), Path::new("/work/app/src"));
+        assert_eq!(crates[0].classes, None);
+        assert_eq!(
+            crates[1].classes,
+            Some(vec!["px-4".to_string(), "rounded-md".to_string()])
+        );
+
+        let missing = CargoContent {
+            dependencies: vec!["leptos".to_string()],
+            ..CargoContent::default()
+        };
+        assert!(missing.select(METADATA).is_err());
+        assert!(CargoContent::default()
+            .resolve(Path::new("."))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_resolve_workspace_members() {
+        let dir =
+            std::env::temp_dir().join(format!("tailwind-rs-cargo-content-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("ui/src")).unwrap();
+        std::fs::write(
+            dir.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"ui\"]\nresolver = \"2\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("ui/Cargo.toml"),
+            "[package]\nname = \"ui\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+             [package.metadata.tailwind-rs]\nclasses = [\"shadow-lg\"]\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("ui/src/lib.rs"), "").unwrap();
+
+        let cargo = CargoContent {
+            workspace: true,
+            ..CargoContent::default()
+        };
+        let crates = cargo.resolve(&dir).unwrap();
+        assert_eq!(crates.len(), 1);
+        assert_eq!(crates[0].name, "ui");
+        assert_eq!(crates[0].classes, Some(vec!["shadow-lg".to_string()]));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,400 @@
+//! Content sources for tailwind-rs
+//!
+//! The `[content]` section names the files scanned for classes. Globs are
+//! matched against paths relative to the project root (or to the scanned
+//! directory when it lies outside the root), and the last glob matching a
+//! path decides, so a `!` glob excludes files an earlier glob included.
+//! Files ignored by `.gitignore` or `.ignore` are skipped, and files can be
+//! transformed by extension before they are scanned:
+//!
+//! ```toml
+//! [content]
+//! include = ["src/**/*.rs", "docs/**/*.md", "!src/generated/**"]
+//! exclude = ["**/*.snap"]
+//! gitignore = true
+//!
+//! [content.transform]
+//! md = "markdown"
+//! ```
+//!
+//! Other crates can be scanned too; see [`CargoContent`].
+
+use super::cargo::{CargoContent, CrateSource};
+use crate::error::{Result, TailwindError};
+use globset::{Glob, GlobBuilder, GlobMatcher};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// The files scanned when no `include` globs are configured
+pub const DEFAULT_INCLUDE: &[&str] = &[
+    "**/*.rs",
+    "**/*.html",
+    "**/*.js",
+    "**/*.ts",
+    "**/*.jsx",
+    "**/*.tsx",
+];
+
+/// Which files are scanned for classes, and how they are read
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContentConfig {
+    /// Globs of files to scan; a leading `!` excludes matching files again
+    #[serde(default = "default_include")]
+    pub include: Vec<String>,
+    /// Globs of files never scanned, whatever `include` says
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// Skip files ignored by `.gitignore`, `.ignore` and git's exclude files
+    #[serde(default = "default_gitignore")]
+    pub gitignore: bool,
+    /// Transformer applied to files by extension (`md = "markdown"`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub transform: BTreeMap<String, Transformer>,
+    /// Workspace members and dependencies scanned along with the sources
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cargo: Option<CargoContent>,
+}
+
+/// Rewrites a file's contents before classes are extracted from it
+///
+/// Transformers blank out text rather than removing it, so byte offsets and
+/// line numbers still point into the original file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Transformer {
+    /// Drop fenced code blocks, so examples in documentation aren't scanned
+    Markdown,
+    /// Drop fenced code blocks and `import`/`export` statements, keeping the
+    /// JSX and prose of an MDX file
+    Mdx,
+}
+
+fn default_include() -> Vec<String> {
+    DEFAULT_INCLUDE
+        .iter()
+        .map(|glob| glob.to_string())
+        .collect()
+}
+
+fn default_gitignore() -> bool {
+    true
+}
+
+impl Default for ContentConfig {
+    fn default() -> Self {
+        Self {
+            include: default_include(),
+            exclude: Vec::new(),
+            gitignore: default_gitignore(),
+            transform: BTreeMap::new(),
+            cargo: None,
+        }
+    }
+}
+
+impl ContentConfig {
+    /// Read the `content` section of a TOML or JSON configuration file
+    ///
+    /// Any configuration file may carry the section; the defaults are
+    /// returned when it has none.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            TailwindError::config(format!("Failed to read config file {:?}: {}", path, e))
+        })?;
+        Self::parse(&content)
+    }
+
+    /// Read the `content` section of a TOML or JSON document
+    pub fn parse(content: &str) -> Result<Self> {
+        #[derive(Deserialize)]
+        struct Document {
+            #[serde(default)]
+            content: ContentConfig,
+        }
+
+        let document: Document = if content.trim_start().starts_with('{') {
+            serde_json::from_str(content)
+                .map_err(|e| TailwindError::config(format!("JSON parsing error: {}", e)))?
+        } else {
+            toml::from_str(content)
+                .map_err(|e| TailwindError::config(format!("TOML parsing error: {}", e)))?
+        };
+        Ok(document.content)
+    }
+
+    /// The crates named by the `cargo` section, found with `cargo metadata`
+    /// run from `root`
+    pub fn crates(&self, root: &Path) -> Result<Vec<CrateSource>> {
+        match &self.cargo {
+            Some(cargo) => cargo.resolve(root),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Compile the globs, matching paths relative to `root`
+    pub fn sources(&self, root: &Path) -> Result<ContentSources> {
+        let include = self
+            .include
+            .iter()
+            .map(|glob| match glob.strip_prefix('!') {
+                Some(glob) => Ok((compile(glob)?, false)),
+                None => Ok((compile(glob)?, true)),
+            })
+            .collect::<Result<_>>()?;
+        let exclude = self
+            .exclude
+            .iter()
+            .map(|glob| compile(glob.strip_prefix('!').unwrap_or(glob)))
+            .collect::<Result<_>>()?;
+
+        Ok(ContentSources {
+            root: root.canonicalize().unwrap_or_else(|_| root.to_path_buf()),
+            include,
+            exclude,
+            gitignore: self.gitignore,
+            transform: self.transform.clone(),
+        })
+    }
+}
+
+/// Compile a glob whose `*` stays within one path component
+fn compile(glob: &str) -> Result<GlobMatcher> {
+    GlobBuilder::new(glob)
+        .literal_separator(true)
+        .build()
+        .map(|glob: Glob| glob.compile_matcher())
+        .map_err(|e| TailwindError::config(format!("Invalid content glob '{}': {}", glob, e)))
+}
+
+/// Compiled content sources, shared by everything that scans for classes
+#[derive(Debug, Clone)]
+pub struct ContentSources {
+    root: PathBuf,
+    /// Include globs in order, with whether a match includes or excludes
+    include: Vec<(GlobMatcher, bool)>,
+    exclude: Vec<GlobMatcher>,
+    gitignore: bool,
+    transform: BTreeMap<String, Transformer>,
+}
+
+impl ContentSources {
+    /// Whether a file is scanned, given its path relative to the root
+    pub fn matches(&self, relative: &Path) -> bool {
+        let included = self
+            .include
+            .iter()
+            .rev()
+            .find(|(glob, _)| glob.is_match(relative))
+            .is_some_and(|&(_, include)| include);
+        included && !self.exclude.iter().any(|glob| glob.is_match(relative))
+    }
+
+    /// Whether the globs match a file, wherever it is
+    ///
+    /// Ignore files aren't consulted, so a file named explicitly (or by a
+    /// watcher event) is scanned whenever the globs match it.
+    pub fn includes_file(&self, path: &Path) -> bool {
+        let base = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let name = path.strip_prefix(base).unwrap_or(path);
+        self.matches(&self.prefix(base).join(name))
+    }
+
+    /// The files to scan under `path`, in a stable order
+    ///
+    /// A `path` naming a file is returned when [`Self::includes_file`]
+    /// accepts it.
+    pub fn files(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        if path.is_file() {
+            return Ok(if self.includes_file(path) {
+                vec![path.to_path_buf()]
+            } else {
+                Vec::new()
+            });
+        }
+
+        let prefix = self.prefix(path);
+        let mut files = Vec::new();
+        let walker = ignore::WalkBuilder::new(path)
+            .standard_filters(self.gitignore)
+            .require_git(false)
+            .sort_by_file_name(|a, b| a.cmp(b))
+            .build();
+        for entry in walker {
+            let entry = entry.map_err(|e| TailwindError::build(e.to_string()))?;
+            if !entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file())
+            {
+                continue;
+            }
+            let relative = prefix.join(entry.path().strip_prefix(path).unwrap_or(entry.path()));
+            if self.matches(&relative) {
+                files.push(entry.into_path());
+            }
+        }
+        Ok(files)
+    }
+
+    /// The transformer configured for a file's extension
+    pub fn transformer(&self, path: &Path) -> Option<Transformer> {
+        let extension = path.extension()?.to_str()?;
+        self.transform.get(extension).copied()
+    }
+
+    /// Apply the file's transformer, if it has one, to its contents
+    pub fn transform<'a>(&self, path: &Path, content: &'a str) -> Cow<'a, str> {
+        match self.transformer(path) {
+            Some(transformer) => Cow::Owned(transformer.apply(content)),
+            None => Cow::Borrowed(content),
+        }
+    }
+
+    /// Read a file and apply its transformer
+    pub fn read(&self, path: &Path) -> Result<String> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(match self.transformer(path) {
+            Some(transformer) => transformer.apply(&content),
+            None => content,
+        })
+    }
+
+    /// The path of a directory relative to the root, or an empty path when
+    /// it lies outside the root and its files are matched relative to it
+    fn prefix(&self, dir: &Path) -> PathBuf {
+        dir.canonicalize()
+            .ok()
+            .and_then(|dir| dir.strip_prefix(&self.root).ok().map(Path::to_path_buf))
+            .unwrap_or_default()
+    }
+}
+
+impl Transformer {
+    /// Transform a file's contents
+    pub fn apply(self, content: &str) -> String {
+        let mut output = String::with_capacity(content.len());
+        let mut fence: Option<(u8, usize)> = None;
+
+        for line in content.split_inclusive('\n') {
+            let blank = match fence {
+                Some((marker, length)) => {
+                    if fence_marker(line).is_some_and(|(m, l)| m == marker && l >= length) {
+                        fence = None;
+                    }
+                    true
+                }
+                None => {
+                    fence = fence_marker(line);
+                    fence.is_some()
+                        || (self == Self::Mdx
+                            && (line.starts_with("import ") || line.starts_with("export ")))
+                }
+            };
+
+            if blank {
+                output.extend(line.chars().map(|c| if c == '\n' { '\n' } else { ' ' }));
+            } else {
+                output.push_str(line);
+            }
+        }
+        output
+    }
+}
+
+/// The marker byte and length of a line opening or closing a code fence
+fn fence_marker(line: &str) -> Option<(u8, usize)> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = &line.as_bytes()[indent..];
+    let marker = *rest.first().filter(|&&b| b == b'`' || b == b'~')?;
+    let length = rest.iter().take_while(|&&b| b == marker).count();
+    (length >= 3).then_some((marker, length))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_section() {
+        let config = ContentConfig::parse(
+            r#"
+[build]
+source = "src"
+
+[content]
+include = ["src/**/*.rs", "docs/**/*.md", "!src/generated/**"]
+exclude = ["**/*.snap.rs"]
+
+[content.transform]
+md = "markdown"
+
+[content.cargo]
+dependencies = ["tailwind-rs-leptos"]
+"#,
+        )
+        .unwrap();
+        assert!(config.gitignore);
+        let cargo = config.cargo.as_ref().unwrap();
+        assert!(!cargo.workspace);
+        assert_eq!(cargo.dependencies, vec!["tailwind-rs-leptos"]);
+        assert_eq!(config.transform["md"], Transformer::Markdown);
+
+        let sources = config.sources(Path::new(".")).unwrap();
+        assert!(sources.matches(Path::new("src/app/button.rs")));
+        assert!(sources.matches(Path::new("docs/guide.md")));
+        assert!(!sources.matches(Path::new("src/generated/icons.rs")));
+        assert!(!sources.matches(Path::new("src/view.snap.rs")));
+        assert!(!sources.matches(Path::new("src/styles.css")));
+        // `*` doesn't cross directories
+        assert!(!sources.matches(Path::new("docs.md")));
+
+        assert_eq!(ContentConfig::parse("").unwrap(), ContentConfig::default());
+    }
+
+    #[test]
+    fn test_files_respect_gitignore() {
+        let dir =
+            std::env::temp_dir().join(format!("tailwind-rs-content-files-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src/generated")).unwrap();
+        std::fs::write(dir.join(".gitignore"), "generated/\n").unwrap();
+        std::fs::write(dir.join("src/main.rs"), "").unwrap();
+        std::fs::write(dir.join("src/generated/icons.rs"), "").unwrap();
+        std::fs::write(dir.join("src/notes.txt"), "").unwrap();
+
+        let sources = ContentConfig::default().sources(&dir).unwrap();
+        assert_eq!(sources.files(&dir).unwrap(), [dir.join("src/main.rs")]);
+
+        let config = ContentConfig {
+            gitignore: false,
+            ..ContentConfig::default()
+        };
+        assert_eq!(config.sources(&dir).unwrap().files(&dir).unwrap().len(), 2);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_transformers_keep_offsets() {
+        let markdown =
+            "Use `p-4`.\n\n```html\n<div class=\"m-2\"></div>\n```\n<b class=\"font-bold\"></b>\n";
+        let stripped = Transformer::Markdown.apply(markdown);
+        assert_eq!(stripped.len(), markdown.len());
+        assert!(stripped.contains("p-4"));
+        assert!(!stripped.contains("m-2"));
+        assert!(stripped.contains("font-bold"));
+
+        let mdx = "import Card from './card'\n\n<Card className=\"p-4\" />\n";
+        let decoded = Transformer::Mdx.apply(mdx);
+        assert!(!decoded.contains("import"));
+        assert!(decoded.contains("className=\"p-4\""));
+        assert_eq!(decoded.lines().count(), mdx.lines().count());
+    }
+}
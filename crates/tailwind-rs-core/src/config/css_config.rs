@@ -0,0 +1,461 @@
+//! CSS-first configuration (Tailwind v4 style)
+//!
+//! Tailwind v4 projects configure everything from their entry stylesheet. This
+//! module reads the relevant directives from such a stylesheet so it can be
+//! used in place of (or on top of) `tailwind-rs.toml`:
+//!
+//! - `@import "tailwindcss"` selects the default layers
+//! - `@theme { --color-brand-500: #0f766e; }` populates the theme
+//! - `@utility tab-4 { tab-size: 4; }` defines a utility
+//! - `@custom-variant theme-midnight (&:where([data-theme=midnight] *));` defines a variant
+//! - `@source "../templates";` adds a scan path, `@source not "../templates/legacy";`
+//!   excludes one
+//! - `@plugin "forms";` enables a plugin
+//!
+//! Everything else (plain rules, `@layer`, `@keyframes`, ...) is kept verbatim in
+//! [`CssConfig::passthrough`] and emitted after the generated CSS.
+
+use crate::error::{Result, TailwindError};
+
+/// The layers pulled in by `@import "tailwindcss"`
+pub const DEFAULT_LAYERS: [&str; 4] = ["theme", "base", "components", "utilities"];
+
+/// Configuration read from a Tailwind v4 entry stylesheet
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CssConfig {
+    /// Layers requested through `@import "tailwindcss"` and its sub-paths
+    pub layers: Vec<String>,
+    /// Other stylesheets pulled in with `@import`
+    pub imports: Vec<String>,
+    /// `@theme` variables in declaration order, without the leading `--`
+    pub theme: Vec<(String, String)>,
+    /// `@utility` definitions (name, declarations)
+    pub utilities: Vec<(String, String)>,
+    /// `@custom-variant` definitions (name, template)
+    pub variants: Vec<(String, String)>,
+    /// `@source` paths to scan
+    pub sources: Vec<String>,
+    /// `@source not` paths to exclude from scanning
+    pub excluded_sources: Vec<String>,
+    /// `@plugin` names
+    pub plugins: Vec<String>,
+    /// CSS that is not configuration and should be emitted as-is
+    pub passthrough: String,
+    /// Directives that were recognized but could not be applied
+    pub warnings: Vec<String>,
+}
+
+/// A top-level statement: either `prelude;` or `prelude { block }`
+struct Statement<'a> {
+    prelude: &'a str,
+    block: Option<&'a str>,
+    source: &'a str,
+}
+
+impl CssConfig {
+    /// Parse the configuration directives from a stylesheet
+    pub fn parse(css: &str) -> Result<Self> {
+        let mut config = Self::default();
+
+        for statement in split_statements(css)? {
+            let Some(at_rule) = statement.prelude.strip_prefix('@') else {
+                config.push_passthrough(statement.source);
+                continue;
+            };
+
+            let name_end = at_rule
+                .find(|c: char| c.is_whitespace() || c == '(' || c == '"' || c == '\'')
+                .unwrap_or(at_rule.len());
+            let (name, params) = (&at_rule[..name_end], at_rule[name_end..].trim());
+
+            match name {
+                "import" => config.parse_import(params, statement.source)?,
+                "theme" => config.parse_theme(statement.block.unwrap_or_default())?,
+                "utility" => config.parse_utility(params, statement.block)?,
+                "custom-variant" | "variant" => config.parse_variant(params, statement.block)?,
+                "source" => config.parse_source(params)?,
+                "plugin" => {
+                    let plugin = unquote(params).ok_or_else(|| {
+                        TailwindError::config(format!("Invalid @plugin: '{}'", params))
+                    })?;
+                    if statement.block.is_some() {
+                        config
+                            .warnings
+                            .push(format!("Options for @plugin \"{}\" are ignored", plugin));
+                    }
+                    config.plugins.push(plugin);
+                }
+                "config" => config.warnings.push(format!(
+                    "@config {} is not supported; use tailwind-rs.toml instead",
+                    params
+                )),
+                _ => config.push_passthrough(statement.source),
+            }
+        }
+
+        Ok(config)
+    }
+
+    fn push_passthrough(&mut self, source: &str) {
+        self.passthrough.push_str(source.trim());
+        self.passthrough.push('\n');
+    }
+
+    fn parse_import(&mut self, params: &str, source: &str) -> Result<()> {
+        let path = unquote(params)
+            .ok_or_else(|| TailwindError::config(format!("Invalid @import: '{}'", params)))?;
+
+        let layers: &[&str] = match path.as_str() {
+            "tailwindcss" => &DEFAULT_LAYERS,
+            "tailwindcss/theme" | "tailwindcss/theme.css" => &["theme"],
+            "tailwindcss/preflight" | "tailwindcss/preflight.css" => &["base"],
+            "tailwindcss/utilities" | "tailwindcss/utilities.css" => &["utilities"],
+            _ if path.starts_with("http:") || path.starts_with("https:") => {
+                self.push_passthrough(source);
+                return Ok(());
+            }
+            _ => {
+                self.imports.push(path);
+                return Ok(());
+            }
+        };
+
+        for layer in layers {
+            if !self.layers.iter().any(|existing| existing == layer) {
+                self.layers.push(layer.to_string());
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_theme(&mut self, block: &str) -> Result<()> {
+        for statement in split_statements(block)? {
+            if statement.block.is_some() {
+                self.warnings.push(format!(
+                    "Nested rule in @theme is not supported: '{}'",
+                    statement.prelude
+                ));
+                continue;
+            }
+
+            let (name, value) = statement
+                .prelude
+                .split_once(':')
+                .and_then(|(name, value)| Some((name.trim().strip_prefix("--")?, value.trim())))
+                .ok_or_else(|| {
+                    TailwindError::config(format!(
+                        "@theme only accepts custom properties, found '{}'",
+                        statement.prelude
+                    ))
+                })?;
+            self.theme.push((name.to_string(), value.to_string()));
+        }
+        Ok(())
+    }
+
+    fn parse_utility(&mut self, params: &str, block: Option<&str>) -> Result<()> {
+        let block = block.ok_or_else(|| {
+            TailwindError::config(format!("@utility {} is missing a body", params))
+        })?;
+        if params.is_empty() || params.contains(char::is_whitespace) {
+            return Err(TailwindError::config(format!(
+                "Invalid @utility name: '{}'",
+                params
+            )));
+        }
+
+        self.utilities
+            .push((params.to_string(), normalize_whitespace(block)));
+        Ok(())
+    }
+
+    fn parse_variant(&mut self, params: &str, block: Option<&str>) -> Result<()> {
+        let (name, rest) = params
+            .split_once(|c: char| c.is_whitespace() || c == '(')
+            .map(|(name, _)| (name, params[name.len()..].trim()))
+            .unwrap_or((params, ""));
+        if name.is_empty() {
+            return Err(TailwindError::config("@custom-variant is missing a name"));
+        }
+
+        let template = if let Some(inner) = rest.strip_prefix('(').and_then(|r| r.strip_suffix(')'))
+        {
+            inner.trim().to_string()
+        } else if let Some(block) = block {
+            // Block form: `@custom-variant name { &:hover { @slot; } }`
+            let statements = split_statements(block)?;
+            match statements.as_slice() {
+                [statement] if statement.block.is_some_and(|b| b.contains("@slot")) => {
+                    statement.prelude.to_string()
+                }
+                _ => {
+                    return Err(TailwindError::config(format!(
+                        "@custom-variant {} must wrap a single `@slot`",
+                        name
+                    )))
+                }
+            }
+        } else {
+            return Err(TailwindError::config(format!(
+                "@custom-variant {} is missing a selector",
+                name
+            )));
+        };
+
+        self.variants
+            .push((name.to_string(), normalize_whitespace(&template)));
+        Ok(())
+    }
+
+    fn parse_source(&mut self, params: &str) -> Result<()> {
+        if let Some(path) = params.strip_prefix("not").and_then(unquote) {
+            self.excluded_sources.push(path);
+        } else if let Some(path) = unquote(params) {
+            self.sources.push(path);
+        } else {
+            self.warnings
+                .push(format!("@source {} is not supported", params));
+        }
+        Ok(())
+    }
+}
+
+/// Split a stylesheet (or block body) into top-level statements
+fn split_statements(css: &str) -> Result<Vec<Statement<'_>>> {
+    let bytes = css.as_bytes();
+    let mut statements = Vec::new();
+    let mut index = 0;
+
+    while index < bytes.len() {
+        index = skip_whitespace_and_comments(css, index);
+        if index >= bytes.len() {
+            break;
+        }
+
+        let start = index;
+        let mut depth = 0usize;
+        let mut quote: Option<u8> = None;
+
+        loop {
+            if index >= bytes.len() {
+                // Final statement without a terminating `;`
+                let prelude = css[start..].trim();
+                if !prelude.is_empty() {
+                    statements.push(Statement {
+                        prelude,
+                        block: None,
+                        source: &css[start..],
+                    });
+                }
+                break;
+            }
+
+            let byte = bytes[index];
+            match quote {
+                Some(open) if byte == open => quote = None,
+                Some(_) if byte == b'\\' => index += 1,
+                Some(_) => {}
+                None => match byte {
+                    b'"' | b'\'' => quote = Some(byte),
+                    b'(' | b'[' => depth += 1,
+                    b')' | b']' => depth = depth.saturating_sub(1),
+                    b';' if depth == 0 => {
+                        statements.push(Statement {
+                            prelude: css[start..index].trim(),
+                            block: None,
+                            source: &css[start..=index],
+                        });
+                        index += 1;
+                        break;
+                    }
+                    b'{' if depth == 0 => {
+                        let close = find_block_end(css, index)?;
+                        statements.push(Statement {
+                            prelude: css[start..index].trim(),
+                            block: Some(&css[index + 1..close]),
+                            source: &css[start..=close],
+                        });
+                        index = close + 1;
+                        break;
+                    }
+                    b'}' => {
+                        return Err(TailwindError::config(format!(
+                            "Unexpected '}}' at byte {}",
+                            index
+                        )))
+                    }
+                    _ => {}
+                },
+            }
+            index += 1;
+        }
+    }
+
+    Ok(statements)
+}
+
+/// Find the `}` matching the `{` at `open`
+fn find_block_end(css: &str, open: usize) -> Result<usize> {
+    let bytes = css.as_bytes();
+    let mut depth = 0usize;
+    let mut quote: Option<u8> = None;
+    let mut index = open;
+
+    while index < bytes.len() {
+        let byte = bytes[index];
+        match quote {
+            Some(open_quote) if byte == open_quote => quote = None,
+            Some(_) if byte == b'\\' => index += 1,
+            Some(_) => {}
+            None => match byte {
+                b'"' | b'\'' => quote = Some(byte),
+                b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                    index = css[index + 2..]
+                        .find("*/")
+                        .map(|end| index + 2 + end + 1)
+                        .unwrap_or(bytes.len());
+                }
+                b'{' => depth += 1,
+                b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(index);
+                    }
+                }
+                _ => {}
+            },
+        }
+        index += 1;
+    }
+
+    Err(TailwindError::config(format!(
+        "Unclosed block starting at byte {}",
+        open
+    )))
+}
+
+fn skip_whitespace_and_comments(css: &str, mut index: usize) -> usize {
+    let bytes = css.as_bytes();
+    loop {
+        while index < bytes.len() && bytes[index].is_ascii_whitespace() {
+            index += 1;
+        }
+        if css[index..].starts_with("/*") {
+            index = css[index + 2..]
+                .find("*/")
+                .map(|end| index + 2 + end + 2)
+                .unwrap_or(bytes.len());
+        } else {
+            return index;
+        }
+    }
+}
+
+/// Extract a quoted string (`"a"`, `'a'` or `url("a")`), ignoring trailing tokens
+fn unquote(params: &str) -> Option<String> {
+    let params = params.trim();
+    let params = params
+        .strip_prefix("url(")
+        .and_then(|inner| inner.split_once(')'))
+        .map(|(inner, _)| inner.trim())
+        .unwrap_or(params);
+
+    let quote = params.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let inner = &params[1..];
+    inner.find(quote).map(|end| inner[..end].to_string())
+}
+
+/// Collapse runs of whitespace (including newlines) into single spaces
+fn normalize_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENTRY: &str = r#"
+@import "tailwindcss";
+@import "./fonts.css";
+
+/* Design tokens */
+@theme {
+  --color-brand-500: #0f766e;
+  --breakpoint-3xl: 120rem;
+  --font-display: "Satoshi", sans-serif;
+}
+
+@utility tab-4 {
+  tab-size: 4;
+}
+
+@custom-variant theme-midnight (&:where([data-theme=midnight] *));
+@custom-variant pointer-hover {
+  @media (hover: hover) { @slot; }
+}
+
+@source "../templates";
+@source not "../templates/legacy";
+@plugin "forms";
+
+.prose-custom { color: red; }
+"#;
+
+    #[test]
+    fn test_parse_entry_stylesheet() {
+        let config = CssConfig::parse(ENTRY).unwrap();
+
+        assert_eq!(config.layers, DEFAULT_LAYERS.map(String::from).to_vec());
+        assert_eq!(config.imports, vec!["./fonts.css".to_string()]);
+        assert_eq!(
+            config.theme,
+            vec![
+                ("color-brand-500".to_string(), "#0f766e".to_string()),
+                ("breakpoint-3xl".to_string(), "120rem".to_string()),
+                (
+                    "font-display".to_string(),
+                    "\"Satoshi\", sans-serif".to_string()
+                ),
+            ]
+        );
+        assert_eq!(
+            config.utilities,
+            vec![("tab-4".to_string(), "tab-size: 4;".to_string())]
+        );
+        assert_eq!(
+            config.variants,
+            vec![
+                (
+                    "theme-midnight".to_string(),
+                    "&:where([data-theme=midnight] *)".to_string()
+                ),
+                (
+                    "pointer-hover".to_string(),
+                    "@media (hover: hover)".to_string()
+                ),
+            ]
+        );
+        assert_eq!(config.sources, vec!["../templates".to_string()]);
+        assert_eq!(
+            config.excluded_sources,
+            vec!["../templates/legacy".to_string()]
+        );
+        assert_eq!(config.plugins, vec!["forms".to_string()]);
+        assert!(config.passthrough.contains(".prose-custom"));
+    }
+
+    #[test]
+    fn test_partial_imports() {
+        let config =
+            CssConfig::parse("@import \"tailwindcss/theme\";\n@import \"tailwindcss/utilities\";")
+                .unwrap();
+        assert_eq!(config.layers, vec!["theme", "utilities"]);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(CssConfig::parse("@theme { color: red; }").is_err());
+        assert!(CssConfig::parse("@utility tab-4 { tab-size: 4;").is_err());
+        assert!(CssConfig::parse("@custom-variant broken;").is_err());
+    }
+}
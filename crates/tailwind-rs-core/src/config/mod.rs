@@ -4,23 +4,43 @@
 //! including build settings, theme configuration, and responsive breakpoints.
 
 pub mod build;
+pub mod cargo;
+pub mod content;
+pub mod css_config;
+pub mod optimize;
 pub mod parser;
+pub mod safelist;
+pub mod schema;
+pub mod themes;
 pub mod toml_config;
+pub mod watch;
 
 // Re-export main types
-pub use build::BuildConfig;
+pub use build::{BuildConfig, BuildInput};
+pub use cargo::{CargoContent, CrateSource};
+pub use content::{ContentConfig, ContentSources, Transformer};
+pub use css_config::CssConfig;
+pub use optimize::OptimizeConfig;
+pub use safelist::{SafelistEntry, SafelistMatcher};
+pub use schema::{Migration, CONFIG_VERSION};
+pub use themes::NamedTheme;
 pub use toml_config::TailwindConfigToml;
+pub use watch::WatchConfig;
 
+use crate::design_tokens::{DesignTokens, TokenImport};
 use crate::error::{Result, TailwindError};
 use crate::responsive::ResponsiveConfig;
 use crate::theme::Theme;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 
 /// Main configuration for tailwind-rs
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TailwindConfig {
+    /// Schema version, see [`schema`]
+    #[serde(default = "schema::current_version")]
+    pub version: u32,
     /// Build configuration
     pub build: BuildConfig,
     /// Theme configuration
@@ -29,6 +49,33 @@ pub struct TailwindConfig {
     pub responsive: ResponsiveConfig,
     /// Plugin configuration
     pub plugins: Vec<String>,
+    /// Component classes, mapping a class name to the utilities it applies
+    #[serde(default)]
+    pub components: HashMap<String, String>,
+    /// Custom utilities, mapping a utility name to its declarations
+    #[serde(default)]
+    pub utilities: HashMap<String, String>,
+    /// Custom variants, mapping a variant name to its selector or `@media` template
+    #[serde(default)]
+    pub variants: HashMap<String, String>,
+    /// Named themes, each emitting its token overrides under its own selector
+    #[serde(default)]
+    pub themes: BTreeMap<String, NamedTheme>,
+    /// Classes always generated, literally or by pattern
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub safelist: Vec<SafelistEntry>,
+    /// Classes never generated, even when sources use them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocklist: Vec<String>,
+    /// Files scanned for classes
+    #[serde(default)]
+    pub content: ContentConfig,
+    /// Defaults of `tailwind-rs optimize`
+    #[serde(default)]
+    pub optimize: OptimizeConfig,
+    /// Defaults of `tailwind-rs watch`
+    #[serde(default)]
+    pub watch: WatchConfig,
     /// Custom configuration
     pub custom: HashMap<String, serde_json::Value>,
 }
@@ -37,10 +84,20 @@ impl TailwindConfig {
     /// Create a new configuration with default values
     pub fn new() -> Self {
         Self {
+            version: CONFIG_VERSION,
             build: BuildConfig::new(),
             theme: crate::theme::create_default_theme(),
             responsive: ResponsiveConfig::new(),
             plugins: Vec::new(),
+            components: HashMap::new(),
+            utilities: HashMap::new(),
+            variants: HashMap::new(),
+            themes: BTreeMap::new(),
+            safelist: Vec::new(),
+            blocklist: Vec::new(),
+            content: ContentConfig::default(),
+            optimize: OptimizeConfig::default(),
+            watch: WatchConfig::default(),
             custom: HashMap::new(),
         }
     }
@@ -52,20 +109,208 @@ impl TailwindConfig {
             TailwindError::config(format!("Failed to read config file {:?}: {}", path, e))
         })?;
 
-        Self::from_str(&content)
+        let mut config = Self::from_str(&content)?;
+        config.load_design_tokens(path.parent())?;
+        Ok(config)
     }
 
     /// Load configuration from a string
+    ///
+    /// Documents written for an older [`schema`] version are upgraded first.
     pub fn from_str(content: &str) -> Result<Self> {
-        // Try TOML first, then JSON
-        if content.trim().starts_with('[') || content.trim().starts_with('#') {
-            let toml_config: TailwindConfigToml = toml::from_str(content)
+        // JSON configs are objects; anything else is TOML, which may open
+        // with top-level keys like `safelist = [...]`
+        if !content.trim_start().starts_with('{') {
+            let mut document: toml::Table = toml::from_str(content)
                 .map_err(|e| TailwindError::config(format!("TOML parsing error: {}", e)))?;
-            Ok(toml_config.into())
+            let migration = schema::migrate(&mut document)?;
+            // Parse the text itself when nothing moved, so errors keep their position
+            let toml_config: TailwindConfigToml = if migration.changes.is_empty() {
+                toml::from_str(content)
+            } else {
+                toml::Value::Table(document).try_into()
+            }
+            .map_err(|e| TailwindError::config(format!("TOML parsing error: {}", e)))?;
+            toml_config.try_into()
         } else {
-            serde_json::from_str(content)
-                .map_err(|e| TailwindError::config(format!("JSON parsing error: {}", e)))
+            let mut config: Self = serde_json::from_str(content)
+                .map_err(|e| TailwindError::config(format!("JSON parsing error: {}", e)))?;
+            schema::check_version(Some(config.version.into()))?;
+            config.version = CONFIG_VERSION;
+            Ok(config)
+        }
+    }
+
+    /// Load configuration from a Tailwind v4 CSS entry file (e.g. `app.css`)
+    ///
+    /// `@import` paths are resolved relative to the file. `@source` paths are
+    /// kept relative to its directory, which build inputs and content globs
+    /// are resolved against.
+    pub fn from_css_file(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let content = std::fs::read_to_string(&path).map_err(|e| {
+            TailwindError::config(format!("Failed to read CSS entry file {:?}: {}", path, e))
+        })?;
+
+        let mut config = Self::new();
+        config.apply_css_config(&CssConfig::parse(&content)?, path.parent())?;
+        Ok(config)
+    }
+
+    /// Load configuration from the directives of a Tailwind v4 stylesheet
+    pub fn from_css(content: &str) -> Result<Self> {
+        let mut config = Self::new();
+        config.apply_css_config(&CssConfig::parse(content)?, None)?;
+        Ok(config)
+    }
+
+    /// Merge the directives of a parsed CSS entry file into this configuration
+    pub fn apply_css_config(&mut self, css: &CssConfig, base_dir: Option<&Path>) -> Result<()> {
+        let resolve = |path: &str| match base_dir {
+            Some(dir) if !dir.as_os_str().is_empty() => {
+                dir.join(path).to_string_lossy().into_owned()
+            }
+            _ => path.to_string(),
+        };
+
+        // A v4 stylesheet implies v4 output: utilities reference theme variables
+        if !css.layers.is_empty() {
+            self.build.layers = css.layers.clone();
+        }
+        self.build.theme_variables = true;
+        self.build.passthrough.push_str(&css.passthrough);
+        for import in &css.imports {
+            self.build.add_css(resolve(import));
+        }
+        // Inputs and content globs are relative to the stylesheet's directory already
+        for source in &css.sources {
+            self.build.add_input(source.clone());
+        }
+        for excluded in &css.excluded_sources {
+            let glob = exclude_glob(excluded, &css.sources).ok_or_else(|| {
+                TailwindError::config(format!(
+                    "@source not \"{}\" is outside the project and every @source",
+                    excluded
+                ))
+            })?;
+            self.content.exclude.push(format!("{}/**", glob));
+            self.content.exclude.push(glob);
+        }
+
+        for (name, value) in &css.theme {
+            self.apply_theme_variable(name, value)?;
+        }
+        for (name, declarations) in &css.utilities {
+            self.utilities.insert(name.clone(), declarations.clone());
+        }
+        for (name, template) in &css.variants {
+            self.variants.insert(name.clone(), template.clone());
+        }
+        for plugin in &css.plugins {
+            if !self.plugins.contains(plugin) {
+                self.plugins.push(plugin.clone());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Import the design token file named by `theme.tokens`
+    ///
+    /// The path is resolved relative to `base_dir` (the config file's
+    /// directory when loaded with [`TailwindConfig::from_file`]). Each token
+    /// is applied like an `@theme` variable; the returned import lists the
+    /// tokens that were skipped.
+    pub fn load_design_tokens(&mut self, base_dir: Option<&Path>) -> Result<Option<TokenImport>> {
+        let Some(path) = self.design_tokens_path(base_dir) else {
+            return Ok(None);
+        };
+
+        let import = DesignTokens::from_file(&path)?.import()?;
+        for (name, value) in &import.variables {
+            self.apply_theme_variable(name, value)?;
+        }
+        Ok(Some(import))
+    }
+
+    /// The design token file named by `theme.tokens`, resolved relative to
+    /// `base_dir` like [`TailwindConfig::load_design_tokens`] does
+    pub fn design_tokens_path(&self, base_dir: Option<&Path>) -> Option<PathBuf> {
+        let tokens = self.theme.tokens.as_ref()?;
+        Some(match base_dir {
+            Some(dir) => dir.join(tokens),
+            None => PathBuf::from(tokens),
+        })
+    }
+
+    /// Apply a single `@theme` variable (name without the leading `--`)
+    fn apply_theme_variable(&mut self, name: &str, value: &str) -> Result<()> {
+        use crate::theme::{BorderRadius, Color, Spacing, ThemeValue};
+
+        // `--color-*: initial` clears a namespace, `--*: initial` clears everything
+        if let Some(namespace) = name.strip_suffix('*') {
+            if value != "initial" {
+                return Err(TailwindError::config(format!(
+                    "Theme namespace --{} can only be reset to 'initial'",
+                    name
+                )));
+            }
+            self.theme
+                .custom
+                .retain(|key, _| !key.starts_with(namespace));
+            match namespace {
+                "" => {
+                    self.theme.colors.clear();
+                    self.theme.spacing.clear();
+                    self.theme.border_radius.clear();
+                }
+                "color-" => self.theme.colors.clear(),
+                "spacing-" => self.theme.spacing.clear(),
+                "radius-" => self.theme.border_radius.clear(),
+                _ => {}
+            }
+            return Ok(());
+        }
+
+        self.theme
+            .add_custom(name, ThemeValue::String(value.to_string()));
+
+        if let Some(color) = name.strip_prefix("color-") {
+            if let Ok(parsed) = value.parse::<Color>() {
+                self.theme.add_color(color, parsed);
+            }
+        } else if let Some(step) = name.strip_prefix("spacing-") {
+            if let Ok(parsed) = value.parse::<Spacing>() {
+                self.theme.add_spacing(step, parsed);
+            }
+        } else if let Some(radius) = name.strip_prefix("radius-") {
+            if let Ok(parsed) = value.parse::<BorderRadius>() {
+                self.theme.add_border_radius(radius, parsed);
+            }
+        } else if let Some(breakpoint) = name.strip_prefix("breakpoint-") {
+            // Breakpoints become media variants so custom sizes (`3xl`) work too
+            self.variants.insert(
+                breakpoint.to_string(),
+                format!("@media (min-width: {})", value),
+            );
+            if let (Ok(key), Some(width)) = (
+                breakpoint.parse::<crate::responsive::Breakpoint>(),
+                css_length_to_px(value),
+            ) {
+                self.responsive
+                    .breakpoints
+                    .entry(key)
+                    .and_modify(|config| config.min_width = width)
+                    .or_insert(crate::responsive::responsive_config::BreakpointConfig {
+                        min_width: width,
+                        max_width: None,
+                        enabled: true,
+                        media_query: None,
+                    });
+            }
         }
+
+        Ok(())
     }
 
     /// Save configuration to a file
@@ -108,6 +353,16 @@ impl TailwindConfig {
         // Validate responsive config
         self.responsive.validate()?;
 
+        for (name, theme) in &self.themes {
+            theme.validate(name)?;
+        }
+
+        for entry in &self.safelist {
+            entry.regex()?;
+        }
+
+        self.optimize.validate().map_err(TailwindError::config)?;
+
         Ok(())
     }
 
@@ -182,21 +437,79 @@ impl TailwindConfig {
     }
 }
 
+/// Convert a `px` or `rem` length to whole pixels (assuming a 16px root)
+fn css_length_to_px(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let pixels = if let Some(rem) = value.strip_suffix("rem") {
+        rem.trim().parse::<f32>().ok()? * 16.0
+    } else {
+        value.strip_suffix("px")?.trim().parse::<f32>().ok()?
+    };
+    (pixels >= 0.0).then(|| pixels.round() as u32)
+}
+
+/// The content glob excluding an `@source not` path
+///
+/// Paths inside the project are matched from its root; paths outside it are
+/// matched from the `@source` directory containing them, like the files
+/// scanned there.
+fn exclude_glob(excluded: &str, sources: &[String]) -> Option<String> {
+    let excluded = normalize_relative(excluded);
+    if !excluded.starts_with("..") {
+        return Some(excluded);
+    }
+    sources.iter().find_map(|source| {
+        let source = normalize_relative(source);
+        excluded
+            .strip_prefix(&source)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .map(str::to_string)
+    })
+}
+
+/// Resolve the `.` and `..` components of a relative path without touching
+/// the filesystem
+fn normalize_relative(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => {}
+            ".." if parts.last().is_some_and(|last| *last != "..") => {
+                parts.pop();
+            }
+            _ => parts.push(part),
+        }
+    }
+    parts.join("/")
+}
+
 impl Default for TailwindConfig {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl From<TailwindConfigToml> for TailwindConfig {
-    fn from(toml_config: TailwindConfigToml) -> Self {
-        Self {
+impl TryFrom<TailwindConfigToml> for TailwindConfig {
+    type Error = TailwindError;
+
+    fn try_from(toml_config: TailwindConfigToml) -> Result<Self> {
+        Ok(Self {
+            version: CONFIG_VERSION,
             build: toml_config.build.into(),
-            theme: toml_config.theme.into(),
+            theme: toml_config.theme.try_into()?,
             responsive: toml_config.responsive.into(),
             plugins: toml_config.plugins.unwrap_or_default(),
+            components: toml_config.components.unwrap_or_default(),
+            utilities: toml_config.utilities.unwrap_or_default(),
+            variants: toml_config.variants.unwrap_or_default(),
+            themes: toml_config.themes.unwrap_or_default(),
+            safelist: toml_config.safelist.unwrap_or_default(),
+            blocklist: toml_config.blocklist.unwrap_or_default(),
+            content: toml_config.content.unwrap_or_default(),
+            optimize: toml_config.optimize.unwrap_or_default(),
+            watch: toml_config.watch.unwrap_or_default(),
             custom: Self::convert_toml_to_json_values(toml_config.custom.unwrap_or_default()),
-        }
+        })
     }
 }
 
@@ -241,4 +554,290 @@ container_padding = 16
         assert_eq!(config.build.output, "dist/styles.css");
         assert!(config.build.minify);
     }
+
+    #[test]
+    fn test_css_first_config() {
+        let css = r#"
+@import "tailwindcss";
+@theme {
+  --color-brand-500: #0f766e;
+  --color-accent: oklch(0.7 0.2 30);
+  --breakpoint-md: 50rem;
+  --breakpoint-3xl: 120rem;
+}
+@utility tab-4 { tab-size: 4; }
+@custom-variant theme-midnight (&:where([data-theme=midnight] *));
+@source "../templates";
+@plugin "forms";
+"#;
+
+        let config = TailwindConfig::from_css(css).unwrap();
+        assert_eq!(config.build.layers.len(), 4);
+        assert!(config.build.input.contains(&"../templates".to_string()));
+        assert_eq!(config.plugins, vec!["forms".to_string()]);
+        assert!(config.theme.get_color("brand-500").is_ok());
+        assert!(config.theme.get_custom("color-accent").is_ok());
+        assert_eq!(
+            config.responsive.breakpoints[&crate::responsive::Breakpoint::Md].min_width,
+            800
+        );
+
+        let mut generator = crate::CssGenerator::new();
+        generator.apply_config(&config).unwrap();
+        for class in ["tab-4", "theme-midnight:tab-4", "3xl:p-4", "md:p-4"] {
+            generator.add_class(class).unwrap();
+        }
+
+        let rule = &generator.rules()["theme-midnight:tab-4"];
+        assert_eq!(
+            rule.selector,
+            ".theme-midnight\\:tab-4:where([data-theme=midnight] *)"
+        );
+        assert_eq!(
+            generator.rules()["3xl:p-4"].media_query.as_deref(),
+            Some("(min-width: 120rem)")
+        );
+        assert_eq!(
+            generator.rules()["md:p-4"].media_query.as_deref(),
+            Some("(min-width: 50rem)")
+        );
+    }
+
+    #[test]
+    fn test_css_entry_sources_layers_and_passthrough() {
+        let css = r#"
+@import "tailwindcss/theme";
+@import "tailwindcss/utilities";
+@source "../templates";
+@source not "../templates/legacy";
+@source not "./src/generated";
+.prose-custom { color: red; }
+"#;
+        let mut config = TailwindConfig::from_css(css).unwrap();
+        assert_eq!(config.build.layers, vec!["theme", "utilities"]);
+
+        let sources = config.content.sources(Path::new(".")).unwrap();
+        assert!(sources.matches(Path::new("src/app.rs")));
+        assert!(!sources.matches(Path::new("src/generated/classes.rs")));
+        // Files of `../templates` are matched relative to it
+        assert!(sources.matches(Path::new("index.html")));
+        assert!(!sources.matches(Path::new("legacy/index.html")));
+
+        config
+            .components
+            .insert("btn".to_string(), "p-4".to_string());
+        let mut generator = crate::CssGenerator::new();
+        generator.apply_config(&config).unwrap();
+        generator.add_class("m-2").unwrap();
+        generator.add_class("btn").unwrap();
+        let output = generator.generate_css();
+        assert!(output.contains(".m-2"));
+        assert!(!output.contains("@layer components"));
+        assert!(output.trim_end().ends_with(".prose-custom { color: red; }"));
+
+        assert!(TailwindConfig::from_css("@source not \"../vendor\";").is_err());
+    }
+
+    #[test]
+    fn test_css_theme_namespace_reset() {
+        let config =
+            TailwindConfig::from_css("@theme { --color-*: initial; --color-ink: #111111; }")
+                .unwrap();
+        assert_eq!(config.theme.colors.len(), 1);
+        assert!(config.theme.get_color("ink").is_ok());
+    }
+
+    #[test]
+    fn test_toml_components() {
+        let toml_content = r#"
+[build]
+output = "dist/styles.css"
+
+[theme]
+name = "default"
+
+[responsive]
+breakpoints = { sm = 640 }
+container_centering = true
+container_padding = 16
+
+[components]
+btn-primary = "px-4 py-2 rounded-md bg-blue-600 hover:bg-blue-700"
+"#;
+
+        let config = TailwindConfig::from_str(toml_content).unwrap();
+        assert_eq!(
+            config.components.get("btn-primary").map(String::as_str),
+            Some("px-4 py-2 rounded-md bg-blue-600 hover:bg-blue-700")
+        );
+
+        let mut generator = crate::CssGenerator::new();
+        generator.apply_config(&config).unwrap();
+        generator.add_class("btn-primary").unwrap();
+        assert!(generator.generate_css().contains(".btn-primary:hover"));
+    }
+
+    #[test]
+    fn test_toml_theme_namespaces() {
+        let toml_content = r##"
+[build]
+output = "dist/styles.css"
+
+[theme]
+name = "default"
+
+[theme.spacing]
+gutter = "1.5rem"
+
+[theme.font]
+display = "Inter, sans-serif"
+
+[theme.extend.color]
+brand-500 = "#0f766e"
+
+[responsive]
+breakpoints = { sm = 640 }
+container_centering = true
+container_padding = 16
+"##;
+
+        let config = TailwindConfig::from_str(toml_content).unwrap();
+        assert!(config.theme.validate().is_ok());
+        let theme = config.theme.to_tailwind_theme();
+        assert_eq!(theme.font.len(), 1);
+        assert_eq!(theme.font_family("display"), Some("Inter, sans-serif"));
+        assert_eq!(theme.get("color", "brand-500"), Some("#0f766e"));
+        assert_eq!(theme.get("color", "red-500"), Some("#ef4444"));
+        assert_eq!(theme.get("spacing", "gutter"), Some("1.5rem"));
+
+        // Round trips through the TOML representation
+        let toml_config: TailwindConfigToml = config.clone().into();
+        let toml_out = toml::to_string_pretty(&toml_config).unwrap();
+        let reparsed: TailwindConfig = toml::from_str::<TailwindConfigToml>(&toml_out)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(reparsed.theme.namespaces, config.theme.namespaces);
+        assert_eq!(reparsed.theme.spacing, config.theme.spacing);
+    }
+
+    #[test]
+    fn test_toml_themes() {
+        let toml_content = r##"
+[build]
+output = "dist/styles.css"
+
+[theme]
+name = "default"
+
+[responsive]
+breakpoints = { sm = 640 }
+container_centering = true
+container_padding = 16
+
+[themes.dark]
+media = "(prefers-color-scheme: dark)"
+
+[themes.dark.tokens]
+color-white = "#0a0a0a"
+
+[themes.brand-a.tokens]
+color-blue-500 = "#7c3aed"
+"##;
+
+        let config = TailwindConfig::from_str(toml_content).unwrap();
+        assert_eq!(config.themes.len(), 2);
+        for (name, theme) in &config.themes {
+            assert!(theme.validate(name).is_ok());
+        }
+        let brand = &config.themes["brand-a"];
+        assert_eq!(brand.selector_for("brand-a"), "[data-theme=brand-a]");
+        assert_eq!(
+            brand.tokens.get("color-blue-500").map(String::as_str),
+            Some("#7c3aed")
+        );
+        assert_eq!(config.themes["dark"].selector_for("dark"), ":root");
+        assert_eq!(
+            config.themes["dark"].variant_template("dark"),
+            "@media (prefers-color-scheme: dark)"
+        );
+
+        assert!(NamedTheme::new().validate("1st").is_err());
+        assert!(NamedTheme::new()
+            .with_selector(" ")
+            .validate("brand-b")
+            .is_err());
+    }
+
+    #[test]
+    fn test_toml_theme_palettes() {
+        let toml_content = r##"
+[build]
+output = "dist/styles.css"
+
+[theme]
+name = "default"
+
+[theme.palettes]
+brand = "#0f766e"
+
+[responsive]
+breakpoints = { sm = 640 }
+container_centering = true
+container_padding = 16
+"##;
+
+        let config = TailwindConfig::from_str(toml_content).unwrap();
+        assert!(config.theme.colors.contains_key("brand-500"));
+
+        let invalid = toml_content.replace("#0f766e", "teal-ish");
+        let error = TailwindConfig::from_str(&invalid).unwrap_err();
+        assert!(error.to_string().contains("palette 'brand'"));
+    }
+
+    #[test]
+    fn test_theme_design_tokens() {
+        let dir = std::env::temp_dir().join(format!("tailwind-rs-tokens-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("tokens.json"),
+            r##"{
+                "color": { "$type": "color", "brand": { "$value": "#0f766e" } },
+                "breakpoint": { "3xl": { "$type": "dimension", "$value": "120rem" } },
+                "typography": { "body": { "$type": "typography", "$value": {} } }
+            }"##,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("tailwind-rs.toml"),
+            r#"
+[build]
+output = "dist/styles.css"
+
+[theme]
+name = "default"
+tokens = "tokens.json"
+
+[responsive]
+breakpoints = { sm = 640 }
+container_centering = true
+container_padding = 16
+"#,
+        )
+        .unwrap();
+
+        let mut config = TailwindConfig::from_file(dir.join("tailwind-rs.toml")).unwrap();
+        assert_eq!(config.theme.tokens.as_deref(), Some("tokens.json"));
+        assert!(config.theme.colors.contains_key("brand"));
+        assert_eq!(
+            config.variants.get("3xl").map(String::as_str),
+            Some("@media (min-width: 120rem)")
+        );
+
+        let import = config.load_design_tokens(Some(&dir)).unwrap().unwrap();
+        assert_eq!(import.unsupported.len(), 1);
+        assert_eq!(import.unsupported[0].path, "typography.body");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -0,0 +1,64 @@
+//! Optimization configuration for tailwind-rs
+//!
+//! The `[optimize]` section holds the defaults of `tailwind-rs optimize`,
+//! which post-processes a built stylesheet:
+//!
+//! ```toml
+//! [optimize]
+//! level = 3
+//! minify = true
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Settings for optimizing a built stylesheet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OptimizeConfig {
+    /// Optimization level, from 1 (safe rewrites only) to 3, see
+    /// [`OptimizationConfig::for_level`](crate::css_optimizer::OptimizationConfig::for_level)
+    pub level: u8,
+    /// Minify the optimized stylesheet
+    pub minify: bool,
+}
+
+impl OptimizeConfig {
+    /// Validate optimization configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=3).contains(&self.level) {
+            return Err(format!(
+                "Optimization level must be between 1 and 3, got {}",
+                self.level
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Default for OptimizeConfig {
+    fn default() -> Self {
+        Self {
+            level: 3,
+            minify: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optimize_config_defaults() {
+        let config: OptimizeConfig = toml::from_str("minify = true").unwrap();
+        assert_eq!(config.level, 3);
+        assert!(config.minify);
+        assert!(config.validate().is_ok());
+
+        let config = OptimizeConfig {
+            level: 4,
+            ..OptimizeConfig::default()
+        };
+        assert!(config.validate().is_err());
+    }
+}
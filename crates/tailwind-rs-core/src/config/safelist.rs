@@ -0,0 +1,244 @@
+//! Safelist and blocklist configuration for tailwind-rs
+//!
+//! The safelist forces classes into the output even when no source mentions
+//! them, either literally or by matching a pattern against the utility
+//! catalog; the blocklist keeps classes out even when sources do:
+//!
+//! ```toml
+//! safelist = [
+//!     "prose",
+//!     { pattern = "^(bg|text)-(red|green|blue)-(100|500)$", variants = ["hover", "md"] },
+//! ]
+//! blocklist = ["container", "animate-bounce"]
+//! ```
+
+use crate::error::{Result, TailwindError};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// A safelist entry: a literal class or a pattern expanded against the utility catalog
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SafelistEntry {
+    /// A literal class, variants included (`hover:bg-red-500`)
+    Class(String),
+    /// A regular expression matched against every utility in the catalog
+    Pattern {
+        /// Regular expression a utility has to match
+        pattern: String,
+        /// Variants each matching utility is also generated with
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        variants: Vec<String>,
+    },
+}
+
+impl SafelistEntry {
+    /// Create a pattern entry
+    pub fn pattern(pattern: impl Into<String>) -> Self {
+        Self::Pattern {
+            pattern: pattern.into(),
+            variants: Vec::new(),
+        }
+    }
+
+    /// Add a variant to a pattern entry (literal classes are left unchanged)
+    pub fn with_variant(mut self, variant: impl Into<String>) -> Self {
+        if let Self::Pattern { variants, .. } = &mut self {
+            variants.push(variant.into());
+        }
+        self
+    }
+
+    /// Compile the entry's pattern, if it has one
+    pub fn regex(&self) -> Result<Option<Regex>> {
+        match self {
+            Self::Class(_) => Ok(None),
+            Self::Pattern { pattern, .. } => compile(pattern).map(Some),
+        }
+    }
+
+    /// Expand the entry into class names
+    ///
+    /// Literal classes expand to themselves. Patterns expand to every catalog
+    /// utility they match, followed by each match under every variant
+    /// (`hover:bg-red-500`).
+    pub fn expand<'a>(&self, catalog: impl IntoIterator<Item = &'a str>) -> Result<Vec<String>> {
+        let (regex, variants) = match self {
+            Self::Class(class) => return Ok(vec![class.clone()]),
+            Self::Pattern { pattern, variants } => (compile(pattern)?, variants),
+        };
+
+        let matches: Vec<&str> = catalog
+            .into_iter()
+            .filter(|class| regex.is_match(class))
+            .collect();
+        let mut classes: Vec<String> = matches.iter().map(|class| class.to_string()).collect();
+        for variant in variants {
+            classes.extend(matches.iter().map(|class| format!("{}:{}", variant, class)));
+        }
+        Ok(classes)
+    }
+}
+
+/// A safelist with its patterns compiled, for matching many classes
+#[derive(Debug, Clone, Default)]
+pub struct SafelistMatcher {
+    classes: HashSet<String>,
+    patterns: Vec<(Regex, Vec<String>)>,
+}
+
+impl SafelistMatcher {
+    /// Compile every pattern of a safelist, failing on the first invalid one
+    pub fn new(entries: &[SafelistEntry]) -> Result<Self> {
+        let mut matcher = Self::default();
+        for entry in entries {
+            match entry {
+                SafelistEntry::Class(class) => {
+                    matcher.classes.insert(class.clone());
+                }
+                SafelistEntry::Pattern { pattern, variants } => {
+                    matcher.patterns.push((compile(pattern)?, variants.clone()));
+                }
+            }
+        }
+        Ok(matcher)
+    }
+
+    /// Check whether a class is covered by the safelist
+    ///
+    /// Variants are stripped before a pattern is matched and must be among
+    /// the pattern's variants.
+    pub fn matches(&self, class: &str) -> bool {
+        if self.classes.contains(class) {
+            return true;
+        }
+        let (prefix, base) = split_variants(class);
+        self.patterns.iter().any(|(regex, variants)| {
+            regex.is_match(base)
+                && (prefix.is_empty()
+                    || prefix
+                        .split(':')
+                        .all(|variant| variants.iter().any(|v| v == variant)))
+        })
+    }
+}
+
+/// Compile a safelist pattern
+fn compile(pattern: &str) -> Result<Regex> {
+    Regex::new(pattern).map_err(|e| {
+        TailwindError::config(format!("Invalid safelist pattern '{}': {}", pattern, e))
+    })
+}
+
+/// Check whether a blocklist forbids a class
+///
+/// A class is blocked when it, or the utility it applies variants to, is
+/// listed: blocking `container` also blocks `md:container`.
+pub fn is_blocked(blocklist: &[String], class: &str) -> bool {
+    let (_, base) = split_variants(class);
+    blocklist
+        .iter()
+        .any(|blocked| blocked == class || blocked == base)
+}
+
+/// Split a class into its variant prefix and base utility
+///
+/// Colons inside arbitrary values (`bg-[url(https://x)]`) don't separate variants.
+fn split_variants(class: &str) -> (&str, &str) {
+    let mut depth = 0i32;
+    let mut split = None;
+    for (index, c) in class.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth -= 1,
+            ':' if depth == 0 => split = Some(index),
+            _ => {}
+        }
+    }
+    match split {
+        Some(index) => (&class[..index], &class[index + 1..]),
+        None => ("", class),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_pattern_with_variants() {
+        let catalog = [
+            "bg-red-100",
+            "bg-red-500",
+            "bg-red-600",
+            "text-blue-500",
+            "border-blue-500",
+        ];
+        let entry =
+            SafelistEntry::pattern("^(bg|text)-(red|green|blue)-(100|500)$").with_variant("hover");
+        let classes = entry.expand(catalog).unwrap();
+        assert_eq!(
+            classes,
+            vec![
+                "bg-red-100",
+                "bg-red-500",
+                "text-blue-500",
+                "hover:bg-red-100",
+                "hover:bg-red-500",
+                "hover:text-blue-500",
+            ]
+        );
+
+        let literal = SafelistEntry::Class("prose".to_string());
+        assert_eq!(literal.expand(catalog).unwrap(), vec!["prose"]);
+        assert!(SafelistEntry::pattern("(").expand(catalog).is_err());
+    }
+
+    #[test]
+    fn test_matcher() {
+        let matcher = SafelistMatcher::new(&[
+            SafelistEntry::Class("prose".to_string()),
+            SafelistEntry::pattern("^bg-(red|blue)-500$").with_variant("hover"),
+        ])
+        .unwrap();
+        assert!(matcher.matches("prose"));
+        assert!(matcher.matches("bg-red-500"));
+        assert!(matcher.matches("hover:bg-red-500"));
+        assert!(!matcher.matches("md:bg-red-500"));
+        assert!(!matcher.matches("bg-red-600"));
+        assert!(!matcher.matches("md:prose"));
+
+        assert!(SafelistMatcher::new(&[SafelistEntry::pattern("(")]).is_err());
+    }
+
+    #[test]
+    fn test_blocklist() {
+        let blocklist = vec!["container".to_string(), "animate-bounce".to_string()];
+        assert!(is_blocked(&blocklist, "container"));
+        assert!(is_blocked(&blocklist, "md:container"));
+        assert!(!is_blocked(&blocklist, "animate-spin"));
+        assert!(!is_blocked(&blocklist, "bg-[url(a:b)]"));
+    }
+
+    #[test]
+    fn test_toml_entries() {
+        #[derive(Deserialize)]
+        struct Config {
+            safelist: Vec<SafelistEntry>,
+        }
+
+        let config: Config = toml::from_str(
+            r#"safelist = ["prose", { pattern = "^bg-red-\\d+$", variants = ["md"] }]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.safelist[0],
+            SafelistEntry::Class("prose".to_string())
+        );
+        assert_eq!(
+            config.safelist[1],
+            SafelistEntry::pattern("^bg-red-\\d+$").with_variant("md")
+        );
+    }
+}
@@ -0,0 +1,523 @@
+//! Versioned configuration schema for tailwind-rs
+//!
+//! Every tool reads the same `tailwind-rs.toml`: the `[build]` section sets
+//! the inputs and the output stylesheet, `[content]` the scanned files,
+//! `[theme]`, `variants`, `utilities` and `plugins` what is generated, and
+//! `[optimize]` and `[watch]` the defaults of those commands. The top-level
+//! `version` key names the schema revision a file was written for:
+//!
+//! ```toml
+//! version = 2
+//!
+//! [build]
+//! input = ["src"]
+//! output = "dist/styles.css"
+//! purge = true
+//!
+//! [optimize]
+//! level = 3
+//!
+//! [watch]
+//! debounce = 300
+//! ```
+//!
+//! Files without `version` are version 1, which also covers the layout the
+//! CLI used to write (`[build] source` and `tree_shake`, `[optimize] remove_unused`
+//! and `source_maps`, `[watch] patterns`).
+//! They are upgraded in memory when loaded; [`migrate_document`] rewrites
+//! them, and [`json_schema`] describes the current version for editors.
+
+use crate::error::{Result, TailwindError};
+use serde_json::{json, Value};
+
+/// The schema version written by this release
+pub const CONFIG_VERSION: u32 = 2;
+
+/// The version assumed for files without a `version` key
+const UNVERSIONED: u32 = 1;
+
+/// The watch patterns the CLI wrote by default, already covered by `[content]`
+const LEGACY_WATCH_PATTERNS: &[&str] = &["**/*.rs"];
+
+/// What upgrading a configuration document changed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Migration {
+    /// Version the document was written for
+    pub from_version: u32,
+    /// One description per key that was moved or rewritten
+    pub changes: Vec<String>,
+}
+
+impl Migration {
+    /// Whether the document was written for an older version
+    pub fn is_needed(&self) -> bool {
+        self.from_version < CONFIG_VERSION
+    }
+}
+
+pub(crate) fn current_version() -> u32 {
+    CONFIG_VERSION
+}
+
+/// Check the `version` key of a document, returning the version it names
+pub fn check_version(version: Option<i64>) -> Result<u32> {
+    let Some(version) = version else {
+        return Ok(UNVERSIONED);
+    };
+    match u32::try_from(version) {
+        Ok(version @ 1..=CONFIG_VERSION) => Ok(version),
+        Ok(version) if version > CONFIG_VERSION => Err(TailwindError::config(format!(
+            "Configuration version {} is newer than the supported version {}",
+            version, CONFIG_VERSION
+        ))),
+        _ => Err(TailwindError::config(format!(
+            "Invalid configuration version {}",
+            version
+        ))),
+    }
+}
+
+/// Upgrade a parsed TOML configuration to [`CONFIG_VERSION`] in place
+pub fn migrate(document: &mut toml::Table) -> Result<Migration> {
+    let version = document
+        .get("version")
+        .map(|version| {
+            version.as_integer().ok_or_else(|| {
+                TailwindError::config("Configuration version must be an integer".to_string())
+            })
+        })
+        .transpose()?;
+    let mut migration = Migration {
+        from_version: check_version(version)?,
+        changes: Vec::new(),
+    };
+    if migration.from_version == UNVERSIONED {
+        migrate_v1(document, &mut migration.changes);
+    }
+    document.insert(
+        "version".to_string(),
+        toml::Value::Integer(CONFIG_VERSION.into()),
+    );
+    Ok(migration)
+}
+
+/// Move the keys of the CLI's former layout to their current names
+fn migrate_v1(document: &mut toml::Table, changes: &mut Vec<String>) {
+    if let Some(build) = document
+        .get_mut("build")
+        .and_then(toml::Value::as_table_mut)
+    {
+        if let Some(source) = build.remove("source") {
+            if build.contains_key("input") {
+                changes.push("build.source dropped in favor of build.input".to_string());
+            } else {
+                build.insert("input".to_string(), toml::Value::Array(vec![source]));
+                changes.push("build.source moved to build.input".to_string());
+            }
+        }
+        if let Some(tree_shake) = build.remove("tree_shake") {
+            if build.contains_key("purge") {
+                changes.push("build.tree_shake dropped in favor of build.purge".to_string());
+            } else {
+                build.insert("purge".to_string(), tree_shake);
+                changes.push("build.tree_shake renamed to build.purge".to_string());
+            }
+        }
+    }
+
+    // `tailwind-rs optimize` rewrites a built stylesheet; purging and source
+    // maps are part of the build
+    if let Some(optimize) = document
+        .get_mut("optimize")
+        .and_then(toml::Value::as_table_mut)
+    {
+        for (key, replacement) in [
+            ("remove_unused", "build.purge"),
+            ("source_maps", "build.source_maps"),
+        ] {
+            if optimize.remove(key).is_some() {
+                changes.push(format!("optimize.{} dropped; use {}", key, replacement));
+            }
+        }
+    }
+
+    let patterns = document
+        .get_mut("watch")
+        .and_then(toml::Value::as_table_mut)
+        .and_then(|watch| watch.remove("patterns"));
+    if let Some(patterns) = patterns {
+        let legacy = patterns.as_array().is_some_and(|patterns| {
+            patterns
+                .iter()
+                .map(toml::Value::as_str)
+                .eq(LEGACY_WATCH_PATTERNS.iter().copied().map(Some))
+        });
+        let content = document
+            .entry("content")
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        match content.as_table_mut() {
+            Some(content) if !legacy && !content.contains_key("include") => {
+                content.insert("include".to_string(), patterns);
+                changes.push("watch.patterns moved to content.include".to_string());
+            }
+            _ => changes
+                .push("watch.patterns dropped; rebuilds follow the content sources".to_string()),
+        }
+        if document
+            .get("content")
+            .and_then(toml::Value::as_table)
+            .is_some_and(toml::Table::is_empty)
+        {
+            document.remove("content");
+        }
+    }
+}
+
+/// Upgrade a TOML or JSON configuration file's contents to
+/// [`CONFIG_VERSION`], returning the rewritten document
+///
+/// Comments are not preserved. JSON documents were always written in the
+/// current layout, so only their `version` is updated.
+pub fn migrate_document(content: &str) -> Result<(String, Migration)> {
+    if content.trim_start().starts_with('{') {
+        let mut document: Value = serde_json::from_str(content)
+            .map_err(|e| TailwindError::config(format!("JSON parsing error: {}", e)))?;
+        let object = document.as_object_mut().ok_or_else(|| {
+            TailwindError::config("Configuration must be a JSON object".to_string())
+        })?;
+        let version = object
+            .get("version")
+            .map(|version| {
+                version.as_i64().ok_or_else(|| {
+                    TailwindError::config("Configuration version must be an integer".to_string())
+                })
+            })
+            .transpose()?;
+        let migration = Migration {
+            from_version: check_version(version)?,
+            changes: Vec::new(),
+        };
+        object.insert("version".to_string(), json!(CONFIG_VERSION));
+        let migrated = serde_json::to_string_pretty(&document)
+            .map_err(|e| TailwindError::config(format!("JSON serialization error: {}", e)))?;
+        return Ok((migrated, migration));
+    }
+
+    let mut document: toml::Table = toml::from_str(content)
+        .map_err(|e| TailwindError::config(format!("TOML parsing error: {}", e)))?;
+    let migration = migrate(&mut document)?;
+    let migrated = toml::to_string_pretty(&document)
+        .map_err(|e| TailwindError::config(format!("TOML serialization error: {}", e)))?;
+    Ok((migrated, migration))
+}
+
+/// JSON Schema of the current configuration version, for editor completion
+/// and validation of `tailwind-rs.toml` and `tailwind-rs.json`
+pub fn json_schema() -> Value {
+    let strings = json!({ "type": "array", "items": { "type": "string" } });
+    let string_map = json!({ "type": "object", "additionalProperties": { "type": "string" } });
+
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": "https://cloud-shuttle.github.io/tailwind-rs/schema/tailwind-rs.json",
+        "title": "tailwind-rs configuration",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "version": {
+                "description": "Schema version the file was written for",
+                "type": "integer",
+                "minimum": 1,
+                "maximum": CONFIG_VERSION,
+                "default": CONFIG_VERSION
+            },
+            "build": {
+                "description": "Inputs and the output stylesheet",
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "input": {
+                        "description": "Directories or globs of the sources to scan",
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "default": ["src/**/*.rs"]
+                    },
+                    "output": {
+                        "description": "Path of the generated stylesheet",
+                        "type": "string",
+                        "default": "dist/styles.css"
+                    },
+                    "watch": { "type": "boolean", "default": false },
+                    "minify": { "type": "boolean", "default": false },
+                    "source_maps": {
+                        "description": "Write a source map next to the stylesheet",
+                        "type": "boolean",
+                        "default": false
+                    },
+                    "purge": {
+                        "description": "Remove rules of classes the sources don't use",
+                        "type": "boolean",
+                        "default": true
+                    },
+                    "additional_css": strings,
+                    "postcss_plugins": strings,
+                    "layers": {
+                        "type": "array",
+                        "items": { "enum": ["theme", "base", "components", "utilities"] }
+                    },
+                    "theme_variables": {
+                        "description": "Reference theme tokens through CSS custom properties",
+                        "type": "boolean",
+                        "default": false
+                    },
+                    "color_format": { "enum": ["hex", "rgb", "oklch", "oklch-with-fallback"] }
+                }
+            },
+            "content": {
+                "description": "Files scanned for classes",
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "include": {
+                        "description": "Globs of files to scan; a leading `!` excludes files again",
+                        "type": "array",
+                        "items": { "type": "string" }
+                    },
+                    "exclude": strings,
+                    "gitignore": { "type": "boolean", "default": true },
+                    "transform": {
+                        "description": "Transformer applied to files by extension",
+                        "type": "object",
+                        "additionalProperties": { "enum": ["markdown", "mdx"] }
+                    },
+                    "cargo": {
+                        "description": "Crates scanned along with the sources",
+                        "type": "object",
+                        "additionalProperties": false,
+                        "properties": {
+                            "workspace": { "type": "boolean", "default": false },
+                            "dependencies": strings,
+                            "manifest_path": { "type": "string" }
+                        }
+                    }
+                }
+            },
+            "theme": {
+                "description": "Theme tokens, by namespace",
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "colors": string_map,
+                    "spacing": string_map,
+                    "border_radius": string_map,
+                    "box_shadows": string_map,
+                    "palettes": string_map,
+                    "tokens": {
+                        "description": "W3C design token file to import, relative to this file",
+                        "type": "string"
+                    },
+                    "custom": { "type": "object" },
+                    "extend": { "type": "object" }
+                }
+            },
+            "responsive": {
+                "type": "object",
+                "properties": {
+                    "breakpoints": {
+                        "type": "object",
+                        "additionalProperties": { "type": "integer", "minimum": 0 }
+                    },
+                    "container_centering": { "type": "boolean" },
+                    "container_padding": { "type": "integer", "minimum": 0 }
+                }
+            },
+            "plugins": strings,
+            "components": {
+                "description": "Component classes and the utilities they apply",
+                "type": "object",
+                "additionalProperties": { "type": "string" }
+            },
+            "utilities": {
+                "description": "Custom utilities and their declarations",
+                "type": "object",
+                "additionalProperties": { "type": "string" }
+            },
+            "variants": {
+                "description": "Custom variants and their selector or `@media` template",
+                "type": "object",
+                "additionalProperties": { "type": "string" }
+            },
+            "themes": {
+                "description": "Named themes overriding a subset of the tokens",
+                "type": "object",
+                "additionalProperties": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {
+                        "selector": { "type": "string" },
+                        "media": { "type": "string" },
+                        "tokens": string_map
+                    }
+                }
+            },
+            "safelist": {
+                "description": "Classes always generated, literally or by pattern",
+                "type": "array",
+                "items": {
+                    "anyOf": [
+                        { "type": "string" },
+                        {
+                            "type": "object",
+                            "required": ["pattern"],
+                            "additionalProperties": false,
+                            "properties": {
+                                "pattern": { "type": "string" },
+                                "variants": strings
+                            }
+                        }
+                    ]
+                }
+            },
+            "blocklist": strings,
+            "optimize": {
+                "description": "Defaults of `tailwind-rs optimize`",
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "level": { "type": "integer", "minimum": 1, "maximum": 3, "default": 3 },
+                    "minify": { "type": "boolean", "default": false }
+                }
+            },
+            "watch": {
+                "description": "Defaults of `tailwind-rs watch`",
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "debounce": {
+                        "description": "Milliseconds to wait after a change before rebuilding",
+                        "type": "integer",
+                        "minimum": 0,
+                        "default": 300
+                    }
+                }
+            },
+            "custom": { "type": "object" }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{TailwindConfig, TailwindConfigToml};
+
+    const LEGACY: &str = r#"
+[build]
+source = "src"
+output = "dist/styles.css"
+tree_shake = true
+minify = true
+source_maps = false
+
+[optimize]
+level = 2
+remove_unused = true
+minify = true
+source_maps = false
+
+[watch]
+debounce = 500
+patterns = ["**/*.rs"]
+"#;
+
+    #[test]
+    fn test_migrate_legacy_cli_config() {
+        let (migrated, migration) = migrate_document(LEGACY).unwrap();
+        assert_eq!(migration.from_version, 1);
+        assert!(migration.is_needed());
+        assert_eq!(
+            migration.changes,
+            vec![
+                "build.source moved to build.input",
+                "build.tree_shake renamed to build.purge",
+                "optimize.remove_unused dropped; use build.purge",
+                "optimize.source_maps dropped; use build.source_maps",
+                "watch.patterns dropped; rebuilds follow the content sources",
+            ]
+        );
+
+        let config = TailwindConfig::from_str(&migrated).unwrap();
+        assert_eq!(config, TailwindConfig::from_str(LEGACY).unwrap());
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.build.input, vec!["src"]);
+        assert!(config.build.purge);
+        assert!(config.build.minify);
+        assert_eq!(config.optimize.level, 2);
+        assert!(config.optimize.minify);
+        assert_eq!(config.watch.debounce, 500);
+
+        // Migrating again changes nothing
+        let (again, migration) = migrate_document(&migrated).unwrap();
+        assert!(!migration.is_needed());
+        assert!(migration.changes.is_empty());
+        assert_eq!(again, migrated);
+    }
+
+    #[test]
+    fn test_migrate_custom_watch_patterns() {
+        let mut document: toml::Table =
+            toml::from_str("[watch]\npatterns = [\"src/**/*.rs\", \"templates/**/*.html\"]\n")
+                .unwrap();
+        migrate(&mut document).unwrap();
+        assert_eq!(document["content"]["include"].as_array().unwrap().len(), 2);
+        assert!(document["watch"].get("patterns").is_none());
+    }
+
+    #[test]
+    fn test_check_version() {
+        assert_eq!(check_version(None).unwrap(), 1);
+        assert_eq!(check_version(Some(2)).unwrap(), 2);
+        assert!(check_version(Some(0)).is_err());
+        assert!(check_version(Some(CONFIG_VERSION as i64 + 1)).is_err());
+        assert!(TailwindConfig::from_str("version = 99\n").is_err());
+        assert!(TailwindConfig::from_str(r#"{"version": 99}"#).is_err());
+    }
+
+    #[test]
+    fn test_json_schema_covers_config() {
+        let schema = json_schema();
+        let properties = |value: &Value| -> Vec<String> {
+            value["properties"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .cloned()
+                .collect()
+        };
+
+        // Every key a configuration is written with is described
+        let mut config = TailwindConfig::new();
+        config.content.cargo = Some(Default::default());
+        config.build.color_format = Some(crate::color_space::ColorFormat::Hex);
+        let toml_config: TailwindConfigToml = config.into();
+        let document = serde_json::to_value(&toml_config).unwrap();
+        for (section, value) in document.as_object().unwrap() {
+            assert!(
+                properties(&schema).contains(section),
+                "{} is missing from the schema",
+                section
+            );
+            if ["build", "content", "optimize", "watch"].contains(&section.as_str()) {
+                for key in value.as_object().unwrap().keys() {
+                    assert!(
+                        properties(&schema["properties"][section]).contains(key),
+                        "{}.{} is missing from the schema",
+                        section,
+                        key
+                    );
+                }
+            }
+        }
+        assert_eq!(schema["properties"]["version"]["maximum"], CONFIG_VERSION);
+    }
+}
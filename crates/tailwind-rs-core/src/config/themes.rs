@@ -0,0 +1,120 @@
+//! Named theme configuration for tailwind-rs
+//!
+//! A named theme overrides a subset of the theme tokens and is emitted as its
+//! own block of custom properties, so a single set of utilities can serve
+//! several themes:
+//!
+//! ```toml
+//! [themes.dark]
+//! media = "(prefers-color-scheme: dark)"
+//!
+//! [themes.dark.tokens]
+//! color-white = "#0a0a0a"
+//!
+//! [themes.brand-a.tokens]
+//! color-blue-500 = "#7c3aed"
+//! ```
+//!
+//! `brand-a` is scoped to `[data-theme=brand-a]`, while `dark` applies to
+//! `:root` whenever the media query matches.
+
+use crate::error::{Result, TailwindError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A named theme overriding a subset of the theme tokens
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NamedTheme {
+    /// Selector scoping the theme (defaults to `[data-theme=<name>]`, or
+    /// `:root` when only a media query is given)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+    /// Media query activating the theme (`(prefers-color-scheme: dark)`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media: Option<String>,
+    /// Token overrides, keyed by name without the leading `--`
+    #[serde(default)]
+    pub tokens: BTreeMap<String, String>,
+}
+
+impl NamedTheme {
+    /// Create a theme scoped to `[data-theme=<name>]`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scope the theme to a selector
+    pub fn with_selector(mut self, selector: impl Into<String>) -> Self {
+        self.selector = Some(selector.into());
+        self
+    }
+
+    /// Activate the theme through a media query
+    pub fn with_media(mut self, media: impl Into<String>) -> Self {
+        self.media = Some(media.into());
+        self
+    }
+
+    /// Override a token (name with or without the leading `--`)
+    pub fn with_token(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.tokens
+            .insert(name.trim_start_matches("--").to_string(), value.into());
+        self
+    }
+
+    /// The selector the theme's variables are emitted under
+    pub fn selector_for(&self, name: &str) -> String {
+        match (&self.selector, &self.media) {
+            (Some(selector), _) => selector.clone(),
+            (None, Some(_)) => ":root".to_string(),
+            (None, None) => format!("[data-theme={}]", name),
+        }
+    }
+
+    /// The `theme-<name>` variant template matching this theme
+    ///
+    /// Selector-scoped themes match the element carrying the selector and its
+    /// descendants; media-only themes become a media variant.
+    pub fn variant_template(&self, name: &str) -> String {
+        match (&self.selector, &self.media) {
+            (None, Some(media)) => format!("@media {}", media),
+            _ => {
+                let selector = self.selector_for(name);
+                format!("&:where({}, {} *)", selector, selector)
+            }
+        }
+    }
+
+    /// Validate the theme
+    pub fn validate(&self, name: &str) -> Result<()> {
+        let valid_name = name.starts_with(|c: char| c.is_ascii_alphabetic())
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'));
+        if !valid_name {
+            return Err(TailwindError::config(format!(
+                "Invalid theme name '{}': use letters, digits, '-' and '_'",
+                name
+            )));
+        }
+        if matches!(&self.selector, Some(selector) if selector.trim().is_empty()) {
+            return Err(TailwindError::config(format!(
+                "Theme '{}' has an empty selector",
+                name
+            )));
+        }
+        if matches!(&self.media, Some(media) if media.trim().is_empty()) {
+            return Err(TailwindError::config(format!(
+                "Theme '{}' has an empty media query",
+                name
+            )));
+        }
+        if let Some(token) = self.tokens.keys().find(|token| token.is_empty()) {
+            return Err(TailwindError::config(format!(
+                "Theme '{}' has an invalid token name '{}'",
+                name, token
+            )));
+        }
+        Ok(())
+    }
+}
@@ -1,20 +1,37 @@
 //! TOML configuration structures for tailwind-rs
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// TOML representation of the main configuration
+///
+/// Sections a file leaves out take the values of
+/// [`TailwindConfig::new`](super::TailwindConfig::new), so a file can hold
+/// just the settings it changes.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TailwindConfigToml {
+    pub version: Option<u32>,
+    #[serde(default)]
     pub build: BuildConfigToml,
+    #[serde(default = "default_theme")]
     pub theme: crate::theme::ThemeToml,
+    #[serde(default = "default_responsive")]
     pub responsive: ResponsiveConfigToml,
     pub plugins: Option<Vec<String>>,
+    pub components: Option<HashMap<String, String>>,
+    pub utilities: Option<HashMap<String, String>>,
+    pub variants: Option<HashMap<String, String>>,
+    pub themes: Option<BTreeMap<String, super::NamedTheme>>,
+    pub safelist: Option<Vec<super::SafelistEntry>>,
+    pub blocklist: Option<Vec<String>>,
+    pub content: Option<super::ContentConfig>,
+    pub optimize: Option<super::OptimizeConfig>,
+    pub watch: Option<super::WatchConfig>,
     pub custom: Option<HashMap<String, toml::Value>>,
 }
 
 /// TOML representation of build configuration
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct BuildConfigToml {
     pub input: Option<Vec<String>>,
     pub output: Option<String>,
@@ -24,6 +41,9 @@ pub struct BuildConfigToml {
     pub purge: Option<bool>,
     pub additional_css: Option<Vec<String>>,
     pub postcss_plugins: Option<Vec<String>>,
+    pub layers: Option<Vec<String>>,
+    pub theme_variables: Option<bool>,
+    pub color_format: Option<crate::color_space::ColorFormat>,
 }
 
 /// TOML representation of responsive configuration
@@ -34,9 +54,24 @@ pub struct ResponsiveConfigToml {
     pub container_padding: u32,
 }
 
+fn default_theme() -> crate::theme::ThemeToml {
+    crate::theme::create_default_theme().into()
+}
+
+fn default_responsive() -> ResponsiveConfigToml {
+    ResponsiveConfigToml {
+        breakpoints: super::TailwindConfig::convert_breakpoints_to_toml(
+            &crate::responsive::ResponsiveConfig::new().breakpoints,
+        ),
+        container_centering: false,
+        container_padding: 0,
+    }
+}
+
 impl From<super::TailwindConfig> for TailwindConfigToml {
     fn from(config: super::TailwindConfig) -> Self {
         Self {
+            version: Some(config.version),
             build: BuildConfigToml {
                 input: Some(config.build.input),
                 output: Some(config.build.output),
@@ -46,6 +81,9 @@ impl From<super::TailwindConfig> for TailwindConfigToml {
                 purge: Some(config.build.purge),
                 additional_css: Some(config.build.additional_css),
                 postcss_plugins: Some(config.build.postcss_plugins),
+                layers: Some(config.build.layers),
+                theme_variables: Some(config.build.theme_variables),
+                color_format: config.build.color_format,
             },
             theme: config.theme.into(),
             responsive: ResponsiveConfigToml {
@@ -56,6 +94,15 @@ impl From<super::TailwindConfig> for TailwindConfigToml {
                 container_padding: 0, // Default value since this field doesn't exist in ResponsiveConfig
             },
             plugins: Some(config.plugins),
+            components: Some(config.components),
+            utilities: Some(config.utilities),
+            variants: Some(config.variants),
+            themes: Some(config.themes),
+            safelist: Some(config.safelist),
+            blocklist: Some(config.blocklist),
+            content: Some(config.content),
+            optimize: Some(config.optimize),
+            watch: Some(config.watch),
             custom: Some(super::TailwindConfig::convert_json_to_toml_values(
                 &config.custom,
             )),
@@ -119,4 +166,21 @@ mod tests {
         let responsive_config: crate::responsive::ResponsiveConfig = toml_config.into();
         assert!(!responsive_config.breakpoints.is_empty());
     }
+
+    #[test]
+    fn test_missing_sections_take_defaults() {
+        let config = crate::config::TailwindConfig::from_str(
+            "safelist = [\"p-4\"]\n\n[build]\nminify = true\n",
+        )
+        .unwrap();
+        let defaults = crate::config::TailwindConfig::new();
+
+        assert!(config.build.minify);
+        assert_eq!(config.build.output, defaults.build.output);
+        assert_eq!(config.theme.name, defaults.theme.name);
+        assert_eq!(
+            config.responsive.breakpoints.len(),
+            defaults.responsive.breakpoints.len()
+        );
+    }
 }
@@ -0,0 +1,25 @@
+//! Watch configuration for tailwind-rs
+//!
+//! The `[watch]` section holds the defaults of `tailwind-rs watch`. Which
+//! changes trigger a rebuild follows the `[content]` section:
+//!
+//! ```toml
+//! [watch]
+//! debounce = 300
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Settings for rebuilding when sources change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchConfig {
+    /// Milliseconds to wait after a change before rebuilding
+    pub debounce: u64,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self { debounce: 300 }
+    }
+}
@@ -0,0 +1,295 @@
+//! Component Classes
+//!
+//! This module implements `@apply`-style component classes: a single class name
+//! (e.g. `btn-primary`) that bundles several utilities. The declarations of the
+//! applied utilities are merged into one rule per variant combination, and the
+//! resulting rules are emitted in the `components` cascade layer only once the
+//! component is referenced.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use tailwind_rs_core::CssGenerator;
+//!
+//! let mut generator = CssGenerator::new();
+//! generator
+//!     .add_component("btn-primary", "px-4 py-2 rounded-md bg-blue-600 hover:bg-blue-700")
+//!     .unwrap();
+//!
+//! // Nothing is emitted until the component is used
+//! assert!(!generator.generate_css().contains(".btn-primary"));
+//!
+//! generator.add_class("btn-primary").unwrap();
+//! let css = generator.generate_css();
+//! assert!(css.contains("@layer components"));
+//! assert!(css.contains(".btn-primary:hover"));
+//! ```
+
+use super::types::{CssProperty, CssRule};
+use super::utils::escape_class_selector;
+use crate::error::{Result, TailwindError};
+use std::collections::{BTreeMap, HashMap};
+
+/// A rule's selector and optional media query
+type RuleKey = (String, Option<String>);
+
+/// Registry of component definitions and the components referenced so far
+#[derive(Debug, Clone, Default)]
+pub struct ComponentRegistry {
+    /// Component name to the utilities it applies
+    definitions: HashMap<String, Vec<String>>,
+    /// Referenced class name to its compiled rules
+    active: BTreeMap<String, Vec<CssRule>>,
+}
+
+impl ComponentRegistry {
+    /// Create an empty component registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define (or redefine) a component from a whitespace-separated utility list
+    pub fn define(&mut self, name: &str, utilities: &str) {
+        let utilities = utilities.split_whitespace().map(str::to_string).collect();
+        self.definitions.insert(name.to_string(), utilities);
+        // A redefinition invalidates anything compiled from the old utilities
+        self.active
+            .retain(|class, _| class != name && !class.ends_with(&format!(":{}", name)));
+    }
+
+    /// Check whether a component with the given name is defined
+    pub fn is_defined(&self, name: &str) -> bool {
+        self.definitions.contains_key(name)
+    }
+
+    /// Get the utilities applied by a component
+    pub fn utilities(&self, name: &str) -> Option<&[String]> {
+        self.definitions.get(name).map(Vec::as_slice)
+    }
+
+    /// Get the names of all defined components
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        self.definitions.keys()
+    }
+
+    /// Record the compiled rules for a referenced component class
+    pub fn activate(&mut self, class: &str, rules: Vec<CssRule>) {
+        self.active.insert(class.to_string(), rules);
+    }
+
+    /// Check whether a component class has been referenced
+    pub fn is_active(&self, class: &str) -> bool {
+        self.active.contains_key(class)
+    }
+
+    /// Get the rules of every referenced component
+    ///
+    /// Rules are ordered by specificity, so variant-prefixed rules follow the
+    /// rules they override, and by class name otherwise.
+    pub fn active_rules(&self) -> Vec<&CssRule> {
+        let mut rules: Vec<&CssRule> = self.active.values().flatten().collect();
+        rules.sort_by_key(|rule| rule.specificity);
+        rules
+    }
+
+    /// Number of defined components
+    pub fn len(&self) -> usize {
+        self.definitions.len()
+    }
+
+    /// Check whether no components are defined
+    pub fn is_empty(&self) -> bool {
+        self.definitions.is_empty()
+    }
+
+    /// Forget which components have been referenced, keeping the definitions
+    pub fn clear_active(&mut self) {
+        self.active.clear();
+    }
+}
+
+/// Component methods for CssGenerator
+pub trait CssGeneratorComponents {
+    /// Define a component class from a whitespace-separated list of utilities
+    fn add_component(&mut self, name: &str, utilities: &str) -> Result<()>;
+
+    /// Compile the rules for a (possibly variant-prefixed) component class
+    fn compile_component(&self, class: &str) -> Result<Option<Vec<CssRule>>>;
+}
+
+impl CssGeneratorComponents for super::CssGenerator {
+    fn add_component(&mut self, name: &str, utilities: &str) -> Result<()> {
+        if name.is_empty() || name.contains(char::is_whitespace) || name.contains(':') {
+            return Err(TailwindError::class_generation(format!(
+                "Invalid component name: '{}'",
+                name
+            )));
+        }
+        if utilities.split_whitespace().any(|utility| utility == name) {
+            return Err(TailwindError::class_generation(format!(
+                "Component '{}' cannot apply itself",
+                name
+            )));
+        }
+
+        // Validate eagerly so a typo surfaces at definition time, not at build time
+        let mut candidate = self.components.clone();
+        candidate.define(name, utilities);
+        let previous = std::mem::replace(&mut self.components, candidate);
+        if let Err(error) = self.compile_component(name) {
+            self.components = previous;
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    fn compile_component(&self, class: &str) -> Result<Option<Vec<CssRule>>> {
+        let (outer_variants, name) = self.variant_parser.split_variants(class);
+        let Some(utilities) = self.components.utilities(&name) else {
+            return Ok(None);
+        };
+
+        let base_selector = format!(".{}", escape_class_selector(class));
+        // (selector, media query) -> merged declarations, in first-seen order
+        let mut groups: Vec<(RuleKey, Vec<CssProperty>)> = Vec::new();
+
+        for utility in utilities {
+            let (inner_variants, base_utility) = self.variant_parser.split_variants(utility);
+            let properties = self.class_to_properties(&base_utility).map_err(|_| {
+                TailwindError::class_generation(format!(
+                    "Component '{}' applies unknown utility '{}'",
+                    name, utility
+                ))
+            })?;
+
+            let variants: Vec<String> = outer_variants
+                .iter()
+                .chain(inner_variants.iter())
+                .cloned()
+                .collect();
+            let key = self
+                .variant_parser
+                .apply_variants(&base_selector, &variants);
+
+            let declarations = match groups.iter_mut().find(|(existing, _)| *existing == key) {
+                Some((_, declarations)) => declarations,
+                None => {
+                    groups.push((key, Vec::new()));
+                    &mut groups.last_mut().expect("group was just pushed").1
+                }
+            };
+            for property in properties {
+                // Later utilities win, mirroring the cascade order of `@apply`
+                declarations.retain(|existing| existing.name != property.name);
+                declarations.push(property);
+            }
+        }
+
+        let specificity = outer_variants.len() as u32 * 10;
+        Ok(Some(
+            groups
+                .into_iter()
+                .map(|((selector, media_query), properties)| CssRule {
+                    selector,
+                    properties,
+                    media_query,
+                    specificity,
+                })
+                .collect(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css_generator::CssGenerator;
+
+    #[test]
+    fn test_component_emitted_only_when_referenced() {
+        let mut generator = CssGenerator::new();
+        generator
+            .add_component("btn-primary", "px-4 py-2 bg-blue-600")
+            .unwrap();
+
+        assert!(!generator.generate_css().contains("btn-primary"));
+
+        generator.add_class("btn-primary").unwrap();
+        let css = generator.generate_css();
+        assert!(css.contains("@layer components {"));
+        assert!(css.contains(".btn-primary {"));
+        assert!(css.contains("padding-left: 1rem"));
+        assert!(css.contains("padding-top: 0.5rem"));
+    }
+
+    #[test]
+    fn test_component_declarations_are_merged() {
+        let mut generator = CssGenerator::new();
+        generator
+            .add_component("card", "p-4 rounded-md p-6")
+            .unwrap();
+
+        let rules = generator.compile_component("card").unwrap().unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].selector, ".card");
+
+        let padding: Vec<_> = rules[0]
+            .properties
+            .iter()
+            .filter(|property| property.name == "padding")
+            .collect();
+        assert_eq!(padding.len(), 1);
+        assert_eq!(padding[0].value, "1.5rem");
+    }
+
+    #[test]
+    fn test_component_variants_become_nested_rules() {
+        let mut generator = CssGenerator::new();
+        generator
+            .add_component(
+                "btn-primary",
+                "px-4 py-2 rounded-md bg-blue-600 hover:bg-blue-700 dark:bg-blue-500 md:px-6",
+            )
+            .unwrap();
+
+        let rules = generator.compile_component("btn-primary").unwrap().unwrap();
+        let selectors: Vec<_> = rules
+            .iter()
+            .map(|rule| (rule.selector.as_str(), rule.media_query.as_deref()))
+            .collect();
+
+        assert!(selectors.contains(&(".btn-primary", None)));
+        assert!(selectors.contains(&(".btn-primary:hover", None)));
+        assert!(selectors.contains(&(".dark .btn-primary", None)));
+        assert!(selectors.contains(&(".btn-primary", Some("(min-width: 768px)"))));
+    }
+
+    #[test]
+    fn test_variant_prefixed_component_usage() {
+        let mut generator = CssGenerator::new();
+        generator.add_component("btn", "px-4 hover:px-6").unwrap();
+
+        let rules = generator.compile_component("md:btn").unwrap().unwrap();
+        assert!(rules
+            .iter()
+            .all(|rule| rule.media_query.as_deref() == Some("(min-width: 768px)")));
+        assert!(rules.iter().any(|rule| rule.selector == ".md\\:btn:hover"));
+    }
+
+    #[test]
+    fn test_unknown_utility_is_rejected() {
+        let mut generator = CssGenerator::new();
+        assert!(generator
+            .add_component("broken", "px-4 not-a-real-utility")
+            .is_err());
+        assert!(!generator.components.is_defined("broken"));
+    }
+
+    #[test]
+    fn test_self_application_is_rejected() {
+        let mut generator = CssGenerator::new();
+        assert!(generator.add_component("btn", "px-4 btn").is_err());
+        assert!(generator.add_component("bad name", "px-4").is_err());
+    }
+}
@@ -3,6 +3,10 @@
 //! This module contains the main CSS generation logic,
 //! orchestrating parsers and generating final CSS output.
 
+use crate::css_generator::parsers::{
+    ArbitraryParser, BackdropFilterUtilitiesParser, ContainerQueryParser, LogicalPropertiesParser,
+    MaskUtilitiesParser, TextShadowParser, UtilityParser,
+};
 use crate::css_generator::types::{CssProperty, CssRule};
 use crate::error::{Result, TailwindError};
 use crate::responsive::Breakpoint;
@@ -531,18 +535,20 @@ impl CssGenerator {
         }
     }
 
-    fn parse_text_shadow_class(&self, _class: &str) -> Option<Vec<CssProperty>> {
-        None
+    fn parse_text_shadow_class(&self, class: &str) -> Option<Vec<CssProperty>> {
+        TextShadowParser::new().parse_class(class)
     }
-    fn parse_mask_class(&self, _class: &str) -> Option<Vec<CssProperty>> {
-        None
+    fn parse_mask_class(&self, class: &str) -> Option<Vec<CssProperty>> {
+        MaskUtilitiesParser::new().parse_class(class)
     }
-    fn parse_logical_properties_class(&self, _class: &str) -> Option<Vec<CssProperty>> {
-        None
+    fn parse_logical_properties_class(&self, class: &str) -> Option<Vec<CssProperty>> {
+        LogicalPropertiesParser::new().parse_class(class)
     }
-    fn parse_enhanced_backdrop_filter_class(&self, _class: &str) -> Option<Vec<CssProperty>> {
-        None
+    fn parse_enhanced_backdrop_filter_class(&self, class: &str) -> Option<Vec<CssProperty>> {
+        BackdropFilterUtilitiesParser::new().parse_class(class)
     }
+    // Device variants (`motion-reduce:p-4`) and nesting (`[&>*]:p-4`) are
+    // variant prefixes, stripped before the base class gets here
     fn parse_modern_css_features_class(&self, _class: &str) -> Option<Vec<CssProperty>> {
         None
     }
@@ -564,11 +570,11 @@ impl CssGenerator {
     ) -> Option<Vec<CssProperty>> {
         None
     }
-    fn parse_container_query_class(&self, _class: &str) -> Option<Vec<CssProperty>> {
-        None
+    fn parse_container_query_class(&self, class: &str) -> Option<Vec<CssProperty>> {
+        ContainerQueryParser::new().parse_class(class)
     }
-    fn parse_color_function_class(&self, _class: &str) -> Option<Vec<CssProperty>> {
-        None
+    fn parse_color_function_class(&self, class: &str) -> Option<Vec<CssProperty>> {
+        ArbitraryParser::new().parse_class(class)
     }
     fn parse_performance_optimization_class(&self, _class: &str) -> Option<Vec<CssProperty>> {
         None
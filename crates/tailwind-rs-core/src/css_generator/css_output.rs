@@ -3,6 +3,7 @@
 //! This module handles converting CSS rules into actual CSS output.
 
 use super::types::CssRule;
+use super::utils::create_media_query;
 use std::collections::HashMap;
 
 /// CSS output generator
@@ -18,11 +19,11 @@ impl CssOutputGenerator {
 
         // Add custom properties
         if !custom_properties.is_empty() {
-            css.push_str(":root {\n");
-            for (name, value) in custom_properties {
-                css.push_str(&format!("  --{}: {};\n", name, value));
-            }
-            css.push_str("}\n\n");
+            css.push_str(&Self::generate_properties_block(
+                ":root",
+                None,
+                Self::sorted_properties(custom_properties),
+            ));
         }
 
         // Group rules by media query
@@ -47,7 +48,7 @@ impl CssOutputGenerator {
 
         // Generate responsive rules
         for (media_query, rules) in responsive_rules {
-            css.push_str(&format!("@media {} {{\n", media_query));
+            css.push_str(&format!("{} {{\n", create_media_query(&media_query)));
             for rule in rules {
                 css.push_str(&Self::rule_to_css(rule));
             }
@@ -57,6 +58,93 @@ impl CssOutputGenerator {
         css
     }
 
+    /// Generate an `@layer` block for the given rules, preserving their order
+    ///
+    /// Returns an empty string when there are no rules.
+    pub fn generate_layer_css(layer: &str, rules: &[&CssRule]) -> String {
+        if rules.is_empty() {
+            return String::new();
+        }
+
+        let mut css = format!("@layer {} {{\n", layer);
+        for rule in rules {
+            match rule.media_query {
+                Some(ref media_query) => {
+                    css.push_str(&format!("{} {{\n", create_media_query(media_query)));
+                    css.push_str(&Self::rule_to_css(rule));
+                    css.push_str("}\n\n");
+                }
+                None => css.push_str(&Self::rule_to_css(rule)),
+            }
+        }
+        css.push_str("}\n\n");
+        css
+    }
+
+    /// Generate a minified `@layer` block for the given rules
+    pub fn generate_minified_layer_css(layer: &str, rules: &[&CssRule]) -> String {
+        if rules.is_empty() {
+            return String::new();
+        }
+
+        let mut css = format!("@layer {}{{", layer);
+        for rule in rules {
+            match rule.media_query {
+                Some(ref media_query) => {
+                    css.push_str(&format!("{}{{", create_media_query(media_query)));
+                    css.push_str(&Self::rule_to_minified_css(rule));
+                    css.push('}');
+                }
+                None => css.push_str(&Self::rule_to_minified_css(rule)),
+            }
+        }
+        css.push('}');
+        css
+    }
+
+    /// Generate a custom property block for a selector, optionally inside a media query
+    pub fn generate_properties_block<'a>(
+        selector: &str,
+        media_query: Option<&str>,
+        properties: impl IntoIterator<Item = (&'a String, &'a String)>,
+    ) -> String {
+        let mut block = format!("{} {{\n", selector);
+        for (name, value) in properties {
+            block.push_str(&format!("  --{}: {};\n", name, value));
+        }
+        block.push_str("}\n\n");
+
+        match media_query {
+            Some(media_query) => format!("{} {{\n{}}}\n\n", create_media_query(media_query), block),
+            None => block,
+        }
+    }
+
+    /// Generate a minified custom property block
+    pub fn generate_minified_properties_block<'a>(
+        selector: &str,
+        media_query: Option<&str>,
+        properties: impl IntoIterator<Item = (&'a String, &'a String)>,
+    ) -> String {
+        let mut block = format!("{}{{", selector);
+        for (name, value) in properties {
+            block.push_str(&format!("--{}:{};", name, value));
+        }
+        block.push('}');
+
+        match media_query {
+            Some(media_query) => format!("{}{{{}}}", create_media_query(media_query), block),
+            None => block,
+        }
+    }
+
+    /// Custom properties in name order, so output is stable between runs
+    fn sorted_properties(custom_properties: &HashMap<String, String>) -> Vec<(&String, &String)> {
+        let mut properties: Vec<_> = custom_properties.iter().collect();
+        properties.sort();
+        properties
+    }
+
     /// Convert a CSS rule to CSS string
     fn rule_to_css(rule: &CssRule) -> String {
         let mut css = String::new();
@@ -87,11 +175,11 @@ impl CssOutputGenerator {
 
         // Add custom properties
         if !custom_properties.is_empty() {
-            css.push_str(":root{");
-            for (name, value) in custom_properties {
-                css.push_str(&format!("--{}:{};", name, value));
-            }
-            css.push('}');
+            css.push_str(&Self::generate_minified_properties_block(
+                ":root",
+                None,
+                Self::sorted_properties(custom_properties),
+            ));
         }
 
         // Group rules by media query
@@ -116,7 +204,7 @@ impl CssOutputGenerator {
 
         // Generate responsive rules
         for (media_query, rules) in responsive_rules {
-            css.push_str(&format!("@media {} {{", media_query));
+            css.push_str(&format!("{} {{", create_media_query(&media_query)));
             for rule in rules {
                 css.push_str(&Self::rule_to_minified_css(rule));
             }
@@ -0,0 +1,239 @@
+//! Custom Utilities
+//!
+//! This module holds utilities defined outside the built-in parsers, such as
+//! those declared with the Tailwind v4 `@utility` directive or the `[utilities]`
+//! config table. Static utilities (`tab-4`) map to fixed declarations, while
+//! functional utilities (`tab-*`) substitute the class suffix wherever
+//! `--value(...)` appears in a declaration value.
+
+use super::types::CssProperty;
+use crate::error::{Result, TailwindError};
+use std::collections::HashMap;
+
+/// Registry of user-defined utilities
+#[derive(Debug, Clone, Default)]
+pub struct CustomUtilityRegistry {
+    /// Utilities matched by their exact name
+    static_utilities: HashMap<String, Vec<CssProperty>>,
+    /// Functional utilities keyed by their prefix (`tab-` for `tab-*`)
+    functional_utilities: Vec<(String, Vec<CssProperty>)>,
+}
+
+impl CustomUtilityRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Define a utility from a declaration block such as `tab-size: 4`
+    pub fn define(&mut self, name: &str, declarations: &str) -> Result<()> {
+        let properties = parse_declarations(declarations)?;
+        if properties.is_empty() {
+            return Err(TailwindError::class_generation(format!(
+                "Utility '{}' has no declarations",
+                name
+            )));
+        }
+
+        if let Some(prefix) = name.strip_suffix('*') {
+            if prefix.is_empty() || !prefix.ends_with('-') {
+                return Err(TailwindError::class_generation(format!(
+                    "Functional utility '{}' must end with '-*'",
+                    name
+                )));
+            }
+            self.functional_utilities
+                .retain(|(existing, _)| existing != prefix);
+            self.functional_utilities
+                .push((prefix.to_string(), properties));
+            // Longest prefix first so `tab-size-*` wins over `tab-*`
+            self.functional_utilities
+                .sort_by_key(|(prefix, _)| std::cmp::Reverse(prefix.len()));
+        } else {
+            if name.is_empty() || name.contains(char::is_whitespace) {
+                return Err(TailwindError::class_generation(format!(
+                    "Invalid utility name: '{}'",
+                    name
+                )));
+            }
+            self.static_utilities.insert(name.to_string(), properties);
+        }
+
+        Ok(())
+    }
+
+    /// Resolve a (variant-free) class to its declarations
+    pub fn resolve(&self, class: &str) -> Option<Vec<CssProperty>> {
+        if let Some(properties) = self.static_utilities.get(class) {
+            return Some(properties.clone());
+        }
+
+        self.functional_utilities
+            .iter()
+            .find_map(|(prefix, templates)| {
+                let suffix = class.strip_prefix(prefix.as_str())?;
+                templates
+                    .iter()
+                    .map(|template| {
+                        substitute_value(&template.value, suffix).map(|value| CssProperty {
+                            name: template.name.clone(),
+                            value,
+                            important: template.important,
+                        })
+                    })
+                    .collect::<Option<Vec<_>>>()
+            })
+    }
+
+    /// Number of defined utilities
+    pub fn len(&self) -> usize {
+        self.static_utilities.len() + self.functional_utilities.len()
+    }
+
+    /// Check whether no utilities are defined
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Parse a declaration block (`tab-size: 4; color: red !important`) into properties
+pub fn parse_declarations(block: &str) -> Result<Vec<CssProperty>> {
+    let mut properties = Vec::new();
+
+    for declaration in split_top_level(block, ';') {
+        let declaration = declaration.trim();
+        if declaration.is_empty() {
+            continue;
+        }
+        if declaration.contains('{') {
+            return Err(TailwindError::class_generation(format!(
+                "Nested rules are not supported in utility declarations: '{}'",
+                declaration
+            )));
+        }
+
+        let (name, value) = declaration.split_once(':').ok_or_else(|| {
+            TailwindError::class_generation(format!("Invalid declaration: '{}'", declaration))
+        })?;
+        let value = value.trim();
+        let (value, important) = match value.strip_suffix("!important") {
+            Some(value) => (value.trim_end(), true),
+            None => (value, false),
+        };
+
+        properties.push(CssProperty {
+            name: name.trim().to_string(),
+            value: value.to_string(),
+            important,
+        });
+    }
+
+    Ok(properties)
+}
+
+/// Split on a delimiter, ignoring delimiters inside parentheses, brackets and quotes
+pub(crate) fn split_top_level(input: &str, delimiter: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+
+    for (index, ch) in input.char_indices() {
+        match quote {
+            Some(open) if ch == open => quote = None,
+            Some(_) => {}
+            None => match ch {
+                '"' | '\'' => quote = Some(ch),
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth = depth.saturating_sub(1),
+                _ if ch == delimiter && depth == 0 => {
+                    parts.push(&input[start..index]);
+                    start = index + ch.len_utf8();
+                }
+                _ => {}
+            },
+        }
+    }
+    parts.push(&input[start..]);
+    parts
+}
+
+/// Replace every `--value(...)` in a template with the resolved suffix
+fn substitute_value(template: &str, suffix: &str) -> Option<String> {
+    let mut output = String::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find("--value(") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + "--value(".len()..];
+        let end = after.find(')')?;
+        output.push_str(&resolve_value(&after[..end], suffix)?);
+        rest = &after[end + 1..];
+    }
+    output.push_str(rest);
+
+    Some(output)
+}
+
+/// Resolve a class suffix against the accepted `--value()` types
+fn resolve_value(types: &str, suffix: &str) -> Option<String> {
+    types.split(',').map(str::trim).find_map(|kind| match kind {
+        "integer" => suffix.parse::<i64>().ok().map(|_| suffix.to_string()),
+        "number" => suffix
+            .parse::<f64>()
+            .ok()
+            .filter(|number| number.is_finite())
+            .map(|_| suffix.to_string()),
+        "percentage" => suffix
+            .strip_suffix('%')
+            .and_then(|number| number.parse::<f64>().ok())
+            .map(|_| suffix.to_string()),
+        "[*]" => suffix
+            .strip_prefix('[')
+            .and_then(|inner| inner.strip_suffix(']'))
+            .map(|inner| inner.replace('_', " ")),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_utility() {
+        let mut registry = CustomUtilityRegistry::new();
+        registry.define("tab-4", "tab-size: 4").unwrap();
+
+        let properties = registry.resolve("tab-4").unwrap();
+        assert_eq!(properties.len(), 1);
+        assert_eq!(properties[0].name, "tab-size");
+        assert_eq!(properties[0].value, "4");
+        assert!(registry.resolve("tab-8").is_none());
+    }
+
+    #[test]
+    fn test_functional_utility() {
+        let mut registry = CustomUtilityRegistry::new();
+        registry
+            .define("tab-*", "tab-size: --value(integer, [*])")
+            .unwrap();
+
+        assert_eq!(registry.resolve("tab-2").unwrap()[0].value, "2");
+        assert_eq!(registry.resolve("tab-[3_4]").unwrap()[0].value, "3 4");
+        assert!(registry.resolve("tab-wide").is_none());
+    }
+
+    #[test]
+    fn test_parse_declarations() {
+        let properties =
+            parse_declarations("content: 'a;b'; background: url(a;b) !important;").unwrap();
+        assert_eq!(properties.len(), 2);
+        assert_eq!(properties[0].value, "'a;b'");
+        assert_eq!(properties[1].value, "url(a;b)");
+        assert!(properties[1].important);
+
+        assert!(parse_declarations("&:hover { color: red }").is_err());
+        assert!(parse_declarations("color red").is_err());
+    }
+}
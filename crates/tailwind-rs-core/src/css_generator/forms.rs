@@ -0,0 +1,543 @@
+//! Forms Plugin
+//!
+//! This module implements the forms plugin, a port of `@tailwindcss/forms`:
+//! a reset that makes form elements easy to restyle with utilities. Text
+//! inputs, textareas, selects, checkboxes and radios get consistent borders,
+//! padding and focus rings, selects get a chevron, and checked checkboxes and
+//! radios get a checkmark or dot, all drawn with theme colors.
+//!
+//! With the `base` strategy the styles apply to the elements themselves in
+//! the `base` cascade layer; with the `class` strategy they're opt-in through
+//! `form-input`, `form-textarea`, `form-select`, `form-multiselect`,
+//! `form-checkbox` and `form-radio`. The plugin is enabled from the
+//! configuration's plugin list as `forms` (both strategies), `forms:base` or
+//! `forms:class`.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use tailwind_rs_core::css_generator::forms::FormsStrategy;
+//! use tailwind_rs_core::CssGenerator;
+//!
+//! let mut generator = CssGenerator::new();
+//! generator.enable_forms(FormsStrategy::Class);
+//! generator.add_class("form-checkbox").unwrap();
+//!
+//! let css = generator.generate_css();
+//! assert!(css.contains(".form-checkbox:checked {"));
+//! assert!(css.contains("background-image: url(\"data:image/svg+xml,"));
+//! ```
+
+use super::types::{CssProperty, CssRule};
+use super::utils::escape_class_selector;
+
+/// How the forms plugin applies its styles
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FormsStrategy {
+    /// Style form elements globally in the `base` layer
+    Base,
+    /// Only style elements with a `form-*` class
+    Class,
+    /// Both global styles and `form-*` classes
+    Both,
+}
+
+impl FormsStrategy {
+    /// Parse a plugin list entry (`forms`, `forms:base`, `@tailwindcss/forms:class`)
+    pub fn from_plugin(plugin: &str) -> Option<Self> {
+        let (name, strategy) = match plugin.split_once(':') {
+            Some((name, strategy)) => (name, Some(strategy)),
+            None => (plugin, None),
+        };
+        if name != "forms" && name != "@tailwindcss/forms" {
+            return None;
+        }
+        match strategy {
+            None => Some(Self::Both),
+            Some("base") => Some(Self::Base),
+            Some("class") => Some(Self::Class),
+            Some(_) => None,
+        }
+    }
+
+    /// Whether element styles are emitted in the base layer
+    pub fn includes_base(self) -> bool {
+        matches!(self, Self::Base | Self::Both)
+    }
+
+    /// Whether the `form-*` classes are available
+    pub fn includes_class(self) -> bool {
+        matches!(self, Self::Class | Self::Both)
+    }
+}
+
+/// Text-like input types styled like text inputs
+const TEXT_INPUT_TYPES: &[&str] = &[
+    "text",
+    "email",
+    "url",
+    "password",
+    "number",
+    "date",
+    "datetime-local",
+    "month",
+    "search",
+    "tel",
+    "time",
+    "week",
+];
+
+/// A group of styles: the elements and classes it applies to, and a selector suffix
+struct FormStyle {
+    /// Element selectors for the base strategy
+    elements: Vec<String>,
+    /// Classes for the class strategy
+    classes: &'static [&'static str],
+    /// Appended to every selector (`:focus`, `::placeholder`)
+    suffix: &'static str,
+    /// Declarations
+    properties: Vec<CssProperty>,
+}
+
+/// Classes styled like text inputs
+const TEXT_CLASSES: &[&str] = &[
+    "form-input",
+    "form-textarea",
+    "form-select",
+    "form-multiselect",
+];
+
+/// Every class of the class strategy
+const FORM_CLASSES: &[&str] = &[
+    "form-input",
+    "form-textarea",
+    "form-select",
+    "form-multiselect",
+    "form-checkbox",
+    "form-radio",
+];
+
+/// Forms plugin methods for CssGenerator
+pub trait CssGeneratorForms {
+    /// Enable the forms plugin with the given strategy
+    fn enable_forms(&mut self, strategy: FormsStrategy);
+
+    /// Rules of the `base` layer, empty unless the base strategy is enabled
+    fn forms_base_rules(&self) -> Vec<CssRule>;
+
+    /// Compile a (possibly variant-prefixed) `form-*` class
+    ///
+    /// Returns `None` when the class strategy isn't enabled or the class
+    /// isn't a form class.
+    fn compile_form_class(&self, class: &str) -> Option<Vec<CssRule>>;
+}
+
+impl CssGeneratorForms for super::CssGenerator {
+    fn enable_forms(&mut self, strategy: FormsStrategy) {
+        self.forms = Some(strategy);
+    }
+
+    fn forms_base_rules(&self) -> Vec<CssRule> {
+        if !self.forms.is_some_and(FormsStrategy::includes_base) {
+            return Vec::new();
+        }
+        self.form_styles()
+            .into_iter()
+            .filter(|style| !style.elements.is_empty())
+            .map(|style| CssRule {
+                selector: style
+                    .elements
+                    .iter()
+                    .map(|element| format!("{}{}", element, style.suffix))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                properties: style.properties,
+                media_query: None,
+                specificity: 0,
+            })
+            .collect()
+    }
+
+    fn compile_form_class(&self, class: &str) -> Option<Vec<CssRule>> {
+        if !self.forms.is_some_and(FormsStrategy::includes_class) {
+            return None;
+        }
+        let (variants, name) = self.variant_parser.split_variants(class);
+        if !FORM_CLASSES.contains(&name.as_str()) {
+            return None;
+        }
+
+        let (selector, media_query) = self
+            .variant_parser
+            .apply_variants(&format!(".{}", escape_class_selector(class)), &variants);
+        let specificity = variants.len() as u32 * 10;
+        Some(
+            self.form_styles()
+                .into_iter()
+                .filter(|style| style.classes.contains(&name.as_str()))
+                .map(|style| CssRule {
+                    selector: format!("{}{}", selector, style.suffix),
+                    properties: style.properties,
+                    media_query: media_query.clone(),
+                    specificity,
+                })
+                .collect(),
+        )
+    }
+}
+
+impl super::CssGenerator {
+    /// The plugin's styles, with colors taken from the theme
+    fn form_styles(&self) -> Vec<FormStyle> {
+        let gray = "var(--color-gray-500)";
+        let blue = "var(--color-blue-600)";
+        let white = "var(--color-white)";
+        // Colors inside SVG backgrounds can't reference variables
+        let chevron_color = self
+            .theme_variables
+            .get("color-gray-500")
+            .unwrap_or("#6b7280");
+
+        let mut text_inputs: Vec<String> = TEXT_INPUT_TYPES
+            .iter()
+            .map(|kind| format!("input:where([type='{}'])", kind))
+            .collect();
+        text_inputs.insert(1, "input:where(:not([type]))".to_string());
+        text_inputs.extend(["select:where([multiple])", "textarea", "select"].map(String::from));
+
+        let checkbox = "input:where([type='checkbox'])".to_string();
+        let radio = "input:where([type='radio'])".to_string();
+        let toggles = || vec![checkbox.clone(), radio.clone()];
+
+        let focus_ring = |offset: &str| {
+            declarations(&[
+                ("outline", "2px solid transparent"),
+                ("outline-offset", "2px"),
+                ("--tw-ring-inset", "var(--tw-empty,/*!*/ /*!*/)"),
+                ("--tw-ring-offset-width", offset),
+                ("--tw-ring-offset-color", white),
+                ("--tw-ring-color", blue),
+                (
+                    "--tw-ring-offset-shadow",
+                    "var(--tw-ring-inset) 0 0 0 var(--tw-ring-offset-width) var(--tw-ring-offset-color)",
+                ),
+                (
+                    "--tw-ring-shadow",
+                    "var(--tw-ring-inset) 0 0 0 calc(1px + var(--tw-ring-offset-width)) var(--tw-ring-color)",
+                ),
+                (
+                    "box-shadow",
+                    "var(--tw-ring-offset-shadow), var(--tw-ring-shadow), var(--tw-shadow)",
+                ),
+            ])
+        };
+        let checked = || {
+            declarations(&[
+                ("border-color", "transparent"),
+                ("background-color", "currentColor"),
+                ("background-size", "100% 100%"),
+                ("background-position", "center"),
+                ("background-repeat", "no-repeat"),
+            ])
+        };
+
+        let mut text_focus = focus_ring("0px");
+        text_focus.push(property("border-color", blue));
+
+        let mut checkbox_checked = checked();
+        checkbox_checked.push(property("background-image", &svg_url(CHECKMARK_SVG)));
+        let mut radio_checked = checked();
+        radio_checked.push(property("background-image", &svg_url(RADIO_SVG)));
+        let mut indeterminate = checked();
+        indeterminate.push(property("background-image", &svg_url(INDETERMINATE_SVG)));
+
+        vec![
+            FormStyle {
+                elements: text_inputs.clone(),
+                classes: TEXT_CLASSES,
+                suffix: "",
+                properties: declarations(&[
+                    ("appearance", "none"),
+                    ("background-color", white),
+                    ("border-color", gray),
+                    ("border-width", "1px"),
+                    ("border-radius", "0"),
+                    ("padding-top", "0.5rem"),
+                    ("padding-right", "0.75rem"),
+                    ("padding-bottom", "0.5rem"),
+                    ("padding-left", "0.75rem"),
+                    ("font-size", "1rem"),
+                    ("line-height", "1.5rem"),
+                    ("--tw-shadow", "0 0 #0000"),
+                ]),
+            },
+            FormStyle {
+                elements: text_inputs,
+                classes: TEXT_CLASSES,
+                suffix: ":focus",
+                properties: text_focus,
+            },
+            FormStyle {
+                elements: vec!["input".to_string(), "textarea".to_string()],
+                classes: &["form-input", "form-textarea"],
+                suffix: "::placeholder",
+                properties: declarations(&[("color", gray), ("opacity", "1")]),
+            },
+            FormStyle {
+                elements: vec!["::-webkit-datetime-edit-fields-wrapper".to_string()],
+                classes: &["form-input"],
+                suffix: "",
+                properties: declarations(&[("padding", "0")]),
+            },
+            FormStyle {
+                elements: vec!["::-webkit-date-and-time-value".to_string()],
+                classes: &["form-input"],
+                suffix: "",
+                properties: declarations(&[("min-height", "1.5em"), ("text-align", "inherit")]),
+            },
+            FormStyle {
+                elements: vec!["::-webkit-datetime-edit".to_string()],
+                classes: &["form-input"],
+                suffix: "",
+                properties: declarations(&[
+                    ("display", "inline-flex"),
+                    ("padding-top", "0"),
+                    ("padding-bottom", "0"),
+                ]),
+            },
+            FormStyle {
+                elements: vec!["select".to_string()],
+                classes: &["form-select"],
+                suffix: "",
+                properties: declarations(&[
+                    ("background-image", &svg_url(&chevron_svg(chevron_color))),
+                    ("background-position", "right 0.5rem center"),
+                    ("background-repeat", "no-repeat"),
+                    ("background-size", "1.5em 1.5em"),
+                    ("padding-right", "2.5rem"),
+                    ("print-color-adjust", "exact"),
+                ]),
+            },
+            FormStyle {
+                elements: vec![
+                    "select:where([multiple])".to_string(),
+                    "select:where([size]:not([size='1']))".to_string(),
+                ],
+                classes: &[],
+                suffix: "",
+                properties: declarations(&[
+                    ("background-image", "initial"),
+                    ("background-position", "initial"),
+                    ("background-repeat", "unset"),
+                    ("background-size", "initial"),
+                    ("padding-right", "0.75rem"),
+                    ("print-color-adjust", "unset"),
+                ]),
+            },
+            FormStyle {
+                elements: toggles(),
+                classes: &["form-checkbox", "form-radio"],
+                suffix: "",
+                properties: declarations(&[
+                    ("appearance", "none"),
+                    ("padding", "0"),
+                    ("print-color-adjust", "exact"),
+                    ("display", "inline-block"),
+                    ("vertical-align", "middle"),
+                    ("background-origin", "border-box"),
+                    ("user-select", "none"),
+                    ("flex-shrink", "0"),
+                    ("height", "1rem"),
+                    ("width", "1rem"),
+                    ("color", blue),
+                    ("background-color", white),
+                    ("border-color", gray),
+                    ("border-width", "1px"),
+                    ("--tw-shadow", "0 0 #0000"),
+                ]),
+            },
+            FormStyle {
+                elements: vec![checkbox.clone()],
+                classes: &["form-checkbox"],
+                suffix: "",
+                properties: declarations(&[("border-radius", "0")]),
+            },
+            FormStyle {
+                elements: vec![radio.clone()],
+                classes: &["form-radio"],
+                suffix: "",
+                properties: declarations(&[("border-radius", "100%")]),
+            },
+            FormStyle {
+                elements: toggles(),
+                classes: &["form-checkbox", "form-radio"],
+                suffix: ":focus",
+                properties: focus_ring("2px"),
+            },
+            FormStyle {
+                elements: vec![checkbox.clone()],
+                classes: &["form-checkbox"],
+                suffix: ":checked",
+                properties: checkbox_checked,
+            },
+            FormStyle {
+                elements: vec![radio.clone()],
+                classes: &["form-radio"],
+                suffix: ":checked",
+                properties: radio_checked,
+            },
+            FormStyle {
+                elements: toggles(),
+                classes: &["form-checkbox", "form-radio"],
+                suffix: ":checked:hover",
+                properties: declarations(&[
+                    ("border-color", "transparent"),
+                    ("background-color", "currentColor"),
+                ]),
+            },
+            FormStyle {
+                elements: toggles(),
+                classes: &["form-checkbox", "form-radio"],
+                suffix: ":checked:focus",
+                properties: declarations(&[
+                    ("border-color", "transparent"),
+                    ("background-color", "currentColor"),
+                ]),
+            },
+            FormStyle {
+                elements: vec![checkbox.clone()],
+                classes: &["form-checkbox"],
+                suffix: ":indeterminate",
+                properties: indeterminate,
+            },
+            FormStyle {
+                elements: vec!["input:where([type='file'])".to_string()],
+                classes: &[],
+                suffix: "",
+                properties: declarations(&[
+                    ("background", "unset"),
+                    ("border-color", "inherit"),
+                    ("border-width", "0"),
+                    ("border-radius", "0"),
+                    ("padding", "0"),
+                    ("font-size", "unset"),
+                    ("line-height", "inherit"),
+                ]),
+            },
+            FormStyle {
+                elements: vec!["input:where([type='file'])".to_string()],
+                classes: &[],
+                suffix: ":focus",
+                properties: declarations(&[
+                    ("outline", "1px solid ButtonText"),
+                    ("outline", "1px auto -webkit-focus-ring-color"),
+                ]),
+            },
+        ]
+    }
+}
+
+/// Checkmark drawn on checked checkboxes
+const CHECKMARK_SVG: &str = "<svg viewBox='0 0 16 16' fill='white' xmlns='http://www.w3.org/2000/svg'><path d='M12.207 4.793a1 1 0 010 1.414l-5 5a1 1 0 01-1.414 0l-2-2a1 1 0 011.414-1.414L6.5 9.086l4.293-4.293a1 1 0 011.414 0z'/></svg>";
+
+/// Dot drawn on checked radios
+const RADIO_SVG: &str = "<svg viewBox='0 0 16 16' fill='white' xmlns='http://www.w3.org/2000/svg'><circle cx='8' cy='8' r='3'/></svg>";
+
+/// Dash drawn on indeterminate checkboxes
+const INDETERMINATE_SVG: &str = "<svg xmlns='http://www.w3.org/2000/svg' fill='none' viewBox='0 0 16 16'><path stroke='white' stroke-linecap='round' stroke-linejoin='round' stroke-width='2' d='M4 8h8'/></svg>";
+
+/// Chevron drawn on selects, in the given color
+fn chevron_svg(color: &str) -> String {
+    format!(
+        "<svg xmlns='http://www.w3.org/2000/svg' fill='none' viewBox='0 0 20 20'><path stroke='{}' stroke-linecap='round' stroke-linejoin='round' stroke-width='1.5' d='M6 8l4 4 4-4'/></svg>",
+        color
+    )
+}
+
+/// Encode an SVG document as a `url()` data URL
+fn svg_url(svg: &str) -> String {
+    let encoded = svg
+        .replace('%', "%25")
+        .replace('<', "%3c")
+        .replace('>', "%3e")
+        .replace('#', "%23")
+        .replace('"', "'");
+    format!("url(\"data:image/svg+xml,{}\")", encoded)
+}
+
+fn property(name: &str, value: &str) -> CssProperty {
+    CssProperty {
+        name: name.to_string(),
+        value: value.to_string(),
+        important: false,
+    }
+}
+
+fn declarations(declarations: &[(&str, &str)]) -> Vec<CssProperty> {
+    declarations
+        .iter()
+        .map(|(name, value)| property(name, value))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TailwindConfig;
+    use crate::css_generator::CssGenerator;
+
+    #[test]
+    fn test_strategy_from_plugin() {
+        assert_eq!(
+            FormsStrategy::from_plugin("forms"),
+            Some(FormsStrategy::Both)
+        );
+        assert_eq!(
+            FormsStrategy::from_plugin("@tailwindcss/forms:class"),
+            Some(FormsStrategy::Class)
+        );
+        assert_eq!(
+            FormsStrategy::from_plugin("forms:base"),
+            Some(FormsStrategy::Base)
+        );
+        assert_eq!(FormsStrategy::from_plugin("forms:other"), None);
+        assert_eq!(FormsStrategy::from_plugin("typography"), None);
+    }
+
+    #[test]
+    fn test_base_strategy() {
+        let mut generator = CssGenerator::new();
+        assert!(generator.forms_base_rules().is_empty());
+
+        generator.enable_forms(FormsStrategy::Base);
+        let css = generator.generate_css();
+        assert!(css.starts_with("@layer base {"));
+        assert!(css.contains("input:where([type='text']), input:where(:not([type])),"));
+        assert!(css.contains("input:where([type='checkbox']):checked {"));
+        // The chevron is drawn in the theme's gray
+        assert!(css.contains("stroke='%236b7280'"));
+        assert!(css.contains("--color-blue-600: "));
+        assert!(generator.add_class("form-input").is_err());
+    }
+
+    #[test]
+    fn test_class_strategy_from_config() {
+        let mut config = TailwindConfig::new();
+        config.plugins.push("forms:class".to_string());
+        config
+            .theme
+            .add_color("gray-500", crate::theme::Color::hex("#123456"));
+
+        let mut generator = CssGenerator::new();
+        generator.apply_config(&config).unwrap();
+        assert!(generator.forms_base_rules().is_empty());
+
+        generator.add_class("form-select").unwrap();
+        generator.add_class("md:form-radio").unwrap();
+        let css = generator.generate_css();
+        assert!(css.contains(".form-select {"));
+        assert!(css.contains("stroke='%23123456'"));
+        assert!(css.contains(".md\\:form-radio:checked {"));
+        assert!(!css.contains("input:where("));
+    }
+}
@@ -2,29 +2,37 @@
 //!
 //! This module contains the core CssGenerator struct and its main functionality.
 
+use super::components::ComponentRegistry;
+use super::custom_utilities::CustomUtilityRegistry;
+use super::forms::FormsStrategy;
 use super::parsers::{
     AccentColorParser, AccessibilityParser, AdvancedBorderParser, AdvancedColorParser,
     AdvancedGridParser, AdvancedSpacingParser, AlignContentParser, AlignItemsParser,
     AlignSelfParser, AnimationParser, ArbitraryParser, AspectRatioParser,
     BackdropFilterUtilitiesParser, BackgroundParser, BackgroundPropertiesParser,
     BorderUtilitiesParser, BoxUtilitiesParser, BreakControlParser, ColorParser, ColumnsParser,
-    DataAttributeParser, DivideParser, EffectsParser, EffectsUtilitiesParser,
+    ContainerQueryParser, DataAttributeParser, DivideParser, EffectsParser, EffectsUtilitiesParser,
     FilterUtilitiesParser, FlexBasisParser, FlexDirectionParser, FlexGrowParser, FlexParser,
     FlexShrinkParser, FlexWrapParser, FlexboxParser, FractionalTransformsParser, GapParser,
     GradientParser, GridAutoColumnsParser, GridAutoFlowParser, GridAutoRowsParser,
     GridColumnParser, GridRowParser, GridTemplateColumnsParser, GridTemplateRowsParser,
     GroupParser, InsetParser, InteractiveParser, JustifyContentParser, JustifyItemsParser,
-    JustifySelfParser, LayoutParser, LayoutUtilitiesParser, MarginParser, MaskUtilitiesParser,
-    ObjectFitParser, OrderParser, OverflowParser, OverscrollParser, PlaceContentParser,
-    PlaceItemsParser, PlaceSelfParser, PositionParser, PositioningParser, ProseParser, RingParser,
-    ShadowParser, SizingParser, SpacingParser, SvgParser, TableParser, TransformParser,
-    TransitionParser, TransitionPropertiesParser, TypographyParser, VisibilityParser, ZIndexParser,
+    JustifySelfParser, LayoutParser, LayoutUtilitiesParser, LogicalPropertiesParser, MarginParser,
+    MaskUtilitiesParser, ObjectFitParser, OrderParser, OverflowParser, OverscrollParser,
+    PlaceContentParser, PlaceItemsParser, PlaceSelfParser, PositionParser, PositioningParser,
+    ProseParser, RingParser, ShadowParser, SizingParser, SpacingParser, SvgParser, TableParser,
+    TextShadowParser, TransformParser, TransitionParser, TransitionPropertiesParser,
+    TypographyParser, VisibilityParser, ZIndexParser,
 };
+use super::theme_variables::{ThemeScope, ThemeVariables};
 use super::types::{CssGenerationConfig, CssProperty, CssRule};
+use super::value_resolver::ValueResolver;
 use super::variants::VariantParser;
+use crate::color_space::{convert_colors, ColorFormat};
+use crate::config::{SafelistEntry, TailwindConfig};
 use crate::error::Result;
 use crate::responsive::Breakpoint;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 /// CSS generator that converts Tailwind classes to CSS rules
 #[derive(Debug, Clone)]
@@ -35,6 +43,16 @@ pub struct CssGenerator {
     pub breakpoints: HashMap<Breakpoint, String>,
     /// Custom CSS properties
     pub custom_properties: HashMap<String, String>,
+    /// Component classes (`@apply`-style utility bundles)
+    pub components: ComponentRegistry,
+    /// User-defined utilities (`@utility` / `[utilities]`)
+    pub custom_utilities: CustomUtilityRegistry,
+    /// Theme tokens exposed as CSS custom properties
+    pub theme_variables: ThemeVariables,
+    /// Classes never generated, even when added
+    pub blocklist: Vec<String>,
+    /// Forms plugin strategy, when the plugin is enabled
+    pub forms: Option<FormsStrategy>,
     /// Generation configuration
     pub config: CssGenerationConfig,
     /// Spacing parser
@@ -79,6 +97,10 @@ pub struct CssGenerator {
     pub interactive_parser: InteractiveParser,
     /// Prose parser
     pub prose_parser: ProseParser,
+    /// Text shadow parser
+    pub text_shadow_parser: TextShadowParser,
+    /// Logical properties parser
+    pub logical_properties_parser: LogicalPropertiesParser,
     /// Divide parser
     pub divide_parser: DivideParser,
     /// Gradient parser
@@ -111,6 +133,8 @@ pub struct CssGenerator {
     pub overflow_parser: OverflowParser,
     /// Overscroll parser
     pub overscroll_parser: OverscrollParser,
+    /// Container query parser (`@container`, `@container/name`)
+    pub container_query_parser: ContainerQueryParser,
     /// Position parser
     pub position_parser: PositionParser,
     /// Inset parser
@@ -214,6 +238,17 @@ impl CssGenerator {
         <Self as CssGeneratorOperations>::add_class(self, class)
     }
 
+    /// Check whether a class resolves to CSS, without adding it
+    pub fn resolves_class(&self, class: &str) -> bool {
+        use super::components::CssGeneratorComponents;
+        use super::forms::CssGeneratorForms;
+        use super::typography::CssGeneratorTypography;
+        matches!(self.compile_component(class), Ok(Some(_)))
+            || matches!(self.compile_prose(class), Ok(Some(_)))
+            || self.compile_form_class(class).is_some()
+            || self.class_to_css_rule(class).is_ok()
+    }
+
     /// Add a CSS selector directly (for non-Tailwind CSS selectors)
     pub fn add_css_selector(&mut self, selector: &str, properties: &str) -> Result<()> {
         use super::generator_operations::CssGeneratorOperations;
@@ -232,6 +267,135 @@ impl CssGenerator {
         <Self as CssGeneratorOperations>::add_custom_property(self, name, value)
     }
 
+    /// Define a component class that applies a whitespace-separated list of utilities
+    ///
+    /// The component is emitted in the `components` layer once it is referenced
+    /// through [`CssGenerator::add_class`].
+    pub fn add_component(&mut self, name: &str, utilities: &str) -> Result<()> {
+        use super::components::CssGeneratorComponents;
+        <Self as CssGeneratorComponents>::add_component(self, name, utilities)
+    }
+
+    /// Define a custom utility from a declaration block (e.g. `tab-size: 4`)
+    ///
+    /// Names ending in `-*` define functional utilities whose `--value(...)`
+    /// placeholders are filled from the class suffix.
+    pub fn add_utility(&mut self, name: &str, declarations: &str) -> Result<()> {
+        self.custom_utilities.define(name, declarations)
+    }
+
+    /// Define a custom variant from a template such as `&:where(.theme-midnight *)`
+    pub fn add_variant(&mut self, name: &str, template: &str) -> Result<()> {
+        self.variant_parser.add_custom_variant(name, template)
+    }
+
+    /// Candidate utilities that safelist patterns are matched against
+    pub fn utility_catalog(&self) -> Vec<String> {
+        use super::safelist::CssGeneratorSafelist;
+        <Self as CssGeneratorSafelist>::utility_catalog(self)
+    }
+
+    /// Generate every class a safelist covers, returning the classes added
+    ///
+    /// Literal entries must be valid classes; pattern entries expand to the
+    /// catalog utilities they match, with each of their variants.
+    pub fn add_safelist(&mut self, entries: &[SafelistEntry]) -> Result<Vec<String>> {
+        use super::safelist::CssGeneratorSafelist;
+        <Self as CssGeneratorSafelist>::add_safelist(self, entries)
+    }
+
+    /// Forbid a class, with or without variants, removing any rule already generated
+    pub fn block_class(&mut self, class: &str) {
+        use super::safelist::CssGeneratorSafelist;
+        <Self as CssGeneratorSafelist>::block_class(self, class)
+    }
+
+    /// Check whether the blocklist forbids a class
+    pub fn is_blocked(&self, class: &str) -> bool {
+        use super::safelist::CssGeneratorSafelist;
+        <Self as CssGeneratorSafelist>::is_blocked(self, class)
+    }
+
+    /// Enable the forms plugin, resetting form elements or adding `form-*` classes
+    pub fn enable_forms(&mut self, strategy: FormsStrategy) {
+        use super::forms::CssGeneratorForms;
+        <Self as CssGeneratorForms>::enable_forms(self, strategy)
+    }
+
+    /// Rules of the `base` cascade layer
+    pub fn base_rules(&self) -> Vec<CssRule> {
+        use super::forms::CssGeneratorForms;
+        <Self as CssGeneratorForms>::forms_base_rules(self)
+    }
+
+    /// Apply the generator-relevant parts of a [`TailwindConfig`]
+    pub fn apply_config(&mut self, config: &TailwindConfig) -> Result<()> {
+        // Named themes only work through variables, so they imply variable output
+        self.config.theme_variables = config.build.theme_variables || !config.themes.is_empty();
+        self.config.color_format = config.build.color_format;
+        self.config.layers = config.build.layers.clone();
+        self.config.passthrough = config.build.passthrough.clone();
+        self.theme_variables.apply_theme(&config.theme);
+
+        for (name, theme) in &config.themes {
+            theme.validate(name)?;
+            let mut scope = ThemeScope::new(name, theme.selector_for(name));
+            scope.media_query = theme.media.clone();
+            for (token, value) in &theme.tokens {
+                scope = scope.with_token(token, value);
+            }
+            self.theme_variables.add_scope(scope);
+
+            // `theme-<name>:` variant for one-off overrides, unless defined explicitly
+            let variant = format!("theme-{}", name);
+            if !config.variants.contains_key(&variant) {
+                self.add_variant(&variant, &theme.variant_template(name))?;
+            }
+        }
+
+        let mut variants: Vec<_> = config.variants.iter().collect();
+        variants.sort();
+        for (name, template) in variants {
+            self.add_variant(name, template)?;
+        }
+
+        let mut utilities: Vec<_> = config.utilities.iter().collect();
+        utilities.sort();
+        for (name, declarations) in utilities {
+            self.add_utility(name, declarations)?;
+        }
+
+        let mut components: Vec<_> = config.components.iter().collect();
+        components.sort();
+        for (name, utilities) in components {
+            self.add_component(name, utilities)?;
+        }
+
+        for strategy in config
+            .plugins
+            .iter()
+            .filter_map(|p| FormsStrategy::from_plugin(p))
+        {
+            self.enable_forms(strategy);
+        }
+
+        for class in &config.blocklist {
+            self.block_class(class);
+        }
+        self.add_safelist(&config.safelist)?;
+        Ok(())
+    }
+
+    /// Get the resolver for spacing and sizing values
+    ///
+    /// The spacing base follows the theme's `--spacing` token.
+    pub fn value_resolver(&self) -> ValueResolver {
+        ValueResolver::from_spacing_token(
+            self.theme_variables.get("spacing"),
+            self.config.theme_variables,
+        )
+    }
+
     /// Get the current configuration
     pub fn config(&self) -> &CssGenerationConfig {
         &self.config
@@ -291,59 +455,209 @@ impl CssGenerator {
         <Self as CssGeneratorOperations>::update_rule(self, selector, rule)
     }
 
+    /// Custom properties for the `:root` block
+    ///
+    /// Combines the theme tokens referenced by the generated rules with the
+    /// properties added through [`CssGenerator::add_custom_property`].
+    pub fn root_properties(&self) -> HashMap<String, String> {
+        let base_rules = self.base_rules();
+        let component_rules = self.components.active_rules();
+        let rules = self
+            .rules
+            .values()
+            .chain(base_rules.iter())
+            .chain(component_rules.iter().copied());
+
+        let mut properties: HashMap<String, String> = self
+            .theme_variables
+            .referenced(rules, self.config.theme_variables)
+            .into_iter()
+            .collect();
+        properties.extend(self.custom_properties.clone());
+        properties
+    }
+
+    /// Get the token overrides of each named theme referenced by the generated rules
+    pub fn theme_scopes(&self) -> Vec<(&ThemeScope, BTreeMap<String, String>)> {
+        let base_rules = self.base_rules();
+        let component_rules = self.components.active_rules();
+        let rules = self
+            .rules
+            .values()
+            .chain(base_rules.iter())
+            .chain(component_rules.iter().copied());
+        self.theme_variables.referenced_scopes(rules)
+    }
+
     /// Generate CSS from all added classes
     pub fn generate_css(&self) -> String {
-        super::css_output::CssOutputGenerator::generate_css(&self.rules, &self.custom_properties)
+        self.render(false)
     }
 
     /// Generate minified CSS from all added classes
     pub fn generate_minified_css(&self) -> String {
-        super::css_output::CssOutputGenerator::generate_minified_css(
-            &self.rules,
-            &self.custom_properties,
-        )
+        self.render(true)
     }
 
-    /// Convert a class name to a CSS rule
-    pub fn class_to_css_rule(&self, class: &str) -> Result<CssRule> {
-        let (variants, base_class) = self.parse_variants(class);
-        let properties = self.class_to_properties(class)?;
-
-        // Build selector with variants
-        let mut selector = String::new();
-        for variant in &variants {
-            let variant_selector = self.variant_parser.get_variant_selector(variant);
-            if !variant_selector.is_empty() {
-                selector.push_str(&variant_selector);
-            }
+    /// Render the stylesheet followed by the passthrough CSS
+    fn render(&self, minified: bool) -> String {
+        let mut css = self.render_colors(minified);
+        if !self.config.passthrough.is_empty() {
+            css.push_str(&self.config.passthrough);
         }
+        css
+    }
 
-        // Add the base class
-        selector.push_str(&format!(".{}", base_class));
-
-        // Determine media query for responsive and device variants
-        let media_query = variants.iter().find_map(|variant| {
-            // Try responsive media query first
-            if let Some(responsive_query) = self.variant_parser.get_responsive_media_query(variant)
-            {
-                Some(responsive_query)
-            } else {
-                // Try device media query
-                self.variant_parser.get_device_media_query(variant)
+    /// Render the stylesheet, writing colors in the configured [`ColorFormat`]
+    ///
+    /// With [`ColorFormat::OklchWithFallback`] the stylesheet is rendered with
+    /// sRGB hex colors, followed by an `@supports` block redeclaring every
+    /// color in OKLCH.
+    fn render_colors(&self, minified: bool) -> String {
+        match self.config.color_format {
+            Some(ColorFormat::OklchWithFallback) => {
+                let fallback = self.render_with(Some(ColorFormat::Hex), false, minified);
+                let modern = self.render_with(Some(ColorFormat::Oklch), true, minified);
+                if modern.is_empty() {
+                    fallback
+                } else if minified {
+                    format!(
+                        "{}@supports {}{{{}}}",
+                        fallback,
+                        ColorFormat::SUPPORTS_QUERY,
+                        modern
+                    )
+                } else {
+                    format!(
+                        "{}@supports {} {{\n{}}}\n",
+                        fallback,
+                        ColorFormat::SUPPORTS_QUERY,
+                        modern
+                    )
+                }
             }
-        });
+            format => self.render_with(format, false, minified),
+        }
+    }
+
+    /// Render base styles, components, `:root` tokens, utilities and theme
+    /// scopes, leaving out the layers that aren't configured
+    ///
+    /// With `only_colors`, only the declarations containing literal colors
+    /// are kept, converted to `format`.
+    fn render_with(
+        &self,
+        format: Option<ColorFormat>,
+        only_colors: bool,
+        minified: bool,
+    ) -> String {
+        use super::css_output::CssOutputGenerator;
+
+        let convert = |value: &str| match format.and_then(|format| convert_colors(value, format)) {
+            Some(converted) => Some(converted),
+            None => (!only_colors).then(|| value.to_string()),
+        };
+        let convert_rule = |rule: &CssRule| {
+            let properties: Vec<CssProperty> = rule
+                .properties
+                .iter()
+                .filter_map(|property| {
+                    Some(CssProperty {
+                        value: convert(&property.value)?,
+                        ..property.clone()
+                    })
+                })
+                .collect();
+            (!properties.is_empty()).then(|| CssRule {
+                properties,
+                ..rule.clone()
+            })
+        };
+        let convert_properties = |properties: &mut dyn Iterator<Item = (&String, &String)>| {
+            properties
+                .filter_map(|(name, value)| Some((name.clone(), convert(value)?)))
+                .collect::<BTreeMap<String, String>>()
+        };
+
+        let emits = |layer: &str| self.config.layers.iter().any(|l| l == layer);
 
-        Ok(CssRule {
-            selector,
-            properties,
-            media_query: media_query.map(|s| s.to_string()),
-            specificity: variants.len() as u32 * 10, // Higher specificity for more variants
-        })
+        let base: Vec<CssRule> = if emits("base") {
+            self.base_rules().iter().filter_map(convert_rule).collect()
+        } else {
+            Vec::new()
+        };
+        let base: Vec<&CssRule> = base.iter().collect();
+        let components: Vec<CssRule> = if emits("components") {
+            self.components
+                .active_rules()
+                .into_iter()
+                .filter_map(convert_rule)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        let components: Vec<&CssRule> = components.iter().collect();
+        let rules: HashMap<String, CssRule> = if emits("utilities") {
+            self.rules
+                .iter()
+                .filter_map(|(key, rule)| Some((key.clone(), convert_rule(rule)?)))
+                .collect()
+        } else {
+            HashMap::new()
+        };
+        let (root, themes) = if emits("theme") {
+            let root: HashMap<String, String> =
+                convert_properties(&mut self.root_properties().iter())
+                    .into_iter()
+                    .collect();
+            let themes: Vec<_> = self
+                .theme_scopes()
+                .into_iter()
+                .map(|(scope, tokens)| (scope, convert_properties(&mut tokens.iter())))
+                .filter(|(_, tokens)| !tokens.is_empty())
+                .collect();
+            (root, themes)
+        } else {
+            (HashMap::new(), Vec::new())
+        };
+
+        if minified {
+            let mut css = CssOutputGenerator::generate_minified_layer_css("base", &base);
+            css.push_str(&CssOutputGenerator::generate_minified_layer_css(
+                "components",
+                &components,
+            ));
+            css.push_str(&CssOutputGenerator::generate_minified_css(&rules, &root));
+            for (scope, tokens) in &themes {
+                css.push_str(&CssOutputGenerator::generate_minified_properties_block(
+                    &scope.selector,
+                    scope.media_query.as_deref(),
+                    tokens,
+                ));
+            }
+            css
+        } else {
+            let mut css = CssOutputGenerator::generate_layer_css("base", &base);
+            css.push_str(&CssOutputGenerator::generate_layer_css(
+                "components",
+                &components,
+            ));
+            css.push_str(&CssOutputGenerator::generate_css(&rules, &root));
+            for (scope, tokens) in &themes {
+                css.push_str(&CssOutputGenerator::generate_properties_block(
+                    &scope.selector,
+                    scope.media_query.as_deref(),
+                    tokens,
+                ));
+            }
+            css
+        }
     }
 
-    /// Parse variants from a class name and return (variants, base_class)
-    fn parse_variants(&self, class: &str) -> (Vec<String>, String) {
-        self.variant_parser.parse_variants(class)
+    /// Convert a class name to a CSS rule
+    pub fn class_to_css_rule(&self, class: &str) -> Result<CssRule> {
+        use super::generator_parsers::CssGeneratorParsers;
+        <Self as CssGeneratorParsers>::class_to_css_rule(self, class)
     }
 
     /// Convert a class name to CSS properties
@@ -2,24 +2,28 @@
 //!
 //! This module contains the constructor and initialization methods for CssGenerator.
 
+use super::components::ComponentRegistry;
+use super::custom_utilities::CustomUtilityRegistry;
 use super::parsers::{
     AccentColorParser, AccessibilityParser, AdvancedBorderParser, AdvancedColorParser,
     AdvancedGridParser, AdvancedSpacingParser, AlignContentParser, AlignItemsParser,
     AlignSelfParser, AnimationParser, ArbitraryParser, AspectRatioParser,
     BackdropFilterUtilitiesParser, BackgroundParser, BackgroundPropertiesParser,
     BorderUtilitiesParser, BoxUtilitiesParser, BreakControlParser, ColorParser, ColumnsParser,
-    DataAttributeParser, DivideParser, EffectsParser, EffectsUtilitiesParser,
+    ContainerQueryParser, DataAttributeParser, DivideParser, EffectsParser, EffectsUtilitiesParser,
     FilterUtilitiesParser, FlexBasisParser, FlexDirectionParser, FlexGrowParser, FlexParser,
     FlexShrinkParser, FlexWrapParser, FlexboxParser, FractionalTransformsParser, GapParser,
     GradientParser, GridAutoColumnsParser, GridAutoFlowParser, GridAutoRowsParser,
     GridColumnParser, GridRowParser, GridTemplateColumnsParser, GridTemplateRowsParser,
     GroupParser, InsetParser, InteractiveParser, JustifyContentParser, JustifyItemsParser,
-    JustifySelfParser, LayoutParser, LayoutUtilitiesParser, MarginParser, MaskUtilitiesParser,
-    ObjectFitParser, OrderParser, OverflowParser, OverscrollParser, PlaceContentParser,
-    PlaceItemsParser, PlaceSelfParser, PositionParser, PositioningParser, ProseParser, RingParser,
-    ShadowParser, SizingParser, SpacingParser, SvgParser, TableParser, TransformParser,
-    TransitionParser, TransitionPropertiesParser, TypographyParser, VisibilityParser, ZIndexParser,
+    JustifySelfParser, LayoutParser, LayoutUtilitiesParser, LogicalPropertiesParser, MarginParser,
+    MaskUtilitiesParser, ObjectFitParser, OrderParser, OverflowParser, OverscrollParser,
+    PlaceContentParser, PlaceItemsParser, PlaceSelfParser, PositionParser, PositioningParser,
+    ProseParser, RingParser, ShadowParser, SizingParser, SpacingParser, SvgParser, TableParser,
+    TextShadowParser, TransformParser, TransitionParser, TransitionPropertiesParser,
+    TypographyParser, VisibilityParser, ZIndexParser,
 };
+use super::theme_variables::ThemeVariables;
 use super::types::CssGenerationConfig;
 use super::variants::VariantParser;
 use crate::responsive::Breakpoint;
@@ -46,6 +50,11 @@ impl CssGeneratorBuilder for super::CssGenerator {
             rules: HashMap::new(),
             breakpoints: HashMap::new(),
             custom_properties: HashMap::new(),
+            components: ComponentRegistry::new(),
+            custom_utilities: CustomUtilityRegistry::new(),
+            theme_variables: ThemeVariables::new(),
+            blocklist: Vec::new(),
+            forms: None,
             config: CssGenerationConfig::default(),
             spacing_parser: SpacingParser::new(),
             advanced_spacing_parser: AdvancedSpacingParser::new(),
@@ -68,6 +77,8 @@ impl CssGeneratorBuilder for super::CssGenerator {
             animation_parser: AnimationParser::new(),
             interactive_parser: InteractiveParser::new(),
             prose_parser: ProseParser::new(),
+            text_shadow_parser: TextShadowParser::new(),
+            logical_properties_parser: LogicalPropertiesParser::new(),
             divide_parser: DivideParser::new(),
             gradient_parser: GradientParser::new(),
             object_fit_parser: ObjectFitParser::new(),
@@ -84,6 +95,7 @@ impl CssGeneratorBuilder for super::CssGenerator {
             layout_utilities_parser: LayoutUtilitiesParser::new(),
             overflow_parser: OverflowParser::new(),
             overscroll_parser: OverscrollParser::new(),
+            container_query_parser: ContainerQueryParser::new(),
             position_parser: PositionParser::new(),
             inset_parser: InsetParser::new(),
             visibility_parser: VisibilityParser::new(),
@@ -133,6 +145,11 @@ impl CssGeneratorBuilder for super::CssGenerator {
             rules: HashMap::new(),
             breakpoints: HashMap::new(),
             custom_properties: HashMap::new(),
+            components: ComponentRegistry::new(),
+            custom_utilities: CustomUtilityRegistry::new(),
+            theme_variables: ThemeVariables::new(),
+            blocklist: Vec::new(),
+            forms: None,
             config,
             spacing_parser: SpacingParser::new(),
             advanced_spacing_parser: AdvancedSpacingParser::new(),
@@ -155,6 +172,8 @@ impl CssGeneratorBuilder for super::CssGenerator {
             animation_parser: AnimationParser::new(),
             interactive_parser: InteractiveParser::new(),
             prose_parser: ProseParser::new(),
+            text_shadow_parser: TextShadowParser::new(),
+            logical_properties_parser: LogicalPropertiesParser::new(),
             divide_parser: DivideParser::new(),
             gradient_parser: GradientParser::new(),
             object_fit_parser: ObjectFitParser::new(),
@@ -171,6 +190,7 @@ impl CssGeneratorBuilder for super::CssGenerator {
             layout_utilities_parser: LayoutUtilitiesParser::new(),
             overflow_parser: OverflowParser::new(),
             overscroll_parser: OverscrollParser::new(),
+            container_query_parser: ContainerQueryParser::new(),
             position_parser: PositionParser::new(),
             inset_parser: InsetParser::new(),
             visibility_parser: VisibilityParser::new(),
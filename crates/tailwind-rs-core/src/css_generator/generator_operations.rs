@@ -29,6 +29,26 @@ pub trait CssGeneratorOperations {
 
 impl CssGeneratorOperations for super::CssGenerator {
     fn add_class(&mut self, class: &str) -> Result<()> {
+        use super::components::CssGeneratorComponents;
+        use super::forms::CssGeneratorForms;
+        use super::typography::CssGeneratorTypography;
+        if self.is_blocked(class) {
+            return Ok(());
+        }
+        if let Some(rules) = self.compile_component(class)? {
+            self.components.activate(class, rules);
+            return Ok(());
+        }
+        // Prose classes style descendants, so they're emitted like components
+        if let Some(rules) = self.compile_prose(class)? {
+            self.components.activate(class, rules);
+            return Ok(());
+        }
+        if let Some(rules) = self.compile_form_class(class) {
+            self.components.activate(class, rules);
+            return Ok(());
+        }
+
         let rule = self.class_to_css_rule(class)?;
         self.rules.insert(class.to_string(), rule);
         Ok(())
@@ -10,9 +10,11 @@ use super::parsers::{
     GridTemplateColumnsParser, GridTemplateRowsParser, InsetParser, JustifyContentParser,
     JustifyItemsParser, JustifySelfParser, LayoutParser, OrderParser, OverflowParser,
     OverscrollParser, PlaceContentParser, PlaceItemsParser, PlaceSelfParser, PositionParser,
-    TransformParser, TypographyParser, UtilityParser, VisibilityParser, ZIndexParser,
+    SpacingScaleParser, TransformParser, TypographyParser, UtilityParser, VisibilityParser,
+    ZIndexParser,
 };
 use super::types::CssProperty;
+use super::utils::escape_class_selector;
 use crate::error::{Result, TailwindError};
 
 /// Parser methods trait for CssGenerator
@@ -20,6 +22,9 @@ pub trait CssGeneratorParsers {
     /// Convert a class name to CSS properties
     fn class_to_properties(&self, class: &str) -> Result<Vec<CssProperty>>;
 
+    /// Convert a variant-free class name to CSS properties using the built-in parsers
+    fn builtin_class_to_properties(&self, base_class: &str) -> Result<Vec<CssProperty>>;
+
     /// Parse variants from a class name and return (variants, base_class)
     fn parse_variants(&self, class: &str) -> (Vec<String>, String);
 
@@ -29,482 +34,525 @@ pub trait CssGeneratorParsers {
 
 impl CssGeneratorParsers for super::CssGenerator {
     fn class_to_properties(&self, class: &str) -> Result<Vec<CssProperty>> {
-        // First, parse variants and get the base class
-        let (_variants, base_class) = self.parse_variants(class);
+        // First, strip every variant and get the base class
+        let (_variants, base_class) = self.variant_parser.split_variants(class);
+
+        // User-defined utilities shadow the built-in parsers
+        if let Some(properties) = self.custom_utilities.resolve(&base_class) {
+            return Ok(properties);
+        }
+
+        // Theme colors (`bg-brand-500`) are not known to the palette parsers
+        let use_variables = self.config.theme_variables;
+        if let Some(properties) = self
+            .theme_variables
+            .resolve_color_utility(&base_class, use_variables)
+        {
+            return Ok(properties);
+        }
+
+        let mut properties = self.builtin_class_to_properties(&base_class)?;
+        if use_variables {
+            self.theme_variables.tokenize(&base_class, &mut properties);
+        }
+        Ok(properties)
+    }
+
+    fn builtin_class_to_properties(&self, base_class: &str) -> Result<Vec<CssProperty>> {
+        // Numeric, fractional and arbitrary spacing and sizing values share one resolver
+        if let Some(properties) =
+            SpacingScaleParser::new(self.value_resolver()).parse_class(base_class)
+        {
+            return Ok(properties);
+        }
+
+        // Classes produced by the text shadow and logical property builder
+        // traits, resolved from the same utility scales
+        if let Some(properties) = self.text_shadow_parser.parse_class(base_class) {
+            return Ok(properties);
+        }
+
+        if let Some(properties) = self.logical_properties_parser.parse_class(base_class) {
+            return Ok(properties);
+        }
 
         // Try to parse the base class using comprehensive patterns
         // Try advanced parsers first (higher priority)
-        if let Some(properties) = self.advanced_color_parser.parse_class(&base_class) {
+        if let Some(properties) = self.advanced_color_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.advanced_spacing_parser.parse_class(&base_class) {
+        if let Some(properties) = self.advanced_spacing_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.typography_parser.parse_class(&base_class) {
+        if let Some(properties) = self.typography_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.positioning_parser.parse_class(&base_class) {
+        if let Some(properties) = self.positioning_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.flexbox_parser.parse_class(&base_class) {
+        if let Some(properties) = self.flexbox_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.sizing_parser.parse_class(&base_class) {
+        if let Some(properties) = self.sizing_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.advanced_border_parser.parse_class(&base_class) {
+        if let Some(properties) = self.advanced_border_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.ring_parser.parse_class(&base_class) {
+        if let Some(properties) = self.ring_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.transition_parser.parse_class(&base_class) {
+        if let Some(properties) = self.transition_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.shadow_parser.parse_class(&base_class) {
+        if let Some(properties) = self.shadow_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.svg_parser.parse_class(&base_class) {
+        if let Some(properties) = self.svg_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.margin_parser.parse_class(&base_class) {
+        if let Some(properties) = self.margin_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.group_parser.parse_class(&base_class) {
+        if let Some(properties) = self.group_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.advanced_grid_parser.parse_class(&base_class) {
+        if let Some(properties) = self.advanced_grid_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.prose_parser.parse_class(&base_class) {
+        if let Some(properties) = self.prose_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.divide_parser.parse_class(&base_class) {
+        if let Some(properties) = self.divide_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.gradient_parser.parse_class(&base_class) {
+        if let Some(properties) = self.gradient_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.object_fit_parser.parse_class(&base_class) {
+        if let Some(properties) = self.object_fit_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.transform_parser.parse_class(&base_class) {
+        if let Some(properties) = self.transform_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.arbitrary_parser.parse_class(&base_class) {
+        if let Some(properties) = self.arbitrary_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.data_attribute_parser.parse_class(&base_class) {
+        if let Some(properties) = self.data_attribute_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.background_properties_parser.parse_class(&base_class) {
+        if let Some(properties) = self.background_properties_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.background_parser.parse_class(&base_class) {
+        if let Some(properties) = self.background_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.border_utilities_parser.parse_class(&base_class) {
+        if let Some(properties) = self.border_utilities_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.effects_utilities_parser.parse_class(&base_class) {
+        if let Some(properties) = self.effects_utilities_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.filter_utilities_parser.parse_class(&base_class) {
+        if let Some(properties) = self.filter_utilities_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
         if let Some(properties) = self
             .backdrop_filter_utilities_parser
-            .parse_class(&base_class)
+            .parse_class(base_class)
         {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.accessibility_parser.parse_class(&base_class) {
+        if let Some(properties) = self.accessibility_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.table_parser.parse_class(&base_class) {
+        if let Some(properties) = self.table_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.mask_utilities_parser.parse_class(&base_class) {
+        if let Some(properties) = self.mask_utilities_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.transition_properties_parser.parse_class(&base_class) {
+        if let Some(properties) = self.transition_properties_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.fractional_transforms_parser.parse_class(&base_class) {
+        if let Some(properties) = self.fractional_transforms_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.aspect_ratio_parser.parse_class(&base_class) {
+        if let Some(properties) = self.aspect_ratio_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.columns_parser.parse_class(&base_class) {
+        if let Some(properties) = self.columns_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.break_control_parser.parse_class(&base_class) {
+        if let Some(properties) = self.break_control_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.box_utilities_parser.parse_class(&base_class) {
+        if let Some(properties) = self.box_utilities_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.layout_utilities_parser.parse_class(&base_class) {
+        if let Some(properties) = self.layout_utilities_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.place_content_parser.parse_class(&base_class) {
+        if let Some(properties) = self.place_content_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.place_items_parser.parse_class(&base_class) {
+        if let Some(properties) = self.place_items_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.place_self_parser.parse_class(&base_class) {
+        if let Some(properties) = self.place_self_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
         // Grid and Flexbox parsers
-        if let Some(properties) = self.grid_template_columns_parser.parse_class(&base_class) {
+        if let Some(properties) = self.grid_template_columns_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.grid_column_parser.parse_class(&base_class) {
+        if let Some(properties) = self.grid_column_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.grid_template_rows_parser.parse_class(&base_class) {
+        if let Some(properties) = self.grid_template_rows_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.grid_row_parser.parse_class(&base_class) {
+        if let Some(properties) = self.grid_row_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.grid_auto_flow_parser.parse_class(&base_class) {
+        if let Some(properties) = self.grid_auto_flow_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.grid_auto_columns_parser.parse_class(&base_class) {
+        if let Some(properties) = self.grid_auto_columns_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.grid_auto_rows_parser.parse_class(&base_class) {
+        if let Some(properties) = self.grid_auto_rows_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.gap_parser.parse_class(&base_class) {
+        if let Some(properties) = self.gap_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
         // Flexbox parsers
-        if let Some(properties) = self.flex_basis_parser.parse_class(&base_class) {
+        if let Some(properties) = self.flex_basis_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.flex_direction_parser.parse_class(&base_class) {
+        if let Some(properties) = self.flex_direction_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.flex_wrap_parser.parse_class(&base_class) {
+        if let Some(properties) = self.flex_wrap_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.flex_parser.parse_class(&base_class) {
+        if let Some(properties) = self.flex_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.flex_grow_parser.parse_class(&base_class) {
+        if let Some(properties) = self.flex_grow_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.flex_shrink_parser.parse_class(&base_class) {
+        if let Some(properties) = self.flex_shrink_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.order_parser.parse_class(&base_class) {
+        if let Some(properties) = self.order_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
         // Justify and Align parsers
-        if let Some(properties) = self.justify_content_parser.parse_class(&base_class) {
+        if let Some(properties) = self.justify_content_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.justify_items_parser.parse_class(&base_class) {
+        if let Some(properties) = self.justify_items_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.justify_self_parser.parse_class(&base_class) {
+        if let Some(properties) = self.justify_self_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.align_content_parser.parse_class(&base_class) {
+        if let Some(properties) = self.align_content_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.align_items_parser.parse_class(&base_class) {
+        if let Some(properties) = self.align_items_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.align_self_parser.parse_class(&base_class) {
+        if let Some(properties) = self.align_self_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
         // Layout parsers
-        if let Some(properties) = self.overflow_parser.parse_class(&base_class) {
+        if let Some(properties) = self.overflow_parser.parse_class(base_class) {
+            return Ok(properties);
+        }
+
+        if let Some(properties) = self.overscroll_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.overscroll_parser.parse_class(&base_class) {
+        if let Some(properties) = self.container_query_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.position_parser.parse_class(&base_class) {
+        if let Some(properties) = self.position_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.inset_parser.parse_class(&base_class) {
+        if let Some(properties) = self.inset_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.visibility_parser.parse_class(&base_class) {
+        if let Some(properties) = self.visibility_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.z_index_parser.parse_class(&base_class) {
+        if let Some(properties) = self.z_index_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.typography_parser.parse_class(&base_class) {
+        if let Some(properties) = self.typography_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
         // Try basic parsers
-        if let Some(properties) = self.parse_spacing_class(&base_class) {
+        if let Some(properties) = self.parse_spacing_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_color_class(&base_class) {
+        if let Some(properties) = self.parse_color_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_typography_class(&base_class) {
+        if let Some(properties) = self.parse_typography_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_layout_class(&base_class) {
+        if let Some(properties) = self.parse_layout_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_flexbox_class(&base_class) {
+        if let Some(properties) = self.parse_flexbox_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_grid_class(&base_class) {
+        if let Some(properties) = self.parse_grid_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_border_class(&base_class) {
+        if let Some(properties) = self.parse_border_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_effects_class(&base_class) {
+        if let Some(properties) = self.parse_effects_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_transform_class(&base_class) {
+        if let Some(properties) = self.parse_transform_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_animation_class(&base_class) {
+        if let Some(properties) = self.parse_animation_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_interactive_class(&base_class) {
+        if let Some(properties) = self.parse_interactive_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.accent_color_parser.parse_class(&base_class) {
+        if let Some(properties) = self.accent_color_parser.parse_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_sizing_class(&base_class) {
+        if let Some(properties) = self.parse_sizing_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_background_class(&base_class) {
+        if let Some(properties) = self.parse_background_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_filter_class(&base_class) {
+        if let Some(properties) = self.parse_filter_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_transition_class(&base_class) {
+        if let Some(properties) = self.parse_transition_class(base_class) {
             return Ok(properties);
         }
 
         // Try new layout parsers
-        if let Some(properties) = self.parse_overflow_class(&base_class) {
+        if let Some(properties) = self.parse_overflow_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_overscroll_class(&base_class) {
+        if let Some(properties) = self.parse_overscroll_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_position_class(&base_class) {
+        if let Some(properties) = self.parse_position_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_inset_class(&base_class) {
+        if let Some(properties) = self.parse_inset_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_visibility_class(&base_class) {
+        if let Some(properties) = self.parse_visibility_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_z_index_class(&base_class) {
+        if let Some(properties) = self.parse_z_index_class(base_class) {
             return Ok(properties);
         }
 
         // Try new flexbox parsers
-        if let Some(properties) = self.parse_flex_basis_class(&base_class) {
+        if let Some(properties) = self.parse_flex_basis_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_flex_direction_class(&base_class) {
+        if let Some(properties) = self.parse_flex_direction_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_flex_wrap_class(&base_class) {
+        if let Some(properties) = self.parse_flex_wrap_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_flex_class(&base_class) {
+        if let Some(properties) = self.parse_flex_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_flex_grow_class(&base_class) {
+        if let Some(properties) = self.parse_flex_grow_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_flex_shrink_class(&base_class) {
+        if let Some(properties) = self.parse_flex_shrink_class(base_class) {
             return Ok(properties);
         }
 
         // Try new order and grid parsers
-        if let Some(properties) = self.parse_order_class(&base_class) {
+        if let Some(properties) = self.parse_order_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_grid_template_columns_class(&base_class) {
+        if let Some(properties) = self.parse_grid_template_columns_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_grid_column_class(&base_class) {
+        if let Some(properties) = self.parse_grid_column_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_grid_template_rows_class(&base_class) {
+        if let Some(properties) = self.parse_grid_template_rows_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_grid_row_class(&base_class) {
+        if let Some(properties) = self.parse_grid_row_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_grid_auto_flow_class(&base_class) {
+        if let Some(properties) = self.parse_grid_auto_flow_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_grid_auto_columns_class(&base_class) {
+        if let Some(properties) = self.parse_grid_auto_columns_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_grid_auto_rows_class(&base_class) {
+        if let Some(properties) = self.parse_grid_auto_rows_class(base_class) {
             return Ok(properties);
         }
 
         // Try new gap and justify parsers
-        if let Some(properties) = self.parse_gap_class(&base_class) {
+        if let Some(properties) = self.parse_gap_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_justify_content_class(&base_class) {
+        if let Some(properties) = self.parse_justify_content_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_justify_items_class(&base_class) {
+        if let Some(properties) = self.parse_justify_items_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_justify_self_class(&base_class) {
+        if let Some(properties) = self.parse_justify_self_class(base_class) {
             return Ok(properties);
         }
 
         // Try new align and place parsers
-        if let Some(properties) = self.parse_align_content_class(&base_class) {
+        if let Some(properties) = self.parse_align_content_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_align_items_class(&base_class) {
+        if let Some(properties) = self.parse_align_items_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_align_self_class(&base_class) {
+        if let Some(properties) = self.parse_align_self_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_place_content_class(&base_class) {
+        if let Some(properties) = self.parse_place_content_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_place_items_class(&base_class) {
+        if let Some(properties) = self.parse_place_items_class(base_class) {
             return Ok(properties);
         }
 
-        if let Some(properties) = self.parse_place_self_class(&base_class) {
+        if let Some(properties) = self.parse_place_self_class(base_class) {
             return Ok(properties);
         }
 
         // Fallback to hardcoded classes for backwards compatibility
-        match base_class.as_str() {
+        match base_class {
             // Display utilities
             "block" => Ok(vec![CssProperty {
                 name: "display".to_string(),
@@ -534,7 +582,7 @@ impl CssGeneratorParsers for super::CssGenerator {
 
             _ => Err(TailwindError::class_generation(format!(
                 "Unknown class: {}",
-                class
+                base_class
             ))),
         }
     }
@@ -544,37 +592,22 @@ impl CssGeneratorParsers for super::CssGenerator {
     }
 
     fn class_to_css_rule(&self, class: &str) -> Result<super::types::CssRule> {
-        let (variants, base_class) = self.parse_variants(class);
+        let (variants, _) = self.variant_parser.split_variants(class);
+        self.variant_parser.validate_variants(&variants)?;
         let properties = self.class_to_properties(class)?;
 
-        // Build selector with variants
-        let mut selector = String::new();
-        for variant in &variants {
-            let variant_selector = self.variant_parser.get_variant_selector(variant);
-            if !variant_selector.is_empty() {
-                selector.push_str(&variant_selector);
-            }
-        }
-
-        // Add the base class
-        selector.push_str(&format!(".{}", base_class));
-
-        // Determine media query for responsive and device variants
-        let media_query = variants.iter().find_map(|variant| {
-            // Try responsive media query first
-            if let Some(responsive_query) = self.variant_parser.get_responsive_media_query(variant)
-            {
-                Some(responsive_query)
-            } else {
-                // Try device media query
-                self.variant_parser.get_device_media_query(variant)
-            }
-        });
+        // Apply every variant to the escaped class selector; responsive, device
+        // and media-based custom variants are combined into one media query,
+        // while a container query variant replaces it
+        let base_selector = format!(".{}", escape_class_selector(class));
+        let (selector, media_query) = self
+            .variant_parser
+            .apply_variants(&base_selector, &variants);
 
         Ok(super::types::CssRule {
             selector,
             properties,
-            media_query: media_query.map(|s| s.to_string()),
+            media_query,
             specificity: variants.len() as u32 * 10, // Higher specificity for more variants
         })
     }
@@ -24,10 +24,13 @@
 //! // .hover\:bg-blue-600:hover { background-color: #2563eb; }
 //! ```
 
+pub mod components;
 pub mod core;
 pub mod core_parsers;
 pub mod css_output;
+pub mod custom_utilities;
 pub mod flexbox_parsers;
+pub mod forms;
 pub mod generator;
 pub mod generator_builders;
 pub mod generator_operations;
@@ -35,13 +38,22 @@ pub mod generator_parsers;
 pub mod grid_parsers;
 pub mod layout_parsers;
 pub mod parsers;
+pub mod safelist;
+pub mod theme_variables;
 pub mod types;
+pub mod typography;
 pub mod utils;
+pub mod value_resolver;
 pub mod variants;
 
 // Re-export main types and functionality
+pub use components::ComponentRegistry;
 pub use css_output::CssOutputGenerator;
+pub use custom_utilities::CustomUtilityRegistry;
+pub use forms::FormsStrategy;
 pub use generator::CssGenerator;
 pub use parsers::*;
+pub use theme_variables::{ThemeScope, ThemeVariables};
 pub use types::{CssGenerationConfig, CssProperty, CssRule};
-pub use variants::VariantParser;
+pub use value_resolver::ValueResolver;
+pub use variants::{CustomVariantRule, VariantParser};
@@ -5,6 +5,7 @@
 
 use super::{ParserCategory, UtilityParser};
 use crate::css_generator::types::CssProperty;
+use crate::css_generator::ValueResolver;
 
 #[derive(Debug, Clone)]
 pub struct ArbitraryParser;
@@ -486,18 +487,33 @@ impl ArbitraryParser {
         None
     }
 
-    /// Parse arbitrary background classes (bg-[url(/map.png)])
+    /// Parse arbitrary background classes (bg-[url(/map.png)], bg-[oklch(0.7_0.15_180)])
+    ///
+    /// Images and gradients set `background-image`; anything else, such as a
+    /// color function or a variable, sets `background-color`.
     fn parse_arbitrary_background_class(&self, class: &str) -> Option<Vec<CssProperty>> {
-        if let Some(value) = class.strip_prefix("bg-[") {
-            if let Some(value) = value.strip_suffix("]") {
-                return Some(vec![CssProperty {
-                    name: "background-image".to_string(),
-                    value: value.to_string(),
-                    important: false,
-                }]);
+        let value = class.strip_prefix("bg-")?;
+        let raw = value.strip_prefix('[')?.strip_suffix(']')?;
+        // Underscores stand for spaces, except inside URLs
+        let value = if raw.starts_with("url(") {
+            raw.to_string()
+        } else {
+            ValueResolver::arbitrary(value)?
+        };
+        let is_image = ["url(", "image(", "image-set(", "cross-fade(", "element("]
+            .iter()
+            .any(|function| value.starts_with(function))
+            || value.contains("gradient(");
+        Some(vec![CssProperty {
+            name: if is_image {
+                "background-image"
+            } else {
+                "background-color"
             }
-        }
-        None
+            .to_string(),
+            value,
+            important: false,
+        }])
     }
 
     /// Parse arbitrary filter classes (drop-shadow-[0_3px_1px_rgba(0,0,0,.15)])
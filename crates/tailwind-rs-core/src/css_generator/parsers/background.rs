@@ -1,4 +1,5 @@
 use super::{ParserCategory, UtilityParser};
+use crate::color_space::{apply_opacity, parse_opacity_modifier, ColorFormat};
 use crate::css_generator::types::CssProperty;
 
 /// Parser for background utilities
@@ -172,10 +173,13 @@ impl BackgroundParser {
                     if parts.len() == 2 {
                         let base_color = parts[0];
                         let opacity = parts[1];
-                        if let Some(color_value) = self.get_color_value(base_color) {
+                        if let (Some(color_value), Some(opacity)) = (
+                            self.get_color_value(base_color),
+                            parse_opacity_modifier(opacity),
+                        ) {
                             return Some(vec![CssProperty {
                                 name: "background-color".to_string(),
-                                value: format!("{}/{}", color_value, opacity),
+                                value: apply_opacity(&color_value, opacity, ColorFormat::Rgb),
                                 important: false,
                             }]);
                         }
@@ -4,6 +4,7 @@
 //! including complex color variants, opacity modifiers, and advanced color patterns.
 
 use super::{ParserCategory, UtilityParser};
+use crate::color_space::{apply_opacity, parse_opacity_modifier, ColorFormat};
 use crate::css_generator::types::CssProperty;
 
 #[derive(Debug, Clone)]
@@ -22,7 +23,7 @@ impl AdvancedColorParser {
             if let Some((color_name, opacity)) = color_part.split_once('/') {
                 let color_value = self.get_advanced_color_value(color_name)?;
                 let opacity_value = self.parse_opacity_value(opacity)?;
-                let final_color = self.apply_opacity_to_color(&color_value, &opacity_value);
+                let final_color = self.apply_opacity_to_color(&color_value, opacity_value);
                 return Some(vec![CssProperty {
                     name: "background-color".to_string(),
                     value: final_color,
@@ -46,7 +47,7 @@ impl AdvancedColorParser {
             if let Some((color_name, opacity)) = color_part.split_once('/') {
                 let color_value = self.get_advanced_color_value(color_name)?;
                 let opacity_value = self.parse_opacity_value(opacity)?;
-                let final_color = self.apply_opacity_to_color(&color_value, &opacity_value);
+                let final_color = self.apply_opacity_to_color(&color_value, opacity_value);
                 return Some(vec![CssProperty {
                     name: "border-color".to_string(),
                     value: final_color,
@@ -148,39 +149,14 @@ impl AdvancedColorParser {
         Some(color_value.to_string())
     }
 
-    /// Parse opacity values
-    fn parse_opacity_value(&self, opacity: &str) -> Option<String> {
-        match opacity {
-            "0" => Some("0".to_string()),
-            "5" => Some("0.05".to_string()),
-            "10" => Some("0.1".to_string()),
-            "20" => Some("0.2".to_string()),
-            "25" => Some("0.25".to_string()),
-            "30" => Some("0.3".to_string()),
-            "40" => Some("0.4".to_string()),
-            "50" => Some("0.5".to_string()),
-            "60" => Some("0.6".to_string()),
-            "70" => Some("0.7".to_string()),
-            "75" => Some("0.75".to_string()),
-            "80" => Some("0.8".to_string()),
-            "90" => Some("0.9".to_string()),
-            "95" => Some("0.95".to_string()),
-            "100" => Some("1".to_string()),
-            _ => None,
-        }
+    /// Parse opacity values (`50`, `[0.35]`, `[35%]`)
+    fn parse_opacity_value(&self, opacity: &str) -> Option<f64> {
+        parse_opacity_modifier(opacity)
     }
 
     /// Apply opacity to a color
-    fn apply_opacity_to_color(&self, color: &str, opacity: &str) -> String {
-        // Convert hex to rgba with opacity
-        if color.starts_with('#') && color.len() == 7 {
-            let r = u8::from_str_radix(&color[1..3], 16).unwrap_or(0);
-            let g = u8::from_str_radix(&color[3..5], 16).unwrap_or(0);
-            let b = u8::from_str_radix(&color[5..7], 16).unwrap_or(0);
-            format!("rgba({}, {}, {}, {})", r, g, b, opacity)
-        } else {
-            color.to_string() // Return original if not a hex color
-        }
+    fn apply_opacity_to_color(&self, color: &str, opacity: f64) -> String {
+        apply_opacity(color, opacity, ColorFormat::Rgb)
     }
 }
 
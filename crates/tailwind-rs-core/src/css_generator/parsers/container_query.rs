@@ -0,0 +1,74 @@
+//! Container Query Utilities Parser
+//!
+//! This module provides parsing logic for the utilities that mark an element
+//! as a query container, such as `@container`, `@container/sidebar` and
+//! `@container-normal`. Rules that apply inside a container query are produced
+//! by the `[@container_(...)]` variant instead.
+
+use super::{ParserCategory, UtilityParser};
+use crate::css_generator::types::CssProperty;
+
+#[derive(Debug, Clone)]
+pub struct ContainerQueryParser;
+
+impl ContainerQueryParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn property(name: &str, value: &str) -> CssProperty {
+        CssProperty {
+            name: name.to_string(),
+            value: value.to_string(),
+            important: false,
+        }
+    }
+}
+
+impl UtilityParser for ContainerQueryParser {
+    fn parse_class(&self, class: &str) -> Option<Vec<CssProperty>> {
+        let rest = class.strip_prefix("@container")?;
+        let (kind, name) = match rest.split_once('/') {
+            Some((kind, name)) => (kind, Some(name)),
+            None => (rest, None),
+        };
+
+        let container_type = match kind {
+            "" => "inline-size",
+            "-size" => "size",
+            "-normal" => "normal",
+            _ => return None,
+        };
+
+        let mut properties = vec![Self::property("container-type", container_type)];
+        match name {
+            Some("") => return None,
+            Some(name) => properties.push(Self::property("container-name", name)),
+            None => {}
+        }
+        Some(properties)
+    }
+
+    fn get_supported_patterns(&self) -> Vec<&'static str> {
+        vec![
+            "@container",
+            "@container/*",
+            "@container-size",
+            "@container-size/*",
+            "@container-normal",
+        ]
+    }
+
+    fn get_priority(&self) -> u32 {
+        70
+    }
+    fn get_category(&self) -> ParserCategory {
+        ParserCategory::Layout
+    }
+}
+
+impl Default for ContainerQueryParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
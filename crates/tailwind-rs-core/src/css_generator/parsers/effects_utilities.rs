@@ -461,6 +461,11 @@ impl EffectsUtilitiesParser {
                 value: "view-box".to_string(),
                 important: false,
             }]),
+            "mask-clip-text" => Some(vec![CssProperty {
+                name: "mask-clip".to_string(),
+                value: "text".to_string(),
+                important: false,
+            }]),
             "mask-no-clip" => Some(vec![CssProperty {
                 name: "mask-clip".to_string(),
                 value: "no-clip".to_string(),
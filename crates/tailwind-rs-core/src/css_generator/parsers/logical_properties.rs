@@ -0,0 +1,165 @@
+//! Logical Properties Parser
+//!
+//! This module resolves the classes produced by
+//! [`LogicalPropertiesUtilities`](crate::utilities::logical_properties::LogicalPropertiesUtilities):
+//! inline-start/end margins, paddings and insets (`ms-auto`, `pe-4`,
+//! `start-full`), block margins (`mt-screen`) and logical border widths
+//! (`border-s-2`). Numeric spacing is normally resolved by the spacing scale
+//! first; this parser covers the keyword values of [`SpacingValue`] and the
+//! border widths.
+
+use super::{ParserCategory, UtilityParser};
+use crate::css_generator::types::CssProperty;
+use crate::utilities::spacing::SpacingValue;
+
+/// Spacing class prefixes and the property each one sets
+const SPACING_PROPERTIES: &[(&str, &str)] = &[
+    ("ms", "margin-inline-start"),
+    ("me", "margin-inline-end"),
+    ("mt", "margin-top"),
+    ("mb", "margin-bottom"),
+    ("ps", "padding-inline-start"),
+    ("pe", "padding-inline-end"),
+    ("pt", "padding-top"),
+    ("pb", "padding-bottom"),
+    ("start", "inset-inline-start"),
+    ("end", "inset-inline-end"),
+    ("top", "top"),
+    ("bottom", "bottom"),
+];
+
+/// Border width class prefixes and the property each one sets
+const BORDER_PROPERTIES: &[(&str, &str)] = &[
+    ("border-s", "border-inline-start-width"),
+    ("border-e", "border-inline-end-width"),
+    ("border-t", "border-top-width"),
+    ("border-b", "border-bottom-width"),
+];
+
+/// Parser for logical property utilities
+#[derive(Debug, Clone)]
+pub struct LogicalPropertiesParser;
+
+impl LogicalPropertiesParser {
+    /// Create a new LogicalPropertiesParser
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse margin, padding and inset classes
+    fn parse_spacing_class(&self, class: &str) -> Option<Vec<CssProperty>> {
+        let (property, value) = SPACING_PROPERTIES.iter().find_map(|(prefix, property)| {
+            class
+                .strip_prefix(prefix)
+                .and_then(|rest| rest.strip_prefix('-'))
+                .map(|value| (*property, value))
+        })?;
+        let value = SpacingValue::all_values()
+            .into_iter()
+            .find(|spacing| spacing.to_class_name() == value)?;
+
+        // Intrinsic sizes aren't valid offsets, and paddings can't be `auto`
+        let valid = match value {
+            SpacingValue::Min | SpacingValue::Max | SpacingValue::Fit => false,
+            SpacingValue::Auto => !property.starts_with("padding"),
+            _ => true,
+        };
+        valid.then(|| {
+            vec![CssProperty {
+                name: property.to_string(),
+                value: value.to_css_value(),
+                important: false,
+            }]
+        })
+    }
+
+    /// Parse border width classes (`border-s-2`, `border-e-px`)
+    fn parse_border_width_class(&self, class: &str) -> Option<Vec<CssProperty>> {
+        let (property, width) = BORDER_PROPERTIES.iter().find_map(|(prefix, property)| {
+            class
+                .strip_prefix(prefix)
+                .and_then(|rest| rest.strip_prefix('-'))
+                .map(|width| (*property, width))
+        })?;
+        let width = match width {
+            "px" => "1px".to_string(),
+            width => format!("{}px", width.parse::<u32>().ok()?),
+        };
+        Some(vec![CssProperty {
+            name: property.to_string(),
+            value: width,
+            important: false,
+        }])
+    }
+}
+
+impl UtilityParser for LogicalPropertiesParser {
+    fn parse_class(&self, class: &str) -> Option<Vec<CssProperty>> {
+        self.parse_border_width_class(class)
+            .or_else(|| self.parse_spacing_class(class))
+    }
+
+    fn get_supported_patterns(&self) -> Vec<&'static str> {
+        vec![
+            "ms-*",
+            "me-*",
+            "mt-*",
+            "mb-*",
+            "ps-*",
+            "pe-*",
+            "pt-*",
+            "pb-*",
+            "start-*",
+            "end-*",
+            "top-*",
+            "bottom-*",
+            "border-s-*",
+            "border-e-*",
+            "border-t-*",
+            "border-b-*",
+        ]
+    }
+
+    fn get_priority(&self) -> u32 {
+        80
+    }
+
+    fn get_category(&self) -> ParserCategory {
+        ParserCategory::Spacing
+    }
+}
+
+impl Default for LogicalPropertiesParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_logical_keyword_spacing() {
+        let parser = LogicalPropertiesParser::new();
+
+        let properties = parser.parse_class("ms-auto").unwrap();
+        assert_eq!(properties[0].name, "margin-inline-start");
+        assert_eq!(properties[0].value, "auto");
+        assert_eq!(parser.parse_class("end-full").unwrap()[0].value, "100%");
+        assert_eq!(parser.parse_class("mt-screen").unwrap()[0].value, "100vh");
+        assert!(parser.parse_class("pe-auto").is_none());
+        assert!(parser.parse_class("ms-fit").is_none());
+    }
+
+    #[test]
+    fn test_logical_border_widths() {
+        let parser = LogicalPropertiesParser::new();
+
+        let properties = parser.parse_class("border-s-2").unwrap();
+        assert_eq!(properties[0].name, "border-inline-start-width");
+        assert_eq!(properties[0].value, "2px");
+        assert_eq!(parser.parse_class("border-e-px").unwrap()[0].value, "1px");
+        assert!(parser.parse_class("border-s-red-500").is_none());
+    }
+}
@@ -343,7 +343,7 @@ impl MaskUtilitiesParser {
                 value: "repeat".to_string(),
                 important: false,
             }]),
-            "mask-no-repeat" => Some(vec![CssProperty {
+            "mask-no-repeat" | "mask-repeat-none" => Some(vec![CssProperty {
                 name: "mask-repeat".to_string(),
                 value: "no-repeat".to_string(),
                 important: false,
@@ -375,17 +375,17 @@ impl MaskUtilitiesParser {
     /// Parse mask-size classes
     fn parse_mask_size_class(&self, class: &str) -> Option<Vec<CssProperty>> {
         match class {
-            "mask-auto" => Some(vec![CssProperty {
+            "mask-auto" | "mask-size-auto" => Some(vec![CssProperty {
                 name: "mask-size".to_string(),
                 value: "auto".to_string(),
                 important: false,
             }]),
-            "mask-cover" => Some(vec![CssProperty {
+            "mask-cover" | "mask-size-cover" => Some(vec![CssProperty {
                 name: "mask-size".to_string(),
                 value: "cover".to_string(),
                 important: false,
             }]),
-            "mask-contain" => Some(vec![CssProperty {
+            "mask-contain" | "mask-size-contain" => Some(vec![CssProperty {
                 name: "mask-size".to_string(),
                 value: "contain".to_string(),
                 important: false,
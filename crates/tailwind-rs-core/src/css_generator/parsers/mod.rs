@@ -26,6 +26,7 @@ pub mod shadows;
 pub mod sizing;
 pub mod spacing;
 pub mod spacing_advanced;
+pub mod spacing_scale;
 pub mod svg;
 pub mod table;
 pub mod typography;
@@ -47,6 +48,7 @@ pub mod border_utilities;
 pub mod box_utilities;
 pub mod break_control;
 pub mod columns;
+pub mod container_query;
 pub mod data_attributes;
 pub mod divide;
 pub mod effects_utilities;
@@ -72,6 +74,7 @@ pub mod justify_content;
 pub mod justify_items;
 pub mod justify_self;
 pub mod layout_utilities;
+pub mod logical_properties;
 pub mod mask_image_parser;
 pub mod mask_properties_parser;
 pub mod mask_utilities;
@@ -84,6 +87,7 @@ pub mod place_items;
 pub mod place_self;
 pub mod position;
 pub mod prose;
+pub mod text_shadow;
 pub mod transforms;
 pub mod transition_properties;
 pub mod transitions;
@@ -107,6 +111,7 @@ pub use break_control::BreakControlParser;
 pub use color::ColorParser;
 pub use colors_advanced::AdvancedColorParser;
 pub use columns::ColumnsParser;
+pub use container_query::ContainerQueryParser;
 pub use data_attributes::DataAttributeParser;
 pub use divide::DivideParser;
 pub use effects::EffectsParser;
@@ -138,6 +143,7 @@ pub use justify_items::JustifyItemsParser;
 pub use justify_self::JustifySelfParser;
 pub use layout::LayoutParser;
 pub use layout_utilities::LayoutUtilitiesParser;
+pub use logical_properties::LogicalPropertiesParser;
 pub use margins::MarginParser;
 pub use mask_utilities::MaskUtilitiesParser;
 pub use object_fit::ObjectFitParser;
@@ -155,7 +161,9 @@ pub use shadows::ShadowParser;
 pub use sizing::SizingParser;
 pub use spacing::SpacingParser;
 pub use spacing_advanced::AdvancedSpacingParser;
+pub use spacing_scale::SpacingScaleParser;
 pub use svg::SvgParser;
+pub use text_shadow::TextShadowParser;
 pub use transforms::TransformParser;
 pub use transition_properties::TransitionPropertiesParser;
 pub use transitions::TransitionParser;
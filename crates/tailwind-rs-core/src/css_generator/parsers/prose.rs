@@ -1,274 +1,1121 @@
 //! Prose Utilities Parser
 //!
 //! This module provides parsing logic for prose-related Tailwind CSS utilities,
-//! including the prose plugin classes for beautiful typography.
+//! the typography plugin classes for beautiful typography. It mirrors
+//! `@tailwindcss/typography`:
+//!
+//! - `prose` styles the container and every descendant element of rendered
+//!   Markdown (headings, paragraphs, links, code, lists, tables, ...)
+//! - `prose-sm`, `prose-base`, `prose-lg`, `prose-xl` and `prose-2xl` scale it
+//! - `prose-slate`, `prose-zinc`, ... pick a gray theme, other palette colors
+//!   (`prose-blue`) only recolor links, and `prose-invert` switches to the
+//!   dark variant of the theme
+//! - `prose-headings:`, `prose-a:`, ... apply a utility to those elements
+//!
+//! Descendant selectors are wrapped in `:where()` so they don't add
+//! specificity, and skip anything inside a `not-prose` element.
 
 use super::{ParserCategory, UtilityParser};
+use crate::css_generator::theme_variables::DEFAULT_PALETTE;
 use crate::css_generator::types::CssProperty;
 
+/// Declarations as (property, value) pairs
+type Declarations = &'static [(&'static str, &'static str)];
+
+/// Element styles as (element selector, declarations) pairs
+type ElementStyles = &'static [(&'static str, Declarations)];
+
+/// A prose size: container font size and line height, plus element styles
+#[derive(Debug, Clone, Copy)]
+pub struct ProseSize {
+    /// Size name (`sm`, `base`, `lg`, `xl`, `2xl`)
+    pub name: &'static str,
+    /// Container font size
+    pub font_size: &'static str,
+    /// Container line height
+    pub line_height: &'static str,
+    /// Element margins, font sizes and line heights at this size
+    pub elements: ElementStyles,
+}
+
+/// Color theme variables and the shades they take in the light and inverted themes
+///
+/// A shade is a palette step, `white`, a step with an alpha (`900 / 10%`) or
+/// a literal color.
+const THEME_SHADES: &[(&str, &str, &str)] = &[
+    ("body", "700", "300"),
+    ("headings", "900", "white"),
+    ("lead", "600", "400"),
+    ("links", "900", "white"),
+    ("bold", "900", "white"),
+    ("counters", "500", "400"),
+    ("bullets", "300", "600"),
+    ("hr", "200", "700"),
+    ("quotes", "900", "100"),
+    ("quote-borders", "200", "700"),
+    ("captions", "500", "400"),
+    ("kbd", "900", "white"),
+    ("kbd-shadows", "900 / 10%", "white / 10%"),
+    ("code", "900", "white"),
+    ("pre-code", "200", "300"),
+    ("pre-bg", "800", "rgb(0 0 0 / 50%)"),
+    ("th-borders", "300", "600"),
+    ("td-borders", "200", "700"),
+];
+
+/// Palettes that provide a complete gray theme rather than a link accent
+const GRAY_THEMES: &[&str] = &["slate", "gray", "zinc", "neutral", "stone"];
+
+/// Element modifier names and the elements they target
+const ELEMENT_MODIFIERS: &[(&str, &str)] = &[
+    ("headings", "h1, h2, h3, h4, th"),
+    ("lead", "[class~=\"lead\"]"),
+    ("h1", "h1"),
+    ("h2", "h2"),
+    ("h3", "h3"),
+    ("h4", "h4"),
+    ("p", "p"),
+    ("a", "a"),
+    ("blockquote", "blockquote"),
+    ("figure", "figure"),
+    ("figcaption", "figcaption"),
+    ("strong", "strong"),
+    ("em", "em"),
+    ("kbd", "kbd"),
+    ("code", "code"),
+    ("pre", "pre"),
+    ("ol", "ol"),
+    ("ul", "ul"),
+    ("li", "li"),
+    ("table", "table"),
+    ("thead", "thead"),
+    ("tr", "tr"),
+    ("th", "th"),
+    ("td", "td"),
+    ("img", "img"),
+    ("video", "video"),
+    ("hr", "hr"),
+];
+
+/// Size-independent element styles of `prose`
+const ELEMENT_STYLES: ElementStyles = &[
+    ("[class~=\"lead\"]", &[("color", "var(--tw-prose-lead)")]),
+    (
+        "a",
+        &[
+            ("color", "var(--tw-prose-links)"),
+            ("text-decoration", "underline"),
+            ("font-weight", "500"),
+        ],
+    ),
+    (
+        "strong",
+        &[("color", "var(--tw-prose-bold)"), ("font-weight", "600")],
+    ),
+    (
+        "a strong, blockquote strong, thead th strong",
+        &[("color", "inherit")],
+    ),
+    ("ol", &[("list-style-type", "decimal")]),
+    ("ul", &[("list-style-type", "disc")]),
+    (
+        "ol > li::marker",
+        &[
+            ("font-weight", "400"),
+            ("color", "var(--tw-prose-counters)"),
+        ],
+    ),
+    ("ul > li::marker", &[("color", "var(--tw-prose-bullets)")]),
+    (
+        "dt",
+        &[
+            ("color", "var(--tw-prose-headings)"),
+            ("font-weight", "600"),
+        ],
+    ),
+    (
+        "hr",
+        &[
+            ("border-color", "var(--tw-prose-hr)"),
+            ("border-top-width", "1px"),
+        ],
+    ),
+    (
+        "blockquote",
+        &[
+            ("font-weight", "500"),
+            ("font-style", "italic"),
+            ("color", "var(--tw-prose-quotes)"),
+            ("border-inline-start-width", "0.25rem"),
+            ("border-inline-start-color", "var(--tw-prose-quote-borders)"),
+            ("quotes", "\"\\201C\"\"\\201D\"\"\\2018\"\"\\2019\""),
+        ],
+    ),
+    (
+        "blockquote p:first-of-type::before",
+        &[("content", "open-quote")],
+    ),
+    (
+        "blockquote p:last-of-type::after",
+        &[("content", "close-quote")],
+    ),
+    (
+        "h1",
+        &[
+            ("color", "var(--tw-prose-headings)"),
+            ("font-weight", "800"),
+        ],
+    ),
+    ("h1 strong", &[("font-weight", "900"), ("color", "inherit")]),
+    (
+        "h2",
+        &[
+            ("color", "var(--tw-prose-headings)"),
+            ("font-weight", "700"),
+        ],
+    ),
+    ("h2 strong", &[("font-weight", "800"), ("color", "inherit")]),
+    (
+        "h3",
+        &[
+            ("color", "var(--tw-prose-headings)"),
+            ("font-weight", "600"),
+        ],
+    ),
+    ("h3 strong", &[("font-weight", "700"), ("color", "inherit")]),
+    (
+        "h4",
+        &[
+            ("color", "var(--tw-prose-headings)"),
+            ("font-weight", "600"),
+        ],
+    ),
+    ("h4 strong", &[("font-weight", "700"), ("color", "inherit")]),
+    ("picture", &[("display", "block")]),
+    (
+        "kbd",
+        &[
+            ("font-weight", "500"),
+            ("font-family", "inherit"),
+            ("color", "var(--tw-prose-kbd)"),
+            (
+                "box-shadow",
+                "0 0 0 1px var(--tw-prose-kbd-shadows), 0 3px 0 var(--tw-prose-kbd-shadows)",
+            ),
+        ],
+    ),
+    (
+        "code",
+        &[("color", "var(--tw-prose-code)"), ("font-weight", "600")],
+    ),
+    ("code::before", &[("content", "\"`\"")]),
+    ("code::after", &[("content", "\"`\"")]),
+    (
+        "a code, h1 code, h2 code, h3 code, h4 code, blockquote code, thead th code",
+        &[("color", "inherit")],
+    ),
+    (
+        "pre",
+        &[
+            ("color", "var(--tw-prose-pre-code)"),
+            ("background-color", "var(--tw-prose-pre-bg)"),
+            ("overflow-x", "auto"),
+            ("font-weight", "400"),
+        ],
+    ),
+    (
+        "pre code",
+        &[
+            ("background-color", "transparent"),
+            ("border-width", "0"),
+            ("border-radius", "0"),
+            ("padding", "0"),
+            ("font-weight", "inherit"),
+            ("color", "inherit"),
+            ("font-size", "inherit"),
+            ("font-family", "inherit"),
+            ("line-height", "inherit"),
+        ],
+    ),
+    ("pre code::before", &[("content", "none")]),
+    ("pre code::after", &[("content", "none")]),
+    (
+        "table",
+        &[
+            ("width", "100%"),
+            ("table-layout", "auto"),
+            ("margin-top", "2em"),
+            ("margin-bottom", "2em"),
+        ],
+    ),
+    (
+        "thead",
+        &[
+            ("border-bottom-width", "1px"),
+            ("border-bottom-color", "var(--tw-prose-th-borders)"),
+        ],
+    ),
+    (
+        "thead th",
+        &[
+            ("color", "var(--tw-prose-headings)"),
+            ("font-weight", "600"),
+            ("vertical-align", "bottom"),
+        ],
+    ),
+    (
+        "tbody tr",
+        &[
+            ("border-bottom-width", "1px"),
+            ("border-bottom-color", "var(--tw-prose-td-borders)"),
+        ],
+    ),
+    ("tbody tr:last-child", &[("border-bottom-width", "0")]),
+    ("tbody td", &[("vertical-align", "baseline")]),
+    (
+        "tfoot",
+        &[
+            ("border-top-width", "1px"),
+            ("border-top-color", "var(--tw-prose-th-borders)"),
+        ],
+    ),
+    ("tfoot td", &[("vertical-align", "top")]),
+    ("th, td", &[("text-align", "start")]),
+    ("figure > *", &[("margin-top", "0"), ("margin-bottom", "0")]),
+    ("figcaption", &[("color", "var(--tw-prose-captions)")]),
+    ("hr + *, h2 + *, h3 + *, h4 + *", &[("margin-top", "0")]),
+];
+
+/// Prose sizes, from `@tailwindcss/typography`
+pub const PROSE_SIZES: &[ProseSize] = &[
+    ProseSize {
+        name: "sm",
+        font_size: "0.875rem",
+        line_height: "1.7142857",
+        elements: &[
+            (
+                "p",
+                &[
+                    ("margin-top", "1.1428571em"),
+                    ("margin-bottom", "1.1428571em"),
+                ],
+            ),
+            (
+                "[class~=\"lead\"]",
+                &[
+                    ("font-size", "1.2857143em"),
+                    ("line-height", "1.5555556"),
+                    ("margin-top", "0.8888889em"),
+                    ("margin-bottom", "0.8888889em"),
+                ],
+            ),
+            (
+                "blockquote",
+                &[
+                    ("margin-top", "1.3333333em"),
+                    ("margin-bottom", "1.3333333em"),
+                    ("padding-inline-start", "1.1111111em"),
+                ],
+            ),
+            (
+                "h1",
+                &[
+                    ("font-size", "2.1428571em"),
+                    ("margin-top", "0"),
+                    ("margin-bottom", "0.8em"),
+                    ("line-height", "1.2"),
+                ],
+            ),
+            (
+                "h2",
+                &[
+                    ("font-size", "1.4285714em"),
+                    ("margin-top", "1.6em"),
+                    ("margin-bottom", "0.8em"),
+                    ("line-height", "1.4"),
+                ],
+            ),
+            (
+                "h3",
+                &[
+                    ("font-size", "1.2857143em"),
+                    ("margin-top", "1.5555556em"),
+                    ("margin-bottom", "0.4444444em"),
+                    ("line-height", "1.5555556"),
+                ],
+            ),
+            (
+                "h4",
+                &[
+                    ("margin-top", "1.4285714em"),
+                    ("margin-bottom", "0.5714286em"),
+                    ("line-height", "1.4285714"),
+                ],
+            ),
+            (
+                "img, picture, video",
+                &[
+                    ("margin-top", "1.7142857em"),
+                    ("margin-bottom", "1.7142857em"),
+                ],
+            ),
+            (
+                "kbd",
+                &[
+                    ("font-size", "0.8571429em"),
+                    ("border-radius", "0.3125rem"),
+                    ("padding", "0.1428571em 0.3571429em"),
+                ],
+            ),
+            ("code", &[("font-size", "0.8571429em")]),
+            (
+                "pre",
+                &[
+                    ("font-size", "0.8571429em"),
+                    ("line-height", "1.6666667"),
+                    ("margin-top", "1.6666667em"),
+                    ("margin-bottom", "1.6666667em"),
+                    ("border-radius", "0.25rem"),
+                    ("padding", "0.6666667em 1em"),
+                ],
+            ),
+            (
+                "ol, ul",
+                &[
+                    ("margin-top", "1.1428571em"),
+                    ("margin-bottom", "1.1428571em"),
+                    ("padding-inline-start", "1.5714286em"),
+                ],
+            ),
+            (
+                "li",
+                &[
+                    ("margin-top", "0.2857143em"),
+                    ("margin-bottom", "0.2857143em"),
+                ],
+            ),
+            (
+                "ol > li, ul > li",
+                &[("padding-inline-start", "0.4285714em")],
+            ),
+            (
+                "hr",
+                &[
+                    ("margin-top", "2.8571429em"),
+                    ("margin-bottom", "2.8571429em"),
+                ],
+            ),
+            (
+                "table",
+                &[("font-size", "0.8571429em"), ("line-height", "1.5")],
+            ),
+            (
+                "thead th",
+                &[("padding-inline", "1em"), ("padding-bottom", "0.6666667em")],
+            ),
+            ("tbody td, tfoot td", &[("padding", "0.6666667em 1em")]),
+            (
+                "figure",
+                &[
+                    ("margin-top", "1.7142857em"),
+                    ("margin-bottom", "1.7142857em"),
+                ],
+            ),
+            (
+                "figcaption",
+                &[
+                    ("font-size", "0.8571429em"),
+                    ("line-height", "1.3333333"),
+                    ("margin-top", "0.6666667em"),
+                ],
+            ),
+        ],
+    },
+    ProseSize {
+        name: "base",
+        font_size: "1rem",
+        line_height: "1.75",
+        elements: &[
+            (
+                "p",
+                &[("margin-top", "1.25em"), ("margin-bottom", "1.25em")],
+            ),
+            (
+                "[class~=\"lead\"]",
+                &[
+                    ("font-size", "1.25em"),
+                    ("line-height", "1.6"),
+                    ("margin-top", "1.2em"),
+                    ("margin-bottom", "1.2em"),
+                ],
+            ),
+            (
+                "blockquote",
+                &[
+                    ("margin-top", "1.6em"),
+                    ("margin-bottom", "1.6em"),
+                    ("padding-inline-start", "1em"),
+                ],
+            ),
+            (
+                "h1",
+                &[
+                    ("font-size", "2.25em"),
+                    ("margin-top", "0"),
+                    ("margin-bottom", "0.8888889em"),
+                    ("line-height", "1.1111111"),
+                ],
+            ),
+            (
+                "h2",
+                &[
+                    ("font-size", "1.5em"),
+                    ("margin-top", "2em"),
+                    ("margin-bottom", "1em"),
+                    ("line-height", "1.3333333"),
+                ],
+            ),
+            (
+                "h3",
+                &[
+                    ("font-size", "1.25em"),
+                    ("margin-top", "1.6em"),
+                    ("margin-bottom", "0.6em"),
+                    ("line-height", "1.6"),
+                ],
+            ),
+            (
+                "h4",
+                &[
+                    ("margin-top", "1.5em"),
+                    ("margin-bottom", "0.5em"),
+                    ("line-height", "1.5"),
+                ],
+            ),
+            (
+                "img, picture, video",
+                &[("margin-top", "2em"), ("margin-bottom", "2em")],
+            ),
+            (
+                "kbd",
+                &[
+                    ("font-size", "0.875em"),
+                    ("border-radius", "0.3125rem"),
+                    ("padding", "0.1875em 0.375em"),
+                ],
+            ),
+            ("code", &[("font-size", "0.875em")]),
+            (
+                "pre",
+                &[
+                    ("font-size", "0.875em"),
+                    ("line-height", "1.7142857"),
+                    ("margin-top", "1.7142857em"),
+                    ("margin-bottom", "1.7142857em"),
+                    ("border-radius", "0.375rem"),
+                    ("padding", "0.8571429em 1.1428571em"),
+                ],
+            ),
+            (
+                "ol, ul",
+                &[
+                    ("margin-top", "1.25em"),
+                    ("margin-bottom", "1.25em"),
+                    ("padding-inline-start", "1.625em"),
+                ],
+            ),
+            ("li", &[("margin-top", "0.5em"), ("margin-bottom", "0.5em")]),
+            ("ol > li, ul > li", &[("padding-inline-start", "0.375em")]),
+            ("hr", &[("margin-top", "3em"), ("margin-bottom", "3em")]),
+            (
+                "table",
+                &[("font-size", "0.875em"), ("line-height", "1.7142857")],
+            ),
+            (
+                "thead th",
+                &[
+                    ("padding-inline", "0.5714286em"),
+                    ("padding-bottom", "0.5714286em"),
+                ],
+            ),
+            ("tbody td, tfoot td", &[("padding", "0.5714286em")]),
+            ("figure", &[("margin-top", "2em"), ("margin-bottom", "2em")]),
+            (
+                "figcaption",
+                &[
+                    ("font-size", "0.875em"),
+                    ("line-height", "1.4285714"),
+                    ("margin-top", "0.8571429em"),
+                ],
+            ),
+        ],
+    },
+    ProseSize {
+        name: "lg",
+        font_size: "1.125rem",
+        line_height: "1.7777778",
+        elements: &[
+            (
+                "p",
+                &[
+                    ("margin-top", "1.3333333em"),
+                    ("margin-bottom", "1.3333333em"),
+                ],
+            ),
+            (
+                "[class~=\"lead\"]",
+                &[
+                    ("font-size", "1.2222222em"),
+                    ("line-height", "1.4545455"),
+                    ("margin-top", "1.0909091em"),
+                    ("margin-bottom", "1.0909091em"),
+                ],
+            ),
+            (
+                "blockquote",
+                &[
+                    ("margin-top", "1.6666667em"),
+                    ("margin-bottom", "1.6666667em"),
+                    ("padding-inline-start", "1em"),
+                ],
+            ),
+            (
+                "h1",
+                &[
+                    ("font-size", "2.6666667em"),
+                    ("margin-top", "0"),
+                    ("margin-bottom", "0.8333333em"),
+                    ("line-height", "1"),
+                ],
+            ),
+            (
+                "h2",
+                &[
+                    ("font-size", "1.6666667em"),
+                    ("margin-top", "1.8666667em"),
+                    ("margin-bottom", "1.0666667em"),
+                    ("line-height", "1.3333333"),
+                ],
+            ),
+            (
+                "h3",
+                &[
+                    ("font-size", "1.3333333em"),
+                    ("margin-top", "1.6666667em"),
+                    ("margin-bottom", "0.6666667em"),
+                    ("line-height", "1.5"),
+                ],
+            ),
+            (
+                "h4",
+                &[
+                    ("margin-top", "1.7777778em"),
+                    ("margin-bottom", "0.4444444em"),
+                    ("line-height", "1.5555556"),
+                ],
+            ),
+            (
+                "img, picture, video",
+                &[
+                    ("margin-top", "1.7777778em"),
+                    ("margin-bottom", "1.7777778em"),
+                ],
+            ),
+            (
+                "kbd",
+                &[
+                    ("font-size", "0.8888889em"),
+                    ("border-radius", "0.3125rem"),
+                    ("padding", "0.2222222em 0.4444444em"),
+                ],
+            ),
+            ("code", &[("font-size", "0.8888889em")]),
+            (
+                "pre",
+                &[
+                    ("font-size", "0.8888889em"),
+                    ("line-height", "1.75"),
+                    ("margin-top", "2em"),
+                    ("margin-bottom", "2em"),
+                    ("border-radius", "0.375rem"),
+                    ("padding", "1em 1.5em"),
+                ],
+            ),
+            (
+                "ol, ul",
+                &[
+                    ("margin-top", "1.3333333em"),
+                    ("margin-bottom", "1.3333333em"),
+                    ("padding-inline-start", "1.5555556em"),
+                ],
+            ),
+            (
+                "li",
+                &[
+                    ("margin-top", "0.6666667em"),
+                    ("margin-bottom", "0.6666667em"),
+                ],
+            ),
+            (
+                "ol > li, ul > li",
+                &[("padding-inline-start", "0.4444444em")],
+            ),
+            (
+                "hr",
+                &[
+                    ("margin-top", "3.1111111em"),
+                    ("margin-bottom", "3.1111111em"),
+                ],
+            ),
+            (
+                "table",
+                &[("font-size", "0.8888889em"), ("line-height", "1.5")],
+            ),
+            (
+                "thead th",
+                &[("padding-inline", "0.75em"), ("padding-bottom", "0.75em")],
+            ),
+            ("tbody td, tfoot td", &[("padding", "0.75em")]),
+            (
+                "figure",
+                &[
+                    ("margin-top", "1.7777778em"),
+                    ("margin-bottom", "1.7777778em"),
+                ],
+            ),
+            (
+                "figcaption",
+                &[
+                    ("font-size", "0.8888889em"),
+                    ("line-height", "1.5"),
+                    ("margin-top", "1em"),
+                ],
+            ),
+        ],
+    },
+    ProseSize {
+        name: "xl",
+        font_size: "1.25rem",
+        line_height: "1.8",
+        elements: &[
+            ("p", &[("margin-top", "1.2em"), ("margin-bottom", "1.2em")]),
+            (
+                "[class~=\"lead\"]",
+                &[
+                    ("font-size", "1.2em"),
+                    ("line-height", "1.5"),
+                    ("margin-top", "1em"),
+                    ("margin-bottom", "1em"),
+                ],
+            ),
+            (
+                "blockquote",
+                &[
+                    ("margin-top", "1.6em"),
+                    ("margin-bottom", "1.6em"),
+                    ("padding-inline-start", "1.0666667em"),
+                ],
+            ),
+            (
+                "h1",
+                &[
+                    ("font-size", "2.8em"),
+                    ("margin-top", "0"),
+                    ("margin-bottom", "0.8571429em"),
+                    ("line-height", "1"),
+                ],
+            ),
+            (
+                "h2",
+                &[
+                    ("font-size", "1.8em"),
+                    ("margin-top", "1.5555556em"),
+                    ("margin-bottom", "0.8888889em"),
+                    ("line-height", "1.1111111"),
+                ],
+            ),
+            (
+                "h3",
+                &[
+                    ("font-size", "1.5em"),
+                    ("margin-top", "1.6em"),
+                    ("margin-bottom", "0.6666667em"),
+                    ("line-height", "1.3333333"),
+                ],
+            ),
+            (
+                "h4",
+                &[
+                    ("margin-top", "1.8em"),
+                    ("margin-bottom", "0.6em"),
+                    ("line-height", "1.6"),
+                ],
+            ),
+            (
+                "img, picture, video",
+                &[("margin-top", "2em"), ("margin-bottom", "2em")],
+            ),
+            (
+                "kbd",
+                &[
+                    ("font-size", "0.9em"),
+                    ("border-radius", "0.3125rem"),
+                    ("padding", "0.25em 0.4em"),
+                ],
+            ),
+            ("code", &[("font-size", "0.9em")]),
+            (
+                "pre",
+                &[
+                    ("font-size", "0.9em"),
+                    ("line-height", "1.7777778"),
+                    ("margin-top", "2em"),
+                    ("margin-bottom", "2em"),
+                    ("border-radius", "0.5rem"),
+                    ("padding", "1.1111111em 1.3333333em"),
+                ],
+            ),
+            (
+                "ol, ul",
+                &[
+                    ("margin-top", "1.2em"),
+                    ("margin-bottom", "1.2em"),
+                    ("padding-inline-start", "1.6em"),
+                ],
+            ),
+            ("li", &[("margin-top", "0.6em"), ("margin-bottom", "0.6em")]),
+            ("ol > li, ul > li", &[("padding-inline-start", "0.4em")]),
+            ("hr", &[("margin-top", "2.8em"), ("margin-bottom", "2.8em")]),
+            (
+                "table",
+                &[("font-size", "0.9em"), ("line-height", "1.5555556")],
+            ),
+            (
+                "thead th",
+                &[
+                    ("padding-inline", "0.6666667em"),
+                    ("padding-bottom", "0.8888889em"),
+                ],
+            ),
+            (
+                "tbody td, tfoot td",
+                &[("padding", "0.8888889em 0.6666667em")],
+            ),
+            ("figure", &[("margin-top", "2em"), ("margin-bottom", "2em")]),
+            (
+                "figcaption",
+                &[
+                    ("font-size", "0.9em"),
+                    ("line-height", "1.5555556"),
+                    ("margin-top", "1em"),
+                ],
+            ),
+        ],
+    },
+    ProseSize {
+        name: "2xl",
+        font_size: "1.5rem",
+        line_height: "1.6666667",
+        elements: &[
+            (
+                "p",
+                &[
+                    ("margin-top", "1.3333333em"),
+                    ("margin-bottom", "1.3333333em"),
+                ],
+            ),
+            (
+                "[class~=\"lead\"]",
+                &[
+                    ("font-size", "1.25em"),
+                    ("line-height", "1.4666667"),
+                    ("margin-top", "1.0666667em"),
+                    ("margin-bottom", "1.0666667em"),
+                ],
+            ),
+            (
+                "blockquote",
+                &[
+                    ("margin-top", "1.7777778em"),
+                    ("margin-bottom", "1.7777778em"),
+                    ("padding-inline-start", "1.1111111em"),
+                ],
+            ),
+            (
+                "h1",
+                &[
+                    ("font-size", "2.6666667em"),
+                    ("margin-top", "0"),
+                    ("margin-bottom", "0.875em"),
+                    ("line-height", "1"),
+                ],
+            ),
+            (
+                "h2",
+                &[
+                    ("font-size", "2em"),
+                    ("margin-top", "1.5em"),
+                    ("margin-bottom", "0.8333333em"),
+                    ("line-height", "1.0833333"),
+                ],
+            ),
+            (
+                "h3",
+                &[
+                    ("font-size", "1.5em"),
+                    ("margin-top", "1.5555556em"),
+                    ("margin-bottom", "0.6666667em"),
+                    ("line-height", "1.2222222"),
+                ],
+            ),
+            (
+                "h4",
+                &[
+                    ("margin-top", "1.6666667em"),
+                    ("margin-bottom", "0.6666667em"),
+                    ("line-height", "1.5"),
+                ],
+            ),
+            (
+                "img, picture, video",
+                &[("margin-top", "2em"), ("margin-bottom", "2em")],
+            ),
+            (
+                "kbd",
+                &[
+                    ("font-size", "0.8333333em"),
+                    ("border-radius", "0.375rem"),
+                    ("padding", "0.25em 0.3333333em"),
+                ],
+            ),
+            ("code", &[("font-size", "0.8333333em")]),
+            (
+                "pre",
+                &[
+                    ("font-size", "0.8333333em"),
+                    ("line-height", "1.8"),
+                    ("margin-top", "2em"),
+                    ("margin-bottom", "2em"),
+                    ("border-radius", "0.5rem"),
+                    ("padding", "1.2em 1.6em"),
+                ],
+            ),
+            (
+                "ol, ul",
+                &[
+                    ("margin-top", "1.3333333em"),
+                    ("margin-bottom", "1.3333333em"),
+                    ("padding-inline-start", "1.5833333em"),
+                ],
+            ),
+            ("li", &[("margin-top", "0.5em"), ("margin-bottom", "0.5em")]),
+            (
+                "ol > li, ul > li",
+                &[("padding-inline-start", "0.4166667em")],
+            ),
+            ("hr", &[("margin-top", "3em"), ("margin-bottom", "3em")]),
+            (
+                "table",
+                &[("font-size", "0.8333333em"), ("line-height", "1.4")],
+            ),
+            (
+                "thead th",
+                &[("padding-inline", "0.6em"), ("padding-bottom", "0.8em")],
+            ),
+            ("tbody td, tfoot td", &[("padding", "0.8em 0.6em")]),
+            ("figure", &[("margin-top", "2em"), ("margin-bottom", "2em")]),
+            (
+                "figcaption",
+                &[
+                    ("font-size", "0.8333333em"),
+                    ("line-height", "1.6"),
+                    ("margin-top", "1em"),
+                ],
+            ),
+        ],
+    },
+];
+
 #[derive(Debug, Clone)]
 pub struct ProseParser;
 
 impl ProseParser {
+    /// Cascade order of container and element rules
+    pub const ORDER_BASE: u32 = 0;
+    /// Cascade order of size rules, after the base styles
+    pub const ORDER_SIZE: u32 = 1;
+    /// Cascade order of color theme rules
+    pub const ORDER_THEME: u32 = 2;
+    /// Cascade order of `prose-invert`, after any color theme
+    pub const ORDER_INVERT: u32 = 3;
+    /// Cascade order of element modifiers, after everything else
+    pub const ORDER_MODIFIER: u32 = 4;
+
     pub fn new() -> Self {
         Self
     }
 
-    /// Parse prose classes
+    /// Look up a size by name
+    pub fn size(name: &str) -> Option<&'static ProseSize> {
+        PROSE_SIZES.iter().find(|size| size.name == name)
+    }
+
+    /// Get the elements targeted by an element modifier (`headings`, `a`, ...)
+    pub fn element_modifier(name: &str) -> Option<&'static str> {
+        ELEMENT_MODIFIERS
+            .iter()
+            .find(|(modifier, _)| *modifier == name)
+            .map(|(_, elements)| *elements)
+    }
+
+    /// Descendant selector for elements of a prose container
+    ///
+    /// The elements are wrapped in `:where()` and anything inside a
+    /// `not-prose` element is excluded; a trailing pseudo-element
+    /// (`code::before`) stays outside of both.
+    pub fn descendant_selector(elements: &str) -> String {
+        let (elements, pseudo_element) = match elements.find("::") {
+            Some(index) => elements.split_at(index),
+            None => (elements, ""),
+        };
+        format!(
+            " :where({}):not(:where([class~=\"not-prose\"],[class~=\"not-prose\"] *)){}",
+            elements, pseudo_element
+        )
+    }
+
+    /// Cascade order of a prose class, see the `ORDER_*` constants
+    pub fn order(class: &str) -> u32 {
+        match class.strip_prefix("prose-") {
+            None => Self::ORDER_BASE,
+            Some("invert") => Self::ORDER_INVERT,
+            Some(name) if Self::size(name).is_some() => Self::ORDER_SIZE,
+            Some(_) => Self::ORDER_THEME,
+        }
+    }
+
+    /// Element styles of a prose class, as (element selector, declarations) pairs
+    ///
+    /// Only `prose` and the size classes style descendant elements.
+    pub fn element_styles(&self, class: &str) -> Vec<(&'static str, Vec<CssProperty>)> {
+        let (styles, size) = match class {
+            "prose" => (ELEMENT_STYLES, Self::size("base")),
+            _ => match class.strip_prefix("prose-").and_then(Self::size) {
+                Some(size) => (&[][..], Some(size)),
+                None => return Vec::new(),
+            },
+        };
+        styles
+            .iter()
+            .chain(size.map(|size| size.elements).unwrap_or_default())
+            .map(|(elements, declarations)| (*elements, Self::declarations(declarations)))
+            .collect()
+    }
+
+    /// Theme variables of a gray color theme, light and inverted
+    pub fn gray_theme(name: &str) -> Vec<CssProperty> {
+        let mut properties = Vec::new();
+        for (variable, light, _) in THEME_SHADES {
+            properties.push(Self::property(
+                &format!("--tw-prose-{}", variable),
+                &Self::shade_color(name, light),
+            ));
+        }
+        for (variable, _, invert) in THEME_SHADES {
+            properties.push(Self::property(
+                &format!("--tw-prose-invert-{}", variable),
+                &Self::shade_color(name, invert),
+            ));
+        }
+        properties
+    }
+
+    /// Theme variables of an accent color theme, which only recolors links
+    pub fn accent_theme(name: &str) -> Vec<CssProperty> {
+        vec![
+            Self::property("--tw-prose-links", &Self::shade_color(name, "600")),
+            Self::property("--tw-prose-invert-links", &Self::shade_color(name, "500")),
+        ]
+    }
+
+    /// Resolve a theme shade of a palette to a color value
+    fn shade_color(palette: &str, shade: &str) -> String {
+        let (shade, alpha) = match shade.split_once(" / ") {
+            Some((shade, alpha)) => (shade, Some(alpha)),
+            None => (shade, None),
+        };
+        let color = if shade == "white" {
+            "var(--color-white)".to_string()
+        } else if shade.chars().all(|c| c.is_ascii_digit()) {
+            format!("var(--color-{}-{})", palette, shade)
+        } else {
+            shade.to_string()
+        };
+        match alpha {
+            Some(alpha) => format!("color-mix(in oklab, {} {}, transparent)", color, alpha),
+            None => color,
+        }
+    }
+
+    /// Container declarations of `prose-invert`
+    fn invert() -> Vec<CssProperty> {
+        THEME_SHADES
+            .iter()
+            .map(|(variable, _, _)| {
+                Self::property(
+                    &format!("--tw-prose-{}", variable),
+                    &format!("var(--tw-prose-invert-{})", variable),
+                )
+            })
+            .collect()
+    }
+
+    fn property(name: &str, value: &str) -> CssProperty {
+        CssProperty {
+            name: name.to_string(),
+            value: value.to_string(),
+            important: false,
+        }
+    }
+
+    fn declarations(declarations: Declarations) -> Vec<CssProperty> {
+        declarations
+            .iter()
+            .map(|(name, value)| Self::property(name, value))
+            .collect()
+    }
+
+    /// Parse prose classes into their container declarations
     fn parse_prose_class(&self, class: &str) -> Option<Vec<CssProperty>> {
-        match class {
-            "prose" => Some(vec![
-                // Typography base styles
-                CssProperty {
-                    name: "color".to_string(),
-                    value: "inherit".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "max-width".to_string(),
-                    value: "65ch".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "line-height".to_string(),
-                    value: "1.75".to_string(),
-                    important: false,
-                },
-                // Headings
-                CssProperty {
-                    name: "--tw-prose-headings".to_string(),
-                    value: "var(--tw-prose-headings-color)".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-headings-color".to_string(),
-                    value: "#111827".to_string(),
-                    important: false,
-                },
-                // Body text
-                CssProperty {
-                    name: "--tw-prose-body".to_string(),
-                    value: "var(--tw-prose-body-color)".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-body-color".to_string(),
-                    value: "#374151".to_string(),
-                    important: false,
-                },
-                // Links
-                CssProperty {
-                    name: "--tw-prose-links".to_string(),
-                    value: "var(--tw-prose-links-color)".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-links-color".to_string(),
-                    value: "#2563eb".to_string(),
-                    important: false,
-                },
-                // Bold
-                CssProperty {
-                    name: "--tw-prose-bold".to_string(),
-                    value: "var(--tw-prose-bold-color)".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-bold-color".to_string(),
-                    value: "#111827".to_string(),
-                    important: false,
-                },
-                // Code
-                CssProperty {
-                    name: "--tw-prose-code".to_string(),
-                    value: "var(--tw-prose-code-color)".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-code-color".to_string(),
-                    value: "#111827".to_string(),
-                    important: false,
-                },
-                // Pre
-                CssProperty {
-                    name: "--tw-prose-pre-code".to_string(),
-                    value: "var(--tw-prose-pre-code-color)".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-pre-code-color".to_string(),
-                    value: "#e5e7eb".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-pre-bg".to_string(),
-                    value: "#1f2937".to_string(),
-                    important: false,
-                },
-                // Quotes
-                CssProperty {
-                    name: "--tw-prose-quotes".to_string(),
-                    value: "var(--tw-prose-quotes-color)".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-quotes-color".to_string(),
-                    value: "#111827".to_string(),
-                    important: false,
-                },
-                // Quote borders
-                CssProperty {
-                    name: "--tw-prose-quote-borders".to_string(),
-                    value: "#e5e7eb".to_string(),
-                    important: false,
-                },
-                // Captions
-                CssProperty {
-                    name: "--tw-prose-captions".to_string(),
-                    value: "var(--tw-prose-captions-color)".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-captions-color".to_string(),
-                    value: "#6b7280".to_string(),
-                    important: false,
-                },
-                // Bullets
-                CssProperty {
-                    name: "--tw-prose-bullets".to_string(),
-                    value: "var(--tw-prose-bullets-color)".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-bullets-color".to_string(),
-                    value: "#d1d5db".to_string(),
-                    important: false,
-                },
-                // HR
-                CssProperty {
-                    name: "--tw-prose-hr".to_string(),
-                    value: "var(--tw-prose-hr-color)".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-hr-color".to_string(),
-                    value: "#e5e7eb".to_string(),
-                    important: false,
-                },
-            ]),
-            "prose-sm" => Some(vec![
-                CssProperty {
-                    name: "font-size".to_string(),
-                    value: "0.875rem".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "line-height".to_string(),
-                    value: "1.7142857".to_string(),
-                    important: false,
-                },
-            ]),
-            "prose-lg" => Some(vec![
-                CssProperty {
-                    name: "font-size".to_string(),
-                    value: "1.125rem".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "line-height".to_string(),
-                    value: "1.7777778".to_string(),
-                    important: false,
-                },
-            ]),
-            "prose-xl" => Some(vec![
-                CssProperty {
-                    name: "font-size".to_string(),
-                    value: "1.25rem".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "line-height".to_string(),
-                    value: "1.8".to_string(),
-                    important: false,
-                },
-            ]),
-            "prose-2xl" => Some(vec![
-                CssProperty {
-                    name: "font-size".to_string(),
-                    value: "1.5rem".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "line-height".to_string(),
-                    value: "1.6666667".to_string(),
-                    important: false,
-                },
-            ]),
-            "prose-invert" => Some(vec![
-                // Dark mode prose styles
-                CssProperty {
-                    name: "--tw-prose-headings-color".to_string(),
-                    value: "#f9fafb".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-body-color".to_string(),
-                    value: "#d1d5db".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-links-color".to_string(),
-                    value: "#60a5fa".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-bold-color".to_string(),
-                    value: "#f9fafb".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-code-color".to_string(),
-                    value: "#f9fafb".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-pre-code-color".to_string(),
-                    value: "#d1d5db".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-pre-bg".to_string(),
-                    value: "#0f172a".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-quotes-color".to_string(),
-                    value: "#f9fafb".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-quote-borders".to_string(),
-                    value: "#374151".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-captions-color".to_string(),
-                    value: "#9ca3af".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-bullets-color".to_string(),
-                    value: "#4b5563".to_string(),
-                    important: false,
-                },
-                CssProperty {
-                    name: "--tw-prose-hr-color".to_string(),
-                    value: "#374151".to_string(),
-                    important: false,
-                },
-            ]),
-            _ => None,
+        if class == "prose" {
+            let base = Self::size("base")?;
+            let mut properties = vec![
+                Self::property("color", "var(--tw-prose-body)"),
+                Self::property("max-width", "65ch"),
+                Self::property("font-size", base.font_size),
+                Self::property("line-height", base.line_height),
+            ];
+            properties.extend(Self::gray_theme("gray"));
+            return Some(properties);
+        }
+
+        let name = class.strip_prefix("prose-")?;
+        if name == "invert" {
+            return Some(Self::invert());
+        }
+        if let Some(size) = Self::size(name) {
+            return Some(vec![
+                Self::property("font-size", size.font_size),
+                Self::property("line-height", size.line_height),
+            ]);
+        }
+        if GRAY_THEMES.contains(&name) {
+            return Some(Self::gray_theme(name));
+        }
+        if DEFAULT_PALETTE.iter().any(|(palette, _)| *palette == name) {
+            return Some(Self::accent_theme(name));
         }
+        None
     }
 }
 
@@ -281,10 +1128,13 @@ impl UtilityParser for ProseParser {
         vec![
             "prose",
             "prose-sm",
+            "prose-base",
             "prose-lg",
             "prose-xl",
             "prose-2xl",
             "prose-invert",
+            "prose-*",
+            "prose-*:*",
         ]
     }
 
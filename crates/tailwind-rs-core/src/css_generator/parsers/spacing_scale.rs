@@ -0,0 +1,382 @@
+//! Spacing Scale Parser
+//!
+//! This module handles the numeric, fractional and arbitrary forms of every
+//! spacing and sizing utility through the shared
+//! [`ValueResolver`](crate::css_generator::value_resolver::ValueResolver):
+//! - Padding and margin (`p-13`, `-mt-0.5`)
+//! - Gap and space between (`gap-[--my-gap]`, `space-x-7`)
+//! - Inset (`inset-4.5`, `-top-1/2`)
+//! - Width, height and size (`w-17`, `w-7/12`, `w-(--sidebar)`)
+//! - Scroll margin and padding (`scroll-mt-18`)
+//! - Translate (`-translate-x-1/2`)
+//!
+//! Keywords such as `auto`, `screen` or `min` are left to the dedicated parsers.
+
+use super::{ParserCategory, UtilityParser};
+use crate::css_generator::types::CssProperty;
+use crate::css_generator::value_resolver::ValueResolver;
+use ValueKind::{Length, Spacing};
+
+/// Which values a utility accepts
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ValueKind {
+    /// Spacing scale and arbitrary values
+    Spacing,
+    /// Spacing values plus fractions and `full`
+    Length,
+}
+
+/// A utility prefix, the properties it sets and the values it accepts
+struct ScaleUtility {
+    prefix: &'static str,
+    /// Property names with a `{}` placeholder for the value in their template
+    properties: &'static [(&'static str, &'static str)],
+    kind: ValueKind,
+    negative: bool,
+}
+
+const fn utility(
+    prefix: &'static str,
+    properties: &'static [(&'static str, &'static str)],
+    kind: ValueKind,
+    negative: bool,
+) -> ScaleUtility {
+    ScaleUtility {
+        prefix,
+        properties,
+        kind,
+        negative,
+    }
+}
+
+/// Longest prefixes first, so `px` wins over `p` and `gap-x` over `gap`
+const SCALE_UTILITIES: &[ScaleUtility] = &[
+    utility(
+        "scroll-mx",
+        &[("scroll-margin-left", "{}"), ("scroll-margin-right", "{}")],
+        Spacing,
+        true,
+    ),
+    utility(
+        "scroll-my",
+        &[("scroll-margin-top", "{}"), ("scroll-margin-bottom", "{}")],
+        Spacing,
+        true,
+    ),
+    utility(
+        "scroll-ms",
+        &[("scroll-margin-inline-start", "{}")],
+        Spacing,
+        true,
+    ),
+    utility(
+        "scroll-me",
+        &[("scroll-margin-inline-end", "{}")],
+        Spacing,
+        true,
+    ),
+    utility("scroll-mt", &[("scroll-margin-top", "{}")], Spacing, true),
+    utility("scroll-mr", &[("scroll-margin-right", "{}")], Spacing, true),
+    utility(
+        "scroll-mb",
+        &[("scroll-margin-bottom", "{}")],
+        Spacing,
+        true,
+    ),
+    utility("scroll-ml", &[("scroll-margin-left", "{}")], Spacing, true),
+    utility("scroll-m", &[("scroll-margin", "{}")], Spacing, true),
+    utility(
+        "scroll-px",
+        &[
+            ("scroll-padding-left", "{}"),
+            ("scroll-padding-right", "{}"),
+        ],
+        Spacing,
+        false,
+    ),
+    utility(
+        "scroll-py",
+        &[
+            ("scroll-padding-top", "{}"),
+            ("scroll-padding-bottom", "{}"),
+        ],
+        Spacing,
+        false,
+    ),
+    utility(
+        "scroll-ps",
+        &[("scroll-padding-inline-start", "{}")],
+        Spacing,
+        false,
+    ),
+    utility(
+        "scroll-pe",
+        &[("scroll-padding-inline-end", "{}")],
+        Spacing,
+        false,
+    ),
+    utility("scroll-pt", &[("scroll-padding-top", "{}")], Spacing, false),
+    utility(
+        "scroll-pr",
+        &[("scroll-padding-right", "{}")],
+        Spacing,
+        false,
+    ),
+    utility(
+        "scroll-pb",
+        &[("scroll-padding-bottom", "{}")],
+        Spacing,
+        false,
+    ),
+    utility(
+        "scroll-pl",
+        &[("scroll-padding-left", "{}")],
+        Spacing,
+        false,
+    ),
+    utility("scroll-p", &[("scroll-padding", "{}")], Spacing, false),
+    utility(
+        "translate-x",
+        &[("transform", "translateX({})")],
+        Length,
+        true,
+    ),
+    utility(
+        "translate-y",
+        &[("transform", "translateY({})")],
+        Length,
+        true,
+    ),
+    utility(
+        "space-x",
+        &[
+            ("margin-left", "calc({} * 0.5)"),
+            ("margin-right", "calc({} * 0.5)"),
+        ],
+        Spacing,
+        true,
+    ),
+    utility(
+        "space-y",
+        &[
+            ("margin-top", "calc({} * 0.5)"),
+            ("margin-bottom", "calc({} * 0.5)"),
+        ],
+        Spacing,
+        true,
+    ),
+    utility("inset-x", &[("left", "{}"), ("right", "{}")], Length, true),
+    utility("inset-y", &[("top", "{}"), ("bottom", "{}")], Length, true),
+    utility("inset", &[("inset", "{}")], Length, true),
+    utility("top", &[("top", "{}")], Length, true),
+    utility("right", &[("right", "{}")], Length, true),
+    utility("bottom", &[("bottom", "{}")], Length, true),
+    utility("left", &[("left", "{}")], Length, true),
+    utility("start", &[("inset-inline-start", "{}")], Length, true),
+    utility("end", &[("inset-inline-end", "{}")], Length, true),
+    utility("gap-x", &[("column-gap", "{}")], Spacing, false),
+    utility("gap-y", &[("row-gap", "{}")], Spacing, false),
+    utility("gap", &[("gap", "{}")], Spacing, false),
+    utility("min-w", &[("min-width", "{}")], Length, false),
+    utility("max-w", &[("max-width", "{}")], Length, false),
+    utility("min-h", &[("min-height", "{}")], Length, false),
+    utility("max-h", &[("max-height", "{}")], Length, false),
+    utility("size", &[("width", "{}"), ("height", "{}")], Length, false),
+    utility("basis", &[("flex-basis", "{}")], Length, false),
+    utility("indent", &[("text-indent", "{}")], Spacing, true),
+    utility("w", &[("width", "{}")], Length, false),
+    utility("h", &[("height", "{}")], Length, false),
+    utility(
+        "px",
+        &[("padding-left", "{}"), ("padding-right", "{}")],
+        Spacing,
+        false,
+    ),
+    utility(
+        "py",
+        &[("padding-top", "{}"), ("padding-bottom", "{}")],
+        Spacing,
+        false,
+    ),
+    utility("ps", &[("padding-inline-start", "{}")], Spacing, false),
+    utility("pe", &[("padding-inline-end", "{}")], Spacing, false),
+    utility("pt", &[("padding-top", "{}")], Spacing, false),
+    utility("pr", &[("padding-right", "{}")], Spacing, false),
+    utility("pb", &[("padding-bottom", "{}")], Spacing, false),
+    utility("pl", &[("padding-left", "{}")], Spacing, false),
+    utility("p", &[("padding", "{}")], Spacing, false),
+    utility(
+        "mx",
+        &[("margin-left", "{}"), ("margin-right", "{}")],
+        Spacing,
+        true,
+    ),
+    utility(
+        "my",
+        &[("margin-top", "{}"), ("margin-bottom", "{}")],
+        Spacing,
+        true,
+    ),
+    utility("ms", &[("margin-inline-start", "{}")], Spacing, true),
+    utility("me", &[("margin-inline-end", "{}")], Spacing, true),
+    utility("mt", &[("margin-top", "{}")], Spacing, true),
+    utility("mr", &[("margin-right", "{}")], Spacing, true),
+    utility("mb", &[("margin-bottom", "{}")], Spacing, true),
+    utility("ml", &[("margin-left", "{}")], Spacing, true),
+    utility("m", &[("margin", "{}")], Spacing, true),
+];
+
+/// Parser for the value forms shared by all spacing and sizing utilities
+#[derive(Debug, Clone, Default)]
+pub struct SpacingScaleParser {
+    resolver: ValueResolver,
+}
+
+impl SpacingScaleParser {
+    /// Create a new spacing scale parser
+    pub fn new(resolver: ValueResolver) -> Self {
+        Self { resolver }
+    }
+
+    /// Resolve a value for a utility, or `None` if it is not a scale value
+    fn resolve(&self, utility: &ScaleUtility, value: &str) -> Option<String> {
+        match utility.kind {
+            Spacing => self.resolver.spacing(value),
+            Length => self.resolver.length(value),
+        }
+    }
+}
+
+impl UtilityParser for SpacingScaleParser {
+    fn parse_class(&self, class: &str) -> Option<Vec<CssProperty>> {
+        let (negative, class) = match class.strip_prefix('-') {
+            Some(class) => (true, class),
+            None => (false, class),
+        };
+
+        SCALE_UTILITIES.iter().find_map(|utility| {
+            if negative && !utility.negative {
+                return None;
+            }
+            let value = class.strip_prefix(utility.prefix)?.strip_prefix('-')?;
+            let mut value = self.resolve(utility, value)?;
+            if negative {
+                value = ValueResolver::negate(&value);
+            }
+
+            Some(
+                utility
+                    .properties
+                    .iter()
+                    .map(|(name, template)| CssProperty {
+                        name: name.to_string(),
+                        value: template.replace("{}", &value),
+                        important: false,
+                    })
+                    .collect(),
+            )
+        })
+    }
+
+    fn get_supported_patterns(&self) -> Vec<&'static str> {
+        SCALE_UTILITIES
+            .iter()
+            .map(|utility| utility.prefix)
+            .collect()
+    }
+
+    fn get_priority(&self) -> u32 {
+        90
+    }
+
+    fn get_category(&self) -> ParserCategory {
+        ParserCategory::Spacing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(parser: &SpacingScaleParser, class: &str) -> Vec<(String, String)> {
+        parser
+            .parse_class(class)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|property| (property.name, property.value))
+            .collect()
+    }
+
+    #[test]
+    fn test_off_scale_values() {
+        let parser = SpacingScaleParser::default();
+        assert_eq!(
+            values(&parser, "p-13"),
+            [("padding".into(), "3.25rem".into())]
+        );
+        assert_eq!(
+            values(&parser, "w-17"),
+            [("width".into(), "4.25rem".into())]
+        );
+        assert_eq!(values(&parser, "h-88"), [("height".into(), "22rem".into())]);
+        assert_eq!(
+            values(&parser, "inset-4.5"),
+            [("inset".into(), "1.125rem".into())]
+        );
+        assert_eq!(
+            values(&parser, "-mt-0.5"),
+            [("margin-top".into(), "-0.125rem".into())]
+        );
+        assert_eq!(
+            values(&parser, "px-2"),
+            [
+                ("padding-left".into(), "0.5rem".into()),
+                ("padding-right".into(), "0.5rem".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fractions_and_variables() {
+        let parser = SpacingScaleParser::default();
+        assert_eq!(
+            values(&parser, "w-7/12"),
+            [("width".into(), "58.333333%".into())]
+        );
+        assert_eq!(
+            values(&parser, "-translate-x-1/2"),
+            [("transform".into(), "translateX(-50%)".into())]
+        );
+        assert_eq!(
+            values(&parser, "gap-[--my-gap]"),
+            [("gap".into(), "var(--my-gap)".into())]
+        );
+        assert_eq!(
+            values(&parser, "w-(--sidebar)"),
+            [("width".into(), "var(--sidebar)".into())]
+        );
+
+        // Fractions only apply to sizing and positioning utilities
+        assert!(parser.parse_class("p-1/2").is_none());
+        // Padding cannot be negative
+        assert!(parser.parse_class("-p-4").is_none());
+        // Keywords are left to the dedicated parsers
+        assert!(parser.parse_class("w-auto").is_none());
+    }
+
+    #[test]
+    fn test_theme_variables() {
+        let parser = SpacingScaleParser::new(ValueResolver::new(0.25, true));
+        assert_eq!(
+            values(&parser, "scroll-mt-18"),
+            [(
+                "scroll-margin-top".into(),
+                "calc(var(--spacing) * 18)".into()
+            )]
+        );
+        assert_eq!(
+            values(&parser, "-m-3"),
+            [("margin".into(), "calc(var(--spacing) * -3)".into())]
+        );
+    }
+}
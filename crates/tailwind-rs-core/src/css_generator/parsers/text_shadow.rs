@@ -0,0 +1,92 @@
+//! Text Shadow Parser
+//!
+//! This module resolves the classes produced by
+//! [`TextShadowUtilities`](crate::utilities::text_shadow::TextShadowUtilities),
+//! such as `text-shadow`, `text-shadow-lg` and `text-shadow-inner`, using the
+//! values of [`TextShadow`] so the builder and the generator share one scale.
+
+use super::{ParserCategory, UtilityParser};
+use crate::css_generator::types::CssProperty;
+use crate::utilities::text_shadow::TextShadow;
+
+/// Every text shadow the class builder can produce
+const TEXT_SHADOWS: [TextShadow; 8] = [
+    TextShadow::None,
+    TextShadow::Sm,
+    TextShadow::Md,
+    TextShadow::Default,
+    TextShadow::Lg,
+    TextShadow::Xl,
+    TextShadow::Xl2,
+    TextShadow::Inner,
+];
+
+/// Parser for text shadow utilities
+#[derive(Debug, Clone)]
+pub struct TextShadowParser;
+
+impl TextShadowParser {
+    /// Create a new TextShadowParser
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl UtilityParser for TextShadowParser {
+    fn parse_class(&self, class: &str) -> Option<Vec<CssProperty>> {
+        let shadow = TEXT_SHADOWS
+            .iter()
+            .find(|shadow| shadow.to_class_name() == class)?;
+        Some(vec![CssProperty {
+            name: "text-shadow".to_string(),
+            value: shadow.to_css_value(),
+            important: false,
+        }])
+    }
+
+    fn get_supported_patterns(&self) -> Vec<&'static str> {
+        vec![
+            "text-shadow",
+            "text-shadow-none",
+            "text-shadow-sm",
+            "text-shadow-md",
+            "text-shadow-lg",
+            "text-shadow-xl",
+            "text-shadow-2xl",
+            "text-shadow-inner",
+        ]
+    }
+
+    fn get_priority(&self) -> u32 {
+        90
+    }
+
+    fn get_category(&self) -> ParserCategory {
+        ParserCategory::Effects
+    }
+}
+
+impl Default for TextShadowParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_shadow_values_come_from_the_utility_scale() {
+        let parser = TextShadowParser::new();
+
+        let properties = parser.parse_class("text-shadow-2xl").unwrap();
+        assert_eq!(properties[0].name, "text-shadow");
+        assert_eq!(properties[0].value, TextShadow::Xl2.to_css_value());
+        assert_eq!(
+            parser.parse_class("text-shadow").unwrap()[0].value,
+            "0 1px 3px 0 rgb(0 0 0 / 0.1), 0 1px 2px -1px rgb(0 0 0 / 0.1)"
+        );
+        assert!(parser.parse_class("text-shadow-3xl").is_none());
+    }
+}
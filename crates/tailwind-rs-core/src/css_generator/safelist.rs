@@ -0,0 +1,340 @@
+//! Safelist and Blocklist
+//!
+//! This module expands safelist patterns against the utility catalog, the
+//! candidate utilities derived from the theme, and keeps blocklisted classes
+//! out of the generated CSS.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use tailwind_rs_core::{CssGenerator, SafelistEntry};
+//!
+//! let mut generator = CssGenerator::new();
+//! let added = generator
+//!     .add_safelist(&[
+//!         SafelistEntry::pattern("^bg-(red|blue)-500$").with_variant("hover")
+//!     ])
+//!     .unwrap();
+//! assert_eq!(added.len(), 4);
+//!
+//! generator.block_class("container");
+//! generator.add_class("container").unwrap();
+//!
+//! let css = generator.generate_css();
+//! assert!(css.contains(".hover\\:bg-blue-500:hover"));
+//! assert!(!css.contains(".container"));
+//! ```
+
+use crate::config::safelist::is_blocked;
+use crate::config::SafelistEntry;
+use crate::error::Result;
+use crate::theme_model::TailwindTheme;
+
+/// Theme namespaces and the utility prefixes whose suffix names one of their entries
+const NAMESPACE_UTILITIES: &[(&str, &[&str])] = &[
+    (
+        "color",
+        &[
+            "bg",
+            "text",
+            "border",
+            "outline",
+            "ring",
+            "ring-offset",
+            "fill",
+            "stroke",
+            "accent",
+            "caret",
+            "decoration",
+            "divide",
+            "placeholder",
+            "from",
+            "via",
+            "to",
+            "shadow",
+        ],
+    ),
+    ("font", &["font"]),
+    ("font-weight", &["font"]),
+    ("text", &["text"]),
+    ("tracking", &["tracking"]),
+    ("leading", &["leading"]),
+    ("container", &["max-w"]),
+    (
+        "radius",
+        &[
+            "rounded",
+            "rounded-t",
+            "rounded-r",
+            "rounded-b",
+            "rounded-l",
+        ],
+    ),
+    ("shadow", &["shadow"]),
+    ("inset-shadow", &["inset-shadow"]),
+    ("drop-shadow", &["drop-shadow"]),
+    ("blur", &["blur", "backdrop-blur"]),
+    ("perspective", &["perspective"]),
+    ("aspect", &["aspect"]),
+    ("ease", &["ease"]),
+    ("animate", &["animate"]),
+    ("z-index", &["z"]),
+    ("opacity", &["opacity"]),
+    ("spacing", SPACING_UTILITIES),
+];
+
+/// Utilities taking a spacing step
+const SPACING_UTILITIES: &[&str] = &[
+    "p", "px", "py", "pt", "pr", "pb", "pl", "m", "mx", "my", "mt", "mr", "mb", "ml", "gap",
+    "gap-x", "gap-y", "space-x", "space-y", "w", "h", "size", "min-w", "min-h", "max-h", "inset",
+    "top", "right", "bottom", "left",
+];
+
+/// Steps of the default spacing scale
+const SPACING_STEPS: &[&str] = &[
+    "0", "px", "0.5", "1", "1.5", "2", "2.5", "3", "3.5", "4", "5", "6", "7", "8", "9", "10", "11",
+    "12", "14", "16", "20", "24", "28", "32", "36", "40", "44", "48", "52", "56", "60", "64", "72",
+    "80", "96",
+];
+
+/// Utilities that take no value
+const STATIC_UTILITIES: &[&str] = &[
+    "block",
+    "inline-block",
+    "inline",
+    "flex",
+    "inline-flex",
+    "grid",
+    "inline-grid",
+    "contents",
+    "table",
+    "hidden",
+    "static",
+    "fixed",
+    "absolute",
+    "relative",
+    "sticky",
+    "visible",
+    "invisible",
+    "container",
+    "flex-row",
+    "flex-row-reverse",
+    "flex-col",
+    "flex-col-reverse",
+    "flex-wrap",
+    "flex-nowrap",
+    "flex-1",
+    "flex-auto",
+    "flex-none",
+    "grow",
+    "shrink",
+    "items-start",
+    "items-center",
+    "items-end",
+    "items-stretch",
+    "items-baseline",
+    "justify-start",
+    "justify-center",
+    "justify-end",
+    "justify-between",
+    "justify-around",
+    "justify-evenly",
+    "text-left",
+    "text-center",
+    "text-right",
+    "text-justify",
+    "italic",
+    "not-italic",
+    "underline",
+    "line-through",
+    "no-underline",
+    "uppercase",
+    "lowercase",
+    "capitalize",
+    "truncate",
+    "overflow-hidden",
+    "overflow-auto",
+    "overflow-scroll",
+    "overflow-visible",
+    "rounded-none",
+    "rounded-full",
+    "shadow-none",
+    "border",
+    "border-0",
+    "border-2",
+    "border-4",
+    "border-8",
+    "w-full",
+    "w-screen",
+    "w-auto",
+    "h-full",
+    "h-screen",
+    "h-auto",
+    "cursor-pointer",
+    "pointer-events-none",
+    "select-none",
+    "sr-only",
+    "transition",
+    "transition-all",
+    "transition-colors",
+];
+
+/// Safelist and blocklist operations of the CSS generator
+pub trait CssGeneratorSafelist {
+    /// Candidate utilities derived from the theme, the spacing scale and static utilities
+    fn utility_catalog(&self) -> Vec<String>;
+
+    /// Generate every class a safelist covers, returning the classes added
+    fn add_safelist(&mut self, entries: &[SafelistEntry]) -> Result<Vec<String>>;
+
+    /// Forbid a class (and the same utility under any variant)
+    fn block_class(&mut self, class: &str);
+
+    /// Check whether the blocklist forbids a class
+    fn is_blocked(&self, class: &str) -> bool;
+}
+
+impl CssGeneratorSafelist for super::CssGenerator {
+    fn utility_catalog(&self) -> Vec<String> {
+        let theme = TailwindTheme::from_variables(self.theme_variables.tokens());
+        let mut catalog: Vec<String> = STATIC_UTILITIES.iter().map(|s| s.to_string()).collect();
+
+        for (namespace, prefixes) in NAMESPACE_UTILITIES {
+            let Some(entries) = theme.namespace(namespace) else {
+                continue;
+            };
+            for name in entries.keys() {
+                // Paired line heights aren't utilities of their own
+                if name.contains("--") {
+                    continue;
+                }
+                for prefix in prefixes.iter() {
+                    catalog.push(if name == "DEFAULT" {
+                        prefix.to_string()
+                    } else {
+                        format!("{}-{}", prefix, name)
+                    });
+                }
+            }
+        }
+        for prefix in SPACING_UTILITIES {
+            for step in SPACING_STEPS {
+                catalog.push(format!("{}-{}", prefix, step));
+            }
+        }
+
+        catalog.sort();
+        catalog.dedup();
+        catalog
+    }
+
+    fn add_safelist(&mut self, entries: &[SafelistEntry]) -> Result<Vec<String>> {
+        let catalog = self.utility_catalog();
+        let mut added = Vec::new();
+
+        for entry in entries {
+            let classes = entry.expand(catalog.iter().map(String::as_str))?;
+            for class in classes {
+                if self.is_blocked(&class) {
+                    continue;
+                }
+                match entry {
+                    // A literal class the generator can't build is a configuration error
+                    SafelistEntry::Class(_) => self.add_class(&class)?,
+                    // Catalog candidates aren't all supported under every variant
+                    SafelistEntry::Pattern { .. } => {
+                        if self.add_class(&class).is_err() {
+                            continue;
+                        }
+                    }
+                }
+                added.push(class);
+            }
+        }
+
+        Ok(added)
+    }
+
+    fn block_class(&mut self, class: &str) {
+        if !self.blocklist.iter().any(|blocked| blocked == class) {
+            self.blocklist.push(class.to_string());
+        }
+        let blocklist = std::mem::take(&mut self.blocklist);
+        self.rules.retain(|name, _| !is_blocked(&blocklist, name));
+        self.blocklist = blocklist;
+    }
+
+    fn is_blocked(&self, class: &str) -> bool {
+        is_blocked(&self.blocklist, class)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TailwindConfig;
+    use crate::css_generator::CssGenerator;
+
+    #[test]
+    fn test_utility_catalog() {
+        let generator = CssGenerator::new();
+        let catalog = generator.utility_catalog();
+        for class in [
+            "bg-red-500",
+            "text-blue-100",
+            "p-4",
+            "shadow-md",
+            "z-50",
+            "container",
+        ] {
+            assert!(catalog.iter().any(|c| c == class), "missing {}", class);
+        }
+        assert!(!catalog.iter().any(|c| c.contains("--line-height")));
+    }
+
+    #[test]
+    fn test_safelist_and_blocklist_from_config() {
+        let config = TailwindConfig::from_str(
+            r#"
+safelist = [
+    "underline",
+    { pattern = "^(bg|text)-(red|green|blue)-(100|500)$", variants = ["hover", "md"] },
+]
+blocklist = ["container", "text-green-100"]
+
+[build]
+output = "dist/styles.css"
+
+[theme]
+name = "default"
+
+[responsive]
+breakpoints = { sm = 640 }
+container_centering = true
+container_padding = 16
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.safelist.len(), 2);
+
+        let mut generator = CssGenerator::new();
+        generator.apply_config(&config).unwrap();
+        // 2 utilities x 3 colors x 2 shades, plus hover and md variants, minus
+        // the blocked class under each of them, plus the literal class
+        assert_eq!(generator.rules.len(), 12 * 3 - 3 + 1);
+        assert!(generator.rules.contains_key("md:text-blue-500"));
+        assert!(generator.rules.contains_key("underline"));
+        assert!(!generator.rules.contains_key("hover:text-green-100"));
+
+        generator.add_class("container").unwrap();
+        generator.add_class("md:container").unwrap();
+        assert!(!generator.generate_css().contains(".container"));
+    }
+
+    #[test]
+    fn test_unknown_literal_is_an_error() {
+        let mut generator = CssGenerator::new();
+        let result = generator.add_safelist(&[SafelistEntry::Class("not-a-utility".to_string())]);
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,812 @@
+//! Theme Variables
+//!
+//! This module maps utilities onto theme tokens exposed as CSS custom
+//! properties, Tailwind v4 style. With
+//! [`CssGenerationConfig::theme_variables`](super::types::CssGenerationConfig)
+//! enabled, `bg-blue-500` emits `background-color: var(--color-blue-500)` and
+//! `p-4` emits `padding: calc(var(--spacing) * 4)`, so a theme can be changed
+//! at runtime by overriding a handful of variables.
+//!
+//! Only the tokens that generated rules actually reference end up in the
+//! `:root` block; everything else is pruned. Named themes add [`ThemeScope`]s
+//! whose overrides are emitted under their own selector
+//! (`[data-theme=brand-a]`) or media query, pruned the same way.
+//!
+//! ## Example
+//!
+//! ```rust
+//! use tailwind_rs_core::{CssGenerationConfig, CssGenerator};
+//!
+//! let mut generator = CssGenerator::with_config(CssGenerationConfig {
+//!     theme_variables: true,
+//!     ..Default::default()
+//! });
+//! generator.add_class("bg-blue-500").unwrap();
+//!
+//! let css = generator.generate_css();
+//! assert!(css.contains("--color-blue-500: #3b82f6;"));
+//! assert!(css.contains("background-color: var(--color-blue-500)"));
+//! assert!(!css.contains("--color-red-500"));
+//! ```
+
+use super::types::{CssProperty, CssRule};
+use crate::color_space::{apply_opacity, parse_opacity_modifier, ColorFormat};
+use crate::theme::Theme;
+use crate::theme_model::{length_to_px, TailwindTheme};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Default color palette (`50` through `950`)
+///
+/// These are Tailwind v3's hex values; v4's OKLCH palette isn't used yet.
+pub const DEFAULT_PALETTE: [(&str, [&str; 11]); 22] = [
+    (
+        "slate",
+        [
+            "#f8fafc", "#f1f5f9", "#e2e8f0", "#cbd5e1", "#94a3b8", "#64748b", "#475569", "#334155",
+            "#1e293b", "#0f172a", "#020617",
+        ],
+    ),
+    (
+        "gray",
+        [
+            "#f9fafb", "#f3f4f6", "#e5e7eb", "#d1d5db", "#9ca3af", "#6b7280", "#4b5563", "#374151",
+            "#1f2937", "#111827", "#030712",
+        ],
+    ),
+    (
+        "zinc",
+        [
+            "#fafafa", "#f4f4f5", "#e4e4e7", "#d4d4d8", "#a1a1aa", "#71717a", "#52525b", "#3f3f46",
+            "#27272a", "#18181b", "#09090b",
+        ],
+    ),
+    (
+        "neutral",
+        [
+            "#fafafa", "#f5f5f5", "#e5e5e5", "#d4d4d4", "#a3a3a3", "#737373", "#525252", "#404040",
+            "#262626", "#171717", "#0a0a0a",
+        ],
+    ),
+    (
+        "stone",
+        [
+            "#fafaf9", "#f5f5f4", "#e7e5e4", "#d6d3d1", "#a8a29e", "#78716c", "#57534e", "#44403c",
+            "#292524", "#1c1917", "#0c0a09",
+        ],
+    ),
+    (
+        "red",
+        [
+            "#fef2f2", "#fee2e2", "#fecaca", "#fca5a5", "#f87171", "#ef4444", "#dc2626", "#b91c1c",
+            "#991b1b", "#7f1d1d", "#450a0a",
+        ],
+    ),
+    (
+        "orange",
+        [
+            "#fff7ed", "#ffedd5", "#fed7aa", "#fdba74", "#fb923c", "#f97316", "#ea580c", "#c2410c",
+            "#9a3412", "#7c2d12", "#431407",
+        ],
+    ),
+    (
+        "amber",
+        [
+            "#fffbeb", "#fef3c7", "#fde68a", "#fcd34d", "#fbbf24", "#f59e0b", "#d97706", "#b45309",
+            "#92400e", "#78350f", "#451a03",
+        ],
+    ),
+    (
+        "yellow",
+        [
+            "#fefce8", "#fef9c3", "#fef08a", "#fde047", "#facc15", "#eab308", "#ca8a04", "#a16207",
+            "#854d0e", "#713f12", "#422006",
+        ],
+    ),
+    (
+        "lime",
+        [
+            "#f7fee7", "#ecfccb", "#d9f99d", "#bef264", "#a3e635", "#84cc16", "#65a30d", "#4d7c0f",
+            "#3f6212", "#365314", "#1a2e05",
+        ],
+    ),
+    (
+        "green",
+        [
+            "#f0fdf4", "#dcfce7", "#bbf7d0", "#86efac", "#4ade80", "#22c55e", "#16a34a", "#15803d",
+            "#166534", "#14532d", "#052e16",
+        ],
+    ),
+    (
+        "emerald",
+        [
+            "#ecfdf5", "#d1fae5", "#a7f3d0", "#6ee7b7", "#34d399", "#10b981", "#059669", "#047857",
+            "#065f46", "#064e3b", "#022c22",
+        ],
+    ),
+    (
+        "teal",
+        [
+            "#f0fdfa", "#ccfbf1", "#99f6e4", "#5eead4", "#2dd4bf", "#14b8a6", "#0d9488", "#0f766e",
+            "#115e59", "#134e4a", "#042f2e",
+        ],
+    ),
+    (
+        "cyan",
+        [
+            "#ecfeff", "#cffafe", "#a5f3fc", "#67e8f9", "#22d3ee", "#06b6d4", "#0891b2", "#0e7490",
+            "#155e75", "#164e63", "#083344",
+        ],
+    ),
+    (
+        "sky",
+        [
+            "#f0f9ff", "#e0f2fe", "#bae6fd", "#7dd3fc", "#38bdf8", "#0ea5e9", "#0284c7", "#0369a1",
+            "#075985", "#0c4a6e", "#082f49",
+        ],
+    ),
+    (
+        "blue",
+        [
+            "#eff6ff", "#dbeafe", "#bfdbfe", "#93c5fd", "#60a5fa", "#3b82f6", "#2563eb", "#1d4ed8",
+            "#1e40af", "#1e3a8a", "#172554",
+        ],
+    ),
+    (
+        "indigo",
+        [
+            "#eef2ff", "#e0e7ff", "#c7d2fe", "#a5b4fc", "#818cf8", "#6366f1", "#4f46e5", "#4338ca",
+            "#3730a3", "#312e81", "#1e1b4b",
+        ],
+    ),
+    (
+        "violet",
+        [
+            "#f5f3ff", "#ede9fe", "#ddd6fe", "#c4b5fd", "#a78bfa", "#8b5cf6", "#7c3aed", "#6d28d9",
+            "#5b21b6", "#4c1d95", "#2e1065",
+        ],
+    ),
+    (
+        "purple",
+        [
+            "#faf5ff", "#f3e8ff", "#e9d5ff", "#d8b4fe", "#c084fc", "#a855f7", "#9333ea", "#7e22ce",
+            "#6b21a8", "#581c87", "#3b0764",
+        ],
+    ),
+    (
+        "fuchsia",
+        [
+            "#fdf4ff", "#fae8ff", "#f5d0fe", "#f0abfc", "#e879f9", "#d946ef", "#c026d3", "#a21caf",
+            "#86198f", "#701a75", "#4a044e",
+        ],
+    ),
+    (
+        "pink",
+        [
+            "#fdf2f8", "#fce7f3", "#fbcfe8", "#f9a8d4", "#f472b6", "#ec4899", "#db2777", "#be185d",
+            "#9d174d", "#831843", "#500724",
+        ],
+    ),
+    (
+        "rose",
+        [
+            "#fff1f2", "#ffe4e6", "#fecdd3", "#fda4af", "#fb7185", "#f43f5e", "#e11d48", "#be123c",
+            "#9f1239", "#881337", "#4c0519",
+        ],
+    ),
+];
+
+/// Shade steps of [`DEFAULT_PALETTE`]
+pub const PALETTE_SHADES: [u16; 11] = [50, 100, 200, 300, 400, 500, 600, 700, 800, 900, 950];
+
+/// Utility prefixes that take a color, mapped to the property they set
+const COLOR_UTILITIES: &[(&str, &str)] = &[
+    ("bg", "background-color"),
+    ("text", "color"),
+    ("border", "border-color"),
+    ("outline", "outline-color"),
+    ("decoration", "text-decoration-color"),
+    ("accent", "accent-color"),
+    ("caret", "caret-color"),
+    ("fill", "fill"),
+    ("stroke", "stroke"),
+];
+
+/// Token namespaces and the utility prefixes whose suffix names a token
+const NAMED_TOKEN_UTILITIES: &[(&str, &[&str])] = &[
+    (
+        "color",
+        &[
+            "bg",
+            "text",
+            "border",
+            "border-x",
+            "border-y",
+            "border-t",
+            "border-r",
+            "border-b",
+            "border-l",
+            "outline",
+            "ring",
+            "ring-offset",
+            "fill",
+            "stroke",
+            "accent",
+            "caret",
+            "decoration",
+            "divide",
+            "placeholder",
+            "from",
+            "via",
+            "to",
+            "shadow",
+        ],
+    ),
+    (
+        "radius",
+        &[
+            "rounded",
+            "rounded-t",
+            "rounded-r",
+            "rounded-b",
+            "rounded-l",
+            "rounded-tl",
+            "rounded-tr",
+            "rounded-br",
+            "rounded-bl",
+            "rounded-s",
+            "rounded-e",
+            "rounded-ss",
+            "rounded-se",
+            "rounded-es",
+            "rounded-ee",
+        ],
+    ),
+    ("shadow", &["shadow"]),
+    ("blur", &["blur", "backdrop-blur"]),
+    ("container", &["w", "min-w", "max-w"]),
+    ("font", &["font"]),
+    ("text", &["text"]),
+    ("tracking", &["tracking"]),
+    ("leading", &["leading"]),
+];
+
+/// Token overrides emitted under their own selector, for one named theme
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThemeScope {
+    /// Theme name
+    pub name: String,
+    /// Selector the overrides apply to (`[data-theme=brand-a]`, `:root`)
+    pub selector: String,
+    /// Media query wrapping the block, if any
+    pub media_query: Option<String>,
+    /// Token overrides, keyed by name without the leading `--`
+    pub tokens: HashMap<String, String>,
+}
+
+impl ThemeScope {
+    /// Create an empty scope
+    pub fn new(name: impl Into<String>, selector: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            selector: selector.into(),
+            media_query: None,
+            tokens: HashMap::new(),
+        }
+    }
+
+    /// Wrap the scope in a media query
+    pub fn with_media_query(mut self, media_query: impl Into<String>) -> Self {
+        self.media_query = Some(media_query.into());
+        self
+    }
+
+    /// Override a token (name with or without the leading `--`)
+    pub fn with_token(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.tokens
+            .insert(name.trim_start_matches("--").to_string(), value.into());
+        self
+    }
+}
+
+/// Theme tokens available as CSS custom properties
+#[derive(Debug, Clone)]
+pub struct ThemeVariables {
+    /// Built-in token values, keyed by name without the leading `--`
+    defaults: HashMap<String, String>,
+    /// Values configured by the theme, taking precedence over the defaults
+    overrides: HashMap<String, String>,
+    /// Named themes overriding tokens under their own selector
+    scopes: Vec<ThemeScope>,
+}
+
+impl ThemeVariables {
+    /// Create the default token set
+    pub fn new() -> Self {
+        Self {
+            defaults: TailwindTheme::default()
+                .to_variables()
+                .into_iter()
+                .collect(),
+            overrides: HashMap::new(),
+            scopes: Vec::new(),
+        }
+    }
+
+    /// Set a token value (name without the leading `--`)
+    pub fn set(&mut self, name: &str, value: &str) {
+        self.overrides
+            .insert(name.trim_start_matches("--").to_string(), value.to_string());
+    }
+
+    /// Get the effective value of a token
+    pub fn get(&self, name: &str) -> Option<&str> {
+        let name = name.trim_start_matches("--");
+        self.overrides
+            .get(name)
+            .or_else(|| self.defaults.get(name))
+            .map(String::as_str)
+    }
+
+    /// The effective value of every root token, keyed by name without the leading `--`
+    pub fn tokens(&self) -> BTreeMap<&str, &str> {
+        let mut tokens: BTreeMap<&str, &str> = self
+            .defaults
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        tokens.extend(
+            self.overrides
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str())),
+        );
+        tokens
+    }
+
+    /// Check whether a token exists, at the root or in a named theme
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+            || self
+                .scopes
+                .iter()
+                .any(|scope| scope.tokens.contains_key(name.trim_start_matches("--")))
+    }
+
+    /// Add a named theme, replacing any scope with the same name
+    pub fn add_scope(&mut self, scope: ThemeScope) {
+        self.scopes.retain(|existing| existing.name != scope.name);
+        self.scopes.push(scope);
+    }
+
+    /// The named themes, in the order they were added
+    pub fn scopes(&self) -> &[ThemeScope] {
+        &self.scopes
+    }
+
+    /// Import the tokens of a theme
+    ///
+    /// The theme is resolved into its canonical [`TailwindTheme`] and every
+    /// variable that differs from the default becomes an override. Defaults
+    /// of namespaces the theme replaces are dropped.
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let variables = theme.to_tailwind_theme().to_variables();
+        self.defaults.retain(|name, _| variables.contains_key(name));
+        for (name, value) in variables {
+            if self.defaults.get(&name) != Some(&value) {
+                self.overrides.insert(name, value);
+            }
+        }
+    }
+
+    /// Resolve a color utility against the theme (e.g. `bg-brand-500`, `bg-brand-500/50`)
+    ///
+    /// With `use_variables` every color token is considered and referenced
+    /// through `var()`. Otherwise only colors configured by the theme are
+    /// considered and their value is inlined, so that theme colors work
+    /// without shadowing the built-in palette. An opacity modifier mixes the
+    /// variable with `transparent`, or sets the alpha of an inlined color.
+    pub fn resolve_color_utility(
+        &self,
+        class: &str,
+        use_variables: bool,
+    ) -> Option<Vec<CssProperty>> {
+        COLOR_UTILITIES.iter().find_map(|(prefix, property)| {
+            let color = class.strip_prefix(prefix)?.strip_prefix('-')?;
+            let (color, opacity) = match color.split_once('/') {
+                Some((color, modifier)) => (color, Some(parse_opacity_modifier(modifier)?)),
+                None => (color, None),
+            };
+            let token = format!("color-{}", color);
+
+            let value = if use_variables {
+                if !self.contains(&token) {
+                    return None;
+                }
+                format!("var(--{})", token)
+            } else if self.defaults.contains_key(&token) {
+                return None;
+            } else {
+                self.overrides.get(&token)?.clone()
+            };
+            let value = match opacity {
+                Some(opacity) => apply_opacity(&value, opacity, ColorFormat::Rgb),
+                None => value,
+            };
+
+            Some(vec![CssProperty {
+                name: property.to_string(),
+                value,
+                important: false,
+            }])
+        })
+    }
+
+    /// Replace literal theme values in a utility's properties with `var()` references
+    pub fn tokenize(&self, class: &str, properties: &mut [CssProperty]) {
+        let class = class.trim_start_matches('-');
+        for (index, _) in class.match_indices('-') {
+            let (prefix, key) = (&class[..index], &class[index + 1..]);
+
+            for (namespace, prefixes) in NAMED_TOKEN_UTILITIES {
+                if !prefixes.contains(&prefix) {
+                    continue;
+                }
+                let token = format!("{}-{}", namespace, key);
+                if let Some(literal) = self.defaults.get(&token) {
+                    let replacement = format!("var(--{})", token);
+                    if replace_in_properties(properties, literal, &replacement) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Collect the tokens referenced by a set of rules, with their root values
+    ///
+    /// Tokens referenced by other tokens are included as well. When
+    /// `include_breakpoints` is set, `--breakpoint-*` tokens matching the
+    /// media queries in use are added too (media queries cannot contain
+    /// `var()`, so they only serve as runtime-readable values).
+    pub fn referenced<'a>(
+        &self,
+        rules: impl IntoIterator<Item = &'a CssRule>,
+        include_breakpoints: bool,
+    ) -> BTreeMap<String, String> {
+        let names = self.referenced_names(rules, include_breakpoints);
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let value = self.get(&name)?.to_string();
+                Some((name, value))
+            })
+            .collect()
+    }
+
+    /// Collect the overrides of each named theme referenced by a set of rules
+    ///
+    /// Media-query themes come first, so that an explicit `[data-theme=…]`
+    /// selector wins over a matching `prefers-color-scheme` theme. Themes
+    /// without referenced overrides are left out.
+    pub fn referenced_scopes<'a>(
+        &self,
+        rules: impl IntoIterator<Item = &'a CssRule>,
+    ) -> Vec<(&ThemeScope, BTreeMap<String, String>)> {
+        let names = self.referenced_names(rules, false);
+        let mut scopes: Vec<_> = self
+            .scopes
+            .iter()
+            .map(|scope| {
+                let tokens: BTreeMap<String, String> = scope
+                    .tokens
+                    .iter()
+                    .filter(|(name, _)| names.contains(*name))
+                    .map(|(name, value)| (name.clone(), value.clone()))
+                    .collect();
+                (scope, tokens)
+            })
+            .filter(|(_, tokens)| !tokens.is_empty())
+            .collect();
+        scopes.sort_by_key(|(scope, _)| scope.media_query.is_none());
+        scopes
+    }
+
+    /// Names of the tokens referenced by a set of rules, following references
+    /// through root values and named theme overrides
+    fn referenced_names<'a>(
+        &self,
+        rules: impl IntoIterator<Item = &'a CssRule>,
+        include_breakpoints: bool,
+    ) -> BTreeSet<String> {
+        let mut pending = Vec::new();
+        let mut media_queries = Vec::new();
+        for rule in rules {
+            for property in &rule.properties {
+                pending.extend(var_references(&property.value));
+            }
+            if let Some(media_query) = &rule.media_query {
+                media_queries.push(media_query.as_str());
+            }
+        }
+
+        let mut used = BTreeSet::new();
+        while let Some(name) = pending.pop() {
+            if !self.contains(&name) || !used.insert(name.clone()) {
+                continue;
+            }
+            pending.extend(self.get(&name).map(var_references).unwrap_or_default());
+            for scope in &self.scopes {
+                if let Some(value) = scope.tokens.get(&name) {
+                    pending.extend(var_references(value));
+                }
+            }
+        }
+
+        if include_breakpoints {
+            let names = self.defaults.keys().chain(self.overrides.keys());
+            for name in names.filter(|name| name.starts_with("breakpoint-")) {
+                let value = self.get(name).unwrap_or_default();
+                // Responsive variants spell the width in pixels (`md:` is `768px`)
+                let mut conditions = vec![format!("(min-width: {})", value)];
+                if let Some(px) = length_to_px(value) {
+                    conditions.push(format!("(min-width: {}px)", px));
+                }
+                if media_queries
+                    .iter()
+                    .any(|query| conditions.iter().any(|c| query.contains(c)))
+                {
+                    used.insert(name.clone());
+                }
+            }
+        }
+
+        used
+    }
+}
+
+impl Default for ThemeVariables {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Replace every occurrence of `literal` in the property values, returning
+/// whether anything was replaced
+fn replace_in_properties(properties: &mut [CssProperty], literal: &str, replacement: &str) -> bool {
+    let mut replaced = false;
+    for property in properties.iter_mut() {
+        if let Some(value) = replace_value(&property.value, literal, replacement) {
+            property.value = value;
+            replaced = true;
+        }
+    }
+    replaced
+}
+
+/// Replace whole-value occurrences of `literal` (so `1rem` does not match `11rem`)
+fn replace_value(value: &str, literal: &str, replacement: &str) -> Option<String> {
+    let is_value_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '#' | '%');
+    let mut output = String::new();
+    let mut rest = value;
+    let mut replaced = false;
+
+    while let Some(start) = rest.find(literal) {
+        let end = start + literal.len();
+        let before = rest[..start].chars().next_back();
+        let after = rest[end..].chars().next();
+        output.push_str(&rest[..start]);
+        if before.is_some_and(is_value_char) || after.is_some_and(is_value_char) {
+            output.push_str(literal);
+        } else {
+            output.push_str(replacement);
+            replaced = true;
+        }
+        rest = &rest[end..];
+    }
+    output.push_str(rest);
+
+    replaced.then_some(output)
+}
+
+/// Extract the custom property names referenced through `var(--name)`
+fn var_references(value: &str) -> Vec<String> {
+    value
+        .match_indices("var(--")
+        .map(|(start, _)| {
+            let name = &value[start + "var(--".len()..];
+            let end = name
+                .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+                .unwrap_or(name.len());
+            name[..end].to_string()
+        })
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn property(name: &str, value: &str) -> CssProperty {
+        CssProperty {
+            name: name.to_string(),
+            value: value.to_string(),
+            important: false,
+        }
+    }
+
+    #[test]
+    fn test_tokenize_named_tokens() {
+        let variables = ThemeVariables::new();
+
+        let mut properties = vec![property("background-color", "#3b82f6")];
+        variables.tokenize("bg-blue-500", &mut properties);
+        assert_eq!(properties[0].value, "var(--color-blue-500)");
+
+        let mut properties = vec![property("border-top-left-radius", "0.5rem")];
+        variables.tokenize("rounded-tl-lg", &mut properties);
+        assert_eq!(properties[0].value, "var(--radius-lg)");
+
+        // A literal that does not match the token default is left alone
+        let mut properties = vec![property("background-color", "rgba(59, 130, 246, 0.5)")];
+        variables.tokenize("bg-blue-500/50", &mut properties);
+        assert_eq!(properties[0].value, "rgba(59, 130, 246, 0.5)");
+    }
+
+    #[test]
+    fn test_referenced_tokens_are_transitive_and_pruned() {
+        let mut variables = ThemeVariables::new();
+        variables.set("color-brand", "var(--color-blue-600)");
+
+        let rule = CssRule {
+            selector: ".text-brand".to_string(),
+            properties: vec![property("color", "var(--color-brand)")],
+            media_query: Some("(min-width: 768px)".to_string()),
+            specificity: 10,
+        };
+        let used = variables.referenced([&rule], true);
+
+        assert_eq!(used.len(), 3);
+        assert_eq!(used["color-brand"], "var(--color-blue-600)");
+        assert_eq!(used["color-blue-600"], "#2563eb");
+        assert_eq!(used["breakpoint-md"], "48rem");
+    }
+
+    #[test]
+    fn test_generator_emits_only_referenced_tokens() {
+        use crate::css_generator::{CssGenerationConfig, CssGenerator};
+
+        let mut generator = CssGenerator::with_config(CssGenerationConfig {
+            theme_variables: true,
+            ..Default::default()
+        });
+        generator.theme_variables.set("color-blue-500", "#1d4ed8");
+        for class in ["bg-blue-500", "p-4", "rounded-lg", "md:text-lg"] {
+            generator.add_class(class).unwrap();
+        }
+
+        let css = generator.generate_css();
+        assert!(css.contains("--color-blue-500: #1d4ed8;"));
+        assert!(css.contains("--spacing: 0.25rem;"));
+        assert!(css.contains("--radius-lg: 0.5rem;"));
+        assert!(css.contains("--text-lg--line-height: calc(1.75 / 1.125);"));
+        assert!(css.contains("--breakpoint-md: 48rem;"));
+        assert!(css.contains("padding: calc(var(--spacing) * 4)"));
+        assert!(!css.contains("--color-red-500"));
+        assert!(!css.contains("--font-sans"));
+    }
+
+    #[test]
+    fn test_resolve_color_utility() {
+        let mut variables = ThemeVariables::new();
+        variables.set("color-brand-500", "#0f766e");
+
+        let properties = variables
+            .resolve_color_utility("bg-brand-500", false)
+            .unwrap();
+        assert_eq!(properties[0].value, "#0f766e");
+        let properties = variables
+            .resolve_color_utility("bg-brand-500", true)
+            .unwrap();
+        assert_eq!(properties[0].value, "var(--color-brand-500)");
+
+        // Built-in palette colors are left to the regular parsers unless variables are on
+        assert!(variables
+            .resolve_color_utility("bg-slate-500", false)
+            .is_none());
+        assert!(variables
+            .resolve_color_utility("bg-slate-500", true)
+            .is_some());
+        assert!(variables
+            .resolve_color_utility("bg-nope-500", true)
+            .is_none());
+
+        // Opacity modifiers mix variables and set the alpha of inlined colors
+        let properties = variables
+            .resolve_color_utility("bg-brand-500/50", true)
+            .unwrap();
+        assert_eq!(
+            properties[0].value,
+            "color-mix(in oklab, var(--color-brand-500) 50%, transparent)"
+        );
+        let properties = variables
+            .resolve_color_utility("bg-brand-500/[0.25]", false)
+            .unwrap();
+        assert_eq!(properties[0].value, "rgb(15 118 110 / 0.25)");
+    }
+
+    #[test]
+    fn test_referenced_scopes() {
+        let mut variables = ThemeVariables::new();
+        variables.add_scope(
+            ThemeScope::new("brand-a", "[data-theme=brand-a]")
+                .with_token("color-blue-500", "var(--color-violet-600)")
+                .with_token("color-red-500", "#be123c"),
+        );
+        variables.add_scope(
+            ThemeScope::new("dark", ":root")
+                .with_media_query("(prefers-color-scheme: dark)")
+                .with_token("color-blue-500", "#60a5fa"),
+        );
+
+        let rule = CssRule {
+            selector: ".bg-blue-500".to_string(),
+            properties: vec![property("background-color", "var(--color-blue-500)")],
+            media_query: None,
+            specificity: 10,
+        };
+
+        // Tokens referenced from a theme override are defined at the root
+        let root = variables.referenced([&rule], false);
+        assert_eq!(root["color-blue-500"], "#3b82f6");
+        assert_eq!(root["color-violet-600"], "#7c3aed");
+
+        let scopes = variables.referenced_scopes([&rule]);
+        let names: Vec<_> = scopes
+            .iter()
+            .map(|(scope, _)| scope.name.as_str())
+            .collect();
+        assert_eq!(names, ["dark", "brand-a"]);
+        assert_eq!(scopes[1].1.len(), 1);
+        assert_eq!(scopes[1].1["color-blue-500"], "var(--color-violet-600)");
+    }
+
+    #[test]
+    fn test_generator_emits_named_themes() {
+        use crate::config::{NamedTheme, TailwindConfig};
+        use crate::css_generator::CssGenerator;
+
+        let mut config = TailwindConfig::new();
+        config.themes.insert(
+            "brand-a".to_string(),
+            NamedTheme::new().with_token("color-blue-500", "#7c3aed"),
+        );
+        config.themes.insert(
+            "dark".to_string(),
+            NamedTheme::new()
+                .with_media("(prefers-color-scheme: dark)")
+                .with_token("color-white", "#0a0a0a"),
+        );
+
+        let mut generator = CssGenerator::new();
+        generator.apply_config(&config).unwrap();
+        for class in [
+            "bg-blue-500",
+            "text-white",
+            "theme-brand-a:p-4",
+            "theme-dark:m-2",
+        ] {
+            generator.add_class(class).unwrap();
+        }
+
+        let css = generator.generate_css();
+        assert!(css.contains(":root {\n"));
+        assert!(css.contains("background-color: var(--color-blue-500)"));
+        assert!(css.contains("[data-theme=brand-a] {\n  --color-blue-500: #7c3aed;\n}"));
+        assert!(css.contains(
+            "@media (prefers-color-scheme: dark) {\n:root {\n  --color-white: #0a0a0a;\n}"
+        ));
+        assert!(css
+            .contains(".theme-brand-a\\:p-4:where([data-theme=brand-a], [data-theme=brand-a] *)"));
+        assert!(css.contains("@media (prefers-color-scheme: dark)"));
+    }
+}
@@ -2,11 +2,14 @@
 //!
 //! This module contains the fundamental types used throughout the CSS generation system.
 
+use crate::color_space::ColorFormat;
+use crate::config::css_config::DEFAULT_LAYERS;
 use crate::responsive::Breakpoint;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Represents a CSS rule with selector and properties
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CssRule {
     /// CSS selector (e.g., ".p-4", ".md:bg-blue-500")
     pub selector: String,
@@ -19,7 +22,7 @@ pub struct CssRule {
 }
 
 /// Represents a CSS property
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CssProperty {
     /// Property name (e.g., "padding", "background-color")
     pub name: String,
@@ -42,6 +45,15 @@ pub struct CssGenerationConfig {
     pub include_device_variants: bool,
     /// Custom breakpoints
     pub custom_breakpoints: HashMap<Breakpoint, String>,
+    /// Whether utilities reference theme tokens through `var(--…)` instead of
+    /// inlining their values
+    pub theme_variables: bool,
+    /// How literal colors are written (`None` keeps them as the parsers emit them)
+    pub color_format: Option<ColorFormat>,
+    /// Cascade layers to emit (`theme`, `base`, `components`, `utilities`)
+    pub layers: Vec<String>,
+    /// CSS appended verbatim after the generated rules
+    pub passthrough: String,
 }
 
 impl Default for CssGenerationConfig {
@@ -52,6 +64,13 @@ impl Default for CssGenerationConfig {
             include_interactive: true,
             include_device_variants: true,
             custom_breakpoints: HashMap::new(),
+            theme_variables: false,
+            color_format: None,
+            layers: DEFAULT_LAYERS
+                .iter()
+                .map(|layer| layer.to_string())
+                .collect(),
+            passthrough: String::new(),
         }
     }
 }
@@ -0,0 +1,242 @@
+//! Typography Plugin
+//!
+//! This module compiles the `prose` classes of the typography plugin into
+//! rules. Unlike utilities, a prose class styles the descendants of the
+//! element it's on, so each class compiles to a container rule plus one rule
+//! per element, emitted in the `components` cascade layer like component
+//! classes. The styles themselves live in [`ProseParser`].
+//!
+//! ## Example
+//!
+//! ```rust
+//! use tailwind_rs_core::CssGenerator;
+//!
+//! let mut generator = CssGenerator::new();
+//! generator.add_class("prose").unwrap();
+//! generator.add_class("prose-lg").unwrap();
+//! generator.add_class("dark:prose-invert").unwrap();
+//! generator.add_class("prose-headings:underline").unwrap();
+//!
+//! let css = generator.generate_css();
+//! assert!(css.contains(".prose :where(h1):not(:where([class~=\"not-prose\"],[class~=\"not-prose\"] *))"));
+//! assert!(css.contains(".dark .dark\\:prose-invert {"));
+//! assert!(css.contains("text-decoration-line: underline"));
+//! ```
+
+use super::parsers::{ProseParser, UtilityParser};
+use super::types::CssRule;
+use super::utils::escape_class_selector;
+use crate::error::{Result, TailwindError};
+
+/// Typography plugin methods for CssGenerator
+pub trait CssGeneratorTypography {
+    /// Compile the rules for a (possibly variant-prefixed) prose class
+    ///
+    /// Returns `None` when the class isn't a prose class.
+    fn compile_prose(&self, class: &str) -> Result<Option<Vec<CssRule>>>;
+}
+
+impl CssGeneratorTypography for super::CssGenerator {
+    fn compile_prose(&self, class: &str) -> Result<Option<Vec<CssRule>>> {
+        let (outer_variants, base) = self.variant_parser.split_variants(class);
+        if base == "not-prose" {
+            // Only a marker the descendant selectors exclude
+            return Ok(Some(Vec::new()));
+        }
+        if base != "prose" && !base.starts_with("prose-") {
+            return Ok(None);
+        }
+
+        let (container, media_query) = self.variant_parser.apply_variants(
+            &format!(".{}", escape_class_selector(class)),
+            &outer_variants,
+        );
+        let specificity = outer_variants.len() as u32 * 10;
+
+        // Element modifiers: `prose-headings:underline`
+        if let Some((modifier, utility)) = base.split_once(':') {
+            let Some(elements) = modifier
+                .strip_prefix("prose-")
+                .and_then(ProseParser::element_modifier)
+            else {
+                return Ok(None);
+            };
+            let (inner_variants, utility) = self.variant_parser.split_variants(utility);
+            let properties = self.class_to_properties(&utility).map_err(|_| {
+                TailwindError::class_generation(format!(
+                    "Unknown utility '{}' in prose modifier '{}'",
+                    utility, class
+                ))
+            })?;
+
+            let selector = format!(
+                "{}{}",
+                container,
+                ProseParser::descendant_selector(elements)
+            );
+            let (selector, inner_query) = self
+                .variant_parser
+                .apply_variants(&selector, &inner_variants);
+            let media_query = match (media_query, inner_query) {
+                (Some(outer), Some(inner)) => Some(format!("{} and {}", outer, inner)),
+                (outer, inner) => outer.or(inner),
+            };
+            return Ok(Some(vec![CssRule {
+                selector,
+                properties,
+                media_query,
+                specificity: specificity + ProseParser::ORDER_MODIFIER,
+            }]));
+        }
+
+        // Colors defined by the theme beyond the default palette recolor links
+        let properties = match self.prose_parser.parse_class(&base) {
+            Some(properties) => properties,
+            None => match base.strip_prefix("prose-") {
+                Some(name)
+                    if self
+                        .theme_variables
+                        .get(&format!("color-{}-600", name))
+                        .is_some() =>
+                {
+                    ProseParser::accent_theme(name)
+                }
+                _ => return Ok(None),
+            },
+        };
+
+        let specificity = specificity + ProseParser::order(&base);
+        let mut rules = vec![CssRule {
+            selector: container.clone(),
+            properties,
+            media_query: media_query.clone(),
+            specificity,
+        }];
+        for (elements, properties) in self.prose_parser.element_styles(&base) {
+            rules.push(CssRule {
+                selector: format!(
+                    "{}{}",
+                    container,
+                    ProseParser::descendant_selector(elements)
+                ),
+                properties,
+                media_query: media_query.clone(),
+                specificity,
+            });
+        }
+        Ok(Some(rules))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::css_generator::CssGenerator;
+
+    #[test]
+    fn test_prose_styles_descendants() {
+        let generator = CssGenerator::new();
+        let rules = generator.compile_prose("prose").unwrap().unwrap();
+
+        assert_eq!(rules[0].selector, ".prose");
+        assert!(rules[0]
+            .properties
+            .iter()
+            .any(|p| p.name == "--tw-prose-body" && p.value == "var(--color-gray-700)"));
+
+        let selectors: Vec<&str> = rules.iter().map(|rule| rule.selector.as_str()).collect();
+        for element in [
+            "h1",
+            "h2",
+            "h3",
+            "h4",
+            "p",
+            "a",
+            "pre",
+            "blockquote",
+            "ol, ul",
+            "hr",
+        ] {
+            let selector = format!(
+                ".prose :where({}):not(:where([class~=\"not-prose\"],[class~=\"not-prose\"] *))",
+                element
+            );
+            assert!(
+                selectors.contains(&selector.as_str()),
+                "missing {}",
+                element
+            );
+        }
+        assert!(selectors.contains(
+            &".prose :where(code):not(:where([class~=\"not-prose\"],[class~=\"not-prose\"] *))::before"
+        ));
+    }
+
+    #[test]
+    fn test_prose_sizes_and_themes() {
+        let generator = CssGenerator::new();
+
+        let lg = generator.compile_prose("md:prose-lg").unwrap().unwrap();
+        assert_eq!(lg[0].media_query.as_deref(), Some("(min-width: 768px)"));
+        assert!(lg[0].properties.iter().any(|p| p.value == "1.125rem"));
+        assert!(lg.iter().any(|rule| rule.selector.contains(":where(h1)")
+            && rule.properties.iter().any(|p| p.value == "2.6666667em")));
+
+        let slate = generator.compile_prose("prose-slate").unwrap().unwrap();
+        assert_eq!(slate.len(), 1);
+        assert!(slate[0]
+            .properties
+            .iter()
+            .any(|p| p.name == "--tw-prose-invert-body" && p.value == "var(--color-slate-300)"));
+
+        let invert = generator.compile_prose("prose-invert").unwrap().unwrap();
+        assert!(invert[0].specificity > slate[0].specificity);
+
+        let accent = generator.compile_prose("prose-blue").unwrap().unwrap();
+        assert_eq!(accent[0].properties[0].value, "var(--color-blue-600)");
+        assert!(generator.compile_prose("prose-nope").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_prose_element_modifiers() {
+        let generator = CssGenerator::new();
+
+        let rules = generator
+            .compile_prose("dark:prose-a:hover:text-blue-500")
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            rules[0].selector,
+            ".dark .dark\\:prose-a\\:hover\\:text-blue-500 :where(a):not(:where([class~=\"not-prose\"],[class~=\"not-prose\"] *)):hover"
+        );
+
+        let rules = generator
+            .compile_prose("prose-headings:font-bold")
+            .unwrap()
+            .unwrap();
+        assert!(rules[0].selector.contains(":where(h1, h2, h3, h4, th)"));
+        assert!(generator.compile_prose("prose-headings:nope").is_err());
+        assert!(generator
+            .compile_prose("prose-nope:underline")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_prose_emitted_in_components_layer() {
+        let mut generator = CssGenerator::new();
+        generator.add_class("prose-invert").unwrap();
+        generator.add_class("prose").unwrap();
+        generator.add_class("prose-zinc").unwrap();
+        generator.add_class("not-prose").unwrap();
+
+        let css = generator.generate_css();
+        assert!(css.starts_with("@layer components {"));
+        // The inverted theme wins over the gray theme regardless of class name order
+        let zinc = css.find(".prose-zinc {").unwrap();
+        let invert = css.find(".prose-invert {").unwrap();
+        assert!(css.find(".prose {").unwrap() < zinc);
+        assert!(zinc < invert);
+        assert!(css.contains("--color-zinc-700: "));
+    }
+}
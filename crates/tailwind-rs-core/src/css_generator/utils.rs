@@ -140,14 +140,33 @@ pub fn create_selector(class: &str) -> String {
     format!(".{}", class)
 }
 
+/// Escape a class name for use in a CSS selector (e.g. `md:p-4` -> `md\:p-4`)
+pub fn escape_class_selector(class: &str) -> String {
+    let mut escaped = String::with_capacity(class.len());
+    for ch in class.chars() {
+        if !(ch.is_ascii_alphanumeric() || ch == '-' || ch == '_' || !ch.is_ascii()) {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
 /// Create a responsive selector
 pub fn create_responsive_selector(breakpoint: &str, class: &str) -> String {
     format!("{}.{}", breakpoint, class)
 }
 
-/// Create a media query
+/// Create the at-rule prelude for a rule's condition
+///
+/// Conditions are media queries unless they already name their at-rule,
+/// as container query variants do (`@container (width > 40rem)`).
 pub fn create_media_query(condition: &str) -> String {
-    format!("@media {}", condition)
+    if condition.starts_with('@') {
+        condition.to_string()
+    } else {
+        format!("@media {}", condition)
+    }
 }
 
 /// Validate CSS property
@@ -0,0 +1,237 @@
+//! Value Resolution
+//!
+//! Shared resolution of utility values for spacing and sizing utilities, so
+//! every utility accepts the same value syntax:
+//!
+//! - Any multiple of 0.25 on the spacing scale: `p-13`, `mt-0.5`, `inset-4.5`
+//! - `px` and `0`
+//! - Fractions: `w-7/12`, `-translate-x-1/2`
+//! - Arbitrary values: `gap-[10px]`, `gap-[--my-gap]`
+//! - CSS variable shorthand: `w-(--sidebar)`
+//!
+//! Scale values are emitted as `calc(var(--spacing) * n)` when theme variables
+//! are enabled, and as `n * base` in `rem` otherwise.
+
+/// Default spacing base in `rem` (`--spacing: 0.25rem`)
+pub const DEFAULT_SPACING_BASE: f64 = 0.25;
+
+/// Resolves utility values against the spacing scale
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ValueResolver {
+    /// Size of one spacing step in `rem`
+    spacing_base: f64,
+    /// Reference `--spacing` instead of inlining the computed length
+    use_variables: bool,
+}
+
+impl ValueResolver {
+    /// Create a resolver for a spacing base (in `rem`)
+    pub fn new(spacing_base: f64, use_variables: bool) -> Self {
+        Self {
+            spacing_base,
+            use_variables,
+        }
+    }
+
+    /// Create a resolver from a `--spacing` value such as `0.25rem` or `4px`
+    ///
+    /// Falls back to the default base when the value is not a plain length.
+    pub fn from_spacing_token(token: Option<&str>, use_variables: bool) -> Self {
+        let spacing_base = token
+            .and_then(parse_length_rem)
+            .filter(|base| *base > 0.0)
+            .unwrap_or(DEFAULT_SPACING_BASE);
+        Self::new(spacing_base, use_variables)
+    }
+
+    /// The spacing base in `rem`
+    pub fn spacing_base(&self) -> f64 {
+        self.spacing_base
+    }
+
+    /// Resolve a spacing value (`4`, `0.5`, `px`, `[10px]`, `(--gap)`)
+    pub fn spacing(&self, value: &str) -> Option<String> {
+        match value {
+            "0" => Some("0".to_string()),
+            "px" => Some("1px".to_string()),
+            _ => self.scale(value).or_else(|| Self::arbitrary(value)),
+        }
+    }
+
+    /// Resolve a length that may also be a fraction or `full` (`w-7/12`, `inset-full`)
+    pub fn length(&self, value: &str) -> Option<String> {
+        match value {
+            "full" => Some("100%".to_string()),
+            _ => self.spacing(value).or_else(|| Self::fraction(value)),
+        }
+    }
+
+    /// Resolve a multiple of the spacing scale (`13`, `2.5`)
+    pub fn scale(&self, value: &str) -> Option<String> {
+        let steps = parse_scale_steps(value)?;
+        if self.use_variables {
+            Some(format!("calc(var(--spacing) * {})", format_number(steps)))
+        } else {
+            Some(format!("{}rem", format_number(steps * self.spacing_base)))
+        }
+    }
+
+    /// Resolve a fraction (`7/12`) to a percentage
+    pub fn fraction(value: &str) -> Option<String> {
+        let (numerator, denominator) = value.split_once('/')?;
+        let numerator: u32 = numerator.parse().ok()?;
+        let denominator: u32 = denominator.parse().ok()?;
+        if denominator == 0 {
+            return None;
+        }
+        Some(format!(
+            "{}%",
+            format_number(f64::from(numerator) * 100.0 / f64::from(denominator))
+        ))
+    }
+
+    /// Resolve an arbitrary value (`[10px]`, `[--my-gap]`) or variable shorthand (`(--sidebar)`)
+    pub fn arbitrary(value: &str) -> Option<String> {
+        if let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            if inner.is_empty() {
+                return None;
+            }
+            if inner.starts_with("--") {
+                return Some(format!("var({})", inner));
+            }
+            return Some(inner.replace('_', " "));
+        }
+
+        let inner = value.strip_prefix('(')?.strip_suffix(')')?;
+        inner
+            .starts_with("--")
+            .then(|| format!("var({})", inner.replace('_', " ")))
+    }
+
+    /// Negate a resolved value (for `-mt-4`, `-translate-x-1/2`)
+    pub fn negate(value: &str) -> String {
+        if value == "0" {
+            return value.to_string();
+        }
+        if let Some(steps) = value
+            .strip_prefix("calc(var(--spacing) * ")
+            .and_then(|rest| rest.strip_suffix(')'))
+        {
+            return format!("calc(var(--spacing) * -{})", steps);
+        }
+        let is_plain_number = value
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_digit() || c == '.');
+        if is_plain_number {
+            format!("-{}", value)
+        } else {
+            format!("calc({} * -1)", value)
+        }
+    }
+}
+
+impl Default for ValueResolver {
+    fn default() -> Self {
+        Self::new(DEFAULT_SPACING_BASE, false)
+    }
+}
+
+/// Parse a non-negative multiple of 0.25 written as plain digits (`13`, `2.5`)
+fn parse_scale_steps(value: &str) -> Option<f64> {
+    let mut parts = value.splitn(2, '.');
+    let whole = parts.next()?;
+    let fraction = parts.next().unwrap_or("0");
+    let all_digits = |part: &str| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit());
+    if !all_digits(whole) || !all_digits(fraction) {
+        return None;
+    }
+
+    let steps: f64 = value.parse().ok()?;
+    (steps > 0.0 && (steps * 4.0).fract() == 0.0).then_some(steps)
+}
+
+/// Parse a `rem` or `px` length to `rem` (assuming a 16px root)
+fn parse_length_rem(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if let Some(rem) = value.strip_suffix("rem") {
+        rem.trim().parse().ok()
+    } else {
+        value
+            .strip_suffix("px")?
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .map(|px| px / 16.0)
+    }
+}
+
+/// Format a number with at most six decimals and no trailing zeros
+pub(crate) fn format_number(number: f64) -> String {
+    let formatted = format!("{:.6}", number);
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scale_values() {
+        let inline = ValueResolver::default();
+        assert_eq!(inline.spacing("13").as_deref(), Some("3.25rem"));
+        assert_eq!(inline.spacing("0.5").as_deref(), Some("0.125rem"));
+        assert_eq!(inline.spacing("4.5").as_deref(), Some("1.125rem"));
+        assert_eq!(inline.spacing("px").as_deref(), Some("1px"));
+        assert!(inline.spacing("4.3").is_none());
+        assert!(inline.spacing("1e3").is_none());
+        assert!(inline.spacing("-4").is_none());
+
+        let variables = ValueResolver::new(DEFAULT_SPACING_BASE, true);
+        assert_eq!(
+            variables.spacing("17").as_deref(),
+            Some("calc(var(--spacing) * 17)")
+        );
+
+        let custom = ValueResolver::from_spacing_token(Some("4px"), false);
+        assert_eq!(custom.spacing("3").as_deref(), Some("0.75rem"));
+    }
+
+    #[test]
+    fn test_fractions_and_arbitrary_values() {
+        let resolver = ValueResolver::default();
+        assert_eq!(resolver.length("7/12").as_deref(), Some("58.333333%"));
+        assert_eq!(resolver.length("full").as_deref(), Some("100%"));
+        assert!(resolver.length("1/0").is_none());
+        assert!(resolver.spacing("1/2").is_none());
+
+        assert_eq!(resolver.spacing("[10px]").as_deref(), Some("10px"));
+        assert_eq!(
+            resolver.spacing("[--my-gap]").as_deref(),
+            Some("var(--my-gap)")
+        );
+        assert_eq!(
+            resolver.spacing("(--sidebar)").as_deref(),
+            Some("var(--sidebar)")
+        );
+        assert_eq!(
+            resolver.spacing("[calc(100%_-_1rem)]").as_deref(),
+            Some("calc(100% - 1rem)")
+        );
+        assert!(resolver.spacing("(sidebar)").is_none());
+    }
+
+    #[test]
+    fn test_negate() {
+        assert_eq!(ValueResolver::negate("1rem"), "-1rem");
+        assert_eq!(
+            ValueResolver::negate("calc(var(--spacing) * 4)"),
+            "calc(var(--spacing) * -4)"
+        );
+        assert_eq!(ValueResolver::negate("var(--gap)"), "calc(var(--gap) * -1)");
+        assert_eq!(ValueResolver::negate("0"), "0");
+    }
+}
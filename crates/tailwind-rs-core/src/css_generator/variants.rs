@@ -3,7 +3,72 @@
 //! This module handles the parsing and processing of CSS variants,
 //! including responsive, state, and custom variants.
 
+use super::custom_utilities::split_top_level;
+use crate::custom_variant::CustomVariantType;
+use crate::error::{Result, TailwindError};
 use crate::responsive::Breakpoint;
+use std::collections::HashMap;
+
+/// A user-defined variant, as declared with `@custom-variant`
+#[derive(Debug, Clone, PartialEq)]
+pub enum CustomVariantRule {
+    /// Selector template, split around the `&` placeholder
+    Selector { before: String, after: String },
+    /// Media query condition (e.g. `(min-width: 120rem)`)
+    Media(String),
+    /// Container query, optionally naming the container (e.g. `sidebar (width > 40rem)`)
+    Container(String),
+}
+
+impl CustomVariantRule {
+    /// Parse a variant template such as `&:where([data-theme=midnight] *)`,
+    /// `[dir=rtl] &`, `@media (orientation: portrait)` or `@container (width > 40rem)`
+    pub fn parse(template: &str) -> Result<Self> {
+        let template = template.trim();
+
+        for (at_rule, rule) in [
+            ("@media", Self::Media as fn(String) -> Self),
+            ("@container", Self::Container),
+        ] {
+            if let Some(condition) = template.strip_prefix(at_rule) {
+                let condition = condition.trim();
+                if condition.is_empty() {
+                    return Err(TailwindError::validation(format!(
+                        "Variant {} query cannot be empty",
+                        &at_rule[1..]
+                    )));
+                }
+                return Ok(rule(condition.to_string()));
+            }
+        }
+
+        if template.starts_with('@') {
+            return Err(TailwindError::validation(format!(
+                "Unsupported at-rule in variant template: '{}'",
+                template
+            )));
+        }
+
+        let Some((before, after)) = template.split_once('&') else {
+            return Err(TailwindError::validation(format!(
+                "Variant template '{}' must contain '&'",
+                template
+            )));
+        };
+        // Commas are fine inside `:where(...)`, but not between selectors
+        if after.contains('&') || split_top_level(template, ',').len() > 1 {
+            return Err(TailwindError::validation(format!(
+                "Variant template '{}' must be a single selector with one '&'",
+                template
+            )));
+        }
+
+        Ok(Self::Selector {
+            before: before.to_string(),
+            after: after.to_string(),
+        })
+    }
+}
 
 /// Variant parser for handling CSS variants
 #[derive(Debug, Clone)]
@@ -12,6 +77,8 @@ pub struct VariantParser {
     variants: Vec<String>,
     /// Responsive breakpoints
     breakpoints: Vec<Breakpoint>,
+    /// User-defined variants, checked before the built-in ones
+    custom_variants: HashMap<String, CustomVariantRule>,
 }
 
 impl VariantParser {
@@ -50,7 +117,63 @@ impl VariantParser {
                 Breakpoint::Xl,
                 Breakpoint::Xl2,
             ],
+            custom_variants: HashMap::new(),
+        }
+    }
+
+    /// Register a user-defined variant from a template
+    pub fn add_custom_variant(&mut self, name: &str, template: &str) -> Result<()> {
+        CustomVariantType::validate_name(name)?;
+        let rule = CustomVariantRule::parse(template)?;
+        self.custom_variants.insert(name.to_string(), rule);
+        if !self.is_supported_variant(name) {
+            self.variants.push(name.to_string());
         }
+        Ok(())
+    }
+
+    /// Get a user-defined variant
+    pub fn get_custom_variant(&self, name: &str) -> Option<&CustomVariantRule> {
+        self.custom_variants.get(name)
+    }
+
+    /// Parse an arbitrary variant such as `[&>*]` or `[@container_(width_>_40rem)]`
+    ///
+    /// Underscores stand in for spaces, as they do in arbitrary values.
+    pub fn parse_arbitrary_variant(variant: &str) -> Option<CustomVariantRule> {
+        let template = variant.strip_prefix('[')?.strip_suffix(']')?;
+        CustomVariantRule::parse(&template.replace('_', " ")).ok()
+    }
+
+    /// Split a leading arbitrary variant (`[&:nth-child(3)]:`) off a class
+    fn split_arbitrary_variant(class: &str) -> Option<(&str, &str)> {
+        if !class.starts_with('[') {
+            return None;
+        }
+
+        let mut depth = 0usize;
+        for (index, ch) in class.char_indices() {
+            match ch {
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        let (variant, rest) = class.split_at(index + 1);
+                        return rest.strip_prefix(':').map(|rest| (variant, rest));
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// The variant resolved from the custom table or from arbitrary syntax
+    fn resolve_custom_variant(&self, variant: &str) -> Option<CustomVariantRule> {
+        self.custom_variants
+            .get(variant)
+            .cloned()
+            .or_else(|| Self::parse_arbitrary_variant(variant))
     }
 
     /// Parse variants from a class string
@@ -58,6 +181,19 @@ impl VariantParser {
         let mut variants = Vec::new();
         let mut remaining = class.to_string();
 
+        // User-defined variants take precedence over the built-in table
+        if let Some((name, rest)) = class.split_once(':') {
+            if self.custom_variants.contains_key(name) {
+                return (vec![name.to_string()], rest.to_string());
+            }
+        }
+
+        if let Some((variant, rest)) = Self::split_arbitrary_variant(class) {
+            if Self::parse_arbitrary_variant(variant).is_some() {
+                return (vec![variant.to_string()], rest.to_string());
+            }
+        }
+
         // Parse variants in order of specificity (most specific first)
         // Check for compound variants first
         let compound_patterns = [
@@ -102,6 +238,8 @@ impl VariantParser {
                 // Device variants
                 ("pointer-coarse:", "pointer-coarse"),
                 ("pointer-fine:", "pointer-fine"),
+                ("any-pointer-coarse:", "any-pointer-coarse"),
+                ("any-pointer-fine:", "any-pointer-fine"),
                 ("motion-reduce:", "motion-reduce"),
                 ("motion-safe:", "motion-safe"),
                 ("light:", "light"),
@@ -128,6 +266,115 @@ impl VariantParser {
         (variants, remaining)
     }
 
+    /// Parse every leading variant from a class string (e.g. `md:hover:p-4`)
+    pub fn split_variants(&self, class: &str) -> (Vec<String>, String) {
+        let mut variants = Vec::new();
+        let mut remaining = class.to_string();
+
+        loop {
+            let (parsed, rest) = self.parse_variants(&remaining);
+            if parsed.is_empty() {
+                break;
+            }
+            variants.extend(parsed);
+            remaining = rest;
+        }
+
+        (variants, remaining)
+    }
+
+    /// Check that the variants can share one rule
+    ///
+    /// A rule sits in at most one at-rule, so a container query can't be
+    /// combined with a media query or with a second container query.
+    pub fn validate_variants(&self, variants: &[String]) -> Result<()> {
+        let mut containers = 0;
+        let mut media = false;
+        for variant in variants {
+            match self.resolve_custom_variant(variant) {
+                Some(CustomVariantRule::Container(_)) => containers += 1,
+                Some(CustomVariantRule::Media(_)) => media = true,
+                Some(CustomVariantRule::Selector { .. }) => {}
+                None => {
+                    media |= self.get_responsive_media_query(variant).is_some()
+                        || self.get_device_media_query(variant).is_some();
+                }
+            }
+        }
+
+        if containers > 1 || (containers == 1 && media) {
+            return Err(TailwindError::validation(format!(
+                "Variants {:?} combine a container query with another query",
+                variants
+            )));
+        }
+        Ok(())
+    }
+
+    /// Apply variants to a base selector, returning the selector and media query
+    ///
+    /// Pseudo-class variants are appended (`.btn:hover`), ancestor variants are
+    /// prepended (`.dark .btn`), and responsive/device variants are combined
+    /// into a single media query. A container query variant returns the whole
+    /// at-rule instead (`@container (width > 40rem)`); see [`Self::validate_variants`].
+    pub fn apply_variants(
+        &self,
+        base_selector: &str,
+        variants: &[String],
+    ) -> (String, Option<String>) {
+        let mut prefix = String::new();
+        let mut suffix = String::new();
+        let mut media_queries = Vec::new();
+        let mut container_query = None;
+
+        for variant in variants {
+            match self.resolve_custom_variant(variant) {
+                Some(CustomVariantRule::Selector { before, after }) => {
+                    prefix.push_str(&before);
+                    suffix.push_str(&after);
+                    continue;
+                }
+                Some(CustomVariantRule::Media(query)) => {
+                    media_queries.push(query);
+                    continue;
+                }
+                Some(CustomVariantRule::Container(query)) => {
+                    container_query = Some(format!("@container {}", query));
+                    continue;
+                }
+                None => {}
+            }
+
+            if let Some(query) = self
+                .get_responsive_media_query(variant)
+                .or_else(|| self.get_device_media_query(variant))
+            {
+                media_queries.push(query);
+                continue;
+            }
+
+            let selector = self.get_variant_selector(variant);
+            if selector.starts_with(':') {
+                suffix.push_str(&selector);
+            } else {
+                prefix.push_str(&selector);
+            }
+        }
+
+        let media_query = if container_query.is_some() {
+            container_query
+        } else if media_queries.is_empty() {
+            None
+        } else {
+            Some(media_queries.join(" and "))
+        };
+
+        (
+            format!("{}{}{}", prefix, base_selector, suffix),
+            media_query,
+        )
+    }
+
     /// Get the CSS selector for a variant
     pub fn get_variant_selector(&self, variant: &str) -> String {
         match variant {
@@ -150,9 +397,8 @@ impl VariantParser {
             "odd" => ":nth-child(odd)".to_string(),
             "even" => ":nth-child(even)".to_string(),
             // Device variants use media queries, not selectors
-            "pointer-coarse" | "pointer-fine" | "motion-reduce" | "motion-safe" | "light" => {
-                String::new()
-            }
+            "pointer-coarse" | "pointer-fine" | "any-pointer-coarse" | "any-pointer-fine"
+            | "motion-reduce" | "motion-safe" | "light" => String::new(),
             _ => String::new(),
         }
     }
@@ -162,6 +408,8 @@ impl VariantParser {
         match variant {
             "pointer-coarse" => Some("(pointer: coarse)".to_string()),
             "pointer-fine" => Some("(pointer: fine)".to_string()),
+            "any-pointer-coarse" => Some("(any-pointer: coarse)".to_string()),
+            "any-pointer-fine" => Some("(any-pointer: fine)".to_string()),
             "motion-reduce" => Some("(prefers-reduced-motion: reduce)".to_string()),
             "motion-safe" => Some("(prefers-reduced-motion: no-preference)".to_string()),
             "light" => Some("(prefers-color-scheme: light)".to_string()),
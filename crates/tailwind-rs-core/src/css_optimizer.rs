@@ -46,6 +46,25 @@ impl Default for OptimizationConfig {
     }
 }
 
+impl OptimizationConfig {
+    /// Settings for an optimization level, without minification
+    ///
+    /// Level 1 removes empty rules and duplicate properties, level 2 also
+    /// rewrites and sorts properties, and level 3 also merges compatible rules.
+    pub fn for_level(level: u8) -> Self {
+        Self {
+            minify: false,
+            merge_rules: level >= 3,
+            optimize_properties: level >= 2,
+            optimize_selectors: level >= 3,
+            remove_empty_rules: true,
+            remove_duplicates: true,
+            sort_properties: level >= 2,
+            ..Self::default()
+        }
+    }
+}
+
 /// Results of CSS optimization
 #[derive(Debug, Clone)]
 pub struct OptimizationResults {
@@ -0,0 +1,586 @@
+//! Design token import for tailwind-rs
+//!
+//! This module loads design tokens in the W3C Design Tokens Community Group
+//! (DTCG) format, as exported from Figma or Style Dictionary, and maps them
+//! onto Tailwind v4 theme variables:
+//!
+//! ```json
+//! {
+//!   "color": {
+//!     "$type": "color",
+//!     "blue": { "500": { "$value": "#3b82f6" } },
+//!     "primary": { "$value": "{color.blue.500}" }
+//!   }
+//! }
+//! ```
+//!
+//! becomes `--color-blue-500: #3b82f6` and `--color-primary: #3b82f6`.
+//! Aliases are resolved (cycles are an error), `$type` is inherited from
+//! enclosing groups, and tokens whose type has no theme equivalent are
+//! reported instead of being dropped silently.
+
+use crate::error::{Result, TailwindError};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Theme namespaces per token type, with the group names that select them
+///
+/// A token whose first path segment is one of the group names drops that
+/// segment (`color.blue.500` becomes `color-blue-500`); any other token is
+/// placed under the type's default namespace (`brand.primary` becomes
+/// `color-brand-primary`).
+const COLOR_GROUPS: &[&str] = &["color", "colors"];
+const FONT_GROUPS: &[&str] = &["font", "fonts", "fontFamily", "font-family", "fontFamilies"];
+const SHADOW_GROUPS: &[&str] = &["shadow", "shadows", "boxShadow", "box-shadow"];
+const DURATION_GROUPS: &[&str] = &["duration", "durations"];
+const EASE_GROUPS: &[&str] = &["ease", "easing", "cubicBezier", "timing"];
+
+/// Dimension groups and the namespace they map to
+const DIMENSION_NAMESPACES: &[(&str, &[&str])] = &[
+    ("spacing", &["spacing", "space"]),
+    (
+        "radius",
+        &[
+            "radius",
+            "radii",
+            "borderRadius",
+            "border-radius",
+            "rounded",
+        ],
+    ),
+    ("text", &["text", "fontSize", "font-size", "fontSizes"]),
+    ("tracking", &["tracking", "letterSpacing", "letter-spacing"]),
+    ("breakpoint", &["breakpoint", "breakpoints", "screens"]),
+    ("container", &["container", "containers"]),
+    ("blur", &["blur"]),
+];
+
+/// A token as found in the source file, before alias resolution
+#[derive(Debug, Clone)]
+struct RawToken {
+    value: Value,
+    token_type: Option<String>,
+}
+
+/// A parsed design token file
+#[derive(Debug, Clone, Default)]
+pub struct DesignTokens {
+    /// Tokens keyed by their dotted path (`color.blue.500`)
+    tokens: BTreeMap<String, RawToken>,
+}
+
+/// A token that could not be mapped onto the theme
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnsupportedToken {
+    /// Dotted token path
+    pub path: String,
+    /// Declared or inherited `$type`
+    pub token_type: Option<String>,
+    /// Why the token was skipped
+    pub reason: String,
+}
+
+/// The result of importing design tokens
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TokenImport {
+    /// Theme variables, keyed by name without the leading `--`
+    pub variables: BTreeMap<String, String>,
+    /// Tokens that were skipped
+    pub unsupported: Vec<UnsupportedToken>,
+}
+
+impl TokenImport {
+    /// Render the imported variables as a Tailwind v4 `@theme` block
+    pub fn to_theme_css(&self) -> String {
+        let mut css = String::from("@theme {\n");
+        for (name, value) in &self.variables {
+            css.push_str(&format!("  --{}: {};\n", name, value));
+        }
+        css.push_str("}\n");
+        css
+    }
+}
+
+impl DesignTokens {
+    /// Parse a DTCG token document
+    pub fn from_json(content: &str) -> Result<Self> {
+        let document: Value = serde_json::from_str(content)
+            .map_err(|e| TailwindError::config(format!("Design token parsing error: {}", e)))?;
+        let Value::Object(root) = document else {
+            return Err(TailwindError::config(
+                "Design token file must contain a JSON object",
+            ));
+        };
+
+        let mut tokens = BTreeMap::new();
+        collect_tokens(&root, "", None, &mut tokens);
+        Ok(Self { tokens })
+    }
+
+    /// Load a DTCG token file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            TailwindError::config(format!("Failed to read design tokens {:?}: {}", path, e))
+        })?;
+        Self::from_json(&content)
+    }
+
+    /// Number of tokens
+    pub fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    /// Check whether the document has no tokens
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Resolve a token's value, following aliases
+    pub fn resolve(&self, path: &str) -> Result<Value> {
+        self.resolve_path(path, &mut Vec::new())
+    }
+
+    /// The `$type` of a token, taken from its alias target when not declared
+    pub fn token_type(&self, path: &str) -> Option<&str> {
+        let mut path = path;
+        // Bounded by the token count, so a cycle cannot loop forever
+        for _ in 0..=self.tokens.len() {
+            let token = self.tokens.get(path)?;
+            if let Some(token_type) = &token.token_type {
+                return Some(token_type);
+            }
+            path = token.value.as_str().and_then(alias_path)?;
+        }
+        None
+    }
+
+    /// Map every token onto a theme variable
+    ///
+    /// Alias cycles and aliases to missing tokens are errors; tokens with an
+    /// unsupported type or value are reported in [`TokenImport::unsupported`].
+    pub fn import(&self) -> Result<TokenImport> {
+        let mut import = TokenImport::default();
+
+        for path in self.tokens.keys() {
+            let token_type = self.token_type(path);
+            let value = self.resolve(path)?;
+
+            let variable = match token_type {
+                None => Err("missing $type"),
+                Some(token_type) => variable_name(path, token_type)
+                    .ok_or("unsupported $type")
+                    .and_then(|name| {
+                        let css_value = match token_type {
+                            "color" => color_to_css(&value),
+                            "dimension" | "duration" => dimension_to_css(&value),
+                            "fontFamily" => font_family_to_css(&value),
+                            "shadow" => shadow_to_css(&value),
+                            "cubicBezier" => cubic_bezier_to_css(&value),
+                            _ => None,
+                        };
+                        css_value
+                            .map(|css_value| (name, css_value))
+                            .ok_or("unsupported $value")
+                    }),
+            };
+
+            match variable {
+                Ok((name, css_value)) => {
+                    import.variables.insert(name, css_value);
+                }
+                Err(reason) => import.unsupported.push(UnsupportedToken {
+                    path: path.clone(),
+                    token_type: token_type.map(str::to_string),
+                    reason: reason.to_string(),
+                }),
+            }
+        }
+
+        Ok(import)
+    }
+
+    /// Resolve a token, tracking the alias chain to detect cycles
+    fn resolve_path(&self, path: &str, chain: &mut Vec<String>) -> Result<Value> {
+        if chain.iter().any(|visited| visited == path) {
+            chain.push(path.to_string());
+            return Err(TailwindError::config(format!(
+                "Design token alias cycle: {}",
+                chain.join(" -> ")
+            )));
+        }
+        let token = self.tokens.get(path).ok_or_else(|| {
+            TailwindError::config(format!(
+                "Unknown design token alias '{{{}}}'{}",
+                path,
+                chain
+                    .last()
+                    .map(|from| format!(" in '{}'", from))
+                    .unwrap_or_default()
+            ))
+        })?;
+
+        chain.push(path.to_string());
+        let value = self.resolve_value(&token.value, chain)?;
+        chain.pop();
+        Ok(value)
+    }
+
+    /// Resolve the aliases inside a value
+    fn resolve_value(&self, value: &Value, chain: &mut Vec<String>) -> Result<Value> {
+        match value {
+            Value::String(text) => {
+                if let Some(path) = alias_path(text) {
+                    return self.resolve_path(path, chain);
+                }
+                if !text.contains('{') {
+                    return Ok(value.clone());
+                }
+
+                // Aliases embedded in a larger string (`{spacing.4} {spacing.2}`)
+                let mut output = String::new();
+                let mut rest = text.as_str();
+                while let Some(start) = rest.find('{') {
+                    let Some(end) = rest[start..].find('}') else {
+                        break;
+                    };
+                    output.push_str(&rest[..start]);
+                    let resolved = self.resolve_path(&rest[start + 1..start + end], chain)?;
+                    output.push_str(&scalar_to_string(&resolved).ok_or_else(|| {
+                        TailwindError::config(format!(
+                            "Design token alias '{}' cannot be embedded in a string",
+                            &rest[start..=start + end]
+                        ))
+                    })?);
+                    rest = &rest[start + end + 1..];
+                }
+                output.push_str(rest);
+                Ok(Value::String(output))
+            }
+            Value::Array(items) => items
+                .iter()
+                .map(|item| self.resolve_value(item, chain))
+                .collect::<Result<Vec<_>>>()
+                .map(Value::Array),
+            Value::Object(fields) => fields
+                .iter()
+                .map(|(key, field)| Ok((key.clone(), self.resolve_value(field, chain)?)))
+                .collect::<Result<Map<_, _>>>()
+                .map(Value::Object),
+            _ => Ok(value.clone()),
+        }
+    }
+}
+
+/// Walk a group, collecting tokens and inheriting `$type`
+fn collect_tokens(
+    group: &Map<String, Value>,
+    prefix: &str,
+    inherited_type: Option<&str>,
+    tokens: &mut BTreeMap<String, RawToken>,
+) {
+    let group_type = group
+        .get("$type")
+        .and_then(Value::as_str)
+        .or(inherited_type);
+
+    for (key, child) in group {
+        if key.starts_with('$') {
+            continue;
+        }
+        let Value::Object(child) = child else {
+            continue;
+        };
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+
+        match child.get("$value") {
+            Some(value) => {
+                let token_type = child
+                    .get("$type")
+                    .and_then(Value::as_str)
+                    .or(group_type)
+                    .map(str::to_string);
+                tokens.insert(
+                    path,
+                    RawToken {
+                        value: value.clone(),
+                        token_type,
+                    },
+                );
+            }
+            None => collect_tokens(child, &path, group_type, tokens),
+        }
+    }
+}
+
+/// The path of a whole-value alias (`{color.blue.500}`)
+fn alias_path(value: &str) -> Option<&str> {
+    let path = value.strip_prefix('{')?.strip_suffix('}')?;
+    (!path.is_empty() && !path.contains(['{', '}'])).then_some(path)
+}
+
+/// The theme variable name for a token, or `None` for unsupported types
+fn variable_name(path: &str, token_type: &str) -> Option<String> {
+    let segments: Vec<&str> = path.split('.').collect();
+    let first = segments[0];
+
+    let (namespace, groups): (Option<&str>, &[&str]) = match token_type {
+        "color" => (Some("color"), COLOR_GROUPS),
+        "fontFamily" => (Some("font"), FONT_GROUPS),
+        "shadow" => (Some("shadow"), SHADOW_GROUPS),
+        "duration" => (Some("duration"), DURATION_GROUPS),
+        "cubicBezier" => (Some("ease"), EASE_GROUPS),
+        // Dimensions have no single namespace; ungrouped ones keep their path
+        "dimension" => DIMENSION_NAMESPACES
+            .iter()
+            .find(|(_, groups)| groups.contains(&first))
+            .map_or((None, &[]), |(namespace, groups)| {
+                (Some(*namespace), *groups)
+            }),
+        _ => return None,
+    };
+
+    let rest = if groups.contains(&first) {
+        &segments[1..]
+    } else {
+        &segments[..]
+    };
+    let parts: Vec<String> = namespace
+        .map(str::to_string)
+        .into_iter()
+        .chain(rest.iter().map(|segment| segment.replace(' ', "-")))
+        .collect();
+    Some(parts.join("-"))
+}
+
+/// Format a number without a trailing `.0`
+fn number_to_string(number: &serde_json::Number) -> String {
+    match number.as_f64() {
+        Some(value) if value.fract() == 0.0 && value.abs() < 1e15 => format!("{}", value as i64),
+        _ => number.to_string(),
+    }
+}
+
+/// A string or number value as text
+fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number_to_string(number)),
+        _ => None,
+    }
+}
+
+/// `#3b82f6`, or a DTCG color object (`{ "colorSpace": "oklch", "components": [...] }`)
+fn color_to_css(value: &Value) -> Option<String> {
+    let Value::Object(color) = value else {
+        return value.as_str().map(str::to_string);
+    };
+    if let Some(hex) = color.get("hex").and_then(Value::as_str) {
+        return Some(hex.to_string());
+    }
+
+    let space = color.get("colorSpace")?.as_str()?;
+    let components = color
+        .get("components")?
+        .as_array()?
+        .iter()
+        .map(|component| match component {
+            Value::String(keyword) if keyword == "none" => Some("none".to_string()),
+            _ => scalar_to_string(component),
+        })
+        .collect::<Option<Vec<_>>>()?
+        .join(" ");
+    let alpha = color
+        .get("alpha")
+        .and_then(scalar_to_string)
+        .filter(|alpha| alpha != "1")
+        .map(|alpha| format!(" / {}", alpha))
+        .unwrap_or_default();
+
+    Some(match space {
+        "oklch" | "oklab" | "lab" | "lch" | "hsl" | "hwb" => {
+            format!("{}({}{})", space, components, alpha)
+        }
+        _ => format!("color({} {}{})", space, components, alpha),
+    })
+}
+
+/// `16px`, or a DTCG dimension/duration object (`{ "value": 16, "unit": "px" }`)
+fn dimension_to_css(value: &Value) -> Option<String> {
+    match value {
+        Value::Object(dimension) => {
+            let amount = scalar_to_string(dimension.get("value")?)?;
+            let unit = dimension.get("unit")?.as_str()?;
+            Some(format!("{}{}", amount, unit))
+        }
+        Value::Number(number) if number.as_f64() == Some(0.0) => Some("0".to_string()),
+        Value::String(text) => Some(text.clone()),
+        _ => None,
+    }
+}
+
+/// `Inter`, or a font stack (`["Inter", "system-ui"]`)
+fn font_family_to_css(value: &Value) -> Option<String> {
+    let quote = |family: &str| {
+        if family.contains(' ') && !family.starts_with(['"', '\'']) {
+            format!("\"{}\"", family)
+        } else {
+            family.to_string()
+        }
+    };
+    match value {
+        Value::String(family) => Some(quote(family)),
+        Value::Array(families) => families
+            .iter()
+            .map(|family| family.as_str().map(quote))
+            .collect::<Option<Vec<_>>>()
+            .map(|families| families.join(", ")),
+        _ => None,
+    }
+}
+
+/// A DTCG shadow object, or a list of them for layered shadows
+fn shadow_to_css(value: &Value) -> Option<String> {
+    match value {
+        Value::String(shadow) => Some(shadow.clone()),
+        Value::Array(layers) => layers
+            .iter()
+            .map(shadow_to_css)
+            .collect::<Option<Vec<_>>>()
+            .map(|layers| layers.join(", ")),
+        Value::Object(shadow) => {
+            let length = |key: &str| {
+                shadow
+                    .get(key)
+                    .map_or(Some("0".to_string()), dimension_to_css)
+            };
+            let inset = shadow.get("inset").and_then(Value::as_bool) == Some(true);
+            Some(format!(
+                "{}{} {} {} {} {}",
+                if inset { "inset " } else { "" },
+                length("offsetX")?,
+                length("offsetY")?,
+                length("blur")?,
+                length("spread")?,
+                color_to_css(shadow.get("color")?)?
+            ))
+        }
+        _ => None,
+    }
+}
+
+/// `[0.4, 0, 0.2, 1]` as `cubic-bezier(0.4, 0, 0.2, 1)`
+fn cubic_bezier_to_css(value: &Value) -> Option<String> {
+    match value {
+        Value::String(easing) => Some(easing.clone()),
+        Value::Array(points) if points.len() == 4 => points
+            .iter()
+            .map(scalar_to_string)
+            .collect::<Option<Vec<_>>>()
+            .map(|points| format!("cubic-bezier({})", points.join(", "))),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKENS: &str = r##"{
+        "color": {
+            "$type": "color",
+            "blue": { "500": { "$value": "#3b82f6" } },
+            "primary": { "$value": "{color.blue.500}" },
+            "accent": {
+                "$value": { "colorSpace": "oklch", "components": [0.7, 0.15, 250], "alpha": 0.9 }
+            }
+        },
+        "brand": { "ink": { "$type": "color", "$value": "{color.primary}" } },
+        "spacing": { "$type": "dimension", "$value": { "value": 0.25, "unit": "rem" } },
+        "radius": { "card": { "$type": "dimension", "$value": "12px" } },
+        "font": { "body": { "$type": "fontFamily", "$value": ["Inter Variable", "system-ui"] } },
+        "shadow": {
+            "card": {
+                "$type": "shadow",
+                "$value": { "offsetX": "0", "offsetY": "1px", "blur": "3px", "color": "#0000001a" }
+            }
+        },
+        "motion": {
+            "fast": { "$type": "duration", "$value": { "value": 150, "unit": "ms" } },
+            "ease": { "out": { "$type": "cubicBezier", "$value": [0, 0, 0.2, 1] } }
+        },
+        "typography": {
+            "body": { "$type": "typography", "$value": { "fontSize": "16px" } }
+        }
+    }"##;
+
+    #[test]
+    fn test_import_maps_types_to_theme_variables() {
+        let tokens = DesignTokens::from_json(TOKENS).unwrap();
+        let import = tokens.import().unwrap();
+        let variable = |name: &str| import.variables.get(name).map(String::as_str);
+
+        assert_eq!(variable("color-blue-500"), Some("#3b82f6"));
+        assert_eq!(variable("color-primary"), Some("#3b82f6"));
+        assert_eq!(variable("color-accent"), Some("oklch(0.7 0.15 250 / 0.9)"));
+        assert_eq!(variable("color-brand-ink"), Some("#3b82f6"));
+        assert_eq!(variable("spacing"), Some("0.25rem"));
+        assert_eq!(variable("radius-card"), Some("12px"));
+        assert_eq!(variable("font-body"), Some("\"Inter Variable\", system-ui"));
+        assert_eq!(variable("shadow-card"), Some("0 1px 3px 0 #0000001a"));
+        assert_eq!(variable("duration-motion-fast"), Some("150ms"));
+        assert_eq!(
+            variable("ease-motion-ease-out"),
+            Some("cubic-bezier(0, 0, 0.2, 1)")
+        );
+
+        assert_eq!(import.unsupported.len(), 1);
+        assert_eq!(import.unsupported[0].path, "typography.body");
+        assert_eq!(
+            import.unsupported[0].token_type.as_deref(),
+            Some("typography")
+        );
+    }
+
+    #[test]
+    fn test_alias_cycles_and_missing_aliases() {
+        let cyclic = DesignTokens::from_json(
+            r#"{ "color": { "$type": "color",
+                "a": { "$value": "{color.b}" },
+                "b": { "$value": "{color.a}" } } }"#,
+        )
+        .unwrap();
+        let error = cyclic.import().unwrap_err().to_string();
+        assert!(error.contains("color.a -> color.b -> color.a"), "{}", error);
+
+        let dangling = DesignTokens::from_json(
+            r#"{ "size": { "$type": "dimension", "$value": "{spacing.9}" } }"#,
+        )
+        .unwrap();
+        assert!(dangling.import().is_err());
+    }
+
+    #[test]
+    fn test_type_is_inherited_through_aliases() {
+        let tokens = DesignTokens::from_json(
+            r#"{ "base": { "$type": "dimension", "$value": "4px" },
+                 "spacing": { "gutter": { "$value": "{base}" } },
+                 "label": { "$value": "Hello" } }"#,
+        )
+        .unwrap();
+        assert_eq!(tokens.token_type("spacing.gutter"), Some("dimension"));
+
+        let import = tokens.import().unwrap();
+        assert_eq!(
+            import.variables.get("spacing-gutter").map(String::as_str),
+            Some("4px")
+        );
+        assert_eq!(import.unsupported[0].path, "label");
+        assert_eq!(import.unsupported[0].reason, "missing $type");
+    }
+}
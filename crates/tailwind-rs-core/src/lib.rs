@@ -59,14 +59,18 @@
 
 pub mod arbitrary;
 pub mod ast_parser;
+pub mod build_cache;
+pub mod candidates;
 pub mod class_scanner;
 pub mod classes;
 pub mod color;
+pub mod color_space;
 pub mod config;
 pub mod css_generator;
 pub mod css_optimizer;
 pub mod custom_variant;
 pub mod dark_mode;
+pub mod design_tokens;
 pub mod error;
 // pub mod gradients; // Temporarily disabled due to API issues
 pub mod enhanced_variants;
@@ -75,8 +79,10 @@ pub mod plugin_system;
 #[cfg(feature = "postcss")]
 pub mod postcss_integration;
 pub mod responsive;
+pub mod source_map;
 
 pub mod theme;
+pub mod theme_model;
 pub mod theme_new;
 pub mod tree_shaker;
 pub mod utilities;
@@ -92,11 +98,20 @@ pub mod api_contracts;
 // Re-export commonly used types
 pub use arbitrary::{ArbitraryValue, ArbitraryValueError, ArbitraryValueUtilities};
 pub use ast_parser::AstParser;
+pub use build_cache::{content_hash, BuildCache, CacheKey, FileVersion};
+pub use candidates::{
+    extract_candidates, resolve_candidates, Candidate, CandidateResolver, Candidates,
+};
 pub use class_scanner::{ClassScanner, ScanConfig, ScanResults, ScanStats};
-pub use classes::{ClassBuilder, ClassSet};
+pub use classes::{expand_variant_groups, ClassBuilder, ClassSet};
 pub use color::Color;
+pub use color_space::{ColorFormat, Oklch};
 pub use config::parser::ConfigParser;
-pub use config::{BuildConfig, TailwindConfig};
+pub use config::{
+    BuildConfig, BuildInput, CargoContent, ContentConfig, ContentSources, CrateSource, Migration,
+    NamedTheme, OptimizeConfig, SafelistEntry, SafelistMatcher, TailwindConfig, Transformer,
+    WatchConfig, CONFIG_VERSION,
+};
 // Use the modular CssGenerator structure
 pub use css_generator::{CssGenerationConfig, CssGenerator, CssProperty, CssRule};
 
@@ -113,15 +128,17 @@ pub use css_generator::parsers::{
     GradientParser, GridAutoColumnsParser, GridAutoFlowParser, GridAutoRowsParser,
     GridColumnParser, GridParser, GridRowParser, GridTemplateColumnsParser, GridTemplateRowsParser,
     InsetParser, InteractiveParser, JustifyContentParser, JustifyItemsParser, JustifySelfParser,
-    LayoutParser, LayoutUtilitiesParser, MaskUtilitiesParser, ObjectFitParser, OrderParser,
-    OverflowParser, OverscrollParser, ParserCategory, PlaceContentParser, PlaceItemsParser,
-    PlaceSelfParser, PositionParser, PositioningParser, ProseParser, RingParser, ShadowParser,
-    SizingParser, SpacingParser, SvgParser, TableParser, TransformParser, TransitionParser,
-    TransitionPropertiesParser, TypographyParser, UtilityParser, VisibilityParser, ZIndexParser,
+    LayoutParser, LayoutUtilitiesParser, LogicalPropertiesParser, MaskUtilitiesParser,
+    ObjectFitParser, OrderParser, OverflowParser, OverscrollParser, ParserCategory,
+    PlaceContentParser, PlaceItemsParser, PlaceSelfParser, PositionParser, PositioningParser,
+    ProseParser, RingParser, ShadowParser, SizingParser, SpacingParser, SvgParser, TableParser,
+    TextShadowParser, TransformParser, TransitionParser, TransitionPropertiesParser,
+    TypographyParser, UtilityParser, VisibilityParser, ZIndexParser,
 };
 pub use css_optimizer::{OptimizationConfig, OptimizationResults, OptimizationStats};
 pub use custom_variant::{CustomVariant, CustomVariantManager, CustomVariantType};
 pub use dark_mode::{DarkModeVariant, DarkModeVariantError, DarkModeVariantUtilities};
+pub use design_tokens::{DesignTokens, TokenImport, UnsupportedToken};
 pub use error::{Result, TailwindError};
 // pub use gradients::{Gradient, GradientDirection, GradientError, GradientStop, GradientUtilities};
 pub use performance::{CacheStats, ClassCache, OptimizationLevel, PerformanceOptimizer};
@@ -130,7 +147,9 @@ pub use responsive::{
     AlignItems, Breakpoint, FlexDirection, FlexWrap, JustifyContent, Responsive, ResponsiveBuilder,
     ResponsiveFlex, ResponsiveGrid, ResponsiveValue, State,
 };
+pub use source_map::SourceMap;
 pub use theme::{BorderRadius, BoxShadow, Spacing, Theme, ThemeValue};
+pub use theme_model::{FontSize, TailwindTheme};
 pub use theme_new::{
     AnimationScale, BorderScale, FontFamily, FontSizeScale, FontWeightScale, LetterSpacingScale,
     LineHeightScale, ShadowScale, SpacingScale, SpacingSize, Theme as NewTheme, ThemePreset,
@@ -290,6 +309,249 @@ mod tests {
         assert!(VERSION.chars().any(|c| c.is_ascii_digit()));
     }
 
+    #[test]
+    fn test_builder_caches_scanned_classes() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        let src = dir.join("src");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(
+            src.join("card.rs"),
+            r#"const CARD: &str = "rounded-lg p-4"; fn size() -> &'static str { "w-1/2" }"#,
+        )
+        .unwrap();
+        let output = dir.join("styles.css");
+        let build = || {
+            TailwindBuilder::new()
+                .scan_source(&src)
+                .output_css(&output)
+                .cache_dir(&dir.join("cache"))
+                .build()
+                .unwrap();
+            // Rules are compared as a set, since their order isn't stable
+            let css = std::fs::read_to_string(&output).unwrap();
+            let mut rules: Vec<String> = css.split("\n\n").map(str::to_string).collect();
+            rules.sort();
+            rules.join("\n\n")
+        };
+
+        let css = build();
+        assert!(css.contains(".rounded-lg"));
+        assert!(css.contains(".p-4"));
+        assert!(dir.join("cache/build-cache.json").exists());
+        assert_eq!(build(), css);
+    }
+
+    #[test]
+    fn test_builder_cache_tracks_design_tokens() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path();
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(dir.join("src/app.rs"), r#"const APP: &str = "bg-brand";"#).unwrap();
+        std::fs::write(
+            dir.join("tailwind-rs.toml"),
+            r#"
+[build]
+output = "dist/styles.css"
+
+[theme]
+name = "default"
+tokens = "tokens.json"
+
+[responsive]
+breakpoints = { sm = 640 }
+container_centering = true
+container_padding = 16
+"#,
+        )
+        .unwrap();
+        let output = dir.join("styles.css");
+        let build = |brand: &str| {
+            std::fs::write(
+                dir.join("tokens.json"),
+                format!(
+                    r#"{{ "color": {{ "$type": "color", "brand": {{ "$value": "{}" }} }} }}"#,
+                    brand
+                ),
+            )
+            .unwrap();
+            TailwindBuilder::new()
+                .config_file(&dir.join("tailwind-rs.toml"))
+                .scan_source(&dir.join("src"))
+                .output_css(&output)
+                .cache_dir(&dir.join("cache"))
+                .build()
+                .unwrap();
+            std::fs::read_to_string(&output).unwrap()
+        };
+
+        assert!(build("#0f766e").contains("#0f766e"));
+        let css = build("#7c3aed");
+        assert!(css.contains("#7c3aed"));
+        assert!(!css.contains("#0f766e"));
+    }
+
+    #[test]
+    fn test_builder_reads_content_sources() {
+        let dir = std::env::temp_dir().join(format!(
+            "tailwind-rs-builder-content-{}",
+            std::process::id()
+        ));
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::create_dir_all(dir.join("docs")).unwrap();
+        std::fs::write(
+            dir.join("tailwind-rs.toml"),
+            r#"
+[content]
+include = ["src/**/*.rs", "docs/**/*.md", "!src/legacy.rs"]
+
+[content.transform]
+md = "markdown"
+"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("src/app.rs"), r#"const APP: &str = "p-4";"#).unwrap();
+        std::fs::write(dir.join("src/legacy.rs"), r#"const OLD: &str = "m-8";"#).unwrap();
+        std::fs::write(dir.join("src/page.html"), r#"<p class="underline"></p>"#).unwrap();
+        std::fs::write(
+            dir.join("docs/guide.md"),
+            "<b class=\"font-bold\">Note</b>\n\n```html\n<i class=\"italic\"></i>\n```\n",
+        )
+        .unwrap();
+
+        let output = dir.join("styles.css");
+        TailwindBuilder::new()
+            .scan_source(&dir)
+            .config_file(&dir.join("tailwind-rs.toml"))
+            .output_css(&output)
+            .disable_cache()
+            .build()
+            .unwrap();
+        let css = std::fs::read_to_string(&output).unwrap();
+        assert!(css.contains(".p-4"));
+        assert!(css.contains(".font-bold"));
+        assert!(!css.contains(".m-8"));
+        assert!(!css.contains(".underline"));
+        assert!(!css.contains(".italic"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_builder_scans_workspace_crates() {
+        let dir =
+            std::env::temp_dir().join(format!("tailwind-rs-builder-cargo-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        for member in ["app", "ui", "icons"] {
+            std::fs::create_dir_all(dir.join(member).join("src")).unwrap();
+            std::fs::write(
+                dir.join(member).join("Cargo.toml"),
+                format!(
+                    "[package]\nname = \"{}\"\nversion = \"0.1.0\"\nedition = \"2021\"\n",
+                    member
+                ),
+            )
+            .unwrap();
+        }
+        std::fs::write(
+            dir.join("Cargo.toml"),
+            "[workspace]\nmembers = [\"app\", \"ui\", \"icons\"]\nresolver = \"2\"\n",
+        )
+        .unwrap();
+        let mut icons = std::fs::read_to_string(dir.join("icons/Cargo.toml")).unwrap();
+        icons.push_str("\n[package.metadata.tailwind-rs]\nclasses = [\"shadow-lg\"]\n");
+        std::fs::write(dir.join("icons/Cargo.toml"), icons).unwrap();
+        std::fs::write(dir.join("app/src/main.rs"), r#"const APP: &str = "p-4";"#).unwrap();
+        std::fs::write(dir.join("ui/src/lib.rs"), r#"const UI: &str = "ring-2";"#).unwrap();
+        std::fs::write(dir.join("icons/src/lib.rs"), r#"const ICON: &str = "m-8";"#).unwrap();
+        std::fs::write(
+            dir.join("tailwind-rs.toml"),
+            "[content.cargo]\nworkspace = true\n",
+        )
+        .unwrap();
+
+        let output = dir.join("styles.css");
+        TailwindBuilder::new()
+            .scan_source(&dir.join("app/src"))
+            .config_file(&dir.join("tailwind-rs.toml"))
+            .output_css(&output)
+            .disable_cache()
+            .build()
+            .unwrap();
+        let css = std::fs::read_to_string(&output).unwrap();
+        assert!(css.contains(".p-4"));
+        assert!(css.contains(".ring-2"));
+        assert!(css.contains(".shadow-lg"));
+        // The class manifest replaces scanning the crate
+        assert!(!css.contains(".m-8"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_builder_applies_config_and_writes_source_map() {
+        let dir =
+            std::env::temp_dir().join(format!("tailwind-rs-builder-map-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(dir.join("src")).unwrap();
+        std::fs::write(
+            dir.join("tailwind-rs.toml"),
+            r#"
+safelist = ["m-8"]
+
+[build]
+purge = true
+source_maps = true
+"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("src/app.rs"),
+            "fn app() {\n    view! { <div class=\"p-4\"></div> }\n}\n",
+        )
+        .unwrap();
+
+        let output = dir.join("styles.css");
+        let report = TailwindBuilder::new()
+            .scan_source(&dir.join("src"))
+            .config_file(&dir.join("tailwind-rs.toml"))
+            .output_css(&output)
+            .disable_cache()
+            .build()
+            .unwrap();
+        assert_eq!(report.files_scanned, 1);
+        assert!(report.classes.contains("p-4"));
+        assert_eq!(report.rule_count, 2);
+        // Safelisted classes survive tree shaking
+        let tree_shaking = report.tree_shaking.unwrap();
+        assert!(tree_shaking.kept_classes.contains("m-8"));
+        assert!(tree_shaking.removed_classes.is_empty());
+
+        let css = std::fs::read_to_string(&output).unwrap();
+        assert_eq!(css.len(), report.css_size);
+        assert!(css.contains(".m-8"));
+        assert!(css.ends_with("/*# sourceMappingURL=styles.css.map */\n"));
+
+        let map_path = report.source_map_path.unwrap();
+        assert_eq!(map_path, dir.join("styles.css.map"));
+        let map: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&map_path).unwrap()).unwrap();
+        assert_eq!(map["version"], 3);
+        assert_eq!(map["file"], "styles.css");
+        assert_eq!(map["sources"], serde_json::json!(["src/app.rs"]));
+        // Only `.p-4` has a place in the sources: line 1, column 24
+        let p4 = source_map::rule_selectors(&css)
+            .into_iter()
+            .find(|(_, selector)| *selector == ".p-4")
+            .unwrap()
+            .0;
+        let mut expected = SourceMap::new("styles.css");
+        expected.add_mapping(p4, "src/app.rs", source_map::Position::new(1, 24));
+        assert_eq!(map["mappings"], expected.encoded_mappings());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_defaults() {
         assert_eq!(defaults::DEFAULT_THEME, "default");
@@ -306,6 +568,32 @@ pub struct TailwindBuilder {
     tree_shaking: bool,
     minification: bool,
     source_maps: bool,
+    cache_dir: Option<std::path::PathBuf>,
+    cache: bool,
+    content: Option<ContentConfig>,
+}
+
+/// What a [`TailwindBuilder::build`] produced
+#[derive(Debug, Clone)]
+pub struct BuildReport {
+    /// Where the stylesheet was written
+    pub output_path: std::path::PathBuf,
+    /// Where the source map was written, when source maps are enabled
+    pub source_map_path: Option<std::path::PathBuf>,
+    /// Size of the stylesheet in bytes
+    pub css_size: usize,
+    /// Number of rules in the stylesheet
+    pub rule_count: usize,
+    /// Number of content files scanned, including those of content crates
+    pub files_scanned: usize,
+    /// Classes found in the sources and crate manifests
+    pub classes: std::collections::BTreeSet<String>,
+    /// Whether the stylesheet was minified
+    pub minified: bool,
+    /// Rules removed by tree shaking, when it is enabled
+    pub tree_shaking: Option<TreeShakeResults>,
+    /// Time the build took
+    pub duration: std::time::Duration,
 }
 
 impl Default for TailwindBuilder {
@@ -323,6 +611,9 @@ impl TailwindBuilder {
             tree_shaking: false,
             minification: false,
             source_maps: false,
+            cache_dir: None,
+            cache: true,
+            content: None,
         }
     }
 
@@ -336,11 +627,20 @@ impl TailwindBuilder {
         self
     }
 
+    /// Configuration file to build with, TOML, JSON or a Tailwind v4 CSS
+    /// entry file
+    ///
+    /// Its theme, variants, utilities, plugins, safelist and blocklist are
+    /// applied to the generator, its `[content]` section selects the files
+    /// to scan, and its `minify`, `source_maps` and `purge` build settings
+    /// turn on minification, source maps and tree shaking.
     pub fn config_file(mut self, path: &std::path::Path) -> Self {
         self.config_path = Some(path.to_path_buf());
         self
     }
 
+    /// Remove the rules of classes the sources don't use, such as those a
+    /// safelist pattern no longer covers
     pub fn enable_tree_shaking(mut self) -> Self {
         self.tree_shaking = true;
         self
@@ -351,34 +651,115 @@ impl TailwindBuilder {
         self
     }
 
+    /// Write a `.css.map` next to the stylesheet, mapping each rule to the
+    /// file and line its class was first found at
     pub fn enable_source_maps(mut self) -> Self {
         self.source_maps = true;
         self
     }
 
-    pub fn build(self) -> Result<()> {
-        // Create CSS generator
-        let mut generator = CssGenerator::new();
+    /// Directory to keep the build cache in, instead of `target/tailwind-rs`
+    /// next to the configuration file
+    pub fn cache_dir(mut self, dir: &std::path::Path) -> Self {
+        self.cache_dir = Some(dir.to_path_buf());
+        self
+    }
+
+    /// Rescan every file instead of reusing the cached classes and rules
+    pub fn disable_cache(mut self) -> Self {
+        self.cache = false;
+        self
+    }
+
+    /// Files to scan in the source paths, instead of the configuration
+    /// file's `[content]` section
+    pub fn content(mut self, content: ContentConfig) -> Self {
+        self.content = Some(content);
+        self
+    }
+
+    /// Scan the sources, generate their stylesheet and write it to the
+    /// output path, `dist/styles.css` by default
+    pub fn build(self) -> Result<BuildReport> {
+        let start_time = std::time::Instant::now();
+        let config = self.load_config()?;
+        let file_build = self.config_path.as_ref().map(|_| &config.build);
+        let minify = self.minification || file_build.is_some_and(|build| build.minify);
+        let source_maps = self.source_maps || file_build.is_some_and(|build| build.source_maps);
+        let tree_shaking = self.tree_shaking || file_build.is_some_and(|build| build.purge);
 
-        // Scan source files for classes if paths are provided
+        let mut generator = CssGenerator::new();
+        generator.apply_config(&config)?;
+
+        // Scan source files for classes
+        let content = self
+            .content
+            .clone()
+            .unwrap_or_else(|| config.content.clone());
+        let mut scanned = Vec::new();
+        let mut classes = std::collections::BTreeSet::new();
         if !self.source_paths.is_empty() {
+            let sources = content.sources(self.root())?;
+            let mut cache = self.load_cache(&config, &content)?;
+            let mut resolver = CandidateResolver::new(&generator);
             for path in &self.source_paths {
-                if path.is_file() {
-                    self.scan_file_for_classes(path, &mut generator)?;
-                } else if path.is_dir() {
-                    self.scan_directory_for_classes(path, &mut generator)?;
+                for file in sources.files(path)? {
+                    self.scan_file_for_classes(
+                        &file,
+                        &sources,
+                        &mut resolver,
+                        &mut cache,
+                        &mut classes,
+                    )?;
+                    scanned.push(file);
                 }
             }
-        } else {
-            // Add some basic classes for demonstration
-            generator.add_class("p-4")?;
-            generator.add_class("bg-blue-500")?;
-            generator.add_class("text-white")?;
-            generator.add_class("rounded-md")?;
+
+            // Crates from `[content.cargo]` contribute their class manifest,
+            // or else the files of their `src` directory
+            for krate in content.crates(self.root())? {
+                if let Some(manifest) = &krate.classes {
+                    classes.extend(resolver.resolve(extract_candidates(&manifest.join(" "))));
+                    continue;
+                }
+                let crate_sources = content.sources(&krate.root)?;
+                for file in crate_sources.files(&krate.source_dir())? {
+                    self.scan_file_for_classes(
+                        &file,
+                        &crate_sources,
+                        &mut resolver,
+                        &mut cache,
+                        &mut classes,
+                    )?;
+                    scanned.push(file);
+                }
+            }
+
+            // Reuse the rules generated for classes in earlier builds
+            for class in &classes {
+                if let Some(rule) = cache.rule(class) {
+                    generator.update_rule(class, rule.clone());
+                    continue;
+                }
+                generator.add_class(class)?;
+                if let Some(rule) = generator.rules().get(class) {
+                    cache.insert_rule(class, rule.clone());
+                }
+            }
+            cache.prune();
+            cache.save()?;
         }
 
+        let tree_shaking = tree_shaking
+            .then(|| {
+                let used: std::collections::HashSet<String> = classes.iter().cloned().collect();
+                TreeShaker::with_config(TreeShakeConfig::from(&config))
+                    .shake_classes(&used, &mut generator)
+            })
+            .transpose()?;
+
         // Generate CSS
-        let css = if self.minification {
+        let mut css = if minify {
             generator.generate_minified_css()
         } else {
             generator.generate_css()
@@ -387,87 +768,178 @@ impl TailwindBuilder {
         // Determine output path
         let output_path = self
             .output_path
+            .clone()
             .unwrap_or_else(|| std::path::PathBuf::from("dist/styles.css"));
 
         // Create output directory if it doesn't exist
-        if let Some(parent) = output_path.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
+        let output_dir = output_path
+            .parent()
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(std::path::Path::new("."));
+        std::fs::create_dir_all(output_dir)?;
+
+        let source_map_path = if source_maps {
+            let mut map_path = output_path.clone().into_os_string();
+            map_path.push(".map");
+            let map_path = std::path::PathBuf::from(map_path);
+            let map = self.source_map(&css, &output_path, output_dir, &generator, &scanned)?;
+            std::fs::write(&map_path, map.to_json())?;
+            css.push_str(&format!(
+                "\n/*# sourceMappingURL={} */\n",
+                map_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy())
+                    .unwrap_or_default()
+            ));
+            Some(map_path)
+        } else {
+            None
+        };
 
         // Write CSS to file
-        std::fs::write(&output_path, css)?;
-
-        println!("✅ CSS generated successfully at {}", output_path.display());
-        println!("📊 Generated {} CSS rules", generator.rule_count());
+        std::fs::write(&output_path, &css)?;
+
+        Ok(BuildReport {
+            output_path,
+            source_map_path,
+            css_size: css.len(),
+            rule_count: generator.rule_count(),
+            files_scanned: scanned.len(),
+            classes,
+            minified: minify,
+            tree_shaking,
+            duration: start_time.elapsed(),
+        })
+    }
 
-        if self.tree_shaking {
-            println!("🌳 Tree shaking enabled");
+    /// The configuration file's settings, or the defaults without one
+    fn load_config(&self) -> Result<TailwindConfig> {
+        match &self.config_path {
+            Some(path) if path.extension().is_some_and(|ext| ext == "css") => {
+                TailwindConfig::from_css_file(path)
+            }
+            Some(path) => TailwindConfig::from_file(path),
+            None => Ok(TailwindConfig::new()),
         }
+    }
 
-        if self.minification {
-            println!("🗜️ Minification enabled");
-        }
+    /// The directory content globs are relative to: the configuration
+    /// file's, or the working directory
+    fn root(&self) -> &std::path::Path {
+        self.config_path
+            .as_deref()
+            .and_then(std::path::Path::parent)
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .unwrap_or(std::path::Path::new("."))
+    }
 
-        if self.source_maps {
-            println!("🗺️ Source maps enabled");
+    /// Load the build cache, keyed by the contents of the files the
+    /// configuration was loaded from and the content sources
+    fn load_cache(
+        &self,
+        config: &TailwindConfig,
+        content: &ContentConfig,
+    ) -> Result<BuildCache<Vec<String>>> {
+        let mut inputs = Vec::new();
+        if let Some(path) = &self.config_path {
+            inputs.extend(std::fs::read(path)?);
+            // The design tokens are merged into the theme the rules use
+            if let Some(tokens) = config.design_tokens_path(path.parent()) {
+                inputs.extend(std::fs::read(tokens)?);
+            }
+        }
+        inputs.extend(serde_json::to_vec(content)?);
+        let key = CacheKey::new(
+            concat!("tailwind-rs-core ", env!("CARGO_PKG_VERSION")),
+            content_hash(&inputs),
+        );
+        if !self.cache {
+            return Ok(BuildCache::new(key));
         }
 
-        Ok(())
+        let dir = self
+            .cache_dir
+            .clone()
+            .unwrap_or_else(|| BuildCache::<Vec<String>>::default_dir(self.root()));
+        Ok(BuildCache::load(dir.join("build-cache.json"), key))
     }
 
     /// Scan a single file for Tailwind classes
+    ///
+    /// Every token of the file that could be a class is checked against the
+    /// generator, so classes are found outside `class` attributes as well.
+    /// Files that haven't changed since the last build aren't rescanned.
     fn scan_file_for_classes(
         &self,
         path: &std::path::Path,
-        generator: &mut CssGenerator,
+        sources: &ContentSources,
+        resolver: &mut CandidateResolver,
+        cache: &mut BuildCache<Vec<String>>,
+        classes: &mut std::collections::BTreeSet<String>,
     ) -> Result<()> {
-        let content = std::fs::read_to_string(path)?;
-
-        // Simple regex to find class attributes
-        let class_pattern = regex::Regex::new(r#"class\s*=\s*["']([^"']+)["']"#)?;
-
-        for cap in class_pattern.captures_iter(&content) {
-            if let Some(class_attr) = cap.get(1) {
-                let classes = class_attr.as_str();
-                for class in classes.split_whitespace() {
-                    if !class.is_empty() {
-                        let _ = generator.add_class(class);
-                    }
-                }
-            }
-        }
+        let found = cache.get_or_extract(path, |content| {
+            let content = sources.transform(path, content);
+            resolver.resolve(extract_candidates(&content))
+        })?;
+        classes.extend(found.iter().cloned());
 
         Ok(())
     }
 
-    /// Scan a directory recursively for Tailwind classes
-    fn scan_directory_for_classes(
+    /// Map each rule of the stylesheet to the first place its class occurs
+    /// in the scanned files
+    ///
+    /// Classes only found in crate manifests, or written in a variant group,
+    /// have no single place and stay unmapped.
+    fn source_map(
         &self,
-        dir: &std::path::Path,
-        generator: &mut CssGenerator,
-    ) -> Result<()> {
-        for entry in std::fs::read_dir(dir)? {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.is_file() {
-                if let Some(ext) = path.extension() {
-                    if ext == "rs"
-                        || ext == "html"
-                        || ext == "js"
-                        || ext == "ts"
-                        || ext == "jsx"
-                        || ext == "tsx"
-                    {
-                        self.scan_file_for_classes(&path, generator)?;
-                    }
+        css: &str,
+        output_path: &std::path::Path,
+        output_dir: &std::path::Path,
+        generator: &CssGenerator,
+        scanned: &[std::path::PathBuf],
+    ) -> Result<SourceMap> {
+        let selectors: std::collections::HashMap<&str, &str> = generator
+            .rules()
+            .iter()
+            .map(|(class, rule)| (rule.selector.as_str(), class.as_str()))
+            .collect();
+
+        let mut locations = std::collections::HashMap::new();
+        for file in scanned {
+            if locations.len() == selectors.len() {
+                break;
+            }
+            let content = std::fs::read_to_string(file)?;
+            let lines = source_map::LineIndex::new(&content);
+            for candidate in extract_candidates(&content) {
+                if generator.rules().contains_key(candidate.value) {
+                    locations
+                        .entry(candidate.value.to_string())
+                        .or_insert_with(|| {
+                            (
+                                source_map::source_path(file, output_dir),
+                                lines.position(candidate.start),
+                            )
+                        });
                 }
-            } else if path.is_dir() {
-                self.scan_directory_for_classes(&path, generator)?;
             }
         }
 
-        Ok(())
+        let file = output_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut map = SourceMap::new(file);
+        for (position, selector) in source_map::rule_selectors(css) {
+            let location = selectors
+                .get(selector)
+                .and_then(|class| locations.get(*class));
+            if let Some((source, original)) = location {
+                map.add_mapping(position, source, *original);
+            }
+        }
+        Ok(map)
     }
 }
 
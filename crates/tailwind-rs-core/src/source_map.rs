@@ -0,0 +1,360 @@
+//! Source maps for generated stylesheets
+//!
+//! A [`SourceMap`] links each rule of a generated stylesheet to the place its
+//! class was found in the sources, so browser devtools can jump from a rule
+//! to the Rust file and line that uses it. Maps are written in the
+//! [Source Map v3](https://sourcemaps.info/spec.html) format.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Component, Path};
+
+/// Characters of the base64 VLQ encoding
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// A position in a file, with zero-based line and column
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    /// Zero-based line
+    pub line: usize,
+    /// Zero-based column, in bytes
+    pub column: usize,
+}
+
+impl Position {
+    /// Create a position
+    pub fn new(line: usize, column: usize) -> Self {
+        Self { line, column }
+    }
+
+    /// The position of a byte offset in a text
+    ///
+    /// Scans the text up to the offset; use a [`LineIndex`] to look up many
+    /// offsets of the same text.
+    pub fn of_offset(text: &str, offset: usize) -> Self {
+        LineIndex::new(&text[..offset.min(text.len())]).position(offset)
+    }
+}
+
+/// The start of every line of a text, to turn byte offsets into positions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    /// Byte offset of each line's first character
+    starts: Vec<usize>,
+    len: usize,
+}
+
+impl LineIndex {
+    /// Index the lines of a text
+    pub fn new(text: &str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+        Self {
+            starts,
+            len: text.len(),
+        }
+    }
+
+    /// The position of a byte offset, clamped to the end of the text
+    pub fn position(&self, offset: usize) -> Position {
+        let offset = offset.min(self.len);
+        let line = self.starts.partition_point(|&start| start <= offset) - 1;
+        Position::new(line, offset - self.starts[line])
+    }
+}
+
+/// A generated position and the source position it was produced from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Mapping {
+    generated: Position,
+    source: usize,
+    original: Position,
+}
+
+/// A version 3 source map
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SourceMap {
+    /// Name of the generated file
+    file: String,
+    /// Source paths, as written to the map
+    sources: Vec<String>,
+    /// Index of each source path
+    source_indices: HashMap<String, usize>,
+    mappings: Vec<Mapping>,
+}
+
+/// The JSON form of a source map
+#[derive(Serialize)]
+struct SourceMapJson<'a> {
+    version: u8,
+    file: &'a str,
+    sources: &'a [String],
+    names: [&'a str; 0],
+    mappings: String,
+}
+
+impl SourceMap {
+    /// Create an empty source map for a generated file
+    pub fn new(file: impl Into<String>) -> Self {
+        Self {
+            file: file.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Map a generated position to a position in a source file
+    pub fn add_mapping(&mut self, generated: Position, source: &str, original: Position) {
+        let source = match self.source_indices.get(source) {
+            Some(index) => *index,
+            None => {
+                self.sources.push(source.to_string());
+                self.source_indices
+                    .insert(source.to_string(), self.sources.len() - 1);
+                self.sources.len() - 1
+            }
+        };
+        self.mappings.push(Mapping {
+            generated,
+            source,
+            original,
+        });
+    }
+
+    /// Name of the generated file
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// Source paths, in the order they were first mapped to
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+
+    /// Number of mappings
+    pub fn len(&self) -> usize {
+        self.mappings.len()
+    }
+
+    /// Whether nothing is mapped
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty()
+    }
+
+    /// The source file and position a generated position was mapped to
+    pub fn original_position(&self, generated: Position) -> Option<(&str, Position)> {
+        self.mappings
+            .iter()
+            .find(|mapping| mapping.generated == generated)
+            .map(|mapping| (self.sources[mapping.source].as_str(), mapping.original))
+    }
+
+    /// The `mappings` field: base64 VLQ segments, with lines separated by `;`
+    pub fn encoded_mappings(&self) -> String {
+        let mut mappings = self.mappings.clone();
+        mappings.sort_by_key(|mapping| mapping.generated);
+
+        let mut encoded = String::new();
+        let mut line = 0;
+        let mut previous_column = 0;
+        let mut previous_source = 0;
+        let mut previous_original = Position::default();
+        for (index, mapping) in mappings.iter().enumerate() {
+            if mapping.generated.line > line {
+                for _ in line..mapping.generated.line {
+                    encoded.push(';');
+                }
+                line = mapping.generated.line;
+                previous_column = 0;
+            } else if index > 0 {
+                encoded.push(',');
+            }
+
+            encode_vlq(&mut encoded, mapping.generated.column, previous_column);
+            encode_vlq(&mut encoded, mapping.source, previous_source);
+            encode_vlq(&mut encoded, mapping.original.line, previous_original.line);
+            encode_vlq(
+                &mut encoded,
+                mapping.original.column,
+                previous_original.column,
+            );
+            previous_column = mapping.generated.column;
+            previous_source = mapping.source;
+            previous_original = mapping.original;
+        }
+        encoded
+    }
+
+    /// Serialize the map to JSON
+    pub fn to_json(&self) -> String {
+        let json = SourceMapJson {
+            version: 3,
+            file: &self.file,
+            sources: &self.sources,
+            names: [],
+            mappings: self.encoded_mappings(),
+        };
+        serde_json::to_string(&json).unwrap_or_default()
+    }
+}
+
+/// The selector of every rule in a stylesheet with the position it starts at
+///
+/// At-rules such as `@media` are skipped; the rules nested in them are
+/// included. Works on pretty and minified output alike.
+pub(crate) fn rule_selectors(css: &str) -> Vec<(Position, &str)> {
+    let mut selectors = Vec::new();
+    let mut start = 0;
+    // Line and column are tracked as the bytes are walked, so each rule
+    // doesn't rescan the stylesheet from the top
+    let mut line = 0;
+    let mut line_start = 0;
+    let mut selector_start = None;
+    for (index, byte) in css.bytes().enumerate() {
+        match byte {
+            b'{' => {
+                let selector = css[start..index].trim();
+                if let Some(position) = selector_start {
+                    if !selector.is_empty() && !selector.starts_with('@') {
+                        selectors.push((position, selector));
+                    }
+                }
+                start = index + 1;
+                selector_start = None;
+            }
+            b'}' | b';' => {
+                start = index + 1;
+                selector_start = None;
+            }
+            b'\n' => {
+                line += 1;
+                line_start = index + 1;
+            }
+            byte if byte.is_ascii_whitespace() => {}
+            _ => {
+                selector_start.get_or_insert(Position::new(line, index - line_start));
+            }
+        }
+    }
+    selectors
+}
+
+/// A source path as written to a map in `map_dir`: relative to it when both
+/// resolve, with `/` separators
+pub(crate) fn source_path(source: &Path, map_dir: &Path) -> String {
+    let resolved = (source.canonicalize(), map_dir.canonicalize());
+    let path = match resolved {
+        (Ok(source), Ok(map_dir)) => {
+            let source: Vec<Component> = source.components().collect();
+            let map_dir: Vec<Component> = map_dir.components().collect();
+            let common = source
+                .iter()
+                .zip(&map_dir)
+                .take_while(|(a, b)| a == b)
+                .count();
+            let mut relative = std::path::PathBuf::new();
+            for _ in common..map_dir.len() {
+                relative.push("..");
+            }
+            relative.extend(&source[common..]);
+            relative
+        }
+        _ => source.to_path_buf(),
+    };
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// Append the difference between two values as a base64 VLQ
+fn encode_vlq(out: &mut String, value: usize, previous: usize) {
+    let delta = value as i64 - previous as i64;
+    let mut vlq = if delta < 0 {
+        ((-delta) << 1) | 1
+    } else {
+        delta << 1
+    };
+    loop {
+        let mut digit = (vlq & 0b11111) as usize;
+        vlq >>= 5;
+        if vlq > 0 {
+            digit |= 0b100000;
+        }
+        out.push(BASE64[digit] as char);
+        if vlq == 0 {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_vlq() {
+        let encode = |value: i64| {
+            let mut out = String::new();
+            if value < 0 {
+                encode_vlq(&mut out, 0, (-value) as usize);
+            } else {
+                encode_vlq(&mut out, value as usize, 0);
+            }
+            out
+        };
+        assert_eq!(encode(0), "A");
+        assert_eq!(encode(1), "C");
+        assert_eq!(encode(-1), "D");
+        assert_eq!(encode(15), "e");
+        assert_eq!(encode(16), "gB");
+        assert_eq!(encode(1000), "w+B");
+    }
+
+    #[test]
+    fn test_source_map_json() {
+        let mut map = SourceMap::new("styles.css");
+        map.add_mapping(Position::new(0, 0), "src/app.rs", Position::new(4, 8));
+        map.add_mapping(Position::new(3, 2), "src/card.rs", Position::new(1, 0));
+        map.add_mapping(Position::new(3, 20), "src/app.rs", Position::new(6, 8));
+
+        assert_eq!(map.sources(), ["src/app.rs", "src/card.rs"]);
+        assert_eq!(map.encoded_mappings(), "AAIQ;;;ECHR,kBDKQ");
+        assert_eq!(
+            map.original_position(Position::new(3, 2)),
+            Some(("src/card.rs", Position::new(1, 0)))
+        );
+        assert_eq!(
+            map.to_json(),
+            r#"{"version":3,"file":"styles.css","sources":["src/app.rs","src/card.rs"],"names":[],"mappings":"AAIQ;;;ECHR,kBDKQ"}"#
+        );
+    }
+
+    #[test]
+    fn test_rule_selectors() {
+        let css = ".p-4 {\n  padding: 1rem;\n}\n@media (min-width: 768px) {\n  .md\\:p-4 {\n    padding: 1rem;\n  }\n}\n";
+        let selectors: Vec<_> = rule_selectors(css)
+            .into_iter()
+            .map(|(position, selector)| (position.line, position.column, selector))
+            .collect();
+        assert_eq!(selectors, vec![(0, 0, ".p-4"), (4, 2, ".md\\:p-4")]);
+
+        let minified = ".p-4{padding:1rem}@media (min-width: 768px){.md\\:p-4{padding:1rem}}";
+        let selectors: Vec<_> = rule_selectors(minified)
+            .into_iter()
+            .map(|(position, selector)| (position.column, selector))
+            .collect();
+        assert_eq!(selectors, vec![(0, ".p-4"), (44, ".md\\:p-4")]);
+    }
+
+    #[test]
+    fn test_position_of_offset() {
+        let text = "fn app() {\n    \"p-4\"\n}";
+        assert_eq!(Position::of_offset(text, 0), Position::new(0, 0));
+        assert_eq!(Position::of_offset(text, 16), Position::new(1, 5));
+
+        let lines = LineIndex::new(text);
+        assert_eq!(lines.position(0), Position::new(0, 0));
+        assert_eq!(lines.position(11), Position::new(1, 0));
+        assert_eq!(lines.position(16), Position::new(1, 5));
+        assert_eq!(lines.position(21), Position::new(2, 0));
+        assert_eq!(lines.position(100), Position::new(2, 1));
+    }
+}
@@ -246,15 +246,23 @@ mod sync_builder_tests {
     #[test]
     fn test_tailwind_builder_sync() {
         // Test: TailwindBuilder should work synchronously
+        let temp = tempfile::tempdir().unwrap();
+        let src = temp.path().join("src");
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(src.join("app.rs"), r#"let _ = "p-4 md:p-8";"#).unwrap();
+        let output = temp.path().join("output.css");
+
         let builder = TailwindBuilder::new()
-            .scan_source(std::path::Path::new("src"))
-            .output_css(std::path::Path::new("output.css"))
+            .scan_source(&src)
+            .output_css(&output)
             .enable_minification()
-            .enable_source_maps();
+            .enable_source_maps()
+            .disable_cache();
 
         // Build should complete synchronously
         let result = builder.build();
         assert!(result.is_ok());
+        assert!(output.exists());
     }
 
     #[test]
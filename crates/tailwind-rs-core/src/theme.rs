@@ -1,6 +1,7 @@
 //! Theme system for tailwind-rs
 
 use crate::error::{Result, TailwindError};
+use crate::theme_model::TailwindTheme;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::str::FromStr;
@@ -460,6 +461,15 @@ pub struct Theme {
     pub border_radius: HashMap<String, BorderRadius>,
     pub box_shadows: HashMap<String, BoxShadow>,
     pub custom: HashMap<String, ThemeValue>,
+    /// Design token file (W3C DTCG JSON) imported into the theme
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<String>,
+    /// Namespaces replacing or extending Tailwind's defaults
+    #[serde(
+        default = "TailwindTheme::empty",
+        skip_serializing_if = "TailwindTheme::is_empty"
+    )]
+    pub namespaces: TailwindTheme,
 }
 
 impl Theme {
@@ -472,6 +482,8 @@ impl Theme {
             border_radius: HashMap::new(),
             box_shadows: HashMap::new(),
             custom: HashMap::new(),
+            tokens: None,
+            namespaces: TailwindTheme::empty(),
         }
     }
 
@@ -480,6 +492,21 @@ impl Theme {
         self.colors.insert(name.into(), color);
     }
 
+    /// Add a 50–950 color palette generated from a seed color
+    ///
+    /// Adds `<name>-50` through `<name>-950`, with the seed itself as the
+    /// shade closest in lightness (see [`crate::color_space::generate_palette`]).
+    pub fn add_palette(&mut self, name: &str, seed: &str) -> Result<()> {
+        let seed: crate::color_space::Oklch = seed.parse()?;
+        for (shade, color) in crate::color_space::generate_palette(seed) {
+            self.add_color(
+                format!("{}-{}", name, shade),
+                Color::hex(color.to_css(crate::color_space::ColorFormat::Hex)),
+            );
+        }
+        Ok(())
+    }
+
     /// Add spacing to the theme
     pub fn add_spacing(&mut self, name: impl Into<String>, spacing: Spacing) {
         self.spacing.insert(name.into(), spacing);
@@ -613,7 +640,51 @@ impl Theme {
             }
         }
 
-        Ok(())
+        self.namespaces.validate()
+    }
+
+    /// Resolve the theme into the canonical [`TailwindTheme`]
+    ///
+    /// `namespaces` is layered over Tailwind's defaults first; the typed
+    /// entries and custom values then extend the result, `colors` as
+    /// `color-*`, `border_radius` as `radius-*` and `box_shadows` as
+    /// `shadow-*`.
+    pub fn to_tailwind_theme(&self) -> TailwindTheme {
+        let mut typed = TailwindTheme::empty();
+        for (name, color) in &self.colors {
+            typed.color.insert(name.clone(), color.to_css());
+        }
+        for (name, spacing) in &self.spacing {
+            typed.spacing.insert(name.clone(), spacing.to_css());
+        }
+        for (name, radius) in &self.border_radius {
+            typed.radius.insert(name.clone(), radius.to_css());
+        }
+        for (name, shadow) in &self.box_shadows {
+            typed.shadow.insert(name.clone(), shadow.to_css());
+        }
+        for (name, value) in &self.custom {
+            let value = match value {
+                ThemeValue::Color(color) => color.to_css(),
+                ThemeValue::Spacing(spacing) => spacing.to_css(),
+                ThemeValue::BorderRadius(radius) => radius.to_css(),
+                ThemeValue::BoxShadow(shadow) => shadow.to_css(),
+                ThemeValue::String(value) => value.clone(),
+                ThemeValue::Number(number) => number.to_string(),
+                ThemeValue::Boolean(_) => continue,
+            };
+            typed.set_variable(name, &value);
+        }
+
+        let mut theme = self.namespaces.resolve();
+        theme.extend(&typed);
+        theme
+    }
+}
+
+impl From<&Theme> for TailwindTheme {
+    fn from(theme: &Theme) -> Self {
+        theme.to_tailwind_theme()
     }
 }
 
@@ -626,10 +697,21 @@ pub struct ThemeToml {
     pub border_radius: Option<HashMap<String, String>>,
     pub box_shadows: Option<HashMap<String, String>>,
     pub custom: Option<HashMap<String, toml::Value>>,
+    pub tokens: Option<String>,
+    pub palettes: Option<HashMap<String, String>>,
+    /// Canonical namespaces (`[theme.font]`, `[theme.extend.color]`); `spacing`
+    /// is read through the field above
+    #[serde(flatten)]
+    pub namespaces: TailwindTheme,
 }
 
 impl From<Theme> for ThemeToml {
     fn from(theme: Theme) -> Self {
+        let mut namespaces = theme.namespaces;
+        let spacing = std::mem::take(&mut namespaces.spacing)
+            .into_iter()
+            .chain(theme.spacing.into_iter().map(|(k, v)| (k, v.to_css())))
+            .collect();
         Self {
             name: theme.name,
             colors: Some(
@@ -639,13 +721,7 @@ impl From<Theme> for ThemeToml {
                     .map(|(k, v)| (k, v.to_css()))
                     .collect(),
             ),
-            spacing: Some(
-                theme
-                    .spacing
-                    .into_iter()
-                    .map(|(k, v)| (k, v.to_css()))
-                    .collect(),
-            ),
+            spacing: Some(spacing),
             border_radius: Some(
                 theme
                     .border_radius
@@ -678,13 +754,29 @@ impl From<Theme> for ThemeToml {
                     })
                     .collect(),
             ),
+            tokens: theme.tokens,
+            palettes: None,
+            namespaces,
         }
     }
 }
 
-impl From<ThemeToml> for Theme {
-    fn from(toml_theme: ThemeToml) -> Self {
+impl TryFrom<ThemeToml> for Theme {
+    type Error = TailwindError;
+
+    /// Fails when a palette seed isn't a color
+    fn try_from(toml_theme: ThemeToml) -> Result<Self> {
         let mut theme = Theme::new(toml_theme.name);
+        theme.tokens = toml_theme.tokens;
+        theme.namespaces = toml_theme.namespaces;
+
+        if let Some(palettes) = toml_theme.palettes {
+            for (name, seed) in palettes {
+                theme.add_palette(&name, &seed).map_err(|e| {
+                    TailwindError::theme(format!("Invalid seed for palette '{}': {}", name, e))
+                })?;
+            }
+        }
 
         if let Some(colors) = toml_theme.colors {
             for (name, color_str) in colors {
@@ -696,8 +788,11 @@ impl From<ThemeToml> for Theme {
 
         if let Some(spacing) = toml_theme.spacing {
             for (name, spacing_str) in spacing {
-                if let Ok(spacing_value) = Spacing::from_str(&spacing_str) {
-                    theme.add_spacing(name, spacing_value);
+                match Spacing::from_str(&spacing_str) {
+                    Ok(spacing_value) => theme.add_spacing(name, spacing_value),
+                    // Keep values the typed scale can't hold (`calc()`, `var()`)
+                    Err(_) => theme
+                        .add_custom(format!("spacing-{}", name), ThemeValue::String(spacing_str)),
                 }
             }
         }
@@ -718,11 +813,15 @@ impl From<ThemeToml> for Theme {
             }
         }
 
-        theme
+        Ok(theme)
     }
 }
 
 /// Create a default theme with common values
+///
+/// Radii come from Tailwind's default theme ([`TailwindTheme::default`]);
+/// shadows aren't copied since [`BoxShadow`] holds a single layer, so they
+/// resolve to the defaults through [`Theme::to_tailwind_theme`].
 pub fn create_default_theme() -> Theme {
     let mut theme = Theme::new("default");
 
@@ -747,26 +846,13 @@ pub fn create_default_theme() -> Theme {
     theme.add_spacing("2xl", Spacing::rem(3.0));
 
     // Add default border radius
-    theme.add_border_radius("sm", BorderRadius::rem(0.125));
-    theme.add_border_radius("md", BorderRadius::rem(0.375));
-    theme.add_border_radius("lg", BorderRadius::rem(0.5));
-    theme.add_border_radius("xl", BorderRadius::rem(0.75));
+    for (name, value) in TailwindTheme::default().radius {
+        if let Ok(radius) = BorderRadius::from_str(&value) {
+            theme.add_border_radius(name, radius);
+        }
+    }
     theme.add_border_radius("full", BorderRadius::percent(50.0));
 
-    // Add default box shadows
-    theme.add_box_shadow(
-        "sm",
-        BoxShadow::new(0.0, 1.0, 2.0, 0.0, Color::hex("#000000"), false),
-    );
-    theme.add_box_shadow(
-        "md",
-        BoxShadow::new(0.0, 4.0, 6.0, -1.0, Color::hex("#000000"), false),
-    );
-    theme.add_box_shadow(
-        "lg",
-        BoxShadow::new(0.0, 10.0, 15.0, -3.0, Color::hex("#000000"), false),
-    );
-
     theme
 }
 
@@ -871,5 +957,13 @@ mod tests {
         assert!(theme.get_border_radius("sm").is_ok());
         assert!(theme.get_border_radius("md").is_ok());
         assert!(theme.get_border_radius("lg").is_ok());
+
+        // Resolves to Tailwind's default radii and shadows
+        let resolved = theme.to_tailwind_theme();
+        assert_eq!(resolved.get("radius", "sm"), Some("0.25rem"));
+        assert_eq!(
+            resolved.get("shadow", "sm"),
+            TailwindTheme::default().get("shadow", "sm")
+        );
     }
 }
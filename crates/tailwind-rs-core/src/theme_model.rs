@@ -0,0 +1,759 @@
+//! Canonical theme model for tailwind-rs
+//!
+//! [`TailwindTheme`] holds every theme namespace Tailwind knows about (colors,
+//! fonts, font sizes and line heights, letter spacing, breakpoints,
+//! containers, spacing, radii, shadows, z-index, opacity, easing, blur,
+//! aspect ratios, animations and their keyframes) as plain name/value maps.
+//! Each entry corresponds to a `--<namespace>-<name>` theme variable, so the
+//! model converts losslessly to and from `@theme` variables, TOML and JSON.
+//! [`Theme`](crate::theme::Theme) and [`theme_new::Theme`](crate::theme_new::Theme)
+//! are adapters onto it.
+//!
+//! A theme definition replaces the namespaces it sets and merges the ones
+//! under `extend` into Tailwind's defaults:
+//!
+//! ```toml
+//! [theme.font]
+//! display = "Inter, sans-serif"
+//!
+//! [theme.extend.color]
+//! brand-500 = "#0f766e"
+//! ```
+
+use crate::color_space::Oklch;
+use crate::css_generator::theme_variables::{DEFAULT_PALETTE, PALETTE_SHADES};
+use crate::error::{Result, TailwindError};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Variable prefixes of the theme namespaces
+///
+/// `font-weight` comes before `font` so that the longer prefix wins.
+pub const NAMESPACES: [&str; 20] = [
+    "color",
+    "font-weight",
+    "font",
+    "text",
+    "tracking",
+    "leading",
+    "breakpoint",
+    "container",
+    "spacing",
+    "radius",
+    "shadow",
+    "inset-shadow",
+    "drop-shadow",
+    "blur",
+    "perspective",
+    "aspect",
+    "ease",
+    "animate",
+    "z-index",
+    "opacity",
+];
+
+/// Key of a namespace's bare variable (`--spacing`)
+const DEFAULT_KEY: &str = "DEFAULT";
+
+/// Suffix of the line height paired with a font size (`--text-xs--line-height`)
+const LINE_HEIGHT_SUFFIX: &str = "--line-height";
+
+/// Tailwind's default theme, outside the color palette
+const DEFAULT_TOKENS: &[(&str, &str)] = &[
+    ("color-black", "#000000"),
+    ("color-white", "#ffffff"),
+    ("spacing", "0.25rem"),
+    ("font-sans", "ui-sans-serif, system-ui, sans-serif, \"Apple Color Emoji\", \"Segoe UI Emoji\", \"Segoe UI Symbol\", \"Noto Color Emoji\""),
+    ("font-serif", "ui-serif, Georgia, Cambria, \"Times New Roman\", Times, serif"),
+    ("font-mono", "ui-monospace, SFMono-Regular, Menlo, Monaco, Consolas, \"Liberation Mono\", \"Courier New\", monospace"),
+    ("text-xs", "0.75rem"),
+    ("text-xs--line-height", "calc(1 / 0.75)"),
+    ("text-sm", "0.875rem"),
+    ("text-sm--line-height", "calc(1.25 / 0.875)"),
+    ("text-base", "1rem"),
+    ("text-base--line-height", "calc(1.5 / 1)"),
+    ("text-lg", "1.125rem"),
+    ("text-lg--line-height", "calc(1.75 / 1.125)"),
+    ("text-xl", "1.25rem"),
+    ("text-xl--line-height", "calc(1.75 / 1.25)"),
+    ("text-2xl", "1.5rem"),
+    ("text-2xl--line-height", "calc(2 / 1.5)"),
+    ("text-3xl", "1.875rem"),
+    ("text-3xl--line-height", "calc(2.25 / 1.875)"),
+    ("text-4xl", "2.25rem"),
+    ("text-4xl--line-height", "calc(2.5 / 2.25)"),
+    ("text-5xl", "3rem"),
+    ("text-5xl--line-height", "1"),
+    ("text-6xl", "3.75rem"),
+    ("text-6xl--line-height", "1"),
+    ("text-7xl", "4.5rem"),
+    ("text-7xl--line-height", "1"),
+    ("text-8xl", "6rem"),
+    ("text-8xl--line-height", "1"),
+    ("text-9xl", "8rem"),
+    ("text-9xl--line-height", "1"),
+    ("tracking-tighter", "-0.05em"),
+    ("tracking-tight", "-0.025em"),
+    ("tracking-normal", "0em"),
+    ("tracking-wide", "0.025em"),
+    ("tracking-wider", "0.05em"),
+    ("tracking-widest", "0.1em"),
+    ("leading-tight", "1.25"),
+    ("leading-snug", "1.375"),
+    ("leading-normal", "1.5"),
+    ("leading-relaxed", "1.625"),
+    ("leading-loose", "2"),
+    ("radius-xs", "0.125rem"),
+    ("radius-sm", "0.25rem"),
+    ("radius-md", "0.375rem"),
+    ("radius-lg", "0.5rem"),
+    ("radius-xl", "0.75rem"),
+    ("radius-2xl", "1rem"),
+    ("radius-3xl", "1.5rem"),
+    ("radius-4xl", "2rem"),
+    ("shadow-2xs", "0 1px rgb(0 0 0 / 0.05)"),
+    ("shadow-xs", "0 1px 2px 0 rgb(0 0 0 / 0.05)"),
+    ("shadow-sm", "0 1px 3px 0 rgb(0 0 0 / 0.1), 0 1px 2px -1px rgb(0 0 0 / 0.1)"),
+    ("shadow-md", "0 4px 6px -1px rgb(0 0 0 / 0.1), 0 2px 4px -2px rgb(0 0 0 / 0.1)"),
+    ("shadow-lg", "0 10px 15px -3px rgb(0 0 0 / 0.1), 0 4px 6px -4px rgb(0 0 0 / 0.1)"),
+    ("shadow-xl", "0 20px 25px -5px rgb(0 0 0 / 0.1), 0 8px 10px -6px rgb(0 0 0 / 0.1)"),
+    ("shadow-2xl", "0 25px 50px -12px rgb(0 0 0 / 0.25)"),
+    ("blur-xs", "4px"),
+    ("blur-sm", "8px"),
+    ("blur-md", "12px"),
+    ("blur-lg", "16px"),
+    ("blur-xl", "24px"),
+    ("blur-2xl", "40px"),
+    ("blur-3xl", "64px"),
+    ("container-3xs", "16rem"),
+    ("container-2xs", "18rem"),
+    ("container-xs", "20rem"),
+    ("container-sm", "24rem"),
+    ("container-md", "28rem"),
+    ("container-lg", "32rem"),
+    ("container-xl", "36rem"),
+    ("container-2xl", "42rem"),
+    ("container-3xl", "48rem"),
+    ("container-4xl", "56rem"),
+    ("container-5xl", "64rem"),
+    ("container-6xl", "72rem"),
+    ("container-7xl", "80rem"),
+    ("breakpoint-sm", "40rem"),
+    ("breakpoint-md", "48rem"),
+    ("breakpoint-lg", "64rem"),
+    ("breakpoint-xl", "80rem"),
+    ("breakpoint-2xl", "96rem"),
+    ("font-weight-thin", "100"),
+    ("font-weight-extralight", "200"),
+    ("font-weight-light", "300"),
+    ("font-weight-normal", "400"),
+    ("font-weight-medium", "500"),
+    ("font-weight-semibold", "600"),
+    ("font-weight-bold", "700"),
+    ("font-weight-extrabold", "800"),
+    ("font-weight-black", "900"),
+    ("inset-shadow-2xs", "inset 0 1px rgb(0 0 0 / 0.05)"),
+    ("inset-shadow-xs", "inset 0 1px 1px rgb(0 0 0 / 0.05)"),
+    ("inset-shadow-sm", "inset 0 2px 4px rgb(0 0 0 / 0.05)"),
+    ("drop-shadow-xs", "0 1px 1px rgb(0 0 0 / 0.05)"),
+    ("drop-shadow-sm", "0 1px 2px rgb(0 0 0 / 0.15)"),
+    ("drop-shadow-md", "0 3px 3px rgb(0 0 0 / 0.12)"),
+    ("drop-shadow-lg", "0 4px 4px rgb(0 0 0 / 0.15)"),
+    ("drop-shadow-xl", "0 9px 7px rgb(0 0 0 / 0.1)"),
+    ("drop-shadow-2xl", "0 25px 25px rgb(0 0 0 / 0.15)"),
+    ("perspective-dramatic", "100px"),
+    ("perspective-near", "300px"),
+    ("perspective-normal", "500px"),
+    ("perspective-midrange", "800px"),
+    ("perspective-distant", "1200px"),
+    ("aspect-video", "16 / 9"),
+    ("ease-in", "cubic-bezier(0.4, 0, 1, 1)"),
+    ("ease-out", "cubic-bezier(0, 0, 0.2, 1)"),
+    ("ease-in-out", "cubic-bezier(0.4, 0, 0.2, 1)"),
+    ("animate-spin", "spin 1s linear infinite"),
+    ("animate-ping", "ping 1s cubic-bezier(0, 0, 0.2, 1) infinite"),
+    ("animate-pulse", "pulse 2s cubic-bezier(0.4, 0, 0.6, 1) infinite"),
+    ("animate-bounce", "bounce 1s infinite"),
+    ("z-index-0", "0"),
+    ("z-index-10", "10"),
+    ("z-index-20", "20"),
+    ("z-index-30", "30"),
+    ("z-index-40", "40"),
+    ("z-index-50", "50"),
+    ("z-index-auto", "auto"),
+    ("opacity-0", "0"),
+    ("opacity-5", "0.05"),
+    ("opacity-10", "0.1"),
+    ("opacity-15", "0.15"),
+    ("opacity-20", "0.2"),
+    ("opacity-25", "0.25"),
+    ("opacity-30", "0.3"),
+    ("opacity-35", "0.35"),
+    ("opacity-40", "0.4"),
+    ("opacity-45", "0.45"),
+    ("opacity-50", "0.5"),
+    ("opacity-55", "0.55"),
+    ("opacity-60", "0.6"),
+    ("opacity-65", "0.65"),
+    ("opacity-70", "0.7"),
+    ("opacity-75", "0.75"),
+    ("opacity-80", "0.8"),
+    ("opacity-85", "0.85"),
+    ("opacity-90", "0.9"),
+    ("opacity-95", "0.95"),
+    ("opacity-100", "1"),
+];
+
+/// Keyframes of the default animations
+const DEFAULT_KEYFRAMES: &[(&str, &str)] = &[
+    ("spin", "to { transform: rotate(360deg); }"),
+    ("ping", "75%, 100% { transform: scale(2); opacity: 0; }"),
+    ("pulse", "50% { opacity: 0.5; }"),
+    (
+        "bounce",
+        "0%, 100% { transform: translateY(-25%); animation-timing-function: cubic-bezier(0.8, 0, 1, 1); } 50% { transform: none; animation-timing-function: cubic-bezier(0, 0, 0.2, 1); }",
+    ),
+];
+
+/// A font size and its paired line height
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FontSize<'a> {
+    pub size: &'a str,
+    pub line_height: Option<&'a str>,
+}
+
+/// The canonical theme: every Tailwind namespace, keyed by token name
+///
+/// Values are CSS strings. A namespace's bare variable (`--spacing`) is
+/// stored under `DEFAULT`, and font sizes keep their line height under
+/// `<name>--line-height`, matching the variable names. `Default` is
+/// Tailwind's default theme; [`TailwindTheme::empty`] starts from nothing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct TailwindTheme {
+    /// Colors (`--color-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub color: BTreeMap<String, String>,
+    /// Font weights (`--font-weight-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub font_weight: BTreeMap<String, String>,
+    /// Font families (`--font-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub font: BTreeMap<String, String>,
+    /// Font sizes and their line heights (`--text-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub text: BTreeMap<String, String>,
+    /// Letter spacing (`--tracking-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tracking: BTreeMap<String, String>,
+    /// Line heights (`--leading-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub leading: BTreeMap<String, String>,
+    /// Responsive breakpoints (`--breakpoint-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub breakpoint: BTreeMap<String, String>,
+    /// Container sizes (`--container-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub container: BTreeMap<String, String>,
+    /// Spacing unit and named steps (`--spacing`, `--spacing-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub spacing: BTreeMap<String, String>,
+    /// Border radii (`--radius-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub radius: BTreeMap<String, String>,
+    /// Box shadows (`--shadow-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub shadow: BTreeMap<String, String>,
+    /// Inset shadows (`--inset-shadow-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub inset_shadow: BTreeMap<String, String>,
+    /// Drop shadows (`--drop-shadow-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub drop_shadow: BTreeMap<String, String>,
+    /// Blur radii (`--blur-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub blur: BTreeMap<String, String>,
+    /// Perspective distances (`--perspective-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub perspective: BTreeMap<String, String>,
+    /// Aspect ratios (`--aspect-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub aspect: BTreeMap<String, String>,
+    /// Easing functions (`--ease-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub ease: BTreeMap<String, String>,
+    /// Animations (`--animate-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub animate: BTreeMap<String, String>,
+    /// Z-index layers (`--z-index-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub z_index: BTreeMap<String, String>,
+    /// Opacity steps (`--opacity-*`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub opacity: BTreeMap<String, String>,
+    /// `@keyframes` bodies, keyed by animation name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keyframes: BTreeMap<String, String>,
+    /// Variables outside the namespaces above, keyed by name without the leading `--`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, String>,
+    /// Entries merged into the namespaces instead of replacing them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extend: Option<Box<TailwindTheme>>,
+}
+
+impl Default for TailwindTheme {
+    /// Tailwind's default theme
+    fn default() -> Self {
+        let mut theme = Self::empty();
+        for (name, value) in DEFAULT_TOKENS {
+            theme.set_variable(name, value);
+        }
+        for (color, shades) in DEFAULT_PALETTE {
+            for (shade, value) in PALETTE_SHADES.iter().zip(shades) {
+                theme
+                    .color
+                    .insert(format!("{}-{}", color, shade), value.to_string());
+            }
+        }
+        for (name, body) in DEFAULT_KEYFRAMES {
+            theme.keyframes.insert(name.to_string(), body.to_string());
+        }
+        theme
+    }
+}
+
+impl TailwindTheme {
+    /// Create a theme without any entries
+    pub fn empty() -> Self {
+        Self {
+            color: BTreeMap::new(),
+            font_weight: BTreeMap::new(),
+            font: BTreeMap::new(),
+            text: BTreeMap::new(),
+            tracking: BTreeMap::new(),
+            leading: BTreeMap::new(),
+            breakpoint: BTreeMap::new(),
+            container: BTreeMap::new(),
+            spacing: BTreeMap::new(),
+            radius: BTreeMap::new(),
+            shadow: BTreeMap::new(),
+            inset_shadow: BTreeMap::new(),
+            drop_shadow: BTreeMap::new(),
+            blur: BTreeMap::new(),
+            perspective: BTreeMap::new(),
+            aspect: BTreeMap::new(),
+            ease: BTreeMap::new(),
+            animate: BTreeMap::new(),
+            z_index: BTreeMap::new(),
+            opacity: BTreeMap::new(),
+            keyframes: BTreeMap::new(),
+            variables: BTreeMap::new(),
+            extend: None,
+        }
+    }
+
+    /// Build a theme from `@theme` variables (names with or without the leading `--`)
+    pub fn from_variables<'a>(variables: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut theme = Self::empty();
+        for (name, value) in variables {
+            theme.set_variable(name, value);
+        }
+        theme
+    }
+
+    /// Check whether the theme has no entries
+    pub fn is_empty(&self) -> bool {
+        NAMESPACES
+            .iter()
+            .all(|namespace| self.namespace(namespace).is_none_or(BTreeMap::is_empty))
+            && self.keyframes.is_empty()
+            && self.variables.is_empty()
+            && self.extend.is_none()
+    }
+
+    /// The entries of a namespace, by variable prefix (`color`, `font-weight`)
+    pub fn namespace(&self, namespace: &str) -> Option<&BTreeMap<String, String>> {
+        Some(match namespace {
+            "color" => &self.color,
+            "font-weight" => &self.font_weight,
+            "font" => &self.font,
+            "text" => &self.text,
+            "tracking" => &self.tracking,
+            "leading" => &self.leading,
+            "breakpoint" => &self.breakpoint,
+            "container" => &self.container,
+            "spacing" => &self.spacing,
+            "radius" => &self.radius,
+            "shadow" => &self.shadow,
+            "inset-shadow" => &self.inset_shadow,
+            "drop-shadow" => &self.drop_shadow,
+            "blur" => &self.blur,
+            "perspective" => &self.perspective,
+            "aspect" => &self.aspect,
+            "ease" => &self.ease,
+            "animate" => &self.animate,
+            "z-index" => &self.z_index,
+            "opacity" => &self.opacity,
+            _ => return None,
+        })
+    }
+
+    /// Mutable access to the entries of a namespace
+    pub fn namespace_mut(&mut self, namespace: &str) -> Option<&mut BTreeMap<String, String>> {
+        Some(match namespace {
+            "color" => &mut self.color,
+            "font-weight" => &mut self.font_weight,
+            "font" => &mut self.font,
+            "text" => &mut self.text,
+            "tracking" => &mut self.tracking,
+            "leading" => &mut self.leading,
+            "breakpoint" => &mut self.breakpoint,
+            "container" => &mut self.container,
+            "spacing" => &mut self.spacing,
+            "radius" => &mut self.radius,
+            "shadow" => &mut self.shadow,
+            "inset-shadow" => &mut self.inset_shadow,
+            "drop-shadow" => &mut self.drop_shadow,
+            "blur" => &mut self.blur,
+            "perspective" => &mut self.perspective,
+            "aspect" => &mut self.aspect,
+            "ease" => &mut self.ease,
+            "animate" => &mut self.animate,
+            "z-index" => &mut self.z_index,
+            "opacity" => &mut self.opacity,
+            _ => return None,
+        })
+    }
+
+    /// Set an entry from its variable name (`--color-brand`, `spacing`)
+    ///
+    /// Names outside the known namespaces are kept in `variables`.
+    pub fn set_variable(&mut self, name: &str, value: &str) {
+        let name = name.trim_start_matches("--");
+        for namespace in NAMESPACES {
+            let key = if name == namespace {
+                DEFAULT_KEY
+            } else {
+                match name
+                    .strip_prefix(namespace)
+                    .and_then(|rest| rest.strip_prefix('-'))
+                {
+                    Some(key) if !key.is_empty() => key,
+                    _ => continue,
+                }
+            };
+            if let Some(entries) = self.namespace_mut(namespace) {
+                entries.insert(key.to_string(), value.to_string());
+            }
+            return;
+        }
+        self.variables.insert(name.to_string(), value.to_string());
+    }
+
+    /// The theme as `@theme` variables, keyed by name without the leading `--`
+    ///
+    /// Keyframes and `extend` entries are not variables; resolve the theme
+    /// first to fold `extend` in.
+    pub fn to_variables(&self) -> BTreeMap<String, String> {
+        let mut variables = self.variables.clone();
+        for namespace in NAMESPACES {
+            for (key, value) in self.namespace(namespace).into_iter().flatten() {
+                let name = if key == DEFAULT_KEY {
+                    namespace.to_string()
+                } else {
+                    format!("{}-{}", namespace, key)
+                };
+                variables.insert(name, value.clone());
+            }
+        }
+        variables
+    }
+
+    /// Merge another theme's entries into this one, entry by entry
+    pub fn extend(&mut self, other: &TailwindTheme) {
+        for namespace in NAMESPACES {
+            if let (Some(entries), Some(additions)) =
+                (self.namespace_mut(namespace), other.namespace(namespace))
+            {
+                entries.extend(additions.iter().map(|(k, v)| (k.clone(), v.clone())));
+            }
+        }
+        self.keyframes.extend(other.keyframes.clone());
+        self.variables.extend(other.variables.clone());
+        if let Some(extend) = &other.extend {
+            self.extend(extend);
+        }
+    }
+
+    /// Layer a theme definition over this theme
+    ///
+    /// Every namespace the definition sets replaces ours, as do its
+    /// keyframes; its `extend` entries are merged afterwards.
+    pub fn merge(&mut self, definition: &TailwindTheme) {
+        for namespace in NAMESPACES {
+            if let (Some(entries), Some(replacement)) = (
+                self.namespace_mut(namespace),
+                definition.namespace(namespace),
+            ) {
+                if !replacement.is_empty() {
+                    *entries = replacement.clone();
+                }
+            }
+        }
+        if !definition.keyframes.is_empty() {
+            self.keyframes = definition.keyframes.clone();
+        }
+        self.variables.extend(definition.variables.clone());
+        if let Some(extend) = &definition.extend {
+            self.extend(extend);
+        }
+    }
+
+    /// Resolve a theme definition against Tailwind's defaults
+    pub fn resolve(&self) -> TailwindTheme {
+        let mut theme = Self::default();
+        theme.merge(self);
+        theme
+    }
+
+    /// Look up an entry by namespace and name
+    pub fn get(&self, namespace: &str, name: &str) -> Option<&str> {
+        self.namespace(namespace)?.get(name).map(String::as_str)
+    }
+
+    /// A theme color
+    pub fn color(&self, name: &str) -> Option<Oklch> {
+        self.color.get(name)?.parse().ok()
+    }
+
+    /// A font family stack
+    pub fn font_family(&self, name: &str) -> Option<&str> {
+        self.font.get(name).map(String::as_str)
+    }
+
+    /// A font size with its paired line height
+    pub fn font_size(&self, name: &str) -> Option<FontSize<'_>> {
+        Some(FontSize {
+            size: self.text.get(name)?,
+            line_height: self
+                .text
+                .get(&format!("{}{}", name, LINE_HEIGHT_SUFFIX))
+                .map(String::as_str),
+        })
+    }
+
+    /// A numeric font weight
+    pub fn font_weight(&self, name: &str) -> Option<u16> {
+        self.font_weight.get(name)?.trim().parse().ok()
+    }
+
+    /// A breakpoint's minimum width in pixels (`rem`/`em` count as 16px)
+    pub fn breakpoint_px(&self, name: &str) -> Option<f32> {
+        length_to_px(self.breakpoint.get(name)?)
+    }
+
+    /// The spacing unit that numeric spacing utilities multiply
+    pub fn spacing_unit(&self) -> Option<&str> {
+        self.spacing.get(DEFAULT_KEY).map(String::as_str)
+    }
+
+    /// A z-index layer (`None` for `auto`)
+    pub fn z_index(&self, name: &str) -> Option<i32> {
+        self.z_index.get(name)?.trim().parse().ok()
+    }
+
+    /// An opacity step between 0 and 1
+    pub fn opacity(&self, name: &str) -> Option<f32> {
+        let value = self.opacity.get(name)?.trim();
+        match value.strip_suffix('%') {
+            Some(percent) => percent.parse::<f32>().ok().map(|percent| percent / 100.0),
+            None => value.parse().ok(),
+        }
+    }
+
+    /// The keyframes body of an animation
+    pub fn keyframes(&self, name: &str) -> Option<&str> {
+        self.keyframes.get(name).map(String::as_str)
+    }
+
+    /// Validate the entries whose values have a fixed shape
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |namespace: &str, name: &str, value: &str| {
+            Err(TailwindError::theme(format!(
+                "Invalid theme value for --{}-{}: '{}'",
+                namespace, name, value
+            )))
+        };
+        for (name, value) in &self.color {
+            // Colors may reference other variables or keywords like `currentColor`
+            if value.starts_with('#') && value.parse::<Oklch>().is_err() {
+                return invalid("color", name, value);
+            }
+        }
+        for (name, value) in &self.breakpoint {
+            if length_to_px(value).is_none() {
+                return invalid("breakpoint", name, value);
+            }
+        }
+        for (name, value) in &self.z_index {
+            if value != "auto" && value.trim().parse::<i32>().is_err() {
+                return invalid("z-index", name, value);
+            }
+        }
+        for name in self.opacity.keys() {
+            if !self
+                .opacity(name)
+                .is_some_and(|opacity| (0.0..=1.0).contains(&opacity))
+            {
+                return invalid("opacity", name, &self.opacity[name]);
+            }
+        }
+        for (name, value) in &self.font_weight {
+            if !value.starts_with("var(") && self.font_weight(name).is_none() {
+                return invalid("font-weight", name, value);
+            }
+        }
+        if let Some(extend) = &self.extend {
+            extend.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// Convert a `px`, `rem` or `em` length to pixels
+pub(crate) fn length_to_px(value: &str) -> Option<f32> {
+    let value = value.trim();
+    if let Some(px) = value.strip_suffix("px") {
+        return px.parse().ok();
+    }
+    let rem = value
+        .strip_suffix("rem")
+        .or_else(|| value.strip_suffix("em"))?;
+    rem.parse::<f32>().ok().map(|rem| rem * 16.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_theme() {
+        let theme = TailwindTheme::default();
+        assert_eq!(theme.get("color", "blue-500"), Some("#3b82f6"));
+        assert_eq!(theme.spacing_unit(), Some("0.25rem"));
+        assert_eq!(theme.get("breakpoint", "md"), Some("48rem"));
+        assert_eq!(theme.breakpoint_px("md"), Some(768.0));
+        assert_eq!(theme.get("radius", "sm"), Some("0.25rem"));
+        assert_eq!(
+            theme.get("shadow", "sm"),
+            Some("0 1px 3px 0 rgb(0 0 0 / 0.1), 0 1px 2px -1px rgb(0 0 0 / 0.1)")
+        );
+        assert_eq!(theme.font_weight("semibold"), Some(600));
+        assert_eq!(theme.z_index("50"), Some(50));
+        assert_eq!(theme.z_index("auto"), None);
+        assert_eq!(theme.opacity("75"), Some(0.75));
+        assert_eq!(
+            theme.get("ease", "in-out"),
+            Some("cubic-bezier(0.4, 0, 0.2, 1)")
+        );
+        assert_eq!(theme.get("aspect", "video"), Some("16 / 9"));
+        assert!(theme.keyframes("spin").unwrap().contains("rotate(360deg)"));
+        assert_eq!(
+            theme.font_size("xs"),
+            Some(FontSize {
+                size: "0.75rem",
+                line_height: Some("calc(1 / 0.75)"),
+            })
+        );
+        assert!(theme.color("red-500").is_some());
+        assert!(theme.validate().is_ok());
+    }
+
+    #[test]
+    fn test_variables_round_trip() {
+        let theme = TailwindTheme::default();
+        let variables = theme.to_variables();
+        assert_eq!(variables["spacing"], "0.25rem");
+        assert_eq!(variables["font-weight-bold"], "700");
+        assert_eq!(variables["text-sm--line-height"], "calc(1.25 / 0.875)");
+
+        let mut rebuilt = TailwindTheme::from_variables(
+            variables
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str())),
+        );
+        rebuilt.keyframes = theme.keyframes.clone();
+        assert_eq!(rebuilt, theme);
+
+        let custom = TailwindTheme::from_variables([("--header-height", "4rem")]);
+        assert_eq!(custom.variables["header-height"], "4rem");
+    }
+
+    #[test]
+    fn test_toml_and_json_round_trip() {
+        let toml_str = r##"
+[font]
+display = "Inter, sans-serif"
+
+[z-index]
+modal = "100"
+
+[extend.color]
+brand-500 = "#0f766e"
+"##;
+        let theme: TailwindTheme = toml::from_str(toml_str).unwrap();
+        assert_eq!(theme.get("font", "display"), Some("Inter, sans-serif"));
+        assert_eq!(theme.z_index("modal"), Some(100));
+
+        let toml_out = toml::to_string(&theme).unwrap();
+        assert_eq!(toml::from_str::<TailwindTheme>(&toml_out).unwrap(), theme);
+
+        let defaults = TailwindTheme::default();
+        let json = serde_json::to_string(&defaults).unwrap();
+        assert_eq!(
+            serde_json::from_str::<TailwindTheme>(&json).unwrap(),
+            defaults
+        );
+    }
+
+    #[test]
+    fn test_resolve_replaces_and_extends() {
+        let mut definition = TailwindTheme::empty();
+        definition
+            .font
+            .insert("display".to_string(), "Inter".to_string());
+        let mut extend = TailwindTheme::empty();
+        extend
+            .color
+            .insert("brand-500".to_string(), "#0f766e".to_string());
+        definition.extend = Some(Box::new(extend));
+
+        let theme = definition.resolve();
+        // `font` was replaced, `color` extended
+        assert_eq!(theme.font.len(), 1);
+        assert_eq!(theme.font_family("display"), Some("Inter"));
+        assert_eq!(theme.get("color", "brand-500"), Some("#0f766e"));
+        assert_eq!(theme.get("color", "blue-500"), Some("#3b82f6"));
+        assert!(theme.extend.is_none());
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut theme = TailwindTheme::empty();
+        theme.z_index.insert("modal".to_string(), "top".to_string());
+        assert!(theme.validate().is_err());
+
+        let mut theme = TailwindTheme::empty();
+        theme
+            .breakpoint
+            .insert("3xl".to_string(), "wide".to_string());
+        assert!(theme.validate().is_err());
+
+        let mut theme = TailwindTheme::empty();
+        theme.opacity.insert("half".to_string(), "50%".to_string());
+        assert!(theme.validate().is_ok());
+        theme.opacity.insert("over".to_string(), "1.5".to_string());
+        assert!(theme.validate().is_err());
+    }
+}
@@ -1,6 +1,7 @@
 //! New theme system implementation according to API documentation
 
 use crate::color::Color;
+use crate::theme_model::TailwindTheme;
 use std::collections::HashMap;
 
 /// Theme variant for different component styles
@@ -130,17 +131,19 @@ impl Default for FontSizeScale {
 }
 
 impl FontSizeScale {
-    /// Creates a new font size scale with default values
+    /// Creates a new font size scale with Tailwind's default values
     pub fn new() -> Self {
+        let defaults = TailwindTheme::default();
+        let size = |name| tailwind_default(&defaults, "text", name);
         Self {
-            xs: "0.75rem".to_string(),
-            sm: "0.875rem".to_string(),
-            base: "1rem".to_string(),
-            lg: "1.125rem".to_string(),
-            xl: "1.25rem".to_string(),
-            xxl: "1.5rem".to_string(),
-            xxxl: "1.875rem".to_string(),
-            xxxxl: "2.25rem".to_string(),
+            xs: size("xs"),
+            sm: size("sm"),
+            base: size("base"),
+            lg: size("lg"),
+            xl: size("xl"),
+            xxl: size("2xl"),
+            xxxl: size("3xl"),
+            xxxxl: size("4xl"),
         }
     }
 }
@@ -165,18 +168,20 @@ impl Default for FontWeightScale {
 }
 
 impl FontWeightScale {
-    /// Creates a new font weight scale with default values
+    /// Creates a new font weight scale with Tailwind's default values
     pub fn new() -> Self {
+        let defaults = TailwindTheme::default();
+        let weight = |name| tailwind_default(&defaults, "font-weight", name);
         Self {
-            thin: "100".to_string(),
-            extralight: "200".to_string(),
-            light: "300".to_string(),
-            normal: "400".to_string(),
-            medium: "500".to_string(),
-            semibold: "600".to_string(),
-            bold: "700".to_string(),
-            extrabold: "800".to_string(),
-            black: "900".to_string(),
+            thin: weight("thin"),
+            extralight: weight("extralight"),
+            light: weight("light"),
+            normal: weight("normal"),
+            medium: weight("medium"),
+            semibold: weight("semibold"),
+            bold: weight("bold"),
+            extrabold: weight("extrabold"),
+            black: weight("black"),
         }
     }
 }
@@ -198,15 +203,18 @@ impl Default for LineHeightScale {
 }
 
 impl LineHeightScale {
-    /// Creates a new line height scale with default values
+    /// Creates a new line height scale with Tailwind's default values
     pub fn new() -> Self {
+        let defaults = TailwindTheme::default();
+        let leading = |name| tailwind_default(&defaults, "leading", name);
         Self {
+            // `leading-none` is a static utility, not a theme value
             none: "1".to_string(),
-            tight: "1.25".to_string(),
-            snug: "1.375".to_string(),
-            normal: "1.5".to_string(),
-            relaxed: "1.625".to_string(),
-            loose: "2".to_string(),
+            tight: leading("tight"),
+            snug: leading("snug"),
+            normal: leading("normal"),
+            relaxed: leading("relaxed"),
+            loose: leading("loose"),
         }
     }
 }
@@ -228,15 +236,17 @@ impl Default for LetterSpacingScale {
 }
 
 impl LetterSpacingScale {
-    /// Creates a new letter spacing scale with default values
+    /// Creates a new letter spacing scale with Tailwind's default values
     pub fn new() -> Self {
+        let defaults = TailwindTheme::default();
+        let tracking = |name| tailwind_default(&defaults, "tracking", name);
         Self {
-            tighter: "-0.05em".to_string(),
-            tight: "-0.025em".to_string(),
-            normal: "0em".to_string(),
-            wide: "0.025em".to_string(),
-            wider: "0.05em".to_string(),
-            widest: "0.1em".to_string(),
+            tighter: tracking("tighter"),
+            tight: tracking("tight"),
+            normal: tracking("normal"),
+            wide: tracking("wide"),
+            wider: tracking("wider"),
+            widest: tracking("widest"),
         }
     }
 }
@@ -278,6 +288,9 @@ impl TypographyScale {
 }
 
 /// Shadow scale
+///
+/// Uses the v3 names: `sm` is Tailwind's `shadow-xs`, `base` its `shadow-sm`
+/// and `inner` its `inset-shadow-sm`.
 pub struct ShadowScale {
     pub sm: String,
     pub base: String,
@@ -295,16 +308,18 @@ impl Default for ShadowScale {
 }
 
 impl ShadowScale {
-    /// Creates a new shadow scale with default values
+    /// Creates a new shadow scale with Tailwind's default values
     pub fn new() -> Self {
+        let defaults = TailwindTheme::default();
+        let shadow = |name| tailwind_default(&defaults, "shadow", name);
         Self {
-            sm: "0 1px 2px 0 rgb(0 0 0 / 0.05)".to_string(),
-            base: "0 1px 3px 0 rgb(0 0 0 / 0.1), 0 1px 2px -1px rgb(0 0 0 / 0.1)".to_string(),
-            md: "0 4px 6px -1px rgb(0 0 0 / 0.1), 0 2px 4px -2px rgb(0 0 0 / 0.1)".to_string(),
-            lg: "0 10px 15px -3px rgb(0 0 0 / 0.1), 0 4px 6px -4px rgb(0 0 0 / 0.1)".to_string(),
-            xl: "0 20px 25px -5px rgb(0 0 0 / 0.1), 0 8px 10px -6px rgb(0 0 0 / 0.1)".to_string(),
-            xxl: "0 25px 50px -12px rgb(0 0 0 / 0.25)".to_string(),
-            inner: "inset 0 2px 4px 0 rgb(0 0 0 / 0.05)".to_string(),
+            sm: shadow("xs"),
+            base: shadow("sm"),
+            md: shadow("md"),
+            lg: shadow("lg"),
+            xl: shadow("xl"),
+            xxl: shadow("2xl"),
+            inner: tailwind_default(&defaults, "inset-shadow", "sm"),
         }
     }
 }
@@ -355,18 +370,28 @@ impl Default for AnimationScale {
 }
 
 impl AnimationScale {
-    /// Creates a new animation scale with default values
+    /// Creates a new animation scale with Tailwind's default values
     pub fn new() -> Self {
+        let defaults = TailwindTheme::default();
+        let animation = |name| tailwind_default(&defaults, "animate", name);
         Self {
             none: "none".to_string(),
-            spin: "spin 1s linear infinite".to_string(),
-            ping: "ping 1s cubic-bezier(0, 0, 0.2, 1) infinite".to_string(),
-            pulse: "pulse 2s cubic-bezier(0.4, 0, 0.6, 1) infinite".to_string(),
-            bounce: "bounce 1s infinite".to_string(),
+            spin: animation("spin"),
+            ping: animation("ping"),
+            pulse: animation("pulse"),
+            bounce: animation("bounce"),
         }
     }
 }
 
+/// A value of Tailwind's default theme, read by the scale defaults
+fn tailwind_default(defaults: &TailwindTheme, namespace: &str, name: &str) -> String {
+    defaults
+        .get(namespace, name)
+        .unwrap_or_default()
+        .to_string()
+}
+
 /// Main theme structure according to API documentation
 pub struct Theme {
     pub primary_color: Color,
@@ -458,6 +483,117 @@ impl Theme {
     pub fn apply_to_component(&self, component: &dyn ThemedComponent) -> String {
         component.apply_theme(self)
     }
+
+    /// Converts the theme to the canonical [`TailwindTheme`]
+    ///
+    /// Semantic colors reference the 500 shade of their palette color
+    /// (`--color-primary: var(--color-blue-500)`) and the scales extend
+    /// Tailwind's defaults under their v4 names.
+    pub fn to_tailwind_theme(&self) -> TailwindTheme {
+        let mut theme = TailwindTheme::default();
+        let colors = [
+            ("primary", self.primary_color),
+            ("secondary", self.secondary_color),
+            ("accent", self.accent_color),
+            ("background", self.background_color),
+            ("foreground", self.text_color),
+            ("border", self.border_color),
+            ("success", self.success_color),
+            ("warning", self.warning_color),
+            ("error", self.error_color),
+            ("info", self.info_color),
+        ];
+        for (name, color) in colors {
+            theme.color.insert(
+                name.to_string(),
+                format!("var(--color-{}-500)", color.name()),
+            );
+        }
+
+        let spacing = [
+            (SpacingSize::Xs, "xs"),
+            (SpacingSize::Sm, "sm"),
+            (SpacingSize::Md, "md"),
+            (SpacingSize::Lg, "lg"),
+            (SpacingSize::Xl, "xl"),
+            (SpacingSize::Xxl, "2xl"),
+            (SpacingSize::Xxxl, "3xl"),
+        ];
+        for (size, name) in spacing {
+            theme
+                .spacing
+                .insert(name.to_string(), self.spacing.get(size).to_string());
+        }
+
+        let typography = &self.typography;
+        let font_family = match &typography.font_family {
+            FontFamily::Custom(family) => family.clone(),
+            family => format!("var(--{})", family.class()),
+        };
+        theme
+            .variables
+            .insert("default-font-family".to_string(), font_family);
+        let sizes = &typography.font_sizes;
+        let weights = &typography.font_weights;
+        let leading = &typography.line_heights;
+        let tracking = &typography.letter_spacing;
+        let shadows = &self.shadows;
+        let animations = &self.animations;
+        let scales: &[(&str, &str, &str)] = &[
+            ("text", "xs", &sizes.xs),
+            ("text", "sm", &sizes.sm),
+            ("text", "base", &sizes.base),
+            ("text", "lg", &sizes.lg),
+            ("text", "xl", &sizes.xl),
+            ("text", "2xl", &sizes.xxl),
+            ("text", "3xl", &sizes.xxxl),
+            ("text", "4xl", &sizes.xxxxl),
+            ("font-weight", "thin", &weights.thin),
+            ("font-weight", "extralight", &weights.extralight),
+            ("font-weight", "light", &weights.light),
+            ("font-weight", "normal", &weights.normal),
+            ("font-weight", "medium", &weights.medium),
+            ("font-weight", "semibold", &weights.semibold),
+            ("font-weight", "bold", &weights.bold),
+            ("font-weight", "extrabold", &weights.extrabold),
+            ("font-weight", "black", &weights.black),
+            ("leading", "none", &leading.none),
+            ("leading", "tight", &leading.tight),
+            ("leading", "snug", &leading.snug),
+            ("leading", "normal", &leading.normal),
+            ("leading", "relaxed", &leading.relaxed),
+            ("leading", "loose", &leading.loose),
+            ("tracking", "tighter", &tracking.tighter),
+            ("tracking", "tight", &tracking.tight),
+            ("tracking", "normal", &tracking.normal),
+            ("tracking", "wide", &tracking.wide),
+            ("tracking", "wider", &tracking.wider),
+            ("tracking", "widest", &tracking.widest),
+            ("shadow", "xs", &shadows.sm),
+            ("shadow", "sm", &shadows.base),
+            ("shadow", "md", &shadows.md),
+            ("shadow", "lg", &shadows.lg),
+            ("shadow", "xl", &shadows.xl),
+            ("shadow", "2xl", &shadows.xxl),
+            ("inset-shadow", "sm", &shadows.inner),
+            ("animate", "spin", &animations.spin),
+            ("animate", "ping", &animations.ping),
+            ("animate", "pulse", &animations.pulse),
+            ("animate", "bounce", &animations.bounce),
+        ];
+        for (namespace, name, value) in scales {
+            if let Some(entries) = theme.namespace_mut(namespace) {
+                entries.insert(name.to_string(), value.to_string());
+            }
+        }
+        theme
+    }
+}
+
+impl From<&Theme> for TailwindTheme {
+    fn from(theme: &Theme) -> Self {
+        theme.to_tailwind_theme()
+    }
 }
 
 /// Trait for components that support theming
@@ -707,4 +843,30 @@ mod tests {
         assert!(variants.contains(&ThemeVariant::Danger));
         assert!(variants.contains(&ThemeVariant::Success));
     }
+
+    #[test]
+    fn test_to_tailwind_theme() {
+        let theme = ThemePreset::Vibrant.create().to_tailwind_theme();
+        assert_eq!(
+            theme.get("color", "secondary"),
+            Some("var(--color-green-500)")
+        );
+        assert_eq!(theme.get("spacing", "md"), Some("1rem"));
+        assert_eq!(theme.font_weight("bold"), Some(700));
+        assert_eq!(
+            theme.get("shadow", "sm"),
+            Some(ShadowScale::new().base.as_str())
+        );
+        // The default scales map back onto Tailwind's defaults
+        let defaults = TailwindTheme::default();
+        let theme = Theme::new().to_tailwind_theme();
+        assert_eq!(theme.shadow, defaults.shadow);
+        assert_eq!(theme.inset_shadow, defaults.inset_shadow);
+        assert_eq!(theme.font_weight, defaults.font_weight);
+        assert_eq!(theme.tracking, defaults.tracking);
+        assert_eq!(theme.animate, defaults.animate);
+        // Defaults the old scales don't cover are kept
+        assert_eq!(theme.breakpoint_px("lg"), Some(1024.0));
+        assert_eq!(theme.spacing_unit(), Some("0.25rem"));
+    }
 }
@@ -3,7 +3,11 @@
 //! This module provides functionality to analyze CSS usage and remove unused classes,
 //! optimizing the final CSS bundle size.
 
-use crate::class_scanner::ClassScanner;
+use crate::ast_parser::AstParser;
+use crate::candidates::extract_candidates;
+use crate::classes::split_classes;
+use crate::config::safelist::{is_blocked, SafelistMatcher};
+use crate::config::{ContentConfig, SafelistEntry, TailwindConfig};
 use crate::css_generator::CssGenerator;
 use crate::error::Result;
 use std::collections::{HashMap, HashSet};
@@ -22,10 +26,15 @@ pub struct TreeShakeConfig {
     pub remove_unused_custom: bool,
     /// Classes to always keep (whitelist)
     pub keep_classes: HashSet<String>,
-    /// Classes to always remove (blacklist)
+    /// Classes to always remove (blacklist), with or without variants
     pub remove_classes: HashSet<String>,
+    /// Classes and patterns to always keep
+    pub safelist: Vec<SafelistEntry>,
     /// Whether to analyze dependencies between classes
     pub analyze_dependencies: bool,
+    /// Files scanned for used classes, with globs relative to the working
+    /// directory
+    pub content: ContentConfig,
 }
 
 impl Default for TreeShakeConfig {
@@ -37,7 +46,21 @@ impl Default for TreeShakeConfig {
             remove_unused_custom: true,
             keep_classes: HashSet::new(),
             remove_classes: HashSet::new(),
+            safelist: Vec::new(),
             analyze_dependencies: true,
+            content: ContentConfig::default(),
+        }
+    }
+}
+
+impl From<&TailwindConfig> for TreeShakeConfig {
+    /// Default settings honoring the configuration's safelist and blocklist
+    fn from(config: &TailwindConfig) -> Self {
+        Self {
+            remove_classes: config.blocklist.iter().cloned().collect(),
+            safelist: config.safelist.clone(),
+            content: config.content.clone(),
+            ..Self::default()
         }
     }
 }
@@ -118,15 +141,34 @@ impl TreeShaker {
         &mut self,
         source_paths: &[&Path],
         css_generator: &mut CssGenerator,
+    ) -> Result<TreeShakeResults> {
+        let used_classes = if self.config.enabled {
+            self.scan_used_classes(source_paths)?
+        } else {
+            HashSet::new()
+        };
+        self.shake_classes(&used_classes, css_generator)
+    }
+
+    /// Remove the rules of the classes that aren't used, for callers that
+    /// have already scanned their sources
+    ///
+    /// Safelisted, whitelisted and dependent classes are kept as well. Fails
+    /// when a safelist pattern isn't a valid regular expression.
+    pub fn shake_classes(
+        &mut self,
+        used_classes: &HashSet<String>,
+        css_generator: &mut CssGenerator,
     ) -> Result<TreeShakeResults> {
         let start_time = std::time::Instant::now();
+        let original_size = css_generator.generate_css().len();
 
         if !self.config.enabled {
             return Ok(TreeShakeResults {
                 kept_classes: css_generator.get_rules().keys().cloned().collect(),
                 removed_classes: HashSet::new(),
-                original_size: css_generator.generate_css().len(),
-                optimized_size: css_generator.generate_css().len(),
+                original_size,
+                optimized_size: original_size,
                 reduction_percentage: 0.0,
                 stats: TreeShakeStats {
                     classes_analyzed: css_generator.rule_count(),
@@ -139,25 +181,21 @@ impl TreeShaker {
             });
         }
 
-        // Scan source files for used classes
-        let used_classes = self.scan_used_classes(source_paths)?;
-
         // Build dependency graph if enabled
         if self.config.analyze_dependencies {
             self.build_dependency_graph(css_generator);
         }
 
         // Determine which classes to keep
-        let classes_to_keep = self.determine_classes_to_keep(&used_classes, css_generator);
+        let classes_to_keep = self.determine_classes_to_keep(used_classes, css_generator)?;
 
         // Remove unused classes and track statistics
         let removal_stats = self.remove_unused_classes(css_generator, &classes_to_keep);
 
         // Calculate results
-        let original_size = css_generator.generate_css().len();
         let optimized_size = css_generator.generate_css().len();
         let reduction_percentage = if original_size > 0 {
-            ((original_size - optimized_size) as f64 / original_size as f64) * 100.0
+            (original_size.saturating_sub(optimized_size) as f64 / original_size as f64) * 100.0
         } else {
             0.0
         };
@@ -182,13 +220,43 @@ impl TreeShaker {
     }
 
     /// Scan source files to find used classes
+    ///
+    /// Every token that could be a class counts as used; Rust files are also
+    /// parsed for classes built through the `ClassBuilder` API.
     fn scan_used_classes(&self, source_paths: &[&Path]) -> Result<HashSet<String>> {
-        let mut scanner = ClassScanner::new();
+        let sources = self.config.content.sources(Path::new("."))?;
+        let mut parser = AstParser::new();
         let mut all_used_classes = HashSet::new();
 
         for path in source_paths {
-            let results = scanner.scan_directory(path)?;
-            all_used_classes.extend(results.classes);
+            for file in sources.files(path)? {
+                let content = sources.read(&file)?;
+                if file.extension().is_some_and(|ext| ext == "rs") {
+                    // Files that don't parse still have their candidates
+                    let _ = parser.parse_content(&content);
+                }
+                all_used_classes.extend(
+                    extract_candidates(&content)
+                        .flat_map(|candidate| split_classes(candidate.value)),
+                );
+            }
+        }
+        all_used_classes.extend(parser.get_classes().iter().cloned());
+
+        // Classes of the crates from `[content.cargo]` are used too
+        for krate in self.config.content.crates(Path::new("."))? {
+            if let Some(manifest) = krate.classes {
+                all_used_classes.extend(manifest);
+                continue;
+            }
+            let crate_sources = self.config.content.sources(&krate.root)?;
+            for file in crate_sources.files(&krate.source_dir())? {
+                let content = crate_sources.read(&file)?;
+                all_used_classes.extend(
+                    extract_candidates(&content)
+                        .flat_map(|candidate| split_classes(candidate.value)),
+                );
+            }
         }
 
         Ok(all_used_classes)
@@ -247,8 +315,9 @@ impl TreeShaker {
     fn determine_classes_to_keep(
         &self,
         used_classes: &HashSet<String>,
-        _css_generator: &CssGenerator,
-    ) -> HashSet<String> {
+        css_generator: &CssGenerator,
+    ) -> Result<HashSet<String>> {
+        let safelist = SafelistMatcher::new(&self.config.safelist)?;
         let mut classes_to_keep = HashSet::new();
 
         // Add explicitly used classes
@@ -257,10 +326,14 @@ impl TreeShaker {
         // Add whitelisted classes
         classes_to_keep.extend(self.config.keep_classes.iter().cloned());
 
-        // Remove blacklisted classes
-        for class in &self.config.remove_classes {
-            classes_to_keep.remove(class);
-        }
+        // Add generated classes covered by the safelist
+        classes_to_keep.extend(
+            css_generator
+                .get_rules()
+                .keys()
+                .filter(|class| safelist.matches(class))
+                .cloned(),
+        );
 
         // Add dependent classes if dependency analysis is enabled
         if self.config.analyze_dependencies {
@@ -295,10 +368,14 @@ impl TreeShaker {
             }
         }
 
-        classes_to_keep
+        // Remove blacklisted classes last, so dependencies can't bring them back
+        let blocklist: Vec<String> = self.config.remove_classes.iter().cloned().collect();
+        classes_to_keep.retain(|class| !is_blocked(&blocklist, class));
+
+        Ok(classes_to_keep)
     }
 
-    /// Remove unused classes from CSS generator
+    /// Remove the rules of unused classes from the CSS generator
     fn remove_unused_classes(
         &self,
         css_generator: &mut CssGenerator,
@@ -312,6 +389,7 @@ impl TreeShaker {
 
         for (class_name, _rule) in rules {
             if !classes_to_keep.contains(&class_name) {
+                css_generator.remove_rule(&class_name);
                 removed_classes.insert(class_name.clone());
 
                 // Categorize the removed class
@@ -367,6 +445,7 @@ impl TreeShaker {
     pub fn clear(&mut self) {
         self.config.keep_classes.clear();
         self.config.remove_classes.clear();
+        self.config.safelist.clear();
         self.dependency_graph.clear();
         self.reverse_dependencies.clear();
     }
@@ -398,7 +477,9 @@ mod tests {
             remove_unused_custom: false,
             keep_classes: HashSet::new(),
             remove_classes: HashSet::new(),
+            safelist: Vec::new(),
             analyze_dependencies: false,
+            content: ContentConfig::default(),
         };
 
         let shaker = TreeShaker::with_config(config);
@@ -465,13 +546,78 @@ mod tests {
         css_generator.add_class("bg-blue-500").unwrap();
         css_generator.add_class("m-2").unwrap();
 
-        let classes_to_keep = shaker.determine_classes_to_keep(&used_classes, &css_generator);
+        let classes_to_keep = shaker
+            .determine_classes_to_keep(&used_classes, &css_generator)
+            .unwrap();
 
         assert!(classes_to_keep.contains("p-4"));
         assert!(classes_to_keep.contains("bg-blue-500"));
         assert!(!classes_to_keep.contains("m-2"));
     }
 
+    #[test]
+    fn test_safelist_and_blocklist() {
+        let mut config = TailwindConfig::new();
+        config.safelist = vec![SafelistEntry::pattern("^bg-(red|blue)-500$").with_variant("hover")];
+        config.blocklist = vec!["p-4".to_string()];
+        let shaker = TreeShaker::with_config(TreeShakeConfig::from(&config));
+
+        let mut css_generator = CssGenerator::new();
+        for class in [
+            "p-4",
+            "md:p-4",
+            "m-2",
+            "bg-red-500",
+            "hover:bg-blue-500",
+            "md:bg-red-500",
+        ] {
+            css_generator.add_class(class).unwrap();
+        }
+        let used_classes: HashSet<String> = ["p-4", "md:p-4", "m-2"]
+            .iter()
+            .map(|class| class.to_string())
+            .collect();
+
+        let classes_to_keep = shaker
+            .determine_classes_to_keep(&used_classes, &css_generator)
+            .unwrap();
+
+        assert!(classes_to_keep.contains("m-2"));
+        assert!(classes_to_keep.contains("bg-red-500"));
+        assert!(classes_to_keep.contains("hover:bg-blue-500"));
+        // `md` isn't one of the pattern's variants
+        assert!(!classes_to_keep.contains("md:bg-red-500"));
+        assert!(!classes_to_keep.contains("p-4"));
+        assert!(!classes_to_keep.contains("md:p-4"));
+    }
+
+    #[test]
+    fn test_shake_scans_content_sources() {
+        let dir = std::env::temp_dir().join(format!("tailwind-rs-shake-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("app.rs"), r#"const APP: &str = "p-4";"#).unwrap();
+        std::fs::write(dir.join("page.html"), r#"<p class="m-2"></p>"#).unwrap();
+        std::fs::write(dir.join("notes.md"), "`bg-red-500`").unwrap();
+
+        let mut css_generator = CssGenerator::new();
+        for class in ["p-4", "m-2", "bg-red-500", "text-lg"] {
+            css_generator.add_class(class).unwrap();
+        }
+        let mut shaker = TreeShaker::new();
+        let results = shaker.shake(&[&dir], &mut css_generator).unwrap();
+
+        assert!(results.kept_classes.contains("p-4"));
+        assert!(results.kept_classes.contains("m-2"));
+        // Markdown isn't part of the default content
+        assert!(results.removed_classes.contains("bg-red-500"));
+        assert!(results.removed_classes.contains("text-lg"));
+        assert_eq!(css_generator.rule_count(), 2);
+        assert!(!css_generator.generate_css().contains(".text-lg"));
+        assert!(results.optimized_size < results.original_size);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_disabled_tree_shaking() {
         let mut config = TreeShakeConfig::default();
@@ -141,8 +141,8 @@ impl ColorFunction {
                 }
             }
             ColorFunction::Lab { l, a, b, alpha } => {
-                if let Some(a) = alpha {
-                    format!("lab({}% {} {} / {})", l, a, b, a)
+                if let Some(alpha) = alpha {
+                    format!("lab({}% {} {} / {})", l, a, b, alpha)
                 } else {
                     format!("lab({}% {} {})", l, a, b)
                 }
@@ -158,6 +158,8 @@ impl ColorFunction {
     }
 
     /// Convert to class name
+    ///
+    /// Spaces in the arbitrary value are written as underscores.
     pub fn to_class_name(&self) -> String {
         match self {
             ColorFunction::Rgb { r, g, b, alpha } => {
@@ -176,23 +178,23 @@ impl ColorFunction {
             }
             ColorFunction::Oklch { l, c, h, alpha } => {
                 if let Some(a) = alpha {
-                    format!("bg-[oklch({} {} {} / {})]", l, c, h, a)
+                    format!("bg-[oklch({}_{}_{}_/_{})]", l, c, h, a)
                 } else {
-                    format!("bg-[oklch({} {} {})]", l, c, h)
+                    format!("bg-[oklch({}_{}_{})]", l, c, h)
                 }
             }
             ColorFunction::Lab { l, a, b, alpha } => {
-                if let Some(a) = alpha {
-                    format!("bg-[lab({}% {} {} / {})]", l, a, b, a)
+                if let Some(alpha) = alpha {
+                    format!("bg-[lab({}%_{}_{}_/_{})]", l, a, b, alpha)
                 } else {
-                    format!("bg-[lab({}% {} {})]", l, a, b)
+                    format!("bg-[lab({}%_{}_{})]", l, a, b)
                 }
             }
             ColorFunction::Lch { l, c, h, alpha } => {
                 if let Some(a) = alpha {
-                    format!("bg-[lch({}% {} {} / {})]", l, c, h, a)
+                    format!("bg-[lch({}%_{}_{}_/_{})]", l, c, h, a)
                 } else {
-                    format!("bg-[lch({}% {} {})]", l, c, h)
+                    format!("bg-[lch({}%_{}_{})]", l, c, h)
                 }
             }
         }
@@ -228,7 +230,7 @@ impl CssVariable {
     /// Convert to class name
     pub fn to_class_name(&self) -> String {
         if let Some(fallback) = &self.fallback {
-            format!("bg-[var(--{}, {})]", self.name, fallback)
+            format!("bg-[var(--{},_{})]", self.name, fallback.replace(' ', "_"))
         } else {
             format!("bg-[var(--{})]", self.name)
         }
@@ -283,14 +285,14 @@ mod tests {
     fn test_oklch_color_function() {
         let color = ColorFunction::oklch(0.7, 0.15, 0.0);
         assert_eq!(color.to_css_value(), "oklch(0.7 0.15 0)");
-        assert_eq!(color.to_class_name(), "bg-[oklch(0.7 0.15 0)]");
+        assert_eq!(color.to_class_name(), "bg-[oklch(0.7_0.15_0)]");
     }
 
     #[test]
     fn test_oklcha_color_function() {
         let color = ColorFunction::oklcha(0.7, 0.15, 0.0, 0.9);
         assert_eq!(color.to_css_value(), "oklch(0.7 0.15 0 / 0.9)");
-        assert_eq!(color.to_class_name(), "bg-[oklch(0.7 0.15 0 / 0.9)]");
+        assert_eq!(color.to_class_name(), "bg-[oklch(0.7_0.15_0_/_0.9)]");
     }
 
     #[test]
@@ -302,7 +304,7 @@ mod tests {
             alpha: None,
         };
         assert_eq!(color.to_css_value(), "lab(70% 0 0)");
-        assert_eq!(color.to_class_name(), "bg-[lab(70% 0 0)]");
+        assert_eq!(color.to_class_name(), "bg-[lab(70%_0_0)]");
     }
 
     #[test]
@@ -314,7 +316,7 @@ mod tests {
             alpha: None,
         };
         assert_eq!(color.to_css_value(), "lch(70% 0 0)");
-        assert_eq!(color.to_class_name(), "bg-[lch(70% 0 0)]");
+        assert_eq!(color.to_class_name(), "bg-[lch(70%_0_0)]");
     }
 
     #[test]
@@ -328,7 +330,7 @@ mod tests {
     fn test_css_variable_with_fallback() {
         let var = CssVariable::with_fallback("primary-color".to_string(), "#ff0000".to_string());
         assert_eq!(var.to_css_value(), "var(--primary-color, #ff0000)");
-        assert_eq!(var.to_class_name(), "bg-[var(--primary-color, #ff0000)]");
+        assert_eq!(var.to_class_name(), "bg-[var(--primary-color,_#ff0000)]");
     }
 
     #[test]
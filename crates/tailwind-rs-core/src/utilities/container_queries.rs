@@ -2,7 +2,12 @@
 //!
 //! This module provides support for CSS container queries including
 //! @container queries and container-based responsive utilities.
+//!
+//! A query applies a utility through an arbitrary variant
+//! (`[@container_(width_>_768px)]:p-4`), which generates the rule inside the
+//! `@container` block; `@container` marks the element being queried.
 
+use crate::classes::ClassBuilder;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
@@ -121,7 +126,15 @@ impl ContainerQuery {
         }
     }
 
+    /// Convert to the variant that applies a utility inside this query
+    pub fn to_variant(&self) -> String {
+        format!("[{}]", self.to_css_query().replace(' ', "_"))
+    }
+
     /// Convert to class name
+    #[deprecated(
+        note = "names no utility; use `to_variant` or `ContainerQueryUtilities::container_query`"
+    )]
     pub fn to_class_name(&self) -> String {
         match self {
             ContainerQuery::InlineSize(size) => {
@@ -216,6 +229,30 @@ impl ContainerOrientation {
     }
 }
 
+/// Trait for adding container query utilities to a class builder
+pub trait ContainerQueryUtilities {
+    /// Mark the element as an inline-size query container (`@container`)
+    fn query_container(self) -> Self;
+    /// Mark the element as a named inline-size query container (`@container/{name}`)
+    fn named_query_container(self, name: &str) -> Self;
+    /// Apply a class inside a container query (`[@container_(width_>_768px)]:{class}`)
+    fn container_query(self, query: ContainerQuery, class: &str) -> Self;
+}
+
+impl ContainerQueryUtilities for ClassBuilder {
+    fn query_container(self) -> Self {
+        self.class("@container")
+    }
+
+    fn named_query_container(self, name: &str) -> Self {
+        self.class(format!("@container/{}", name))
+    }
+
+    fn container_query(self, query: ContainerQuery, class: &str) -> Self {
+        self.custom_variant(query.to_variant(), class)
+    }
+}
+
 impl fmt::Display for ContainerQuery {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.to_css_query())
@@ -241,6 +278,7 @@ impl fmt::Display for ContainerOrientation {
 }
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
     use super::*;
 
@@ -286,6 +324,22 @@ mod tests {
         assert_eq!(query.to_class_name(), "@container/orientation:landscape");
     }
 
+    #[test]
+    fn test_container_query_variant() {
+        let query = ContainerQuery::width(ContainerSize::Md);
+        assert_eq!(query.to_variant(), "[@container_(width_>_768px)]");
+
+        let classes = ClassBuilder::new()
+            .named_query_container("sidebar")
+            .container_query(query, "p-4")
+            .build()
+            .to_css_classes();
+        assert_eq!(
+            classes,
+            "@container/sidebar [@container_(width_>_768px)]:p-4"
+        );
+    }
+
     #[test]
     fn test_custom_container_size() {
         let size = ContainerSize::Custom("500px".to_string());
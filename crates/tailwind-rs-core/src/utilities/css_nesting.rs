@@ -2,6 +2,10 @@
 //!
 //! This module provides utilities for CSS nesting features.
 //! It includes support for nested selectors, nested media queries, and nested pseudo-classes.
+//!
+//! The `nested_*` builders prefix a utility with the matching variant, using
+//! a built-in variant where one exists (`hover:`, `md:`) and an arbitrary
+//! variant otherwise (`[&>*]:`, `[@media_print]:`).
 
 use crate::classes::ClassBuilder;
 use serde::{Deserialize, Serialize};
@@ -53,6 +57,21 @@ impl NestingSelector {
         }
     }
 
+    /// Get the variant that applies a utility to the elements this selector matches
+    ///
+    /// Custom selectors are matched relative to the element (`& {selector}`).
+    pub fn to_variant(&self) -> String {
+        match self {
+            NestingSelector::DirectChild => "[&>*]".to_string(),
+            NestingSelector::Descendant => "[&_*]".to_string(),
+            NestingSelector::AdjacentSibling => "[&+*]".to_string(),
+            NestingSelector::GeneralSibling => "[&~*]".to_string(),
+            NestingSelector::Custom(selector) => {
+                format!("[&_{}]", selector.trim().replace(' ', "_"))
+            }
+        }
+    }
+
     /// Get the CSS value for this nesting selector
     pub fn to_css_value(&self) -> String {
         self.to_string()
@@ -116,6 +135,19 @@ impl NestingPseudoClass {
         }
     }
 
+    /// Get the variant that applies a utility in this pseudo-class state
+    pub fn to_variant(&self) -> String {
+        match self {
+            NestingPseudoClass::Hover => "hover".to_string(),
+            NestingPseudoClass::Focus => "focus".to_string(),
+            NestingPseudoClass::Active => "active".to_string(),
+            NestingPseudoClass::Visited => "visited".to_string(),
+            NestingPseudoClass::FirstChild => "first".to_string(),
+            NestingPseudoClass::LastChild => "last".to_string(),
+            _ => format!("[&{}]", self.to_string().replace(' ', "_")),
+        }
+    }
+
     /// Get the CSS value for this nesting pseudo-class
     pub fn to_css_value(&self) -> String {
         self.to_string()
@@ -185,6 +217,18 @@ impl NestingMediaQuery {
         }
     }
 
+    /// Get the variant that applies a utility inside this media query
+    pub fn to_variant(&self) -> String {
+        match self {
+            NestingMediaQuery::Small => "sm".to_string(),
+            NestingMediaQuery::Medium => "md".to_string(),
+            NestingMediaQuery::Large => "lg".to_string(),
+            NestingMediaQuery::ExtraLarge => "xl".to_string(),
+            NestingMediaQuery::Light => "light".to_string(),
+            _ => format!("[@media_{}]", self.to_string().replace(' ', "_")),
+        }
+    }
+
     /// Get the CSS value for this nesting media query
     pub fn to_css_value(&self) -> String {
         self.to_string()
@@ -194,16 +238,19 @@ impl NestingMediaQuery {
 /// Trait for adding CSS nesting to ClassBuilder
 pub trait CssNestingUtilities {
     /// Set nesting selector
+    #[deprecated(note = "adds a class with no CSS; use `nested_class` to nest a utility")]
     fn nesting_selector(self, selector: NestingSelector) -> Self;
     /// Set nesting pseudo-class
+    #[deprecated(note = "adds a class with no CSS; use `nested_pseudo_class` to nest a utility")]
     fn nesting_pseudo_class(self, pseudo_class: NestingPseudoClass) -> Self;
     /// Set nesting media query
+    #[deprecated(note = "adds a class with no CSS; use `nested_media_query` to nest a utility")]
     fn nesting_media_query(self, media_query: NestingMediaQuery) -> Self;
-    /// Set nested class with selector
+    /// Apply a class to the elements matched by a selector (`[&>*]:{class}`)
     fn nested_class(self, selector: NestingSelector, class: &str) -> Self;
-    /// Set nested class with pseudo-class
+    /// Apply a class in a pseudo-class state (`hover:{class}`)
     fn nested_pseudo_class(self, pseudo_class: NestingPseudoClass, class: &str) -> Self;
-    /// Set nested class with media query
+    /// Apply a class inside a media query (`md:{class}`)
     fn nested_media_query(self, media_query: NestingMediaQuery, class: &str) -> Self;
 }
 
@@ -221,18 +268,15 @@ impl CssNestingUtilities for ClassBuilder {
     }
 
     fn nested_class(self, selector: NestingSelector, class: &str) -> Self {
-        let nested_class = format!("{}-{}", selector.to_class_name(), class);
-        self.class(&nested_class)
+        self.custom_variant(selector.to_variant(), class)
     }
 
     fn nested_pseudo_class(self, pseudo_class: NestingPseudoClass, class: &str) -> Self {
-        let nested_class = format!("{}-{}", pseudo_class.to_class_name(), class);
-        self.class(&nested_class)
+        self.custom_variant(pseudo_class.to_variant(), class)
     }
 
     fn nested_media_query(self, media_query: NestingMediaQuery, class: &str) -> Self {
-        let nested_class = format!("{}-{}", media_query.to_class_name(), class);
-        self.class(&nested_class)
+        self.custom_variant(media_query.to_variant(), class)
     }
 }
 
@@ -407,20 +451,34 @@ mod tests {
     #[test]
     fn test_css_nesting_utilities() {
         let classes = ClassBuilder::new()
-            .nesting_selector(NestingSelector::DirectChild)
-            .nesting_pseudo_class(NestingPseudoClass::Hover)
-            .nesting_media_query(NestingMediaQuery::Small)
             .nested_class(NestingSelector::Descendant, "text-blue-500")
+            .nested_class(NestingSelector::DirectChild, "p-4")
             .nested_pseudo_class(NestingPseudoClass::Focus, "text-red-500")
-            .nested_media_query(NestingMediaQuery::Medium, "text-green-500");
+            .nested_pseudo_class(NestingPseudoClass::NthChild("2n + 1".to_string()), "p-2")
+            .nested_media_query(NestingMediaQuery::Medium, "text-green-500")
+            .nested_media_query(NestingMediaQuery::Print, "hidden");
+
+        let result = classes.build().to_css_classes();
+        assert!(result.contains("[&_*]:text-blue-500"));
+        assert!(result.contains("[&>*]:p-4"));
+        assert!(result.contains("focus:text-red-500"));
+        assert!(result.contains("[&:nth-child(2n_+_1)]:p-2"));
+        assert!(result.contains("md:text-green-500"));
+        assert!(result.contains("[@media_print]:hidden"));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_css_nesting_deprecated_utilities() {
+        let classes = ClassBuilder::new()
+            .nesting_selector(NestingSelector::DirectChild)
+            .nesting_pseudo_class(NestingPseudoClass::Hover)
+            .nesting_media_query(NestingMediaQuery::Small);
 
         let result = classes.build();
         assert!(result.classes.contains("nest-child"));
         assert!(result.classes.contains("nest-hover"));
         assert!(result.classes.contains("nest-sm"));
-        assert!(result.classes.contains("nest-descendant-text-blue-500"));
-        assert!(result.classes.contains("nest-focus-text-red-500"));
-        assert!(result.classes.contains("nest-md-text-green-500"));
     }
 
     #[test]
@@ -437,17 +495,17 @@ mod tests {
             .nested_dark("text-gray-500")
             .nested_light("text-white");
 
-        let result = classes.build();
-        assert!(result.classes.contains("nest-hover-text-blue-500"));
-        assert!(result.classes.contains("nest-focus-text-red-500"));
-        assert!(result.classes.contains("nest-active-text-green-500"));
-        assert!(result.classes.contains("nest-first-child-text-yellow-500"));
-        assert!(result.classes.contains("nest-last-child-text-purple-500"));
-        assert!(result.classes.contains("nest-sm-text-pink-500"));
-        assert!(result.classes.contains("nest-md-text-indigo-500"));
-        assert!(result.classes.contains("nest-lg-text-cyan-500"));
-        assert!(result.classes.contains("nest-dark-text-gray-500"));
-        assert!(result.classes.contains("nest-light-text-white"));
+        let result = classes.build().to_css_classes();
+        assert!(result.contains("hover:text-blue-500"));
+        assert!(result.contains("focus:text-red-500"));
+        assert!(result.contains("active:text-green-500"));
+        assert!(result.contains("first:text-yellow-500"));
+        assert!(result.contains("last:text-purple-500"));
+        assert!(result.contains("sm:text-pink-500"));
+        assert!(result.contains("md:text-indigo-500"));
+        assert!(result.contains("lg:text-cyan-500"));
+        assert!(result.contains("[@media_(prefers-color-scheme:_dark)]:text-gray-500"));
+        assert!(result.contains("light:text-white"));
     }
 
     #[test]
@@ -471,39 +529,36 @@ mod tests {
     #[test]
     fn test_css_nesting_comprehensive_usage() {
         let classes = ClassBuilder::new()
-            .nesting_selector(NestingSelector::DirectChild)
-            .nesting_pseudo_class(NestingPseudoClass::Hover)
-            .nesting_media_query(NestingMediaQuery::Small)
+            .class("p-2")
             .nested_class(NestingSelector::Descendant, "text-blue-500")
             .nested_pseudo_class(NestingPseudoClass::Focus, "text-red-500")
             .nested_media_query(NestingMediaQuery::Medium, "text-green-500")
             .nested_hover("text-yellow-500")
-            .nested_focus("text-purple-500")
-            .nested_active("text-pink-500")
             .nested_first_child("text-indigo-500")
-            .nested_last_child("text-cyan-500")
-            .nested_sm("text-gray-500")
-            .nested_md("text-white")
-            .nested_lg("text-black")
-            .nested_dark("text-gray-100")
-            .nested_light("text-gray-900");
+            .nested_lg("text-black");
 
-        let result = classes.build();
-        assert!(result.classes.contains("nest-child"));
-        assert!(result.classes.contains("nest-hover"));
-        assert!(result.classes.contains("nest-sm"));
-        assert!(result.classes.contains("nest-descendant-text-blue-500"));
-        assert!(result.classes.contains("nest-focus-text-red-500"));
-        assert!(result.classes.contains("nest-md-text-green-500"));
-        assert!(result.classes.contains("nest-hover-text-yellow-500"));
-        assert!(result.classes.contains("nest-focus-text-purple-500"));
-        assert!(result.classes.contains("nest-active-text-pink-500"));
-        assert!(result.classes.contains("nest-first-child-text-indigo-500"));
-        assert!(result.classes.contains("nest-last-child-text-cyan-500"));
-        assert!(result.classes.contains("nest-sm-text-gray-500"));
-        assert!(result.classes.contains("nest-md-text-white"));
-        assert!(result.classes.contains("nest-lg-text-black"));
-        assert!(result.classes.contains("nest-dark-text-gray-100"));
-        assert!(result.classes.contains("nest-light-text-gray-900"));
+        assert_eq!(
+            classes.build().to_css_classes(),
+            "p-2 [&_*]:text-blue-500 first:text-indigo-500 focus:text-red-500 \
+             hover:text-yellow-500 lg:text-black md:text-green-500"
+        );
+    }
+
+    #[test]
+    fn test_css_nesting_variants() {
+        assert_eq!(NestingSelector::AdjacentSibling.to_variant(), "[&+*]");
+        assert_eq!(NestingSelector::GeneralSibling.to_variant(), "[&~*]");
+        assert_eq!(
+            NestingSelector::Custom("> li".to_string()).to_variant(),
+            "[&_>_li]"
+        );
+        assert_eq!(NestingPseudoClass::Visited.to_variant(), "visited");
+        assert_eq!(NestingPseudoClass::Link.to_variant(), "[&:link]");
+        assert_eq!(
+            NestingPseudoClass::Custom("focus-within".to_string()).to_variant(),
+            "[&:focus-within]"
+        );
+        assert_eq!(NestingMediaQuery::ExtraLarge.to_variant(), "xl");
+        assert_eq!(NestingMediaQuery::Screen.to_variant(), "[@media_screen]");
     }
 }
@@ -2,6 +2,9 @@
 //!
 //! This module provides utilities for device-specific media queries like pointer variants,
 //! motion preferences, and other device capabilities for better accessibility and device targeting.
+//!
+//! The builders prefix a utility with the variant (`motion-reduce:transition-none`),
+//! scoping it to the variant's media query.
 
 use crate::classes::ClassBuilder;
 use serde::{Deserialize, Serialize};
@@ -112,67 +115,67 @@ impl fmt::Display for ColorSchemeVariant {
 
 /// Trait for adding pointer variant utilities to a class builder
 pub trait PointerVariantUtilities {
-    /// Add pointer coarse variant
-    fn pointer_coarse(self) -> Self;
-    /// Add pointer fine variant
-    fn pointer_fine(self) -> Self;
-    /// Add any pointer coarse variant
-    fn any_pointer_coarse(self) -> Self;
-    /// Add any pointer fine variant
-    fn any_pointer_fine(self) -> Self;
+    /// Apply a class when the primary pointer is coarse (`pointer-coarse:{class}`)
+    fn pointer_coarse(self, class: &str) -> Self;
+    /// Apply a class when the primary pointer is fine (`pointer-fine:{class}`)
+    fn pointer_fine(self, class: &str) -> Self;
+    /// Apply a class when any pointer is coarse (`any-pointer-coarse:{class}`)
+    fn any_pointer_coarse(self, class: &str) -> Self;
+    /// Apply a class when any pointer is fine (`any-pointer-fine:{class}`)
+    fn any_pointer_fine(self, class: &str) -> Self;
 }
 
 impl PointerVariantUtilities for ClassBuilder {
-    fn pointer_coarse(self) -> Self {
-        self.class("pointer-coarse")
+    fn pointer_coarse(self, class: &str) -> Self {
+        self.custom_variant(PointerVariant::Coarse.to_class_name(), class)
     }
 
-    fn pointer_fine(self) -> Self {
-        self.class("pointer-fine")
+    fn pointer_fine(self, class: &str) -> Self {
+        self.custom_variant(PointerVariant::Fine.to_class_name(), class)
     }
 
-    fn any_pointer_coarse(self) -> Self {
-        self.class("any-pointer-coarse")
+    fn any_pointer_coarse(self, class: &str) -> Self {
+        self.custom_variant(PointerVariant::AnyCoarse.to_class_name(), class)
     }
 
-    fn any_pointer_fine(self) -> Self {
-        self.class("any-pointer-fine")
+    fn any_pointer_fine(self, class: &str) -> Self {
+        self.custom_variant(PointerVariant::AnyFine.to_class_name(), class)
     }
 }
 
 /// Trait for adding motion variant utilities to a class builder
 pub trait MotionVariantUtilities {
-    /// Add motion reduce variant
-    fn motion_reduce(self) -> Self;
-    /// Add motion safe variant
-    fn motion_safe(self) -> Self;
+    /// Apply a class when reduced motion is preferred (`motion-reduce:{class}`)
+    fn motion_reduce(self, class: &str) -> Self;
+    /// Apply a class when motion is not reduced (`motion-safe:{class}`)
+    fn motion_safe(self, class: &str) -> Self;
 }
 
 impl MotionVariantUtilities for ClassBuilder {
-    fn motion_reduce(self) -> Self {
-        self.class("motion-reduce")
+    fn motion_reduce(self, class: &str) -> Self {
+        self.custom_variant(MotionVariant::Reduced.to_class_name(), class)
     }
 
-    fn motion_safe(self) -> Self {
-        self.class("motion-safe")
+    fn motion_safe(self, class: &str) -> Self {
+        self.custom_variant(MotionVariant::NoPreference.to_class_name(), class)
     }
 }
 
 /// Trait for adding color scheme variant utilities to a class builder
 pub trait ColorSchemeVariantUtilities {
-    /// Add light color scheme variant
-    fn light(self) -> Self;
-    /// Add dark color scheme variant
-    fn dark(self) -> Self;
+    /// Apply a class in the light color scheme (`light:{class}`)
+    fn light(self, class: &str) -> Self;
+    /// Apply a class in dark mode (`dark:{class}`)
+    fn dark(self, class: &str) -> Self;
 }
 
 impl ColorSchemeVariantUtilities for ClassBuilder {
-    fn light(self) -> Self {
-        self.class("light")
+    fn light(self, class: &str) -> Self {
+        self.custom_variant(ColorSchemeVariant::Light.to_class_name(), class)
     }
 
-    fn dark(self) -> Self {
-        self.class("dark")
+    fn dark(self, class: &str) -> Self {
+        self.custom_variant(ColorSchemeVariant::Dark.to_class_name(), class)
     }
 }
 
@@ -250,52 +253,56 @@ mod tests {
     #[test]
     fn test_pointer_variant_utilities() {
         let classes = ClassBuilder::new()
-            .pointer_coarse()
-            .pointer_fine()
-            .any_pointer_coarse()
-            .any_pointer_fine()
-            .build();
-
-        assert!(classes.classes.contains("pointer-coarse"));
-        assert!(classes.classes.contains("pointer-fine"));
-        assert!(classes.classes.contains("any-pointer-coarse"));
-        assert!(classes.classes.contains("any-pointer-fine"));
+            .pointer_coarse("p-4")
+            .pointer_fine("p-2")
+            .any_pointer_coarse("p-4")
+            .any_pointer_fine("p-2")
+            .build()
+            .to_css_classes();
+
+        assert!(classes.contains("pointer-coarse:p-4"));
+        assert!(classes.contains("pointer-fine:p-2"));
+        assert!(classes.contains("any-pointer-coarse:p-4"));
+        assert!(classes.contains("any-pointer-fine:p-2"));
     }
 
     #[test]
     fn test_motion_variant_utilities() {
-        let classes = ClassBuilder::new().motion_reduce().motion_safe().build();
+        let classes = ClassBuilder::new()
+            .motion_reduce("transition-none")
+            .motion_safe("animate-spin")
+            .build()
+            .to_css_classes();
 
-        assert!(classes.classes.contains("motion-reduce"));
-        assert!(classes.classes.contains("motion-safe"));
+        assert!(classes.contains("motion-reduce:transition-none"));
+        assert!(classes.contains("motion-safe:animate-spin"));
     }
 
     #[test]
     fn test_color_scheme_variant_utilities() {
-        let classes = ClassBuilder::new().light().dark().build();
+        let classes = ClassBuilder::new()
+            .light("bg-white")
+            .dark("bg-black")
+            .build()
+            .to_css_classes();
 
-        println!("Generated classes: {:?}", classes.classes);
-        assert!(classes.classes.contains("light"));
-        assert!(classes.classes.contains("dark"));
+        assert!(classes.contains("light:bg-white"));
+        assert!(classes.contains("dark:bg-black"));
     }
 
     #[test]
     fn test_device_variants_comprehensive() {
         let classes = ClassBuilder::new()
-            .pointer_coarse()
-            .pointer_fine()
-            .motion_reduce()
-            .motion_safe()
-            .light()
-            .dark()
-            .build();
-
-        assert!(classes.classes.contains("pointer-coarse"));
-        assert!(classes.classes.contains("pointer-fine"));
-        assert!(classes.classes.contains("motion-reduce"));
-        assert!(classes.classes.contains("motion-safe"));
-        println!("Generated classes: {:?}", classes.classes);
-        assert!(classes.classes.contains("light"));
-        assert!(classes.classes.contains("dark"));
+            .pointer_coarse("p-4")
+            .motion_reduce("transition-none")
+            .dark("bg-black")
+            .class("p-2")
+            .build()
+            .to_css_classes();
+
+        assert_eq!(
+            classes,
+            "p-2 dark:bg-black motion-reduce:transition-none pointer-coarse:p-4"
+        );
     }
 }
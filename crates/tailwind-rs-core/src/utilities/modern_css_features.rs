@@ -173,30 +173,51 @@ impl ModernContainerQuery {
 /// Trait for adding modern CSS features to ClassBuilder
 pub trait ModernCssFeaturesUtilities {
     /// Set cascade layer to base
+    #[deprecated(
+        note = "adds a class with no CSS; generated rules are placed in their cascade layer"
+    )]
     fn layer_base(self) -> Self;
     /// Set cascade layer to components
+    #[deprecated(
+        note = "adds a class with no CSS; generated rules are placed in their cascade layer"
+    )]
     fn layer_components(self) -> Self;
     /// Set cascade layer to utilities
+    #[deprecated(
+        note = "adds a class with no CSS; generated rules are placed in their cascade layer"
+    )]
     fn layer_utilities(self) -> Self;
     /// Set cascade layer with custom name
+    #[deprecated(
+        note = "adds a class with no CSS; generated rules are placed in their cascade layer"
+    )]
     fn layer_custom(self, name: &str) -> Self;
     /// Set cascade layer with custom value
+    #[deprecated(
+        note = "adds a class with no CSS; generated rules are placed in their cascade layer"
+    )]
     fn layer_custom_value(self, layer: CascadeLayer) -> Self;
     /// Set custom property with name and value
     fn custom_property(self, name: &str, _value: &str) -> Self;
     /// Set custom property with custom value
     fn custom_property_value(self, property: CustomProperty) -> Self;
     /// Set container query to small
+    #[deprecated(note = "adds a class with no CSS; use `ContainerQueryUtilities::container_query`")]
     fn container_small(self) -> Self;
     /// Set container query to medium
+    #[deprecated(note = "adds a class with no CSS; use `ContainerQueryUtilities::container_query`")]
     fn container_medium(self) -> Self;
     /// Set container query to large
+    #[deprecated(note = "adds a class with no CSS; use `ContainerQueryUtilities::container_query`")]
     fn container_large(self) -> Self;
     /// Set container query to extra large
+    #[deprecated(note = "adds a class with no CSS; use `ContainerQueryUtilities::container_query`")]
     fn container_extra_large(self) -> Self;
     /// Set container query with custom size
+    #[deprecated(note = "adds a class with no CSS; use `ContainerQueryUtilities::container_query`")]
     fn container_custom(self, size: &str) -> Self;
     /// Set container query with custom value
+    #[deprecated(note = "adds a class with no CSS; use `ContainerQueryUtilities::container_query`")]
     fn container_custom_value(self, query: ModernContainerQuery) -> Self;
 }
 
@@ -257,6 +278,7 @@ impl ModernCssFeaturesUtilities for ClassBuilder {
 }
 
 #[cfg(test)]
+#[allow(deprecated)]
 mod tests {
     use super::*;
     use crate::classes::ClassBuilder;
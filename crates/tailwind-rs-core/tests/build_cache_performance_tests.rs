@@ -0,0 +1,50 @@
+use std::time::Instant;
+use tailwind_rs_core::TailwindBuilder;
+
+#[cfg(test)]
+mod build_cache_performance_tests {
+    use super::*;
+
+    #[test]
+    fn test_unchanged_project_build_performance() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir_all(&src).unwrap();
+
+        // 5000 components, sharing most of their classes
+        for i in 0..5000 {
+            std::fs::write(
+                src.join(format!("component_{}.rs", i)),
+                format!(
+                    r#"fn view() -> &'static str {{ "flex items-center p-{} m-{} text-gray-{}00 rounded-lg" }}"#,
+                    i % 12,
+                    i % 8,
+                    i % 9 + 1
+                ),
+            )
+            .unwrap();
+        }
+
+        let build = || {
+            TailwindBuilder::new()
+                .scan_source(&src)
+                .output_css(&dir.path().join("styles.css"))
+                .cache_dir(&dir.path().join("cache"))
+                .build()
+                .unwrap()
+        };
+        build();
+
+        let start = Instant::now();
+        build();
+        let duration = start.elapsed();
+
+        // The 100ms target is for optimized builds
+        let limit = if cfg!(debug_assertions) { 500 } else { 100 };
+        assert!(
+            duration.as_millis() < limit,
+            "Build of an unchanged 5000-file project too slow: {}ms",
+            duration.as_millis()
+        );
+    }
+}
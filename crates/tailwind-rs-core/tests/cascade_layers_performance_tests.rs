@@ -1,3 +1,6 @@
+// The cascade layer builders are deprecated but still covered
+#![allow(deprecated)]
+
 use std::time::Instant;
 use tailwind_rs_core::utilities::modern_css_features::*;
 use tailwind_rs_core::Breakpoint;
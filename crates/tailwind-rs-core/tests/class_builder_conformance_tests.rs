@@ -0,0 +1,342 @@
+//! Class builder conformance tests
+//!
+//! Every utility class a `ClassBuilder` method can produce must resolve to
+//! CSS. These tests walk the builder-producible classes of the text shadow,
+//! mask, logical property, color function, device variant, nesting, container
+//! query and enhanced backdrop filter modules and assert the generator emits a
+//! rule for each one.
+//!
+//! Builders that can't produce a utility (`layer_base`, `container_small`,
+//! `nesting_pseudo_class`) are deprecated and not covered here.
+
+use tailwind_rs_core::utilities::color_functions::{ColorFunction, CssVariable};
+use tailwind_rs_core::utilities::container_queries::*;
+use tailwind_rs_core::utilities::css_nesting::*;
+use tailwind_rs_core::utilities::device_variants::*;
+use tailwind_rs_core::utilities::enhanced_backdrop_filters::*;
+use tailwind_rs_core::utilities::logical_properties::*;
+use tailwind_rs_core::utilities::mask::*;
+use tailwind_rs_core::utilities::spacing::SpacingValue;
+use tailwind_rs_core::utilities::text_shadow::*;
+use tailwind_rs_core::{ClassBuilder, CssGenerator};
+
+/// Assert that every class of a builder generates a CSS rule
+fn assert_generates_css(builder: ClassBuilder) {
+    let classes = builder.build().to_css_classes();
+    let mut failures = Vec::new();
+
+    for class in classes.split_whitespace() {
+        let mut generator = CssGenerator::new();
+        match generator.add_class(class) {
+            Ok(()) if generator.generate_css().contains('{') => {}
+            Ok(()) => failures.push(format!("{} (no rule emitted)", class)),
+            Err(error) => failures.push(format!("{} ({})", class, error)),
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "classes without CSS:\n  {}",
+        failures.join("\n  ")
+    );
+}
+
+/// Spacing values that are valid for a property; intrinsic sizes never are
+fn spacing_values(allow_auto: bool) -> Vec<SpacingValue> {
+    SpacingValue::all_values()
+        .into_iter()
+        .filter(|value| match value {
+            SpacingValue::Min | SpacingValue::Max | SpacingValue::Fit => false,
+            SpacingValue::Auto => allow_auto,
+            _ => true,
+        })
+        .collect()
+}
+
+#[test]
+fn test_text_shadow_classes_generate_css() {
+    let mut builder = ClassBuilder::new()
+        .text_shadow_none()
+        .text_shadow_sm()
+        .text_shadow_md()
+        .text_shadow()
+        .text_shadow_lg()
+        .text_shadow_xl()
+        .text_shadow_2xl()
+        .text_shadow_inner();
+    for shadow in [
+        TextShadow::None,
+        TextShadow::Sm,
+        TextShadow::Md,
+        TextShadow::Default,
+        TextShadow::Lg,
+        TextShadow::Xl,
+        TextShadow::Xl2,
+        TextShadow::Inner,
+    ] {
+        builder = builder.text_shadow_custom(shadow);
+    }
+    assert_generates_css(builder);
+}
+
+#[test]
+fn test_mask_classes_generate_css() {
+    let builder = ClassBuilder::new()
+        .mask_none()
+        .mask_alpha()
+        .mask_luminance()
+        .mask_repeat_none()
+        .mask_repeat()
+        .mask_repeat_x()
+        .mask_repeat_y()
+        .mask_repeat_round()
+        .mask_repeat_space()
+        .mask_size_auto()
+        .mask_size_cover()
+        .mask_size_contain()
+        .mask_center()
+        .mask_top()
+        .mask_bottom()
+        .mask_left()
+        .mask_right()
+        .mask_top_left()
+        .mask_top_right()
+        .mask_bottom_left()
+        .mask_bottom_right()
+        .mask_clip_border()
+        .mask_clip_padding()
+        .mask_clip_content()
+        .mask_clip_text()
+        .mask_origin_border()
+        .mask_origin_padding()
+        .mask_origin_content();
+    assert_generates_css(builder);
+}
+
+#[test]
+fn test_logical_property_classes_generate_css() {
+    let mut builder = ClassBuilder::new();
+    for value in spacing_values(true) {
+        builder = builder
+            .margin_inline_start(value)
+            .margin_inline_end(value)
+            .margin_block_start(value)
+            .margin_block_end(value)
+            .inset_inline_start(value)
+            .inset_inline_end(value)
+            .inset_block_start(value)
+            .inset_block_end(value);
+    }
+    for value in spacing_values(false) {
+        builder = builder
+            .padding_inline_start(value)
+            .padding_inline_end(value)
+            .padding_block_start(value)
+            .padding_block_end(value);
+    }
+    for width in [0, 1, 2, 4, 8] {
+        let value = SpacingValue::Integer(width);
+        builder = builder
+            .border_inline_start(value)
+            .border_inline_end(value)
+            .border_block_start(value)
+            .border_block_end(value);
+    }
+    builder = builder
+        .margin_inline_start_4()
+        .margin_inline_end_4()
+        .padding_inline_start_2()
+        .padding_inline_end_2()
+        .padding_inline_start_4()
+        .padding_inline_end_4()
+        .border_inline_start_1()
+        .border_inline_end_1()
+        .border_inline_start_2()
+        .border_inline_end_2();
+    assert_generates_css(builder);
+}
+
+#[test]
+fn test_color_function_classes_generate_css() {
+    let colors = [
+        ColorFunction::rgb(255, 0, 0).to_class_name(),
+        ColorFunction::rgba(255, 0, 0, 0.5).to_class_name(),
+        ColorFunction::hsl(210, 50, 40).to_class_name(),
+        ColorFunction::hsla(210, 50, 40, 0.8).to_class_name(),
+        ColorFunction::oklch(0.7, 0.15, 180.0).to_class_name(),
+        ColorFunction::oklcha(0.7, 0.15, 180.0, 0.5).to_class_name(),
+        CssVariable::new("brand".to_string()).to_class_name(),
+        CssVariable::with_fallback("brand".to_string(), "#3b82f6".to_string()).to_class_name(),
+    ];
+    assert_generates_css(ClassBuilder::new().classes(colors));
+}
+
+#[test]
+fn test_device_variant_classes_generate_css() {
+    let builder = ClassBuilder::new()
+        .pointer_coarse("p-4")
+        .pointer_fine("p-4")
+        .any_pointer_coarse("p-4")
+        .any_pointer_fine("p-4")
+        .motion_reduce("transition-none")
+        .motion_safe("p-4")
+        .light("bg-white")
+        .dark("bg-black");
+    assert_generates_css(builder);
+}
+
+#[test]
+fn test_nesting_classes_generate_css() {
+    let mut builder = ClassBuilder::new()
+        .nested_hover("p-4")
+        .nested_focus("p-4")
+        .nested_active("p-4")
+        .nested_first_child("p-4")
+        .nested_last_child("p-4")
+        .nested_sm("p-4")
+        .nested_md("p-4")
+        .nested_lg("p-4")
+        .nested_dark("p-4")
+        .nested_light("p-4");
+    for selector in [
+        NestingSelector::DirectChild,
+        NestingSelector::Descendant,
+        NestingSelector::AdjacentSibling,
+        NestingSelector::GeneralSibling,
+        NestingSelector::Custom("> li".to_string()),
+    ] {
+        builder = builder.nested_class(selector, "p-2");
+    }
+    for pseudo_class in [
+        NestingPseudoClass::Visited,
+        NestingPseudoClass::Link,
+        NestingPseudoClass::NthChild("2n + 1".to_string()),
+        NestingPseudoClass::Custom("focus-within".to_string()),
+    ] {
+        builder = builder.nested_pseudo_class(pseudo_class, "p-2");
+    }
+    for media_query in [
+        NestingMediaQuery::ExtraLarge,
+        NestingMediaQuery::Print,
+        NestingMediaQuery::Screen,
+        NestingMediaQuery::Custom("(orientation: portrait)".to_string()),
+    ] {
+        builder = builder.nested_media_query(media_query, "p-2");
+    }
+    assert_generates_css(builder);
+}
+
+#[test]
+fn test_container_query_classes_generate_css() {
+    let mut builder = ClassBuilder::new()
+        .query_container()
+        .named_query_container("sidebar")
+        .class("@container-size")
+        .class("@container-normal");
+    for query in [
+        ContainerQuery::inline_size(ContainerSize::Sm),
+        ContainerQuery::block_size(ContainerSize::Lg),
+        ContainerQuery::width(ContainerSize::Md),
+        ContainerQuery::height(ContainerSize::Custom("30rem".to_string())),
+        ContainerQuery::aspect_ratio(ContainerAspectRatio::Widescreen),
+        ContainerQuery::orientation(ContainerOrientation::Landscape),
+    ] {
+        builder = builder.container_query(query, "p-4");
+    }
+    assert_generates_css(builder);
+}
+
+#[test]
+fn test_enhanced_backdrop_filter_classes_generate_css() {
+    let mut builder = ClassBuilder::new()
+        .backdrop_blur_none()
+        .backdrop_blur_sm()
+        .backdrop_blur()
+        .backdrop_blur_md()
+        .backdrop_blur_lg()
+        .backdrop_blur_xl()
+        .backdrop_blur_2xl()
+        .backdrop_blur_3xl();
+    for blur in [
+        EnhancedBackdropBlur::None,
+        EnhancedBackdropBlur::Sm,
+        EnhancedBackdropBlur::Default,
+        EnhancedBackdropBlur::Md,
+        EnhancedBackdropBlur::Lg,
+        EnhancedBackdropBlur::Xl,
+        EnhancedBackdropBlur::Xl2,
+        EnhancedBackdropBlur::Xl3,
+    ] {
+        builder = builder.backdrop_blur_custom(blur);
+    }
+    assert_generates_css(builder);
+}
+
+#[test]
+fn test_builder_classes_resolve_to_utility_values() {
+    let css = |class: String| {
+        let mut generator = CssGenerator::new();
+        generator.add_class(&class).unwrap();
+        generator.generate_css()
+    };
+
+    assert!(
+        css(ColorFunction::oklcha(0.7, 0.15, 180.0, 0.5).to_class_name())
+            .contains("background-color: oklch(0.7 0.15 180 / 0.5)")
+    );
+    assert!(css(
+        CssVariable::with_fallback("brand".to_string(), "#3b82f6".to_string()).to_class_name()
+    )
+    .contains("background-color: var(--brand, #3b82f6)"));
+    assert!(css(TextShadow::Lg.to_class_name())
+        .contains(&format!("text-shadow: {}", TextShadow::Lg.to_css_value())));
+    assert!(
+        css("motion-reduce:p-4".to_string()).contains("@media (prefers-reduced-motion: reduce)")
+    );
+    assert!(css("any-pointer-fine:p-4".to_string()).contains("@media (any-pointer: fine)"));
+    assert!(css("border-s-2".to_string()).contains("border-inline-start-width: 2px"));
+}
+
+#[test]
+fn test_variant_builders_wrap_their_utility() {
+    let css = |builder: ClassBuilder| {
+        let mut generator = CssGenerator::new();
+        for class in builder.build().to_css_classes().split_whitespace() {
+            generator.add_class(class).unwrap();
+        }
+        generator.generate_css()
+    };
+
+    let nested = css(ClassBuilder::new().nested_class(NestingSelector::DirectChild, "p-4"));
+    assert!(nested.contains(r".\[\&\>\*\]\:p-4>* {"), "{}", nested);
+
+    let nth = css(ClassBuilder::new()
+        .nested_pseudo_class(NestingPseudoClass::NthChild("3".to_string()), "p-4"));
+    assert!(nth.contains(":nth-child(3) {"), "{}", nth);
+
+    let print = css(ClassBuilder::new().nested_media_query(NestingMediaQuery::Print, "hidden"));
+    assert!(print.contains("@media print {"), "{}", print);
+
+    let container = css(ClassBuilder::new()
+        .named_query_container("card")
+        .container_query(ContainerQuery::width(ContainerSize::Md), "p-4"));
+    assert!(container.contains("container-type: inline-size"));
+    assert!(container.contains("container-name: card"));
+    assert!(
+        container.contains("@container (width > 768px) {"),
+        "{}",
+        container
+    );
+    assert!(!container.contains("@media"), "{}", container);
+}
+
+#[test]
+fn test_container_query_cannot_combine_with_media_query() {
+    let mut generator = CssGenerator::new();
+    assert!(generator
+        .add_class("md:[@container_(width_>_768px)]:p-4")
+        .is_err());
+    assert!(generator
+        .add_class("[@container_(width_>_768px)]:hover:p-4")
+        .is_ok());
+}
@@ -1,3 +1,6 @@
+// `ContainerQuery::to_class_name` is deprecated but still covered
+#![allow(deprecated)]
+
 use tailwind_rs_core::utilities::container_queries::*;
 use tailwind_rs_core::Breakpoint;
 use tailwind_rs_core::ClassBuilder;
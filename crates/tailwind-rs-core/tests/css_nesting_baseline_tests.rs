@@ -1,3 +1,6 @@
+// The argument-less nesting builders are deprecated but still covered
+#![allow(deprecated)]
+
 use tailwind_rs_core::utilities::css_nesting::*;
 use tailwind_rs_core::Breakpoint;
 use tailwind_rs_core::ClassBuilder;
@@ -719,19 +722,19 @@ mod css_nesting_baseline_tests {
         assert!(classes.contains("nest-child"));
         assert!(classes.contains("nest-hover"));
         assert!(classes.contains("nest-sm"));
-        assert!(classes.contains("nest-descendant-text-blue-500"));
-        assert!(classes.contains("nest-focus-text-red-500"));
-        assert!(classes.contains("nest-md-text-green-500"));
-        assert!(classes.contains("nest-hover-text-yellow-500"));
-        assert!(classes.contains("nest-focus-text-purple-500"));
-        assert!(classes.contains("nest-active-text-pink-500"));
-        assert!(classes.contains("nest-first-child-text-indigo-500"));
-        assert!(classes.contains("nest-last-child-text-cyan-500"));
-        assert!(classes.contains("nest-sm-text-gray-500"));
-        assert!(classes.contains("nest-md-text-white"));
-        assert!(classes.contains("nest-lg-text-black"));
-        assert!(classes.contains("nest-dark-text-gray-100"));
-        assert!(classes.contains("nest-light-text-gray-900"));
+        assert!(classes.contains("[&_*]:text-blue-500"));
+        assert!(classes.contains("focus:text-red-500"));
+        assert!(classes.contains("md:text-green-500"));
+        assert!(classes.contains("hover:text-yellow-500"));
+        assert!(classes.contains("focus:text-purple-500"));
+        assert!(classes.contains("active:text-pink-500"));
+        assert!(classes.contains("first:text-indigo-500"));
+        assert!(classes.contains("last:text-cyan-500"));
+        assert!(classes.contains("sm:text-gray-500"));
+        assert!(classes.contains("md:text-white"));
+        assert!(classes.contains("lg:text-black"));
+        assert!(classes.contains("[@media_(prefers-color-scheme:_dark)]:text-gray-100"));
+        assert!(classes.contains("light:text-gray-900"));
         assert!(classes.contains("text-blue-500"));
         assert!(classes.contains("font-bold"));
         assert!(classes.contains("md:nest-descendant"));
@@ -1,3 +1,6 @@
+// The argument-less nesting builders are deprecated but still covered
+#![allow(deprecated)]
+
 use std::time::Instant;
 use tailwind_rs_core::utilities::css_nesting::*;
 use tailwind_rs_core::Breakpoint;
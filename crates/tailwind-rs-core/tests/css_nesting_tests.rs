@@ -1,3 +1,6 @@
+// The argument-less nesting builders are deprecated but still covered
+#![allow(deprecated)]
+
 use tailwind_rs_core::utilities::css_nesting::*;
 use tailwind_rs_core::Breakpoint;
 use tailwind_rs_core::ClassBuilder;
@@ -349,8 +352,8 @@ mod css_nesting_integration_tests {
         let builder =
             ClassBuilder::new().nested_class(NestingSelector::Descendant, "text-blue-500");
 
-        let class_set = builder.build();
-        assert!(class_set.classes.contains("nest-descendant-text-blue-500"));
+        let css_classes = builder.build().to_css_classes();
+        assert_eq!(css_classes, "[&_*]:text-blue-500");
     }
 
     #[test]
@@ -358,8 +361,8 @@ mod css_nesting_integration_tests {
         let builder =
             ClassBuilder::new().nested_pseudo_class(NestingPseudoClass::Hover, "text-red-500");
 
-        let class_set = builder.build();
-        assert!(class_set.classes.contains("nest-hover-text-red-500"));
+        let css_classes = builder.build().to_css_classes();
+        assert_eq!(css_classes, "hover:text-red-500");
     }
 
     #[test]
@@ -367,8 +370,8 @@ mod css_nesting_integration_tests {
         let builder =
             ClassBuilder::new().nested_media_query(NestingMediaQuery::Medium, "text-green-500");
 
-        let class_set = builder.build();
-        assert!(class_set.classes.contains("nest-md-text-green-500"));
+        let css_classes = builder.build().to_css_classes();
+        assert_eq!(css_classes, "md:text-green-500");
     }
 
     #[test]
@@ -385,21 +388,17 @@ mod css_nesting_integration_tests {
             .nested_dark("text-gray-500")
             .nested_light("text-white");
 
-        let class_set = builder.build();
-        assert!(class_set.classes.contains("nest-hover-text-blue-500"));
-        assert!(class_set.classes.contains("nest-focus-text-red-500"));
-        assert!(class_set.classes.contains("nest-active-text-green-500"));
-        assert!(class_set
-            .classes
-            .contains("nest-first-child-text-yellow-500"));
-        assert!(class_set
-            .classes
-            .contains("nest-last-child-text-purple-500"));
-        assert!(class_set.classes.contains("nest-sm-text-pink-500"));
-        assert!(class_set.classes.contains("nest-md-text-indigo-500"));
-        assert!(class_set.classes.contains("nest-lg-text-cyan-500"));
-        assert!(class_set.classes.contains("nest-dark-text-gray-500"));
-        assert!(class_set.classes.contains("nest-light-text-white"));
+        let css_classes = builder.build().to_css_classes();
+        assert!(css_classes.contains("hover:text-blue-500"));
+        assert!(css_classes.contains("focus:text-red-500"));
+        assert!(css_classes.contains("active:text-green-500"));
+        assert!(css_classes.contains("first:text-yellow-500"));
+        assert!(css_classes.contains("last:text-purple-500"));
+        assert!(css_classes.contains("sm:text-pink-500"));
+        assert!(css_classes.contains("md:text-indigo-500"));
+        assert!(css_classes.contains("lg:text-cyan-500"));
+        assert!(css_classes.contains("[@media_(prefers-color-scheme:_dark)]:text-gray-500"));
+        assert!(css_classes.contains("light:text-white"));
     }
 
     #[test]
@@ -524,19 +523,19 @@ mod css_nesting_integration_tests {
         assert!(css_classes.contains("nest-child"));
         assert!(css_classes.contains("nest-hover"));
         assert!(css_classes.contains("nest-sm"));
-        assert!(css_classes.contains("nest-descendant-text-blue-500"));
-        assert!(css_classes.contains("nest-focus-text-red-500"));
-        assert!(css_classes.contains("nest-md-text-green-500"));
-        assert!(css_classes.contains("nest-hover-text-yellow-500"));
-        assert!(css_classes.contains("nest-focus-text-purple-500"));
-        assert!(css_classes.contains("nest-active-text-pink-500"));
-        assert!(css_classes.contains("nest-first-child-text-indigo-500"));
-        assert!(css_classes.contains("nest-last-child-text-cyan-500"));
-        assert!(css_classes.contains("nest-sm-text-gray-500"));
-        assert!(css_classes.contains("nest-md-text-white"));
-        assert!(css_classes.contains("nest-lg-text-black"));
-        assert!(css_classes.contains("nest-dark-text-gray-100"));
-        assert!(css_classes.contains("nest-light-text-gray-900"));
+        assert!(css_classes.contains("[&_*]:text-blue-500"));
+        assert!(css_classes.contains("focus:text-red-500"));
+        assert!(css_classes.contains("md:text-green-500"));
+        assert!(css_classes.contains("hover:text-yellow-500"));
+        assert!(css_classes.contains("focus:text-purple-500"));
+        assert!(css_classes.contains("active:text-pink-500"));
+        assert!(css_classes.contains("first:text-indigo-500"));
+        assert!(css_classes.contains("last:text-cyan-500"));
+        assert!(css_classes.contains("sm:text-gray-500"));
+        assert!(css_classes.contains("md:text-white"));
+        assert!(css_classes.contains("lg:text-black"));
+        assert!(css_classes.contains("[@media_(prefers-color-scheme:_dark)]:text-gray-100"));
+        assert!(css_classes.contains("light:text-gray-900"));
     }
 
     #[test]
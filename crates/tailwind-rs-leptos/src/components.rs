@@ -159,6 +159,9 @@ pub fn CardBody(
 }
 
 /// Input component
+///
+/// The classes only adjust the input's look; enable the `forms` plugin
+/// (`plugins = ["forms"]`) to normalize the underlying element across browsers.
 #[component]
 pub fn Input(
     #[prop(into, optional)] input_type: Signal<String>,
@@ -3,6 +3,8 @@
 //! This module provides efficient class generation using simple string building.
 //! No signals are needed for static class concatenation.
 
+use tailwind_rs_core::expand_variant_groups;
+
 /// Efficient class generation using simple string building
 pub struct DynamicClassBuilder {
     base_classes: String,
@@ -55,6 +57,9 @@ impl DynamicClassBuilder {
     }
 
     /// Get the computed classes as a string
+    ///
+    /// Variant groups (`hover:(bg-blue-600 text-white)`) are expanded into
+    /// individual classes.
     pub fn classes(&self) -> String {
         let mut result = String::new();
 
@@ -95,6 +100,9 @@ impl DynamicClassBuilder {
             result.push_str(&self.custom_classes);
         }
 
+        if result.contains('(') {
+            return expand_variant_groups(&result);
+        }
         result
     }
 
@@ -177,6 +185,18 @@ mod tests {
         assert!(classes.contains("my-custom-class"));
     }
 
+    #[test]
+    fn test_dynamic_class_builder_variant_groups() {
+        let builder = DynamicClassBuilder::new()
+            .base("p-4 w-[calc(100%-1rem)]")
+            .state("hover:(bg-blue-700 text-white)");
+
+        assert_eq!(
+            builder.classes(),
+            "p-4 w-[calc(100%-1rem)] hover:bg-blue-700 hover:text-white"
+        );
+    }
+
     #[test]
     fn test_dynamic_class_builder_combined_classes() {
         let builder = DynamicClassBuilder::new()
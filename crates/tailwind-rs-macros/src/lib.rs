@@ -217,19 +217,19 @@ impl ClassesMacro {
         let mut class_set = tailwind_rs_core::ClassSet::new();
 
         if let Some(ref base) = self.base {
-            class_set.add_classes(base.split_whitespace().map(|s| s.to_string()));
+            class_set.add_classes(tailwind_rs_core::classes::split_classes(base));
         }
 
         if let Some(ref variant) = self.variant {
-            class_set.add_classes(variant.split_whitespace().map(|s| s.to_string()));
+            class_set.add_classes(tailwind_rs_core::classes::split_classes(variant));
         }
 
         if let Some(ref responsive) = self.responsive {
-            class_set.add_classes(responsive.split_whitespace().map(|s| s.to_string()));
+            class_set.add_classes(tailwind_rs_core::classes::split_classes(responsive));
         }
 
         if let Some(ref state) = self.state {
-            class_set.add_classes(state.split_whitespace().map(|s| s.to_string()));
+            class_set.add_classes(tailwind_rs_core::classes::split_classes(state));
         }
 
         for (key, value) in &self.custom {
@@ -300,7 +300,7 @@ impl ResponsiveMacro {
         let mut class_set = tailwind_rs_core::ClassSet::new();
 
         if let Some(ref base) = self.base {
-            class_set.add_classes(base.split_whitespace().map(|s| s.to_string()));
+            class_set.add_classes(tailwind_rs_core::classes::split_classes(base));
         }
 
         if let Some(ref sm) = self.sm {
@@ -543,25 +543,37 @@ impl StateMacro {
         let mut class_set = tailwind_rs_core::ClassSet::new();
 
         if let Some(ref base) = self.base {
-            class_set.add_classes(base.split_whitespace().map(|s| s.to_string()));
+            class_set.add_classes(tailwind_rs_core::classes::split_classes(base));
         }
 
         if let Some(ref hover) = self.hover {
-            class_set.add_classes(hover.split_whitespace().map(|s| format!("hover:{}", s)));
+            class_set.add_classes(
+                tailwind_rs_core::classes::split_classes(hover)
+                    .into_iter()
+                    .map(|s| format!("hover:{}", s)),
+            );
         }
 
         if let Some(ref focus) = self.focus {
-            class_set.add_classes(focus.split_whitespace().map(|s| format!("focus:{}", s)));
+            class_set.add_classes(
+                tailwind_rs_core::classes::split_classes(focus)
+                    .into_iter()
+                    .map(|s| format!("focus:{}", s)),
+            );
         }
 
         if let Some(ref active) = self.active {
-            class_set.add_classes(active.split_whitespace().map(|s| format!("active:{}", s)));
+            class_set.add_classes(
+                tailwind_rs_core::classes::split_classes(active)
+                    .into_iter()
+                    .map(|s| format!("active:{}", s)),
+            );
         }
 
         if let Some(ref disabled) = self.disabled {
             class_set.add_classes(
-                disabled
-                    .split_whitespace()
+                tailwind_rs_core::classes::split_classes(disabled)
+                    .into_iter()
                     .map(|s| format!("disabled:{}", s)),
             );
         }
@@ -636,31 +648,31 @@ impl VariantMacro {
         let mut class_set = tailwind_rs_core::ClassSet::new();
 
         if let Some(ref base) = self.base {
-            class_set.add_classes(base.split_whitespace().map(|s| s.to_string()));
+            class_set.add_classes(tailwind_rs_core::classes::split_classes(base));
         }
 
         if let Some(ref primary) = self.primary {
-            class_set.add_classes(primary.split_whitespace().map(|s| s.to_string()));
+            class_set.add_classes(tailwind_rs_core::classes::split_classes(primary));
         }
 
         if let Some(ref secondary) = self.secondary {
-            class_set.add_classes(secondary.split_whitespace().map(|s| s.to_string()));
+            class_set.add_classes(tailwind_rs_core::classes::split_classes(secondary));
         }
 
         if let Some(ref danger) = self.danger {
-            class_set.add_classes(danger.split_whitespace().map(|s| s.to_string()));
+            class_set.add_classes(tailwind_rs_core::classes::split_classes(danger));
         }
 
         if let Some(ref success) = self.success {
-            class_set.add_classes(success.split_whitespace().map(|s| s.to_string()));
+            class_set.add_classes(tailwind_rs_core::classes::split_classes(success));
         }
 
         if let Some(ref warning) = self.warning {
-            class_set.add_classes(warning.split_whitespace().map(|s| s.to_string()));
+            class_set.add_classes(tailwind_rs_core::classes::split_classes(warning));
         }
 
         if let Some(ref info) = self.info {
-            class_set.add_classes(info.split_whitespace().map(|s| s.to_string()));
+            class_set.add_classes(tailwind_rs_core::classes::split_classes(info));
         }
 
         for (key, value) in &self.custom {
@@ -697,6 +709,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_classes_macro_expands_variant_groups() {
+        let input = quote! {
+            base: "p-4 md:(flex gap-4)",
+            state: "hover:(bg-blue-600 text-white)"
+        };
+
+        let parsed = syn::parse2::<ClassesMacro>(input).unwrap();
+        let classes = parsed.to_class_set().to_css_classes();
+        for class in [
+            "p-4",
+            "md:flex",
+            "md:gap-4",
+            "hover:bg-blue-600",
+            "hover:text-white",
+        ] {
+            assert!(classes.split(' ').any(|c| c == class), "missing {}", class);
+        }
+        assert!(!classes.contains('('));
+    }
+
     #[test]
     fn test_responsive_macro_parser() {
         let input = quote! {
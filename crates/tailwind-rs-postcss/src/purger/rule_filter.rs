@@ -4,6 +4,76 @@ use super::types::*;
 use regex::Regex;
 use std::collections::HashSet;
 
+/// A purge safelist with its patterns compiled
+///
+/// Entries wrapped in slashes are regular expressions (`/^bg-(red|blue)-500$/`)
+/// matched against the utility a class applies its variants to, so they keep
+/// `hover:bg-red-500` as well as `bg-red-500`. Anything else is a class name,
+/// variants included.
+struct Safelist {
+    classes: HashSet<String>,
+    patterns: Vec<Regex>,
+}
+
+impl Safelist {
+    /// Compile the safelist's patterns, failing on the first invalid one
+    fn new(entries: &[String]) -> Result<Self, PurgeError> {
+        let mut classes = HashSet::new();
+        let mut patterns = Vec::new();
+        for entry in entries {
+            match entry
+                .strip_prefix('/')
+                .and_then(|pattern| pattern.strip_suffix('/'))
+            {
+                Some(pattern) => patterns.push(Regex::new(pattern).map_err(|e| {
+                    PurgeError::InvalidConfiguration {
+                        error: format!("Invalid safelist pattern '{}': {}", entry, e),
+                    }
+                })?),
+                None => {
+                    classes.insert(entry.clone());
+                }
+            }
+        }
+        Ok(Self { classes, patterns })
+    }
+
+    /// Check whether the safelist covers a class
+    fn contains(&self, class: &str) -> bool {
+        if self.classes.contains(class) {
+            return true;
+        }
+        let base = base_utility(class);
+        self.patterns.iter().any(|regex| regex.is_match(base))
+    }
+}
+
+/// Check whether a blocklist entry forbids a class
+///
+/// Blocking a utility blocks it under every variant too: `container` also
+/// removes `md:container`.
+fn is_blocked(entry: &str, class: &str) -> bool {
+    entry == class || base_utility(class) == entry
+}
+
+/// The utility a class applies its variants to (`bg-red-500` for
+/// `md:hover:bg-red-500`)
+///
+/// Colons inside arbitrary values (`bg-[url(https://x)]`) don't separate variants.
+fn base_utility(class: &str) -> &str {
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (index, c) in class.char_indices() {
+        match c {
+            '[' | '(' => depth += 1,
+            ']' | ')' => depth -= 1,
+            ':' if depth == 0 => start = index + 1,
+            _ => {}
+        }
+    }
+    &class[start..]
+}
+
 /// Rule filter for CSS purging
 pub struct RuleFilter {
     css_rule_pattern: Regex,
@@ -30,6 +100,7 @@ impl RuleFilter {
         used_classes: &HashSet<String>,
         config: &PurgeConfig,
     ) -> Result<String, PurgeError> {
+        let safelist = Safelist::new(&config.safelist)?;
         let mut filtered_css = String::new();
         let mut _rules_removed = 0;
         let mut _rules_kept = 0;
@@ -39,7 +110,7 @@ impl RuleFilter {
             let selector = cap[1].trim();
             let properties = cap[2].trim();
 
-            if self.should_keep_rule(selector, used_classes, config) {
+            if self.should_keep_rule(selector, used_classes, &safelist, config) {
                 let rule = format!("{} {{\n{}\n}}", selector, properties);
                 filtered_css.push_str(&rule);
                 _rules_kept += 1;
@@ -89,32 +160,37 @@ impl RuleFilter {
     }
 
     /// Check if a rule should be kept
+    ///
+    /// The blocklist wins over everything else, so a blocked class is removed
+    /// even when content uses it; otherwise safelisted and used classes keep
+    /// their rules.
     fn should_keep_rule(
         &self,
         selector: &str,
         used_classes: &HashSet<String>,
+        safelist: &Safelist,
         config: &PurgeConfig,
     ) -> bool {
-        // Check safelist
-        if config
-            .safelist
-            .iter()
-            .any(|pattern| selector.contains(pattern))
-        {
-            return true;
-        }
+        // Extract classes from selector
+        let selector_classes = self.extract_classes_from_selector(selector);
 
         // Check blocklist
-        if config
-            .blocklist
-            .iter()
-            .any(|pattern| selector.contains(pattern))
-        {
+        if selector_classes.iter().any(|class| {
+            config
+                .blocklist
+                .iter()
+                .any(|blocked| is_blocked(blocked, class))
+        }) {
             return false;
         }
 
-        // Extract classes from selector
-        let selector_classes = self.extract_classes_from_selector(selector);
+        // Check safelist
+        if selector_classes
+            .iter()
+            .any(|class| safelist.contains(class))
+        {
+            return true;
+        }
 
         // Check if any class is used
         selector_classes
@@ -123,18 +199,36 @@ impl RuleFilter {
     }
 
     /// Extract classes from a CSS selector
+    ///
+    /// Escaped characters are unescaped (`.md\:p-4` yields `md:p-4`) and
+    /// pseudo-classes are dropped (`.hover\:underline:hover` yields
+    /// `hover:underline`).
     fn extract_classes_from_selector(&self, selector: &str) -> HashSet<String> {
         let mut classes = HashSet::new();
+        let mut chars = selector.chars().peekable();
 
-        // Split selector by combinators
-        let parts: Vec<&str> = selector.split(&[' ', '>', '+', '~', ','][..]).collect();
-
-        for part in parts {
-            let part = part.trim();
-            if part.starts_with('.') {
-                // Extract class name
-                let class_name = part.strip_prefix('.').unwrap_or(part);
-                classes.insert(class_name.to_string());
+        while let Some(c) = chars.next() {
+            if c != '.' {
+                continue;
+            }
+            let mut class_name = String::new();
+            while let Some(&next) = chars.peek() {
+                match next {
+                    '\\' => {
+                        chars.next();
+                        if let Some(escaped) = chars.next() {
+                            class_name.push(escaped);
+                        }
+                    }
+                    c if c.is_alphanumeric() || c == '-' || c == '_' => {
+                        class_name.push(c);
+                        chars.next();
+                    }
+                    _ => break,
+                }
+            }
+            if !class_name.is_empty() {
+                classes.insert(class_name);
             }
         }
 
@@ -178,3 +272,59 @@ impl RuleFilter {
             .to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safelist_and_blocklist() {
+        let filter = RuleFilter::new();
+        let config = PurgeConfig {
+            safelist: vec!["/^bg-(red|blue)-500$/".to_string(), "prose".to_string()],
+            blocklist: vec!["container".to_string()],
+            ..PurgeConfig::default()
+        };
+        let used: HashSet<String> = ["p-4", "container", "md:container"]
+            .iter()
+            .map(|class| class.to_string())
+            .collect();
+
+        let css = ".p-4 { padding: 1rem; }\n\
+                   .m-2 { margin: 0.5rem; }\n\
+                   .hover\\:bg-red-500:hover { background-color: red; }\n\
+                   .prose { max-width: 65ch; }\n\
+                   .container { width: 100%; }\n\
+                   .md\\:container { width: 100%; }";
+        let purged = filter.filter_rules(css, &used, &config).unwrap();
+
+        assert!(purged.contains(".p-4"));
+        assert!(!purged.contains(".m-2"));
+        assert!(purged.contains(".hover\\:bg-red-500"));
+        assert!(purged.contains(".prose"));
+        assert!(!purged.contains(".container"));
+        assert!(!purged.contains("container"));
+    }
+
+    #[test]
+    fn test_invalid_safelist_pattern() {
+        let filter = RuleFilter::new();
+        let config = PurgeConfig {
+            safelist: vec!["/(/".to_string()],
+            ..PurgeConfig::default()
+        };
+        let result = filter.filter_rules(".p-4 { padding: 1rem; }", &HashSet::new(), &config);
+        assert!(matches!(
+            result,
+            Err(PurgeError::InvalidConfiguration { .. })
+        ));
+    }
+
+    #[test]
+    fn test_extract_escaped_classes() {
+        let filter = RuleFilter::new();
+        let classes = filter.extract_classes_from_selector(".md\\:hover\\:p-4:hover > .card");
+        assert!(classes.contains("md:hover:p-4"));
+        assert!(classes.contains("card"));
+    }
+}
@@ -7,7 +7,10 @@ use thiserror::Error;
 #[derive(Debug, Clone)]
 pub struct PurgeConfig {
     pub content_paths: Vec<String>,
+    /// Classes always kept; entries wrapped in slashes are regular expressions
+    /// matched against the utility under any variant
     pub safelist: Vec<String>,
+    /// Classes always removed, under any variant, even when content uses them
     pub blocklist: Vec<String>,
     pub preserve_comments: bool,
     pub preserve_keyframes: bool,
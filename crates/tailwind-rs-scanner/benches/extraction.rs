@@ -0,0 +1,104 @@
+//! Extraction benchmarks
+//!
+//! Compares syntax-aware extraction with candidate extraction over the files
+//! of a repository, and reports how many unique classes each mode finds. The
+//! workspace's own crates are scanned unless `TAILWIND_RS_BENCH_DIR` points
+//! at another repository:
+//!
+//! ```text
+//! TAILWIND_RS_BENCH_DIR=../my-app cargo bench -p tailwind-rs-scanner --bench extraction
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use tailwind_rs_core::extract_candidates;
+use tailwind_rs_scanner::{ClassExtractor, ContentConfig, ExtractionMode, FileInfo, FileScanner};
+use tokio::runtime::Runtime;
+
+/// Read the benchmarked files into memory
+fn load_files(runtime: &Runtime) -> Vec<FileInfo> {
+    let root = std::env::var_os("TAILWIND_RS_BENCH_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| Path::new(env!("CARGO_MANIFEST_DIR")).join(".."));
+    let scanner = FileScanner::new(ContentConfig::default()).unwrap();
+    let files = runtime
+        .block_on(scanner.discover_files(&[root.display().to_string()]))
+        .unwrap();
+    files
+        .into_iter()
+        .filter(|file| file.content.is_some())
+        .collect()
+}
+
+/// Extract the classes of every file
+fn extract_all(runtime: &Runtime, extractor: &ClassExtractor, files: &[FileInfo]) -> usize {
+    runtime.block_on(async {
+        let mut total = 0;
+        for file in files {
+            total += extractor.extract_classes(file).await.unwrap().len();
+        }
+        total
+    })
+}
+
+fn bench_extraction(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let files = load_files(&runtime);
+    let bytes: usize = files
+        .iter()
+        .filter_map(|file| file.content.as_ref())
+        .map(String::len)
+        .sum();
+
+    let mut group = c.benchmark_group("extraction");
+    group.throughput(Throughput::Bytes(bytes as u64));
+    group.sample_size(10);
+
+    for (name, mode) in [
+        ("syntax", ExtractionMode::Syntax),
+        ("candidates", ExtractionMode::Candidates),
+    ] {
+        let extractor = ClassExtractor::from_config(&ContentConfig {
+            extraction_mode: mode,
+            ..ContentConfig::default()
+        });
+
+        let found: BTreeSet<String> = runtime.block_on(async {
+            let mut found = BTreeSet::new();
+            for file in &files {
+                let classes = extractor.extract_classes(file).await.unwrap();
+                found.extend(classes.into_iter().map(|class| class.class_name));
+            }
+            found
+        });
+        eprintln!(
+            "{}: {} unique classes in {} files ({} bytes)",
+            name,
+            found.len(),
+            files.len(),
+            bytes
+        );
+
+        // Candidate validation is cached by the extractor, so iterations
+        // after the first measure a warm scanner as in watch mode
+        group.bench_function(name, |b| {
+            b.iter(|| black_box(extract_all(&runtime, &extractor, &files)))
+        });
+    }
+
+    group.bench_function("tokenize", |b| {
+        b.iter(|| {
+            files
+                .iter()
+                .filter_map(|file| file.content.as_deref())
+                .map(|content| extract_candidates(black_box(content)).count())
+                .sum::<usize>()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_extraction);
+criterion_main!(benches);
@@ -169,6 +169,9 @@ mod tests {
             context: ClassContext::new(),
             line: 1,
             column: 1,
+            byte_range: 0..3,
+            node_kind: None,
+            item: None,
         }];
 
         let entry = CacheEntry {
@@ -192,6 +195,9 @@ mod tests {
             context: ClassContext::new(),
             line: 1,
             column: 1,
+            byte_range: 0..3,
+            node_kind: None,
+            item: None,
         }];
 
         // Add to cache
@@ -3,12 +3,20 @@
 //! This module provides class extraction capabilities for different
 //! file types and languages.
 
-use crate::content_config::ExtractionRule;
+use crate::content_config::{ContentConfig, ExtractionMode, ExtractionRule};
+use crate::dynamic_classes::{partial_classes, DynamicClass};
 use crate::error::{Result, ScannerError};
 use crate::file_scanner::{FileInfo, FileType};
+use crate::rust_macro_extractor::RustMacroExtractor;
+use crate::tree_sitter_parser::TreeSitterParser;
+use dashmap::DashMap;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::ops::Range;
+use std::path::Path;
+use tailwind_rs_core::classes::split_classes;
+use tailwind_rs_core::{extract_candidates, CssGenerator};
 
 /// Class extractor for different file types
 #[derive(Debug)]
@@ -17,6 +25,18 @@ pub struct ClassExtractor {
     patterns: HashMap<FileType, Vec<Regex>>,
     /// Custom extraction rules
     custom_rules: Vec<ExtractionRule>,
+    /// Macro-aware extraction for Rust files
+    rust_macros: RustMacroExtractor,
+    /// Syntax-aware extraction, used for the languages it supports
+    tree_sitter: TreeSitterParser,
+    /// Whether to prefer tree-sitter over the regex patterns
+    use_tree_sitter: bool,
+    /// How classes are found in file contents
+    mode: ExtractionMode,
+    /// Generator that validates candidates
+    generator: CssGenerator,
+    /// Whether each candidate class seen so far resolves
+    resolved: DashMap<String, bool>,
 }
 
 /// Extracted class with context
@@ -30,6 +50,18 @@ pub struct ExtractedClass {
     pub line: usize,
     /// Column number
     pub column: usize,
+    /// Byte range in the file of the class, or of the variant group it was
+    /// expanded from
+    #[serde(default)]
+    pub byte_range: Range<usize>,
+    /// Kind of the syntax node or macro the class was found in, when
+    /// extracted from a syntax tree
+    #[serde(default)]
+    pub node_kind: Option<String>,
+    /// Name of the function, method or component the class appears in,
+    /// when known
+    #[serde(default)]
+    pub item: Option<String>,
 }
 
 /// Class context information
@@ -88,6 +120,44 @@ impl ClassExtractor {
         Self {
             patterns,
             custom_rules: Vec::new(),
+            rust_macros: RustMacroExtractor::new(),
+            tree_sitter: TreeSitterParser::new(),
+            use_tree_sitter: true,
+            mode: ExtractionMode::default(),
+            generator: CssGenerator::new(),
+            resolved: DashMap::new(),
+        }
+    }
+
+    /// Create a class extractor, choosing whether to use tree-sitter
+    ///
+    /// Tree-sitter is only available with the `tree-sitter` feature; without
+    /// it, or for languages it doesn't support, the regex patterns are used.
+    pub fn with_tree_sitter(enabled: bool) -> Self {
+        Self {
+            use_tree_sitter: enabled,
+            ..Self::new()
+        }
+    }
+
+    /// Create a class extractor with the extraction mode and tree-sitter
+    /// setting of a content configuration
+    pub fn from_config(config: &ContentConfig) -> Self {
+        Self {
+            use_tree_sitter: config.enable_tree_sitter,
+            mode: config.extraction_mode,
+            ..Self::new()
+        }
+    }
+
+    /// Validate candidates and dynamic class prefixes against a configured
+    /// generator, so classes of its custom utilities, components and theme
+    /// are found too
+    pub fn with_generator(self, generator: CssGenerator) -> Self {
+        Self {
+            generator,
+            resolved: DashMap::new(),
+            ..self
         }
     }
 
@@ -147,30 +217,172 @@ impl ClassExtractor {
             ScannerError::FileContentNotAvailable("File content not available".to_string())
         })?;
 
+        Ok(self.extract_content(&file_info.path, &file_info.file_type, content))
+    }
+
+    /// Extract classes from the contents of a file that isn't held in a
+    /// [`FileInfo`], such as a memory-mapped one
+    pub(crate) fn extract_content(
+        &self,
+        path: &Path,
+        file_type: &FileType,
+        content: &str,
+    ) -> Vec<ExtractedClass> {
         let mut classes = Vec::new();
 
-        // Extract using file type patterns
-        if let Some(patterns) = self.patterns.get(&file_info.file_type) {
-            for pattern in patterns {
-                let matches = self.extract_with_pattern(content, pattern, &file_info.file_type);
-                classes.extend(matches);
+        if self.mode == ExtractionMode::Candidates {
+            classes.extend(self.extract_candidates(content, file_type));
+        } else {
+            // Extract from the syntax tree, falling back to the file type
+            // patterns when the file doesn't parse
+            let rust_classes = match file_type {
+                FileType::Rust => self.rust_macros.extract_classes(content).ok(),
+                _ => None,
+            };
+            let syntax_classes = rust_classes.or_else(|| {
+                self.syntax_language(path)
+                    .and_then(|language| self.tree_sitter.extract_classes(content, language).ok())
+            });
+            if let Some(syntax_classes) = syntax_classes {
+                classes.extend(syntax_classes);
+            } else if let Some(patterns) = self.patterns.get(file_type) {
+                for pattern in patterns {
+                    let matches = self.extract_with_pattern(content, pattern, file_type);
+                    classes.extend(matches);
+                }
             }
         }
 
         // Extract using custom rules
         for rule in &self.custom_rules {
-            if self.rule_applies_to_file(rule, &file_info.path) {
+            if rule.applies_to(path) {
                 if let Ok(pattern) = Regex::new(&rule.class_pattern) {
-                    let matches =
-                        self.extract_with_pattern(content, &pattern, &file_info.file_type);
+                    let matches = self.extract_with_pattern(content, &pattern, file_type);
                     classes.extend(matches);
                 }
             }
         }
 
+        // Classes found without a syntax tree still get their enclosing item
+        if *file_type == FileType::Rust && classes.iter().any(|class| class.node_kind.is_none()) {
+            if let Ok(items) = self.rust_macros.items(content) {
+                for class in classes.iter_mut().filter(|class| class.node_kind.is_none()) {
+                    class.item = innermost_item(&items, class.byte_range.start);
+                }
+            }
+        }
+
+        classes
+    }
+
+    /// Find the class names a file builds at runtime from partial utilities
+    ///
+    /// Rust files are read with `syn`, and JavaScript, TypeScript and
+    /// embedded scripts with tree-sitter when it is enabled. Only words whose
+    /// prefix starts a utility the generator knows, like `bg-` in
+    /// `bg-{}-500`, are reported.
+    pub fn dynamic_classes(&self, file_info: &FileInfo) -> Result<Vec<DynamicClass>> {
+        let content = file_info.content.as_ref().ok_or_else(|| {
+            ScannerError::FileContentNotAvailable("File content not available".to_string())
+        })?;
+
+        let sites = match file_info.file_type {
+            FileType::Rust => self.rust_macros.dynamic_sites(content).ok(),
+            _ => self
+                .syntax_language(&file_info.path)
+                .and_then(|language| self.tree_sitter.dynamic_sites(content, language).ok()),
+        };
+
+        let lines = LineIndex::new(content);
+        let mut classes = Vec::new();
+        for site in sites.unwrap_or_default() {
+            for (template, prefix) in partial_classes(&site.template) {
+                let Some(family) = self.utility_family(prefix) else {
+                    continue;
+                };
+                let (line, column) = lines.position(site.range.start);
+                classes.push(DynamicClass {
+                    path: file_info.path.clone(),
+                    line,
+                    column,
+                    byte_range: site.range.clone(),
+                    template: template.to_string(),
+                    family,
+                    item: site.item.clone(),
+                });
+            }
+        }
+
         Ok(classes)
     }
 
+    /// The utility family a class prefix like `hover:bg-` starts, if the
+    /// generator resolves any common value for it
+    fn utility_family(&self, prefix: &str) -> Option<String> {
+        const SAMPLE_VALUES: &[&str] = &[
+            "red-500", "white", "4", "1", "sm", "lg", "full", "bold", "center", "none", "auto",
+        ];
+        let utility = prefix.rsplit(':').next().unwrap_or(prefix);
+        SAMPLE_VALUES
+            .iter()
+            .any(|value| self.resolves(&format!("{}{}", utility, value)))
+            .then(|| utility.trim_end_matches('-').to_string())
+    }
+
+    /// The tree-sitter language to parse a file with, if it is supported
+    fn syntax_language(&self, path: &Path) -> Option<&str> {
+        if !self.use_tree_sitter {
+            return None;
+        }
+        self.tree_sitter
+            .language_for_path(path)
+            .filter(|language| self.tree_sitter.is_language_supported(language))
+    }
+
+    /// Extract every candidate token of a file that resolves to a class
+    fn extract_candidates(&self, content: &str, file_type: &FileType) -> Vec<ExtractedClass> {
+        let lines = LineIndex::new(content);
+        let mut classes = Vec::new();
+
+        for candidate in extract_candidates(content) {
+            let range = candidate.range();
+            for class_name in split_classes(candidate.value) {
+                if !self.resolves(&class_name) {
+                    continue;
+                }
+                let (line, column) = lines.position(range.start);
+                let line_text = lines.line_text(range.start);
+                classes.push(ExtractedClass {
+                    context: ClassContext {
+                        code_context: line_text.to_string(),
+                        file_type: format!("{:?}", file_type),
+                        in_string: self.is_in_string(line_text, column - 1),
+                        in_comment: self.is_in_comment(line_text, column - 1),
+                        metadata: HashMap::new(),
+                    },
+                    class_name,
+                    line,
+                    column,
+                    byte_range: range.clone(),
+                    node_kind: None,
+                    item: None,
+                });
+            }
+        }
+
+        classes
+    }
+
+    /// Check whether a candidate class resolves, remembering the answer
+    fn resolves(&self, class: &str) -> bool {
+        if let Some(resolved) = self.resolved.get(class) {
+            return *resolved;
+        }
+        let resolved = self.generator.resolves_class(class);
+        self.resolved.insert(class.to_string(), resolved);
+        resolved
+    }
+
     /// Extract classes using a specific pattern
     fn extract_with_pattern(
         &self,
@@ -179,24 +391,43 @@ impl ClassExtractor {
         file_type: &FileType,
     ) -> Vec<ExtractedClass> {
         let mut classes = Vec::new();
+        let mut line_start = 0;
 
-        for (line_num, line) in content.lines().enumerate() {
-            for mat in pattern.find_iter(line) {
-                let class_name = mat.as_str().to_string();
-                let context = ClassContext {
-                    code_context: line.to_string(),
-                    file_type: format!("{:?}", file_type),
-                    in_string: self.is_in_string(line, mat.start()),
-                    in_comment: self.is_in_comment(line, mat.start()),
-                    metadata: HashMap::new(),
-                };
+        for (line_num, line) in content.split_inclusive('\n').enumerate() {
+            let offset = line_start;
+            line_start += line.len();
+            let line = line.trim_end_matches(['\n', '\r']);
 
-                classes.push(ExtractedClass {
-                    class_name,
-                    context,
-                    line: line_num + 1,
-                    column: mat.start() + 1,
-                });
+            for captures in pattern.captures_iter(line) {
+                // Patterns capture the class list; fall back to the whole match
+                let Some(mat) = captures.get(1).or_else(|| captures.get(0)) else {
+                    continue;
+                };
+                let in_string = self.is_in_string(line, mat.start());
+                let in_comment = self.is_in_comment(line, mat.start());
+
+                for (range, class_names) in class_tokens(mat.as_str()) {
+                    let start = mat.start() + range.start;
+                    for class_name in class_names {
+                        let context = ClassContext {
+                            code_context: line.to_string(),
+                            file_type: format!("{:?}", file_type),
+                            in_string,
+                            in_comment,
+                            metadata: HashMap::new(),
+                        };
+
+                        classes.push(ExtractedClass {
+                            class_name,
+                            context,
+                            line: line_num + 1,
+                            column: start + 1,
+                            byte_range: offset + start..offset + mat.start() + range.end,
+                            node_kind: None,
+                            item: None,
+                        });
+                    }
+                }
             }
         }
 
@@ -219,13 +450,6 @@ impl ClassExtractor {
         before.contains("//") || before.contains("/*")
     }
 
-    /// Check if a rule applies to a file
-    fn rule_applies_to_file(&self, rule: &ExtractionRule, file_path: &std::path::Path) -> bool {
-        // Simple pattern matching - in a real implementation, use proper glob matching
-        let path_str = file_path.to_string_lossy();
-        path_str.contains(&rule.file_pattern)
-    }
-
     /// Add a custom extraction rule
     pub fn add_custom_rule(&mut self, rule: ExtractionRule) -> Result<()> {
         // Validate the regex pattern
@@ -245,6 +469,102 @@ impl ClassExtractor {
     }
 }
 
+/// Split a class list into its top-level tokens and expand each one
+///
+/// Returns the byte range of every token within `text` together with the
+/// classes it expands to; a variant group like `hover:(a b)` is one token.
+pub(crate) fn class_tokens(text: &str) -> Vec<(Range<usize>, Vec<String>)> {
+    let mut ranges = Vec::new();
+    let mut depth = 0usize;
+    let mut start = None;
+
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            c if c.is_whitespace() && depth == 0 => {
+                if let Some(start) = start.take() {
+                    ranges.push(start..i);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
+    }
+    if let Some(start) = start {
+        ranges.push(start..text.len());
+    }
+
+    ranges
+        .into_iter()
+        .map(|range| {
+            let classes = split_classes(&text[range.clone()]);
+            (range, classes)
+        })
+        .collect()
+}
+
+/// Check if a word from a string literal could be a class
+///
+/// Literals outside a class attribute only count as class lists when every
+/// word in them passes, which skips prose and format strings.
+pub(crate) fn looks_like_class(word: &str) -> bool {
+    let Some(first) = word.chars().next() else {
+        return false;
+    };
+    (first.is_ascii_lowercase() || first.is_ascii_digit() || matches!(first, '-' | '!' | '['))
+        && word.is_ascii()
+        && !word.ends_with([':', '.', ',', ';'])
+        && !word.contains(['"', '\'', '`', '{', '}', '\\', '<'])
+}
+
+/// Name of the smallest item whose byte range holds an offset
+pub(crate) fn innermost_item(items: &[(Range<usize>, String)], offset: usize) -> Option<String> {
+    items
+        .iter()
+        .filter(|(range, _)| range.contains(&offset))
+        .min_by_key(|(range, _)| range.len())
+        .map(|(_, name)| name.clone())
+}
+
+/// Line lookup for byte offsets in a file
+pub(crate) struct LineIndex<'a> {
+    content: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub(crate) fn new(content: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            content,
+            line_starts,
+        }
+    }
+
+    /// 1-based line and byte column of an offset
+    pub(crate) fn position(&self, offset: usize) -> (usize, usize) {
+        let line = self
+            .line_starts
+            .partition_point(|&line_start| line_start <= offset);
+        (line, offset - self.line_starts[line - 1] + 1)
+    }
+
+    /// Text of the line holding an offset, without its line ending
+    pub(crate) fn line_text(&self, offset: usize) -> &'a str {
+        let (line, _) = self.position(offset);
+        let start = self.line_starts[line - 1];
+        let end = self
+            .line_starts
+            .get(line)
+            .map_or(self.content.len(), |next| next - 1);
+        self.content[start..end].trim_end_matches('\r')
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -277,6 +597,9 @@ mod tests {
             context: ClassContext::new(),
             line: 1,
             column: 1,
+            byte_range: 0..3,
+            node_kind: None,
+            item: None,
         };
 
         assert_eq!(class.class_name, "p-4");
@@ -293,6 +616,118 @@ mod tests {
         assert!(!extractor.is_in_string(line, 5));
     }
 
+    #[test]
+    fn test_variant_groups_are_expanded() {
+        let extractor = ClassExtractor::new();
+        let pattern = &extractor.patterns[&FileType::Html][0];
+        let content = r#"<div class="p-4 hover:(bg-blue-600 text-white) w-[calc(100%-1rem)]">"#;
+
+        let classes: Vec<String> = extractor
+            .extract_with_pattern(content, pattern, &FileType::Html)
+            .into_iter()
+            .map(|class| class.class_name)
+            .collect();
+        assert_eq!(
+            classes,
+            vec![
+                "p-4",
+                "hover:bg-blue-600",
+                "hover:text-white",
+                "w-[calc(100%-1rem)]"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extracted_byte_ranges() {
+        let extractor = ClassExtractor::new();
+        let pattern = &extractor.patterns[&FileType::Html][0];
+        let content = "<p>\n<div class=\"p-4 hover:(m-2 flex)\">";
+
+        let classes = extractor.extract_with_pattern(content, pattern, &FileType::Html);
+        let ranges: Vec<&str> = classes
+            .iter()
+            .map(|class| &content[class.byte_range.clone()])
+            .collect();
+        assert_eq!(ranges, vec!["p-4", "hover:(m-2 flex)", "hover:(m-2 flex)"]);
+        assert_eq!((classes[1].line, classes[1].column), (2, 17));
+    }
+
+    #[test]
+    fn test_candidate_extraction() {
+        let extractor = ClassExtractor::from_config(&ContentConfig {
+            extraction_mode: ExtractionMode::Candidates,
+            ..ContentConfig::default()
+        });
+        let content = "const CARD: &str = \"rounded-lg p-4\";\nmatch size { Size::Lg => \"text-lg\", _ => \"hidden\" }";
+
+        let classes = extractor.extract_candidates(content, &FileType::Rust);
+        let names: Vec<&str> = classes
+            .iter()
+            .map(|class| class.class_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["rounded-lg", "p-4", "text-lg", "hidden"]);
+        assert_eq!(&content[classes[2].byte_range.clone()], "text-lg");
+        assert_eq!((classes[2].line, classes[2].column), (2, 27));
+        assert!(classes[2].context.in_string);
+    }
+
+    #[tokio::test]
+    async fn test_candidates_record_enclosing_item() {
+        let extractor = ClassExtractor::from_config(&ContentConfig {
+            extraction_mode: ExtractionMode::Candidates,
+            ..ContentConfig::default()
+        });
+        let file_info = FileInfo {
+            path: PathBuf::from("src/card.rs"),
+            file_type: FileType::Rust,
+            size: 0,
+            modified: std::time::SystemTime::UNIX_EPOCH,
+            content: Some("fn card() -> &'static str {\n    \"rounded-lg\"\n}\n".to_string()),
+        };
+
+        let classes = extractor.extract_classes(&file_info).await.unwrap();
+        let class = classes
+            .iter()
+            .find(|class| class.class_name == "rounded-lg")
+            .unwrap();
+        assert_eq!(class.item.as_deref(), Some("card"));
+    }
+
+    #[test]
+    fn test_dynamic_classes() {
+        let extractor = ClassExtractor::new();
+        let file_info = FileInfo {
+            path: PathBuf::from("src/badge.rs"),
+            file_type: FileType::Rust,
+            size: 0,
+            modified: std::time::SystemTime::UNIX_EPOCH,
+            content: Some(
+                "fn badge(tone: &str, id: u32) -> String {\n    \
+                 let key = format!(\"user-{}\", id);\n    \
+                 format!(\"px-2 hover:bg-{tone}-500\")\n}\n"
+                    .to_string(),
+            ),
+        };
+
+        let classes = extractor.dynamic_classes(&file_info).unwrap();
+        assert_eq!(classes.len(), 1);
+        assert_eq!(classes[0].template, "hover:bg-{}-500");
+        assert_eq!(classes[0].family, "bg");
+        assert_eq!(classes[0].item.as_deref(), Some("badge"));
+        assert_eq!((classes[0].line, classes[0].column), (3, 13));
+    }
+
+    #[test]
+    fn test_looks_like_class() {
+        assert!(looks_like_class("hover:bg-blue-500"));
+        assert!(looks_like_class("-mt-4"));
+        assert!(looks_like_class("w-[calc(100%-1rem)]"));
+        assert!(!looks_like_class("Hello"));
+        assert!(!looks_like_class("{}"));
+        assert!(!looks_like_class("world."));
+    }
+
     #[test]
     fn test_comment_detection() {
         let extractor = ClassExtractor::new();
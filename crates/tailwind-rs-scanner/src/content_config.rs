@@ -3,8 +3,10 @@
 //! This module provides configuration structures for content scanning
 //! and file pattern matching.
 
+use crate::glob_matcher::GlobPattern;
 use serde::{Deserialize, Serialize};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use tailwind_rs_core::TailwindConfig;
 
 /// Main scan configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,10 +21,19 @@ pub struct ScanConfig {
     pub enable_cache: bool,
     /// Cache TTL in seconds
     pub cache_ttl: u64,
+    /// Directory to persist the cache in between runs, such as
+    /// `target/tailwind-rs`; the cache is kept in memory when unset
+    #[serde(default)]
+    pub cache_dir: Option<PathBuf>,
     /// Enable file watching
     pub enable_watching: bool,
     /// Watch debounce time in milliseconds
     pub watch_debounce: u64,
+    /// Project configuration whose utilities, components, theme and
+    /// safelist candidates are validated against; the built-in utilities
+    /// are used when unset
+    #[serde(default)]
+    pub tailwind_config: Option<TailwindConfig>,
 }
 
 /// Content configuration
@@ -38,12 +49,30 @@ pub struct ContentConfig {
     pub extensions: Vec<String>,
     /// Directories to ignore
     pub ignore_dirs: Vec<String>,
+    /// Skip files ignored by `.gitignore`, `.ignore` and git's exclude files
+    #[serde(default = "default_gitignore")]
+    pub gitignore: bool,
     /// Enable tree-sitter parsing
     pub enable_tree_sitter: bool,
+    /// How classes are found in file contents
+    #[serde(default)]
+    pub extraction_mode: ExtractionMode,
     /// Custom class extraction rules
     pub custom_rules: Vec<ExtractionRule>,
 }
 
+/// How classes are found in file contents
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtractionMode {
+    /// Read class attributes and framework macros from the file's syntax
+    #[default]
+    Syntax,
+    /// Split files into candidate tokens and keep the ones the generator
+    /// resolves, finding classes anywhere in any file
+    Candidates,
+}
+
 /// File pattern for matching files
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FilePattern {
@@ -71,7 +100,7 @@ pub enum PatternType {
 pub struct ExtractionRule {
     /// Rule name
     pub name: String,
-    /// File pattern this rule applies to
+    /// Glob of the files this rule applies to (`**/*.vue`)
     pub file_pattern: String,
     /// Regex pattern for class extraction
     pub class_pattern: String,
@@ -87,12 +116,18 @@ impl Default for ScanConfig {
             max_workers: None,
             enable_cache: true,
             cache_ttl: 3600, // 1 hour
+            cache_dir: None,
             enable_watching: false,
             watch_debounce: 100, // 100ms
+            tailwind_config: None,
         }
     }
 }
 
+fn default_gitignore() -> bool {
+    true
+}
+
 impl Default for ContentConfig {
     fn default() -> Self {
         Self {
@@ -136,7 +171,9 @@ impl Default for ContentConfig {
                 ".next".to_string(),
                 ".nuxt".to_string(),
             ],
+            gitignore: default_gitignore(),
             enable_tree_sitter: true,
+            extraction_mode: ExtractionMode::default(),
             custom_rules: Vec::new(),
         }
     }
@@ -177,10 +214,7 @@ impl FilePattern {
         };
 
         match self.pattern_type {
-            PatternType::Glob => {
-                // Simple glob matching (in a real implementation, use a proper glob library)
-                self.matches_glob(&target, &pattern)
-            }
+            PatternType::Glob => self.matches_glob(&path_str),
             PatternType::Regex => {
                 // Regex matching
                 if let Ok(regex) = regex::Regex::new(&pattern) {
@@ -193,38 +227,14 @@ impl FilePattern {
         }
     }
 
-    /// Simple glob matching implementation
-    fn matches_glob(&self, path: &str, pattern: &str) -> bool {
-        // This is a simplified glob matcher
-        // In a real implementation, use a proper glob library like globset
-        if pattern == "**/*" {
-            return true;
-        }
-
-        if pattern.starts_with("**/") {
-            let suffix = &pattern[3..];
-            if suffix.contains('*') {
-                // Handle patterns like **/*.rs
-                let suffix_parts: Vec<&str> = suffix.split('*').collect();
-                if suffix_parts.len() == 2 {
-                    let prefix = suffix_parts[0];
-                    let suffix_end = suffix_parts[1];
-                    return path.ends_with(suffix_end);
-                }
-            }
-            return path.ends_with(suffix);
+    /// Glob matching; `**` spans directories, so `**/target/**` matches
+    /// anything below a `target` directory at any depth
+    fn matches_glob(&self, path: &str) -> bool {
+        GlobPattern {
+            case_sensitive: self.case_sensitive,
+            ..GlobPattern::new(&self.pattern)
         }
-
-        if pattern.contains('*') {
-            let parts: Vec<&str> = pattern.split('*').collect();
-            if parts.len() == 2 {
-                let prefix = parts[0];
-                let suffix = parts[1];
-                return path.starts_with(prefix) && path.ends_with(suffix);
-            }
-        }
-
-        path == pattern
+        .matches(path)
     }
 }
 
@@ -253,6 +263,11 @@ impl ExtractionRule {
             context_pattern: Some(context_pattern.to_string()),
         }
     }
+
+    /// Check if the rule's file glob matches a file path
+    pub fn applies_to(&self, file_path: &Path) -> bool {
+        GlobPattern::new(&self.file_pattern).matches(&file_path.to_string_lossy())
+    }
 }
 
 #[cfg(test)]
@@ -291,6 +306,10 @@ mod tests {
 
         assert!(pattern.matches_file(rust_file));
         assert!(!pattern.matches_file(js_file));
+
+        let exclude = FilePattern::new("**/target/**", PatternType::Glob);
+        assert!(exclude.matches_file(Path::new("/work/app/target/debug/build.rs")));
+        assert!(!exclude.matches_file(Path::new("/work/app/src/target.rs")));
     }
 
     #[test]
@@ -301,6 +320,10 @@ mod tests {
         assert_eq!(rule.file_pattern, "**/*.rs");
         assert_eq!(rule.class_pattern, r#"class\s*=\s*"([^"]+)""#);
         assert!(rule.context_pattern.is_none());
+
+        assert!(rule.applies_to(Path::new("src/components/button.rs")));
+        // File patterns are globs, not substrings
+        assert!(!rule.applies_to(Path::new("src/main.rs.bak")));
     }
 
     #[test]
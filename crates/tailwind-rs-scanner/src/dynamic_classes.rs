@@ -0,0 +1,211 @@
+//! Dynamically constructed class detection
+//!
+//! Class names built at runtime, like `format!("bg-{}-500", color)` in Rust
+//! or `` `text-${size}` `` and `"text-" + size` in JavaScript, never appear in
+//! the source as a whole and so are missing from the generated CSS. The
+//! extractors record every interpolated or concatenated string as a
+//! [`DynamicSite`], with its holes written as `{}`; words of it that start
+//! with a utility prefix such as `bg-` are reported as [`DynamicClass`]es.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Range;
+use std::path::PathBuf;
+use tailwind_rs_core::SafelistEntry;
+
+/// Placeholder standing for an interpolated value in a site's template
+pub(crate) const HOLE: &str = "{}";
+
+/// A string built from literal text and runtime values
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct DynamicSite {
+    /// Byte range of the expression in the file
+    pub(crate) range: Range<usize>,
+    /// Literal text of the string with each interpolated value as `{}`
+    pub(crate) template: String,
+    /// Function, method or component the expression appears in, if any
+    pub(crate) item: Option<String>,
+}
+
+/// A class name completed at runtime from a partial utility
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DynamicClass {
+    /// File the class is built in
+    pub path: PathBuf,
+    /// Line number
+    pub line: usize,
+    /// Column number
+    pub column: usize,
+    /// Byte range of the expression building the class
+    pub byte_range: Range<usize>,
+    /// The class with each interpolated value as `{}`, like `hover:bg-{}-500`
+    pub template: String,
+    /// Utility family the class likely belongs to, like `bg`
+    pub family: String,
+    /// Function, method or component the class is built in, when known
+    pub item: Option<String>,
+}
+
+impl DynamicClass {
+    /// Variants the template applies, like `hover` in `hover:bg-{}`
+    pub fn variants(&self) -> Vec<&str> {
+        let (variants, _) = split_template(&self.template);
+        variants
+    }
+
+    /// A safelist entry generating every utility the template can produce
+    pub fn safelist_entry(&self) -> SafelistEntry {
+        let (variants, utility) = split_template(&self.template);
+        let pattern = utility
+            .split(HOLE)
+            .map(regex::escape)
+            .collect::<Vec<_>>()
+            .join(".+");
+        variants.into_iter().fold(
+            SafelistEntry::pattern(format!("^{}$", pattern)),
+            |entry, variant| entry.with_variant(variant),
+        )
+    }
+
+    /// How to make the class visible to the scanner
+    pub fn suggestion(&self) -> String {
+        let pattern = match self.safelist_entry() {
+            SafelistEntry::Pattern { pattern, .. } => pattern,
+            SafelistEntry::Class(class) => class,
+        };
+        format!(
+            "write each complete `{}` class in a `match` arm, or safelist the pattern `{}`",
+            self.family, pattern
+        )
+    }
+}
+
+impl fmt::Display for DynamicClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: class `{}` is built at runtime",
+            self.path.display(),
+            self.line,
+            self.column,
+            self.template
+        )?;
+        if let Some(item) = &self.item {
+            write!(f, " in `{}`", item)?;
+        }
+        write!(f, "; {}", self.suggestion())
+    }
+}
+
+/// The words of a template that interpolate into a utility, with the prefix
+/// before their first hole
+///
+/// `"flex bg-{}-500 {}"` yields `bg-{}-500` with the prefix `bg-`; words
+/// starting with a hole, or whose prefix doesn't end in `-`, can't be told
+/// apart from other text and are skipped.
+pub(crate) fn partial_classes(template: &str) -> Vec<(&str, &str)> {
+    template
+        .split_whitespace()
+        .filter_map(|word| {
+            let (prefix, _) = word.split_once(HOLE)?;
+            let (_, utility) = prefix.rsplit_once(':').unwrap_or(("", prefix));
+            (utility.len() > 1
+                && utility.ends_with('-')
+                && utility
+                    .trim_start_matches(['-', '!'])
+                    .starts_with(|c: char| c.is_ascii_lowercase())
+                && prefix
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | ':' | '!' | '_')))
+            .then_some((word, prefix))
+        })
+        .collect()
+}
+
+/// Convert a Rust format string to a template, replacing each `{…}`
+/// argument with a hole and unescaping `{{` and `}}`
+pub(crate) fn format_template(format: &str) -> String {
+    let mut template = String::new();
+    let mut chars = format.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                template.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                template.push('}');
+            }
+            '{' => {
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                }
+                template.push_str(HOLE);
+            }
+            c => template.push(c),
+        }
+    }
+    template
+}
+
+/// Split a template into its variants and the utility they apply to
+fn split_template(template: &str) -> (Vec<&str>, &str) {
+    let mut parts: Vec<&str> = template.split(':').collect();
+    let utility = parts.pop().unwrap_or_default();
+    (parts, utility)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dynamic(template: &str, family: &str) -> DynamicClass {
+        DynamicClass {
+            path: PathBuf::from("src/badge.rs"),
+            line: 3,
+            column: 14,
+            byte_range: 0..1,
+            template: template.to_string(),
+            family: family.to_string(),
+            item: Some("Badge".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_partial_classes() {
+        assert_eq!(
+            partial_classes("flex bg-{}-500 {} hover:text-{} user-{}x"),
+            vec![
+                ("bg-{}-500", "bg-"),
+                ("hover:text-{}", "hover:text-"),
+                ("user-{}x", "user-"),
+            ]
+        );
+        assert!(partial_classes("Hello {}, {}-{}").is_empty());
+        assert!(partial_classes("https://example.com/{}").is_empty());
+    }
+
+    #[test]
+    fn test_format_template() {
+        assert_eq!(format_template("bg-{color}-500 {:>4}"), "bg-{}-500 {}");
+        assert_eq!(format_template("{{literal}} p-{}"), "{literal} p-{}");
+    }
+
+    #[test]
+    fn test_safelist_suggestion() {
+        let class = dynamic("hover:bg-{}-500", "bg");
+        assert_eq!(
+            class.safelist_entry(),
+            SafelistEntry::pattern("^bg\\-.+\\-500$").with_variant("hover")
+        );
+        assert_eq!(class.variants(), vec!["hover"]);
+        assert_eq!(
+            class.to_string(),
+            "src/badge.rs:3:14: class `hover:bg-{}-500` is built at runtime in `Badge`; \
+             write each complete `bg` class in a `match` arm, or safelist the pattern `^bg\\-.+\\-500$`"
+        );
+    }
+}
@@ -6,22 +6,31 @@
 use crate::cache::{CacheEntry, CacheStats, ScanCache};
 use crate::class_extractor::{ClassContext, ClassExtractor, ExtractedClass};
 use crate::content_config::{ContentConfig, FilePattern, ScanConfig};
+use crate::dynamic_classes::DynamicClass;
 use crate::error::{Result, ScannerError};
+use crate::file_watcher::{ClassDelta, ClassDeltaStream, FileWatcher, WatchConfig, WatchEvent};
 use crate::parallel_processor::{ParallelProcessor, ProcessingStats};
+use crate::usage_index::UsageIndex;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tailwind_rs_core::{content_hash, BuildCache, CacheKey, CssGenerator};
+use tokio::sync::{RwLock, RwLockReadGuard};
 
 /// Main content scanner
 #[derive(Debug)]
 pub struct ContentScanner {
     config: ScanConfig,
-    file_scanner: FileScanner,
-    class_extractor: ClassExtractor,
-    parallel_processor: ParallelProcessor,
+    file_scanner: Arc<FileScanner>,
+    class_extractor: Arc<ClassExtractor>,
+    parallel_processor: Arc<ParallelProcessor>,
     cache: Arc<RwLock<ScanCache>>,
+    /// Classes of scanned files by content hash, persisted when configured
+    file_cache: Arc<RwLock<BuildCache<Vec<ExtractedClass>>>>,
+    /// Where each class of the scanned files is used
+    usage_index: Arc<RwLock<UsageIndex>>,
 }
 
 /// File scanner for discovering and reading files
@@ -41,6 +50,44 @@ pub struct FileInfo {
     pub content: Option<String>,
 }
 
+/// Files at least this large are memory-mapped instead of read into memory
+pub(crate) const MMAP_THRESHOLD: u64 = 1024 * 1024;
+
+/// Contents of a file being processed, dropped once its classes are extracted
+pub(crate) struct FileContents {
+    pub(crate) file_type: FileType,
+    data: FileData,
+}
+
+enum FileData {
+    Read(String),
+    Mapped(memmap2::Mmap),
+}
+
+impl FileContents {
+    /// The contents as text, failing for files that aren't UTF-8
+    pub(crate) fn text(&self) -> Result<&str> {
+        match &self.data {
+            FileData::Read(content) => Ok(content),
+            FileData::Mapped(map) => std::str::from_utf8(map)
+                .map_err(|e| ScannerError::IoError(format!("File is not valid UTF-8: {}", e))),
+        }
+    }
+
+    /// Size of the contents in bytes
+    pub(crate) fn len(&self) -> usize {
+        match &self.data {
+            FileData::Read(content) => content.len(),
+            FileData::Mapped(map) => map.len(),
+        }
+    }
+
+    /// Whether the file was memory-mapped
+    pub(crate) fn is_mapped(&self) -> bool {
+        matches!(self.data, FileData::Mapped(_))
+    }
+}
+
 /// Supported file types
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FileType {
@@ -71,6 +118,15 @@ pub struct ClassSet {
     pub processing_stats: ProcessingStats,
 }
 
+/// State of a watch stream between batches of events
+struct WatchState {
+    watcher: FileWatcher,
+    /// Classes of each watched file as of its last scan
+    known: HashMap<PathBuf, HashSet<String>>,
+    /// Deltas of the current batch not yet yielded
+    pending: VecDeque<ClassDelta>,
+}
+
 impl ClassSet {
     /// Create a new empty class set
     pub fn new() -> Self {
@@ -121,6 +177,15 @@ impl ClassSet {
         classes.sort();
         classes
     }
+
+    /// Index the usages of every class by file, line and enclosing item
+    pub fn usage_index(&self) -> UsageIndex {
+        let mut index = UsageIndex::new();
+        for (path, classes) in &self.file_classes {
+            index.update_file(path.clone(), classes.clone());
+        }
+        index
+    }
 }
 
 impl Default for ClassSet {
@@ -132,10 +197,11 @@ impl Default for ClassSet {
 impl ContentScanner {
     /// Create a new content scanner
     pub fn new(config: ScanConfig) -> Result<Self> {
-        let file_scanner = FileScanner::new(config.content_config.clone())?;
-        let class_extractor = ClassExtractor::new();
-        let parallel_processor = ParallelProcessor::new(config.parallel_processing);
+        let file_scanner = Arc::new(FileScanner::new(config.content_config.clone())?);
+        let class_extractor = Arc::new(class_extractor(&config)?);
+        let parallel_processor = Arc::new(ParallelProcessor::from_config(&config));
         let cache = Arc::new(RwLock::new(ScanCache::new()));
+        let file_cache = Arc::new(RwLock::new(load_file_cache(&config)));
 
         Ok(Self {
             config,
@@ -143,58 +209,253 @@ impl ContentScanner {
             class_extractor,
             parallel_processor,
             cache,
+            file_cache,
+            usage_index: Arc::new(RwLock::new(UsageIndex::new())),
         })
     }
 
     /// Scan for classes in specified paths
+    ///
+    /// Files are discovered lazily and read, extracted and dropped one at a
+    /// time by each worker of the parallel processor, reusing the classes of
+    /// files that haven't changed since they were cached. Cached files no
+    /// scan has visited since the cache was loaded are dropped before it is
+    /// saved. The work runs on a blocking thread, leaving the async runtime
+    /// free meanwhile.
     pub async fn scan_paths(&self, paths: &[String]) -> Result<ClassSet> {
         let start_time = std::time::Instant::now();
 
-        // Discover files
-        let files = self.file_scanner.discover_files(paths).await?;
-
-        // Process files in parallel
-        let results = self
-            .parallel_processor
-            .process_files(&files, &self.class_extractor, &self.config)
-            .await?;
+        let file_scanner = Arc::clone(&self.file_scanner);
+        let class_extractor = Arc::clone(&self.class_extractor);
+        let parallel_processor = Arc::clone(&self.parallel_processor);
+        let file_cache = Arc::clone(&self.file_cache);
+        let enable_cache = self.config.enable_cache;
+        let paths = paths.to_vec();
+        let processed = tokio::task::spawn_blocking(move || {
+            let mut file_cache = file_cache.blocking_write();
+            let processed = parallel_processor.process_paths(
+                file_scanner.discover_paths(&paths),
+                &file_scanner,
+                &class_extractor,
+                enable_cache.then_some(&mut *file_cache),
+            )?;
+            if enable_cache {
+                file_cache.prune();
+                file_cache
+                    .save()
+                    .map_err(|e| ScannerError::CacheError(e.to_string()))?;
+            }
+            Ok::<_, ScannerError>(processed)
+        })
+        .await
+        .map_err(|e| ScannerError::Generic(format!("Scan failed: {}", e)))??;
 
         // Build class set
         let mut class_set = ClassSet::new();
-        for (file_path, classes) in results {
+        for (file_path, classes) in processed.files {
             class_set.add_file_classes(file_path, classes);
         }
 
+        class_set.processing_stats = processed.stats;
         class_set.processing_stats.total_time = start_time.elapsed();
         class_set.total_classes = class_set.classes.len();
 
-        // Update cache
+        // Update cache and usage index
         {
             let mut cache = self.cache.write().await;
+            let mut usage_index = self.usage_index.write().await;
             for (file_path, classes) in &class_set.file_classes {
                 cache.update_file(file_path.clone(), classes.clone());
+                usage_index.update_file(file_path.clone(), classes.clone());
             }
         }
 
         Ok(class_set)
     }
 
+    /// Usages of the classes of every file scanned so far
+    ///
+    /// Each scan adds or replaces the files it found, and watch streams keep
+    /// the index current as files change or are deleted.
+    pub async fn usage_index(&self) -> RwLockReadGuard<'_, UsageIndex> {
+        self.usage_index.read().await
+    }
+
     /// Scan for classes in a single file
     pub async fn scan_file(&self, file_path: &Path) -> Result<Vec<ExtractedClass>> {
         let file_info = self.file_scanner.read_file(file_path).await?;
         self.class_extractor.extract_classes(&file_info).await
     }
 
+    /// Find the class names built at runtime in specified paths, ordered by
+    /// file and position
+    ///
+    /// These classes never appear whole in the source, so they are missing
+    /// from the generated CSS unless written out or safelisted.
+    pub async fn find_dynamic_classes(&self, paths: &[String]) -> Result<Vec<DynamicClass>> {
+        let mut files = self.file_scanner.discover_files(paths).await?;
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let mut classes = Vec::new();
+        for file in &files {
+            if file.content.is_some() {
+                classes.extend(self.class_extractor.dynamic_classes(file)?);
+            }
+        }
+        Ok(classes)
+    }
+
+    /// Watch paths and stream the classes added and removed in changed files
+    ///
+    /// Uses the debounce and exclude patterns of the scan configuration. See
+    /// [`ContentScanner::watch_with`].
+    pub async fn watch(&self, paths: &[String]) -> Result<ClassDeltaStream<'_>> {
+        let config = WatchConfig {
+            paths: paths.iter().map(PathBuf::from).collect(),
+            debounce: std::time::Duration::from_millis(self.config.watch_debounce),
+            exclude_patterns: self.config.content_config.exclude_patterns.clone(),
+            ..WatchConfig::default()
+        };
+        self.watch_with(config).await
+    }
+
+    /// Watch with an explicit watch configuration
+    ///
+    /// The watched paths are scanned once to record the classes of every
+    /// file. After that, each debounced batch of changes rescans only the
+    /// affected files and yields one [`ClassDelta`] per file whose classes
+    /// changed; a deleted file yields all of its classes as removed.
+    pub async fn watch_with(&self, config: WatchConfig) -> Result<ClassDeltaStream<'_>> {
+        let mut watcher = FileWatcher::new(config);
+        // Watch before the initial scan so no change can slip in between
+        watcher.start().await?;
+
+        let roots: Vec<String> = watcher
+            .config()
+            .paths
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        let initial = self.scan_paths(&roots).await?;
+        let known = initial
+            .file_classes
+            .into_iter()
+            .filter(|(path, _)| !watcher.is_ignored(path))
+            .map(|(path, classes)| (path, class_names(&classes)))
+            .collect();
+
+        let state = WatchState {
+            watcher,
+            known,
+            pending: VecDeque::new(),
+        };
+        let stream = futures::stream::unfold(state, move |mut state| async move {
+            loop {
+                if let Some(delta) = state.pending.pop_front() {
+                    return Some((delta, state));
+                }
+                let events = state.watcher.next_events().await?;
+                for event in events {
+                    self.apply_watch_event(&mut state, event).await;
+                }
+            }
+        });
+        Ok(Box::pin(stream))
+    }
+
+    /// Rescan the files affected by a watch event
+    async fn apply_watch_event(&self, state: &mut WatchState, event: WatchEvent) {
+        match event {
+            WatchEvent::Created(path) | WatchEvent::Modified(path) => {
+                self.rescan_watched_path(state, &path).await
+            }
+            WatchEvent::Deleted(path) => self.forget_watched_path(state, &path).await,
+            WatchEvent::Moved(from, to) => {
+                self.forget_watched_path(state, &from).await;
+                self.rescan_watched_path(state, &to).await;
+            }
+        }
+    }
+
+    /// Rescan a changed file, or every file of a new directory
+    async fn rescan_watched_path(&self, state: &mut WatchState, path: &Path) {
+        let files = if path.is_dir() {
+            self.file_scanner
+                .scan_directory(path)
+                .await
+                .unwrap_or_default()
+        } else {
+            match self.file_scanner.read_file(path).await {
+                Ok(file) if self.file_scanner.matches_patterns(&file.path) => vec![file],
+                Ok(_) => return,
+                // The file is gone again by the time we read it
+                Err(_) => return self.forget_watched_path(state, path).await,
+            }
+        };
+
+        for file in files {
+            if state.watcher.is_ignored(&file.path) {
+                continue;
+            }
+            let Ok(classes) = self.class_extractor.extract_classes(&file).await else {
+                continue;
+            };
+            let old = state
+                .known
+                .insert(file.path.clone(), class_names(&classes))
+                .unwrap_or_default();
+            let delta = class_delta(file.path.clone(), &old, &state.known[&file.path]);
+            self.usage_index
+                .write()
+                .await
+                .update_file(file.path.clone(), classes.clone());
+            self.cache.write().await.update_file(file.path, classes);
+            if !delta.is_empty() {
+                state.pending.push_back(delta);
+            }
+        }
+    }
+
+    /// Drop a deleted file, or every file below a deleted directory
+    async fn forget_watched_path(&self, state: &mut WatchState, path: &Path) {
+        let mut removed: Vec<PathBuf> = state
+            .known
+            .keys()
+            .filter(|known| known.starts_with(path))
+            .cloned()
+            .collect();
+        removed.sort();
+
+        let mut cache = self.cache.write().await;
+        let mut usage_index = self.usage_index.write().await;
+        for file in removed {
+            let old = state.known.remove(&file).unwrap_or_default();
+            cache.remove_file(&file);
+            usage_index.remove_file(&file);
+            let delta = class_delta(file, &old, &HashSet::new());
+            if !delta.is_empty() {
+                state.pending.push_back(delta);
+            }
+        }
+    }
+
     /// Get cache statistics
     pub async fn get_cache_stats(&self) -> CacheStats {
         let cache = self.cache.read().await;
-        cache.get_stats()
+        let file_cache = self.file_cache.read().await;
+        CacheStats {
+            hits: file_cache.hits(),
+            misses: file_cache.misses(),
+            ..cache.get_stats()
+        }
     }
 
     /// Clear cache
     pub async fn clear_cache(&self) {
         let mut cache = self.cache.write().await;
         cache.clear();
+        self.file_cache.write().await.clear();
+        self.usage_index.write().await.clear();
     }
 
     /// Get scanner configuration
@@ -205,12 +466,83 @@ impl ContentScanner {
     /// Update scanner configuration
     pub fn update_config(&mut self, config: ScanConfig) -> Result<()> {
         self.config = config;
-        self.file_scanner = FileScanner::new(self.config.content_config.clone())?;
-        self.parallel_processor = ParallelProcessor::new(self.config.parallel_processing);
+        self.file_scanner = Arc::new(FileScanner::new(self.config.content_config.clone())?);
+        self.class_extractor = Arc::new(class_extractor(&self.config)?);
+        self.file_cache = Arc::new(RwLock::new(load_file_cache(&self.config)));
+        self.usage_index = Arc::new(RwLock::new(UsageIndex::new()));
+        self.parallel_processor = Arc::new(ParallelProcessor::from_config(&self.config));
         Ok(())
     }
 }
 
+/// Version of the cached extraction results, bumped when `ExtractedClass`
+/// gains data that older entries lack
+const CACHE_FORMAT: u32 = 2;
+
+/// Create the class extractor of a scan configuration, validating against
+/// the generator of its project configuration when one is set
+fn class_extractor(config: &ScanConfig) -> Result<ClassExtractor> {
+    let extractor = ClassExtractor::from_config(&config.content_config);
+    let Some(tailwind_config) = &config.tailwind_config else {
+        return Ok(extractor);
+    };
+    let mut generator = CssGenerator::new();
+    generator
+        .apply_config(tailwind_config)
+        .map_err(|e| ScannerError::ConfigError(e.to_string()))?;
+    Ok(extractor.with_generator(generator))
+}
+
+/// Load the file cache of a scan configuration
+fn load_file_cache(config: &ScanConfig) -> BuildCache<Vec<ExtractedClass>> {
+    let key = cache_key(config);
+    match &config.cache_dir {
+        Some(dir) => BuildCache::load(dir.join("scan-cache.json"), key),
+        None => BuildCache::new(key),
+    }
+}
+
+/// Key of the file cache of a scan configuration
+///
+/// The cache is keyed by the content and project configurations, so
+/// changing what or how files are extracted starts a new one. They're
+/// hashed through [`serde_json::Value`], whose objects sort their keys, so
+/// the key doesn't depend on the iteration order of their `HashMap`s.
+fn cache_key(config: &ScanConfig) -> CacheKey {
+    let scanner = format!(
+        "tailwind-rs-scanner {} format {}{}",
+        env!("CARGO_PKG_VERSION"),
+        CACHE_FORMAT,
+        if cfg!(feature = "tree-sitter") {
+            " tree-sitter"
+        } else {
+            ""
+        }
+    );
+    let configs = serde_json::to_value((&config.content_config, &config.tailwind_config))
+        .and_then(|value| serde_json::to_vec(&value))
+        .unwrap_or_default();
+    CacheKey::new(scanner, content_hash(&configs))
+}
+
+/// Unique class names of extracted classes
+fn class_names(classes: &[ExtractedClass]) -> HashSet<String> {
+    classes.iter().map(|c| c.class_name.clone()).collect()
+}
+
+/// Classes added and removed between two scans of a file
+fn class_delta(path: PathBuf, old: &HashSet<String>, new: &HashSet<String>) -> ClassDelta {
+    let mut added: Vec<String> = new.difference(old).cloned().collect();
+    let mut removed: Vec<String> = old.difference(new).cloned().collect();
+    added.sort();
+    removed.sort();
+    ClassDelta {
+        path,
+        added,
+        removed,
+    }
+}
+
 impl FileScanner {
     /// Create a new file scanner
     pub fn new(config: ContentConfig) -> Result<Self> {
@@ -261,28 +593,74 @@ impl FileScanner {
         Ok(files)
     }
 
+    /// Discover the files whose contents are scanned, one at a time
+    ///
+    /// Unlike [`FileScanner::discover_files`] nothing is read: directories
+    /// are walked lazily as the iterator advances, so files can be processed
+    /// while the rest of a large tree is still being discovered.
+    pub fn discover_paths<'a>(
+        &'a self,
+        paths: &[String],
+    ) -> impl Iterator<Item = PathBuf> + Send + 'a {
+        let roots: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+        roots
+            .into_iter()
+            .flat_map(
+                move |root| -> Box<dyn Iterator<Item = PathBuf> + Send + 'a> {
+                    if root.is_file() {
+                        Box::new(std::iter::once(root))
+                    } else if root.is_dir() {
+                        Box::new(self.walk_directory(&root))
+                    } else {
+                        Box::new(std::iter::empty())
+                    }
+                },
+            )
+            .filter(move |path| self.should_read_content(&self.detect_file_type(path)))
+    }
+
     /// Scan a directory for files
+    ///
+    /// Ignored directories and files excluded by `.gitignore`, `.ignore` or
+    /// the exclude patterns are skipped without being read.
     async fn scan_directory(&self, dir_path: &Path) -> Result<Vec<FileInfo>> {
         let mut files = Vec::new();
-
-        for entry in walkdir::WalkDir::new(dir_path)
-            .follow_links(false)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            if entry.file_type().is_file() {
-                if let Ok(file_info) = self.read_file(entry.path()).await {
-                    // Check if file matches any of the patterns
-                    if self.matches_patterns(&file_info) {
-                        files.push(file_info);
-                    }
-                }
+        for path in self.walk_directory(dir_path) {
+            if let Ok(file_info) = self.read_file(&path).await {
+                files.push(file_info);
             }
         }
 
         Ok(files)
     }
 
+    /// Walk a directory for the files matching the patterns and size limit
+    fn walk_directory(&self, dir_path: &Path) -> impl Iterator<Item = PathBuf> + Send + '_ {
+        let ignore_dirs = self.config.ignore_dirs.clone();
+
+        let walker = ignore::WalkBuilder::new(dir_path)
+            .standard_filters(self.config.gitignore)
+            .require_git(false)
+            .follow_links(false)
+            .filter_entry(move |entry| {
+                !(entry
+                    .file_type()
+                    .is_some_and(|file_type| file_type.is_dir())
+                    && entry.depth() > 0
+                    && ignore_dirs
+                        .iter()
+                        .any(|dir| entry.file_name() == dir.as_str()))
+            })
+            .build();
+        walker.filter_map(|e| e.ok()).filter_map(move |entry| {
+            let is_file = entry
+                .file_type()
+                .is_some_and(|file_type| file_type.is_file());
+            (is_file && self.matches_patterns(entry.path()) && !self.is_too_large(&entry))
+                .then(|| entry.into_path())
+        })
+    }
+
     /// Read a file and return file information
     pub async fn read_file(&self, file_path: &Path) -> Result<FileInfo> {
         let metadata = std::fs::metadata(file_path)?;
@@ -302,6 +680,33 @@ impl FileScanner {
         })
     }
 
+    /// Read a file's contents for extraction, memory-mapping large files
+    ///
+    /// Returns `None` for files larger than the maximum file size, which is
+    /// checked again here since a file can grow after it was discovered.
+    pub(crate) fn read_contents(&self, file_path: &Path) -> Result<Option<FileContents>> {
+        let mut file = std::fs::File::open(file_path)?;
+        let size = file.metadata()?.len();
+        if self.config.max_file_size.is_some_and(|max| size > max) {
+            return Ok(None);
+        }
+
+        let data = if size >= MMAP_THRESHOLD {
+            // SAFETY: the map is only read while the file's classes are
+            // extracted and is dropped right after. As with any mapped file,
+            // another process truncating it meanwhile can fault the read.
+            FileData::Mapped(unsafe { memmap2::Mmap::map(&file)? })
+        } else {
+            let mut content = String::with_capacity(size as usize);
+            file.read_to_string(&mut content)?;
+            FileData::Read(content)
+        };
+        Ok(Some(FileContents {
+            file_type: self.detect_file_type(file_path),
+            data,
+        }))
+    }
+
     /// Detect file type from extension
     fn detect_file_type(&self, file_path: &Path) -> FileType {
         if let Some(extension) = file_path.extension() {
@@ -314,19 +719,29 @@ impl FileScanner {
         FileType::Other("unknown".to_string())
     }
 
-    /// Check if file matches any of the configured patterns
-    fn matches_patterns(&self, file_info: &FileInfo) -> bool {
-        if self.config.patterns.is_empty() {
-            return true; // No patterns means match all
-        }
+    /// Check if a file matches the configured patterns and no exclude pattern
+    fn matches_patterns(&self, path: &Path) -> bool {
+        // No patterns means match all
+        let included = self.config.patterns.is_empty()
+            || self
+                .config
+                .patterns
+                .iter()
+                .any(|pattern| pattern.matches_file(path));
+        included
+            && !self
+                .config
+                .exclude_patterns
+                .iter()
+                .any(|pattern| pattern.matches_file(path))
+    }
 
-        for pattern in &self.config.patterns {
-            if pattern.matches_file(&file_info.path) {
-                return true;
-            }
+    /// Check if a file is larger than the configured maximum
+    fn is_too_large(&self, entry: &ignore::DirEntry) -> bool {
+        match (self.config.max_file_size, entry.metadata()) {
+            (Some(max), Ok(metadata)) => metadata.len() > max,
+            _ => false,
         }
-
-        false
     }
 
     /// Check if we should read file content
@@ -357,6 +772,14 @@ impl Default for ProcessingStats {
             total_time: std::time::Duration::from_millis(0),
             average_file_time: std::time::Duration::from_millis(0),
             memory_usage: 0,
+            cached_files: 0,
+            skipped_files: 0,
+            mapped_files: 0,
+            workers: 0,
+            discovery_time: std::time::Duration::from_millis(0),
+            read_time: std::time::Duration::from_millis(0),
+            extraction_time: std::time::Duration::from_millis(0),
+            merge_time: std::time::Duration::from_millis(0),
         }
     }
 }
@@ -364,7 +787,9 @@ impl Default for ProcessingStats {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::content_config::{ExtractionMode, PatternType};
     use std::path::PathBuf;
+    use tailwind_rs_core::TailwindConfig;
 
     #[test]
     fn test_class_set_creation() {
@@ -383,12 +808,18 @@ mod tests {
                 context: ClassContext::new(),
                 line: 1,
                 column: 1,
+                byte_range: 0..3,
+                node_kind: None,
+                item: None,
             },
             ExtractedClass {
                 class_name: "bg-blue-500".to_string(),
                 context: ClassContext::new(),
                 line: 2,
                 column: 1,
+                byte_range: 4..15,
+                node_kind: None,
+                item: None,
             },
         ];
 
@@ -414,10 +845,212 @@ mod tests {
         assert_eq!(scanner.detect_file_type(html_file), FileType::Html);
     }
 
+    #[tokio::test]
+    async fn test_discover_files_skips_ignored_and_excluded() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/generated")).unwrap();
+        std::fs::create_dir_all(root.join("node_modules/lib")).unwrap();
+        std::fs::write(root.join(".gitignore"), "generated/\n").unwrap();
+        std::fs::write(root.join("src/app.rs"), "").unwrap();
+        std::fs::write(root.join("src/app.test.rs"), "").unwrap();
+        std::fs::write(root.join("src/generated/icons.rs"), "").unwrap();
+        std::fs::write(root.join("node_modules/lib/index.js"), "").unwrap();
+
+        let mut config = ContentConfig::default();
+        config
+            .exclude_patterns
+            .push(FilePattern::new("**/*.test.rs", PatternType::Glob));
+        let scanner = FileScanner::new(config).unwrap();
+        let files = scanner
+            .discover_files(&[root.to_string_lossy().into_owned()])
+            .await
+            .unwrap();
+
+        let paths: Vec<_> = files.iter().map(|file| file.path.clone()).collect();
+        assert_eq!(paths, [root.join("src/app.rs")]);
+    }
+
     #[tokio::test]
     async fn test_content_scanner_creation() {
         let config = ScanConfig::default();
         let scanner = ContentScanner::new(config);
         assert!(scanner.is_ok());
     }
+
+    #[test]
+    fn test_cache_key_ignores_map_order() {
+        fn config_with<'a>(names: impl Iterator<Item = &'a str>) -> ScanConfig {
+            let mut tailwind_config = TailwindConfig::new();
+            for name in names {
+                tailwind_config
+                    .components
+                    .insert(name.to_string(), format!("@apply p-{}", name.len()));
+            }
+            ScanConfig {
+                tailwind_config: Some(tailwind_config),
+                ..ScanConfig::default()
+            }
+        }
+
+        let names = [
+            "btn", "card", "badge", "alert", "chip", "panel", "tag", "toast",
+        ];
+        let forward = config_with(names.into_iter());
+        let backward = config_with(names.into_iter().rev());
+        assert_eq!(cache_key(&forward), cache_key(&backward));
+        assert_eq!(
+            cache_key(&ScanConfig::default()),
+            cache_key(&ScanConfig::default())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scan_cache_persists_between_scanners() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("src");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("page.html"), r#"<div class="p-4 m-2"></div>"#).unwrap();
+        std::fs::write(root.join("card.html"), r#"<div class="flex"></div>"#).unwrap();
+        let paths = [root.to_string_lossy().into_owned()];
+        let config = ScanConfig {
+            cache_dir: Some(dir.path().join("target/tailwind-rs")),
+            ..ScanConfig::default()
+        };
+
+        let first = ContentScanner::new(config.clone()).unwrap();
+        let classes = first.scan_paths(&paths).await.unwrap();
+        assert_eq!(classes.processing_stats.cached_files, 0);
+        assert!(dir
+            .path()
+            .join("target/tailwind-rs/scan-cache.json")
+            .exists());
+
+        std::fs::write(root.join("card.html"), r#"<div class="grid"></div>"#).unwrap();
+        let second = ContentScanner::new(config.clone()).unwrap();
+        let classes = second.scan_paths(&paths).await.unwrap();
+        assert_eq!(classes.processing_stats.cached_files, 1);
+        assert_eq!(classes.get_all_classes(), vec!["grid", "m-2", "p-4"]);
+        assert_eq!(second.get_cache_stats().await.hits, 1);
+
+        // Deleted files are pruned from the saved cache
+        std::fs::remove_file(root.join("page.html")).unwrap();
+        let third = ContentScanner::new(config).unwrap();
+        third.scan_paths(&paths).await.unwrap();
+        let saved =
+            std::fs::read_to_string(dir.path().join("target/tailwind-rs/scan-cache.json")).unwrap();
+        assert!(saved.contains("card.html"));
+        assert!(!saved.contains("page.html"));
+    }
+
+    #[tokio::test]
+    async fn test_candidates_resolve_against_project_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("page.html"),
+            "<pre>tab-4 btn-primary p-4 not-a-class</pre>",
+        )
+        .unwrap();
+        let paths = [dir.path().to_string_lossy().into_owned()];
+        let mut config = ScanConfig {
+            enable_cache: false,
+            ..ScanConfig::default()
+        };
+        config.content_config.extraction_mode = ExtractionMode::Candidates;
+
+        let scanner = ContentScanner::new(config.clone()).unwrap();
+        let classes = scanner.scan_paths(&paths).await.unwrap();
+        assert_eq!(classes.get_all_classes(), vec!["p-4"]);
+
+        config.tailwind_config = Some(
+            TailwindConfig::from_str(
+                "[utilities]\n\"tab-4\" = \"tab-size: 4\"\n\n[components]\n\"btn-primary\" = \"px-4 py-2\"\n",
+            )
+            .unwrap(),
+        );
+        let scanner = ContentScanner::new(config).unwrap();
+        let classes = scanner.scan_paths(&paths).await.unwrap();
+        assert_eq!(
+            classes.get_all_classes(),
+            vec!["btn-primary", "p-4", "tab-4"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_dynamic_classes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("badge.rs"),
+            "fn badge(size: &str) -> String {\n    \"text-\".to_string() + size\n}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("card.rs"), "const CARD: &str = \"p-4\";\n").unwrap();
+
+        let scanner = ContentScanner::new(ScanConfig::default()).unwrap();
+        let classes = scanner
+            .find_dynamic_classes(&[dir.path().to_string_lossy().into_owned()])
+            .await
+            .unwrap();
+        assert_eq!(classes.len(), 1);
+        assert_eq!(classes[0].template, "text-{}");
+        assert_eq!(classes[0].line, 2);
+        assert!(classes[0].path.ends_with("badge.rs"));
+    }
+
+    #[tokio::test]
+    async fn test_watch_streams_class_deltas() {
+        use futures::StreamExt;
+        use std::time::Duration;
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join("generated")).unwrap();
+        std::fs::write(root.join(".gitignore"), "generated/\n").unwrap();
+        let page = root.join("page.html");
+        std::fs::write(&page, r#"<div class="p-4 m-2"></div>"#).unwrap();
+
+        let scanner = ContentScanner::new(ScanConfig {
+            watch_debounce: 50,
+            ..ScanConfig::default()
+        })
+        .unwrap();
+        let mut changes = scanner
+            .watch(&[root.to_string_lossy().into_owned()])
+            .await
+            .unwrap();
+
+        // Ignored files never produce a delta
+        std::fs::write(
+            root.join("generated/out.html"),
+            r#"<div class="hidden"></div>"#,
+        )
+        .unwrap();
+        // Atomic save: write a temporary file and rename it over the page
+        let temp = root.join("page.html.tmp");
+        std::fs::write(&temp, r#"<div class="p-4 text-center"></div>"#).unwrap();
+        std::fs::rename(&temp, &page).unwrap();
+
+        let delta = tokio::time::timeout(Duration::from_secs(10), changes.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(delta.path, page);
+        assert_eq!(delta.added, vec!["text-center"]);
+        assert_eq!(delta.removed, vec!["m-2"]);
+        assert_eq!(
+            scanner.usage_index().await.files_using("text-center"),
+            vec![&page]
+        );
+        assert!(scanner.usage_index().await.usages("m-2").is_empty());
+
+        std::fs::remove_file(&page).unwrap();
+        let delta = tokio::time::timeout(Duration::from_secs(10), changes.next())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(delta.path, page);
+        assert!(delta.added.is_empty());
+        assert_eq!(delta.removed, vec!["p-4", "text-center"]);
+        assert!(scanner.usage_index().await.is_empty());
+    }
 }
@@ -2,18 +2,42 @@
 //!
 //! This module provides file watching capabilities for
 //! real-time content scanning and updates.
+//!
+//! [`FileWatcher`] runs an OS watcher through `notify` and coalesces each
+//! burst of raw events into a batch of [`WatchEvent`]s once the watched paths
+//! have been quiet for [`WatchConfig::debounce`]. Coalescing looks at the
+//! state of each touched path at the end of the burst, so an editor's atomic
+//! save (write a temporary file, rename it over the original) is reported as
+//! a single change to the original file. Paths matching the exclude patterns
+//! or ignored by `.gitignore` are dropped before a batch is emitted.
 
+use crate::content_config::FilePattern;
 use crate::error::{Result, ScannerError};
-use std::path::PathBuf;
+use futures::Stream;
+use ignore::gitignore::Gitignore;
+use ignore::Match;
+use notify::event::{ModifyKind, RenameMode};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::mpsc;
 
 /// File watcher for real-time updates
-#[derive(Debug)]
 pub struct FileWatcher {
     /// Watch configuration
     config: WatchConfig,
     /// Whether watching is enabled
     enabled: bool,
+    /// Exclude patterns and `.gitignore` rules
+    filter: Arc<WatchFilter>,
+    /// OS watcher, alive while watching
+    watcher: Option<RecommendedWatcher>,
+    /// Debounced event batches
+    events: Option<mpsc::UnboundedReceiver<Vec<WatchEvent>>>,
 }
 
 /// Watch configuration
@@ -25,10 +49,14 @@ pub struct WatchConfig {
     pub debounce: Duration,
     /// Whether to watch recursively
     pub recursive: bool,
+    /// Paths to ignore changes in
+    pub exclude_patterns: Vec<FilePattern>,
+    /// Whether to ignore paths matched by `.gitignore` files
+    pub respect_gitignore: bool,
 }
 
 /// Watch event
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum WatchEvent {
     /// File created
     Created(PathBuf),
@@ -40,23 +68,95 @@ pub enum WatchEvent {
     Moved(PathBuf, PathBuf),
 }
 
+/// Classes added to and removed from a file by a change
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassDelta {
+    /// Changed file
+    pub path: PathBuf,
+    /// Classes the file uses now but didn't before, sorted
+    pub added: Vec<String>,
+    /// Classes the file no longer uses, sorted
+    pub removed: Vec<String>,
+}
+
+/// Stream of class changes returned by
+/// [`ContentScanner::watch`](crate::ContentScanner::watch)
+pub type ClassDeltaStream<'a> = Pin<Box<dyn Stream<Item = ClassDelta> + Send + 'a>>;
+
+impl ClassDelta {
+    /// Check if the change added or removed no classes
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
 impl FileWatcher {
     /// Create a new file watcher
-    pub fn new(config: WatchConfig) -> Self {
+    ///
+    /// Watched paths are canonicalized so event paths can be compared with
+    /// the paths of a scan of the same roots.
+    pub fn new(mut config: WatchConfig) -> Self {
+        config.paths = config
+            .paths
+            .iter()
+            .map(|path| path.canonicalize().unwrap_or_else(|_| path.clone()))
+            .collect();
+        let filter = Arc::new(WatchFilter::new(&config));
+
         Self {
             config,
             enabled: false,
+            filter,
+            watcher: None,
+            events: None,
         }
     }
 
     /// Start watching
     pub async fn start(&mut self) -> Result<()> {
+        if self.enabled {
+            return Ok(());
+        }
+
+        let (raw_tx, raw_rx) = mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            // Watcher errors carry no path to rescan, so they are dropped
+            if let Ok(event) = event {
+                let _ = raw_tx.send(event);
+            }
+        })
+        .map_err(|e| ScannerError::WatchError(e.to_string()))?;
+
+        let mode = if self.config.recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        for path in &self.config.paths {
+            watcher
+                .watch(path, mode)
+                .map_err(|e| ScannerError::WatchError(format!("{}: {}", path.display(), e)))?;
+        }
+
+        let (batch_tx, batch_rx) = mpsc::unbounded_channel();
+        tokio::spawn(debounce_events(
+            raw_rx,
+            batch_tx,
+            self.config.debounce,
+            Arc::clone(&self.filter),
+        ));
+
+        self.watcher = Some(watcher);
+        self.events = Some(batch_rx);
         self.enabled = true;
         Ok(())
     }
 
     /// Stop watching
     pub async fn stop(&mut self) -> Result<()> {
+        // Dropping the OS watcher closes the channels and ends the debouncer
+        self.watcher = None;
+        self.events = None;
         self.enabled = false;
         Ok(())
     }
@@ -65,6 +165,32 @@ impl FileWatcher {
     pub fn is_enabled(&self) -> bool {
         self.enabled
     }
+
+    /// Get the watch configuration
+    pub fn config(&self) -> &WatchConfig {
+        &self.config
+    }
+
+    /// Wait for the next debounced batch of events
+    ///
+    /// Returns `None` when the watcher isn't running.
+    pub async fn next_events(&mut self) -> Option<Vec<WatchEvent>> {
+        self.events.as_mut()?.recv().await
+    }
+
+    /// Check if changes to a path are ignored
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        self.filter.is_ignored(path)
+    }
+}
+
+impl std::fmt::Debug for FileWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileWatcher")
+            .field("config", &self.config)
+            .field("enabled", &self.enabled)
+            .finish()
+    }
 }
 
 impl Default for WatchConfig {
@@ -73,13 +199,198 @@ impl Default for WatchConfig {
             paths: Vec::new(),
             debounce: Duration::from_millis(100),
             recursive: true,
+            exclude_patterns: Vec::new(),
+            respect_gitignore: true,
         }
     }
 }
 
+/// Exclude patterns and `.gitignore` rules applied to watched paths
+#[derive(Debug, Default)]
+struct WatchFilter {
+    exclude_patterns: Vec<FilePattern>,
+    /// Deepest directory first, so nested files override their parents
+    gitignores: Vec<Gitignore>,
+}
+
+impl WatchFilter {
+    fn new(config: &WatchConfig) -> Self {
+        let mut filter = Self {
+            exclude_patterns: config.exclude_patterns.clone(),
+            gitignores: Vec::new(),
+        };
+        if config.respect_gitignore {
+            filter.gitignores = filter.load_gitignores(&config.paths);
+        }
+        filter
+    }
+
+    /// Load the `.gitignore` files inside the watched roots and above them,
+    /// up to the repository root
+    fn load_gitignores(&self, roots: &[PathBuf]) -> Vec<Gitignore> {
+        let mut files = HashSet::new();
+        for root in roots {
+            for dir in root.ancestors().skip(usize::from(root.is_file())) {
+                files.insert(dir.join(".gitignore"));
+                if dir.join(".git").exists() {
+                    break;
+                }
+            }
+            let nested = walkdir::WalkDir::new(root)
+                .into_iter()
+                .filter_entry(|entry| {
+                    entry.file_name() != ".git" && !self.is_excluded(entry.path())
+                })
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.file_name() == ".gitignore");
+            files.extend(nested.map(|entry| entry.into_path()));
+        }
+
+        let mut gitignores: Vec<Gitignore> = files
+            .into_iter()
+            .filter(|file| file.is_file())
+            .map(|file| Gitignore::new(file).0)
+            .collect();
+        gitignores
+            .sort_by_key(|gitignore| std::cmp::Reverse(gitignore.path().components().count()));
+        gitignores
+    }
+
+    fn is_excluded(&self, path: &Path) -> bool {
+        self.exclude_patterns
+            .iter()
+            .any(|pattern| pattern.matches_file(path))
+    }
+
+    fn is_ignored(&self, path: &Path) -> bool {
+        if self.is_excluded(path) {
+            return true;
+        }
+
+        let is_dir = path.is_dir();
+        for gitignore in &self.gitignores {
+            if !path.starts_with(gitignore.path()) {
+                continue;
+            }
+            match gitignore.matched_path_or_any_parents(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+}
+
+/// Collect raw events into bursts separated by `debounce` of quiet and
+/// forward each burst as one batch
+async fn debounce_events(
+    mut raw: mpsc::UnboundedReceiver<Event>,
+    batches: mpsc::UnboundedSender<Vec<WatchEvent>>,
+    debounce: Duration,
+    filter: Arc<WatchFilter>,
+) {
+    while let Some(event) = raw.recv().await {
+        let mut burst = EventBurst::default();
+        burst.record(event);
+        while let Ok(Some(event)) = tokio::time::timeout(debounce, raw.recv()).await {
+            burst.record(event);
+        }
+
+        let events = burst.into_events(&filter);
+        if !events.is_empty() && batches.send(events).is_err() {
+            return;
+        }
+    }
+}
+
+/// Paths touched by one burst of raw events
+#[derive(Debug, Default)]
+struct EventBurst {
+    /// Touched paths, in the order they were first seen
+    touched: Vec<PathBuf>,
+    seen: HashSet<PathBuf>,
+    /// Paths created or renamed into place
+    created: HashSet<PathBuf>,
+    /// Renames reported with both ends
+    renames: Vec<(PathBuf, PathBuf)>,
+}
+
+impl EventBurst {
+    fn record(&mut self, event: Event) {
+        match event.kind {
+            EventKind::Access(_) => return,
+            EventKind::Create(_) | EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+                self.created.extend(event.paths.iter().cloned());
+            }
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if event.paths.len() == 2 => {
+                self.created.insert(event.paths[1].clone());
+                self.renames
+                    .push((event.paths[0].clone(), event.paths[1].clone()));
+            }
+            _ => {}
+        }
+
+        for path in event.paths {
+            if self.seen.insert(path.clone()) {
+                self.touched.push(path);
+            }
+        }
+    }
+
+    /// Resolve the burst against the current state of the filesystem
+    fn into_events(self, filter: &WatchFilter) -> Vec<WatchEvent> {
+        let mut events = Vec::new();
+        let mut handled = HashSet::new();
+
+        for (from, to) in self.renames {
+            if from.exists() || !to.exists() {
+                continue;
+            }
+            match (filter.is_ignored(&from), filter.is_ignored(&to)) {
+                (false, false) => events.push(WatchEvent::Moved(from.clone(), to.clone())),
+                // A temporary file renamed over its target is an atomic save
+                (true, false) => events.push(WatchEvent::Modified(to.clone())),
+                (false, true) => events.push(WatchEvent::Deleted(from.clone())),
+                (true, true) => {}
+            }
+            handled.insert(from);
+            handled.insert(to);
+        }
+
+        for path in self.touched {
+            if handled.contains(&path) || filter.is_ignored(&path) {
+                continue;
+            }
+            let created = self.created.contains(&path);
+            let event = if !path.exists() {
+                WatchEvent::Deleted(path)
+            } else if created {
+                WatchEvent::Created(path)
+            } else if path.is_dir() {
+                // Directory metadata changes when its entries do
+                continue;
+            } else {
+                WatchEvent::Modified(path)
+            };
+            events.push(event);
+        }
+
+        events
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::content_config::PatternType;
+    use notify::event::{CreateKind, DataChange};
+
+    fn event(kind: EventKind, paths: &[&Path]) -> Event {
+        paths.iter().fold(Event::new(kind), |event, path| {
+            event.add_path(path.to_path_buf())
+        })
+    }
 
     #[test]
     fn test_file_watcher_creation() {
@@ -94,5 +405,58 @@ mod tests {
         assert!(config.paths.is_empty());
         assert_eq!(config.debounce, Duration::from_millis(100));
         assert!(config.recursive);
+        assert!(config.respect_gitignore);
+    }
+
+    #[test]
+    fn test_burst_coalesces_atomic_saves() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("app.rs");
+        let temp = dir.path().join("app.rs.tmp");
+        let removed = dir.path().join("old.rs");
+        std::fs::write(&target, "saved").unwrap();
+
+        let mut burst = EventBurst::default();
+        burst.record(event(EventKind::Create(CreateKind::File), &[&temp]));
+        burst.record(event(
+            EventKind::Modify(ModifyKind::Data(DataChange::Content)),
+            &[&temp],
+        ));
+        burst.record(event(
+            EventKind::Modify(ModifyKind::Name(RenameMode::Both)),
+            &[&temp, &target],
+        ));
+        burst.record(event(
+            EventKind::Remove(notify::event::RemoveKind::File),
+            &[&removed],
+        ));
+
+        let filter = WatchFilter {
+            exclude_patterns: vec![FilePattern::new("**/*.tmp", PatternType::Glob)],
+            gitignores: Vec::new(),
+        };
+        assert_eq!(
+            burst.into_events(&filter),
+            vec![WatchEvent::Modified(target), WatchEvent::Deleted(removed)]
+        );
+    }
+
+    #[test]
+    fn test_filter_respects_gitignore() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().canonicalize().unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+        std::fs::create_dir_all(root.join("generated")).unwrap();
+        std::fs::write(root.join(".gitignore"), "generated/\n*.log\n").unwrap();
+
+        let watcher = FileWatcher::new(WatchConfig {
+            paths: vec![root.clone()],
+            exclude_patterns: vec![FilePattern::new("**/vendor/**", PatternType::Glob)],
+            ..WatchConfig::default()
+        });
+        assert!(watcher.is_ignored(&root.join("generated/page.html")));
+        assert!(watcher.is_ignored(&root.join("debug.log")));
+        assert!(watcher.is_ignored(&root.join("vendor/lib.js")));
+        assert!(!watcher.is_ignored(&root.join("src/main.rs")));
     }
 }
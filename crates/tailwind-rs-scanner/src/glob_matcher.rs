@@ -1,11 +1,15 @@
 //! Glob pattern matching implementation
 //!
 //! This module provides glob pattern matching capabilities for
-//! file discovery and filtering.
+//! file discovery and filtering. Patterns follow the `glob` crate's syntax,
+//! where `**` spans directories, and a leading `!` negates a pattern.
 
 use crate::error::{Result, ScannerError};
 
 /// Glob pattern matcher
+///
+/// The last pattern matching a path decides, so a negated pattern excludes
+/// paths an earlier pattern included.
 #[derive(Debug)]
 pub struct GlobMatcher {
     /// Compiled patterns
@@ -15,10 +19,12 @@ pub struct GlobMatcher {
 /// Glob pattern
 #[derive(Debug, Clone)]
 pub struct GlobPattern {
-    /// Pattern string
+    /// Pattern string, without its `!`
     pub pattern: String,
     /// Whether pattern is case sensitive
     pub case_sensitive: bool,
+    /// Whether matching paths are excluded rather than included
+    pub negated: bool,
 }
 
 impl GlobMatcher {
@@ -31,48 +37,59 @@ impl GlobMatcher {
 
     /// Add a pattern
     pub fn add_pattern(&mut self, pattern: &str) -> Result<()> {
-        self.patterns.push(GlobPattern {
-            pattern: pattern.to_string(),
-            case_sensitive: false,
-        });
+        let pattern = GlobPattern::new(pattern);
+        glob::Pattern::new(&pattern.pattern).map_err(|e| {
+            ScannerError::PatternError(format!("Invalid glob '{}': {}", pattern.pattern, e))
+        })?;
+        self.patterns.push(pattern);
         Ok(())
     }
 
-    /// Check if path matches any pattern
+    /// Check if path matches the patterns
     pub fn matches(&self, path: &str) -> bool {
-        for pattern in &self.patterns {
-            if pattern.matches(path) {
-                return true;
-            }
-        }
-        false
+        self.patterns
+            .iter()
+            .rev()
+            .find(|pattern| pattern.matches_path(path))
+            .is_some_and(|pattern| !pattern.negated)
+    }
+}
+
+impl Default for GlobMatcher {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl GlobPattern {
-    /// Check if pattern matches path
-    pub fn matches(&self, path: &str) -> bool {
-        // Simple glob matching implementation
-        // In a real implementation, use a proper glob library
-        if self.pattern == "**/*" {
-            return true;
+    /// Create a case-insensitive pattern, negated if it starts with `!`
+    pub fn new(pattern: &str) -> Self {
+        let (pattern, negated) = match pattern.strip_prefix('!') {
+            Some(pattern) => (pattern, true),
+            None => (pattern, false),
+        };
+        Self {
+            pattern: pattern.to_string(),
+            case_sensitive: false,
+            negated,
         }
+    }
 
-        if self.pattern.starts_with("**/") {
-            let suffix = &self.pattern[3..];
-            if suffix.contains('*') {
-                // Handle patterns like **/*.rs
-                let suffix_parts: Vec<&str> = suffix.split('*').collect();
-                if suffix_parts.len() == 2 {
-                    let _prefix = suffix_parts[0];
-                    let suffix_end = suffix_parts[1];
-                    return path.ends_with(suffix_end);
-                }
-            }
-            return path.ends_with(suffix);
-        }
+    /// Check if pattern matches path, taking negation into account
+    pub fn matches(&self, path: &str) -> bool {
+        self.matches_path(path) != self.negated
+    }
 
-        path == self.pattern
+    /// Check if the pattern, ignoring negation, matches path
+    fn matches_path(&self, path: &str) -> bool {
+        let options = glob::MatchOptions {
+            case_sensitive: self.case_sensitive,
+            require_literal_separator: false,
+            require_literal_leading_dot: false,
+        };
+        glob::Pattern::new(&self.pattern)
+            .map(|pattern| pattern.matches_with(path, options))
+            .unwrap_or(false)
     }
 }
 
@@ -91,9 +108,24 @@ mod tests {
         let pattern = GlobPattern {
             pattern: "**/*.rs".to_string(),
             case_sensitive: false,
+            negated: false,
         };
 
         assert!(pattern.matches("src/main.rs"));
         assert!(!pattern.matches("src/main.js"));
+        assert!(GlobPattern::new("src/**/*.RS").matches("src/ui/button.rs"));
+        assert!(!GlobPattern::new("!**/*.rs").matches("src/main.rs"));
+    }
+
+    #[test]
+    fn test_glob_matcher_negation() {
+        let mut matcher = GlobMatcher::new();
+        matcher.add_pattern("src/**/*.rs").unwrap();
+        matcher.add_pattern("!src/generated/**").unwrap();
+
+        assert!(matcher.matches("src/app.rs"));
+        assert!(!matcher.matches("src/generated/icons.rs"));
+        assert!(!matcher.matches("tests/app.rs"));
+        assert!(matcher.add_pattern("src/[").is_err());
     }
 }
@@ -13,28 +13,33 @@
 //! ## Features
 //!
 //! - **Multi-Language Support**: Rust, JS/TS, HTML, Vue, Svelte, and more
-//! - **Parallel Processing**: Multi-threaded file scanning for speed
+//! - **Parallel Processing**: Streams files through a bounded rayon pool
 //! - **Intelligent Extraction**: Context-aware class detection
+//! - **Rust Macros**: Leptos `view!`, Dioxus `rsx!` and Yew `html!` class syntax
 //! - **File Watching**: Real-time file change detection
+//! - **Usage Index**: Where each class is used, by file, line and item
+//! - **Dynamic Classes**: Reports class names built with `format!` or `+`
 //! - **Tree-sitter Integration**: Accurate AST-based parsing
 //! - **Performance**: Optimized for large codebases
 //!
 //! ## Example
 //!
-//! ```rust
+//! ```rust,no_run
+//! use futures::StreamExt;
 //! use tailwind_rs_scanner::*;
 //!
 //! #[tokio::main]
-//! async fn main() -> Result<(), ScannerError> {
+//! async fn main() -> Result<()> {
 //!     let scanner = ContentScanner::new(ScanConfig::default())?;
+//!     let paths = vec!["src".to_string()];
 //!     
-//!     let classes = scanner.scan_for_classes().await?;
+//!     let classes = scanner.scan_paths(&paths).await?;
 //!     println!("Found {} classes", classes.total_classes());
 //!     
 //!     // Watch for changes
-//!     let mut watcher = scanner.watch().await?;
-//!     while let Some(update) = watcher.next().await {
-//!         println!("Classes updated: {:?}", update);
+//!     let mut changes = scanner.watch(&paths).await?;
+//!     while let Some(delta) = changes.next().await {
+//!         println!("{}: +{:?} -{:?}", delta.path.display(), delta.added, delta.removed);
 //!     }
 //!     
 //!     Ok(())
@@ -44,23 +49,29 @@
 pub mod cache;
 pub mod class_extractor;
 pub mod content_config;
+pub mod dynamic_classes;
 pub mod error;
 pub mod file_scanner;
 pub mod file_watcher;
 pub mod glob_matcher;
 pub mod parallel_processor;
+pub mod rust_macro_extractor;
 pub mod tree_sitter_parser;
+pub mod usage_index;
 
 // Re-export main types
 pub use cache::{CacheEntry, CacheStats, ScanCache};
 pub use class_extractor::{ClassContext, ClassExtractor, ExtractedClass};
-pub use content_config::{ContentConfig, FilePattern, ScanConfig};
+pub use content_config::{ContentConfig, ExtractionMode, FilePattern, ScanConfig};
+pub use dynamic_classes::DynamicClass;
 pub use error::{Result, ScannerError};
 pub use file_scanner::{ClassSet, ContentScanner, FileInfo, FileScanner, FileType};
-pub use file_watcher::{FileWatcher, WatchConfig, WatchEvent};
+pub use file_watcher::{ClassDelta, ClassDeltaStream, FileWatcher, WatchConfig, WatchEvent};
 pub use glob_matcher::{GlobMatcher, GlobPattern};
-pub use parallel_processor::{ParallelProcessor, ProcessingStats};
+pub use parallel_processor::{ParallelProcessor, ProcessedFiles, ProcessingStats};
+pub use rust_macro_extractor::RustMacroExtractor;
 pub use tree_sitter_parser::{LanguageSupport, ParseResult, TreeSitterParser};
+pub use usage_index::{ClassUsage, UsageIndex};
 
 /// Version information
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -2,16 +2,27 @@
 //!
 //! This module provides parallel file processing capabilities for
 //! efficient content scanning.
+//!
+//! Discovered paths are streamed into a rayon pool whose workers each read a
+//! file, extract its classes and drop its contents before taking the next
+//! one, so no more files are held in memory than there are workers. Results
+//! are keyed by path, which makes them independent of the number of workers
+//! and of the order they finish in.
 
 use crate::class_extractor::ClassExtractor;
 use crate::class_extractor::ExtractedClass;
 use crate::content_config::ScanConfig;
 use crate::error::{Result, ScannerError};
-use crate::file_scanner::FileInfo;
+use crate::file_scanner::{FileInfo, FileScanner};
+use parking_lot::Mutex;
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
-use std::time::Instant;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tailwind_rs_core::{BuildCache, FileVersion};
 
 /// Parallel processor for file processing
 #[derive(Debug)]
@@ -20,6 +31,8 @@ pub struct ParallelProcessor {
     enabled: bool,
     /// Maximum number of workers
     max_workers: Option<usize>,
+    /// Pool the files are processed on, built on first use
+    pool: OnceLock<rayon::ThreadPool>,
 }
 
 /// Processing statistics
@@ -37,18 +50,103 @@ pub struct ProcessingStats {
     pub total_time: std::time::Duration,
     /// Average time per file
     pub average_file_time: std::time::Duration,
-    /// Memory usage in bytes
+    /// Peak number of bytes of file contents held at once
     pub memory_usage: usize,
+    /// Number of files whose classes came from the cache
+    pub cached_files: usize,
+    /// Number of files skipped for being too large, unreadable or not UTF-8
+    #[serde(default)]
+    pub skipped_files: usize,
+    /// Number of files that were memory-mapped instead of read
+    #[serde(default)]
+    pub mapped_files: usize,
+    /// Number of workers the files were processed on
+    #[serde(default)]
+    pub workers: usize,
+    /// Time spent walking directories for files
+    #[serde(default)]
+    pub discovery_time: std::time::Duration,
+    /// Time spent reading files, summed over the workers
+    #[serde(default)]
+    pub read_time: std::time::Duration,
+    /// Time spent extracting classes or finding them in the cache, summed
+    /// over the workers
+    #[serde(default)]
+    pub extraction_time: std::time::Duration,
+    /// Time spent merging the results of the workers
+    #[serde(default)]
+    pub merge_time: std::time::Duration,
 }
 
 // Default implementation is in file_scanner.rs
 
+/// Classes extracted by a processing run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ProcessedFiles {
+    /// Classes of each processed file, ordered by path
+    pub files: BTreeMap<PathBuf, Vec<ExtractedClass>>,
+    /// Statistics of the run
+    pub stats: ProcessingStats,
+}
+
+/// Counters shared by the workers of a run
+#[derive(Default)]
+struct Counters {
+    files: AtomicUsize,
+    skipped: AtomicUsize,
+    mapped: AtomicUsize,
+    cached: AtomicUsize,
+    held_bytes: AtomicUsize,
+    peak_bytes: AtomicUsize,
+    discovery_nanos: AtomicU64,
+    read_nanos: AtomicU64,
+    extraction_nanos: AtomicU64,
+}
+
+impl Counters {
+    fn add_time(counter: &AtomicU64, start: Instant) {
+        let nanos = start.elapsed().as_nanos().min(u64::MAX as u128) as u64;
+        counter.fetch_add(nanos, Ordering::Relaxed);
+    }
+
+    fn time(counter: &AtomicU64) -> Duration {
+        Duration::from_nanos(counter.load(Ordering::Relaxed))
+    }
+
+    fn count(counter: &AtomicUsize) -> usize {
+        counter.load(Ordering::Relaxed)
+    }
+
+    fn skip<T>(&self) -> Option<T> {
+        self.skipped.fetch_add(1, Ordering::Relaxed);
+        None
+    }
+}
+
+/// Path iterator recording the time spent advancing it
+struct Timed<'a, I> {
+    paths: I,
+    elapsed: &'a AtomicU64,
+}
+
+impl<I: Iterator<Item = PathBuf>> Iterator for Timed<'_, I> {
+    type Item = PathBuf;
+
+    fn next(&mut self) -> Option<PathBuf> {
+        let start = Instant::now();
+        let path = self.paths.next();
+        Counters::add_time(self.elapsed, start);
+        path
+    }
+}
+
 impl ParallelProcessor {
     /// Create a new parallel processor
     pub fn new(enabled: bool) -> Self {
         Self {
             enabled,
             max_workers: None,
+            pool: OnceLock::new(),
         }
     }
 
@@ -57,64 +155,209 @@ impl ParallelProcessor {
         Self {
             enabled,
             max_workers: Some(max_workers),
+            pool: OnceLock::new(),
         }
     }
 
-    /// Process files in parallel
-    pub async fn process_files(
+    /// Create with the parallelism settings of a scan configuration
+    pub fn from_config(config: &ScanConfig) -> Self {
+        match config.max_workers {
+            Some(max_workers) => Self::with_max_workers(config.parallel_processing, max_workers),
+            None => Self::new(config.parallel_processing),
+        }
+    }
+
+    /// Read and extract streamed paths on the worker pool
+    ///
+    /// Each worker reads one file at a time, memory-mapping large ones, and
+    /// drops its contents once the classes are extracted. Files whose
+    /// classes are in `cache` for their current contents aren't extracted
+    /// again, and the others are added to it. Files over the maximum file
+    /// size, unreadable files and files that aren't UTF-8 are skipped.
+    pub fn process_paths<I>(
         &self,
-        files: &[FileInfo],
+        paths: I,
+        scanner: &FileScanner,
         extractor: &ClassExtractor,
-        config: &ScanConfig,
-    ) -> Result<HashMap<PathBuf, Vec<ExtractedClass>>> {
+        cache: Option<&mut BuildCache<Vec<ExtractedClass>>>,
+    ) -> Result<ProcessedFiles>
+    where
+        I: Iterator<Item = PathBuf> + Send,
+    {
         let start_time = Instant::now();
-        let mut results = HashMap::new();
+        let counters = Counters::default();
+        let cache = cache.map(Mutex::new);
+        let paths = Timed {
+            paths,
+            elapsed: &counters.discovery_nanos,
+        };
+        let process =
+            |path: PathBuf| Self::process_path(path, scanner, extractor, cache.as_ref(), &counters);
 
-        if self.enabled && files.len() > 1 {
-            // Parallel processing
-            self.process_files_parallel(files, extractor, &mut results)
-                .await?;
+        let (results, workers): (Vec<_>, usize) = if self.enabled {
+            let pool = self.pool()?;
+            let results = pool.install(|| paths.par_bridge().filter_map(process).collect());
+            (results, pool.current_num_threads())
         } else {
-            // Sequential processing
-            self.process_files_sequential(files, extractor, &mut results)
-                .await?;
+            (paths.filter_map(process).collect(), 1)
+        };
+
+        let merge_start = Instant::now();
+        let files: BTreeMap<PathBuf, Vec<ExtractedClass>> = results.into_iter().collect();
+        let unique: HashSet<&str> = files
+            .values()
+            .flatten()
+            .map(|class| class.class_name.as_str())
+            .collect();
+        let mut stats = ProcessingStats {
+            total_files: Counters::count(&counters.files),
+            processed_files: files.len(),
+            total_classes: files.values().map(Vec::len).sum(),
+            unique_classes: unique.len(),
+            memory_usage: Counters::count(&counters.peak_bytes),
+            cached_files: Counters::count(&counters.cached),
+            skipped_files: Counters::count(&counters.skipped),
+            mapped_files: Counters::count(&counters.mapped),
+            workers,
+            discovery_time: Counters::time(&counters.discovery_nanos),
+            read_time: Counters::time(&counters.read_nanos),
+            extraction_time: Counters::time(&counters.extraction_nanos),
+            merge_time: merge_start.elapsed(),
+            ..ProcessingStats::default()
+        };
+        stats.total_time = start_time.elapsed();
+        if stats.processed_files > 0 {
+            stats.average_file_time = stats.total_time / stats.processed_files as u32;
         }
 
-        Ok(results)
+        Ok(ProcessedFiles { files, stats })
     }
 
-    /// Process files sequentially
-    async fn process_files_sequential(
+    /// Process files in parallel
+    ///
+    /// The files are already read into memory; see
+    /// [`ParallelProcessor::process_paths`] for reading them as they are
+    /// processed.
+    pub async fn process_files(
         &self,
         files: &[FileInfo],
         extractor: &ClassExtractor,
-        results: &mut HashMap<PathBuf, Vec<ExtractedClass>>,
-    ) -> Result<()> {
-        for file in files {
-            if let Ok(classes) = extractor.extract_classes(file).await {
-                results.insert(file.path.clone(), classes);
-            }
+        _config: &ScanConfig,
+    ) -> Result<HashMap<PathBuf, Vec<ExtractedClass>>> {
+        let extract = |file: &FileInfo| {
+            let content = file.content.as_deref()?;
+            let classes = extractor.extract_content(&file.path, &file.file_type, content);
+            Some((file.path.clone(), classes))
+        };
+
+        if self.enabled && files.len() > 1 {
+            Ok(self
+                .pool()?
+                .install(|| files.par_iter().filter_map(extract).collect()))
+        } else {
+            Ok(files.iter().filter_map(extract).collect())
         }
-        Ok(())
     }
 
-    /// Process files in parallel
-    async fn process_files_parallel(
-        &self,
-        files: &[FileInfo],
+    /// Read one file and extract its classes, recording the run's counters
+    fn process_path(
+        path: PathBuf,
+        scanner: &FileScanner,
         extractor: &ClassExtractor,
-        results: &mut HashMap<PathBuf, Vec<ExtractedClass>>,
-    ) -> Result<()> {
-        // For now, use sequential processing
-        // In a real implementation, this would use rayon or tokio for parallel processing
-        self.process_files_sequential(files, extractor, results)
-            .await
+        cache: Option<&Mutex<&mut BuildCache<Vec<ExtractedClass>>>>,
+        counters: &Counters,
+    ) -> Option<(PathBuf, Vec<ExtractedClass>)> {
+        counters.files.fetch_add(1, Ordering::Relaxed);
+
+        let read_start = Instant::now();
+        let contents = match scanner.read_contents(&path) {
+            Ok(Some(contents)) => contents,
+            _ => return counters.skip(),
+        };
+        let Ok(content) = contents.text() else {
+            return counters.skip();
+        };
+        Counters::add_time(&counters.read_nanos, read_start);
+        if contents.is_mapped() {
+            counters.mapped.fetch_add(1, Ordering::Relaxed);
+        }
+        let held = counters
+            .held_bytes
+            .fetch_add(contents.len(), Ordering::Relaxed)
+            + contents.len();
+        counters.peak_bytes.fetch_max(held, Ordering::Relaxed);
+
+        let extraction_start = Instant::now();
+        // Hash outside the lock, so workers only serialize on the lookups
+        let version = cache.map(|_| FileVersion::of(&path, content));
+        let cached = cache
+            .zip(version)
+            .and_then(|(cache, version)| cache.lock().get_version(&path, version).cloned());
+        let classes = match cached {
+            Some(classes) => {
+                counters.cached.fetch_add(1, Ordering::Relaxed);
+                classes
+            }
+            None => {
+                let classes = extractor.extract_content(&path, &contents.file_type, content);
+                if let Some((cache, version)) = cache.zip(version) {
+                    cache.lock().insert_version(&path, version, classes.clone());
+                }
+                classes
+            }
+        };
+        Counters::add_time(&counters.extraction_nanos, extraction_start);
+
+        counters
+            .held_bytes
+            .fetch_sub(contents.len(), Ordering::Relaxed);
+        Some((path, classes))
+    }
+
+    /// The worker pool, limited to the maximum number of workers
+    fn pool(&self) -> Result<&rayon::ThreadPool> {
+        if let Some(pool) = self.pool.get() {
+            return Ok(pool);
+        }
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.max_workers.unwrap_or(0))
+            .thread_name(|index| format!("tailwind-rs-scanner-{}", index))
+            .build()
+            .map_err(|e| ScannerError::Generic(format!("Failed to start worker pool: {}", e)))?;
+        Ok(self.pool.get_or_init(|| pool))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::content_config::ContentConfig;
+    use crate::file_scanner::MMAP_THRESHOLD;
+
+    fn write_sources(root: &std::path::Path) {
+        for index in 0..24 {
+            std::fs::write(
+                root.join(format!("component_{}.html", index)),
+                format!(
+                    r#"<div class="p-{} m-2 flex"><span class="text-sm">x</span></div>"#,
+                    index % 8
+                ),
+            )
+            .unwrap();
+        }
+    }
+
+    fn process(
+        processor: &ParallelProcessor,
+        config: ContentConfig,
+        paths: &[String],
+    ) -> ProcessedFiles {
+        let scanner = FileScanner::new(config.clone()).unwrap();
+        let extractor = ClassExtractor::from_config(&config);
+        processor
+            .process_paths(scanner.discover_paths(paths), &scanner, &extractor, None)
+            .unwrap()
+    }
 
     #[test]
     fn test_parallel_processor_creation() {
@@ -136,4 +379,70 @@ mod tests {
         assert_eq!(stats.processed_files, 0);
         assert_eq!(stats.total_classes, 0);
     }
+
+    #[test]
+    fn test_results_independent_of_workers() {
+        let dir = tempfile::tempdir().unwrap();
+        write_sources(dir.path());
+        let paths = [dir.path().to_string_lossy().into_owned()];
+
+        let sequential = process(
+            &ParallelProcessor::new(false),
+            ContentConfig::default(),
+            &paths,
+        );
+        let one = process(
+            &ParallelProcessor::with_max_workers(true, 1),
+            ContentConfig::default(),
+            &paths,
+        );
+        let four = process(
+            &ParallelProcessor::with_max_workers(true, 4),
+            ContentConfig::default(),
+            &paths,
+        );
+
+        assert_eq!(sequential.files.len(), 24);
+        assert_eq!(sequential.files, one.files);
+        assert_eq!(sequential.files, four.files);
+        assert_eq!(four.stats.workers, 4);
+        assert_eq!(four.stats.total_files, 24);
+        assert_eq!(four.stats.processed_files, 24);
+        assert_eq!(four.stats.total_classes, 24 * 4);
+        assert_eq!(four.stats.unique_classes, 11);
+        assert!(four.stats.memory_usage > 0);
+    }
+
+    #[test]
+    fn test_large_files_mapped_and_oversized_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        let large = dir.path().join("large.html");
+        let mut content = r#"<div class="grid gap-4"></div>"#.to_string();
+        content.push_str(&"\n".repeat(MMAP_THRESHOLD as usize));
+        std::fs::write(&large, content).unwrap();
+        let paths = [large.to_string_lossy().into_owned()];
+
+        let mapped = process(
+            &ParallelProcessor::new(true),
+            ContentConfig::default(),
+            &paths,
+        );
+        assert_eq!(mapped.stats.mapped_files, 1);
+        let classes: Vec<&str> = mapped.files[&large]
+            .iter()
+            .map(|class| class.class_name.as_str())
+            .collect();
+        assert_eq!(classes, vec!["grid", "gap-4"]);
+
+        let skipped = process(
+            &ParallelProcessor::new(true),
+            ContentConfig {
+                max_file_size: Some(1024),
+                ..ContentConfig::default()
+            },
+            &paths,
+        );
+        assert!(skipped.files.is_empty());
+        assert_eq!(skipped.stats.skipped_files, 1);
+    }
 }
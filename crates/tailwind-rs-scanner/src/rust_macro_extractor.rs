@@ -0,0 +1,849 @@
+//! Rust macro class extraction
+//!
+//! This module parses Rust files with `syn` and reads the class syntax of the
+//! component frameworks from their macro token streams:
+//!
+//! - Leptos `view!`: `class="…"`, `class=("active", move || …)` tuples and
+//!   `class:hidden=move || …` directives
+//! - Dioxus `rsx!`: `class: "…"` fields, whose literals are format strings
+//! - Yew `html!`: `class="…"` and `class={classes!(…)}`, plus `classes!(…)`
+//!   calls anywhere in the file
+//!
+//! Literals inside a class expression (`if` branches, `Some("…")`, the names
+//! of a Leptos tuple or directive) are marked as conditional. Words holding a
+//! `{…}` placeholder of a `format!` or Dioxus literal are dynamic and skipped.
+//! String literals in ordinary code count when every word in them looks like
+//! a class; doc comments and the contents of other macros are ignored. Files
+//! that don't parse return an error, so callers can fall back to a more
+//! forgiving extractor.
+//!
+//! Each class records the innermost function it appears in: `Card` for a
+//! Leptos `#[component] fn Card`, `Type::method` inside an `impl` or trait.
+//!
+//! Format strings and `+` concatenations of string literals are recorded as
+//! dynamic sites, since the classes they build only exist at runtime.
+
+use crate::class_extractor::{
+    class_tokens, innermost_item, looks_like_class, ClassContext, ExtractedClass, LineIndex,
+};
+use crate::dynamic_classes::{format_template, DynamicSite, HOLE};
+use crate::error::{Result, ScannerError};
+use proc_macro2::{Delimiter, Literal, Spacing, TokenStream, TokenTree};
+use std::collections::HashMap;
+use std::ops::Range;
+use syn::spanned::Spanned;
+use syn::visit::Visit;
+
+/// Class extractor for Rust source and framework macros
+#[derive(Debug, Clone, Default)]
+pub struct RustMacroExtractor;
+
+/// Component framework whose macro a class was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Framework {
+    Leptos,
+    Dioxus,
+    Yew,
+}
+
+impl Framework {
+    fn from_macro(name: &str) -> Option<Self> {
+        match name {
+            "view" => Some(Framework::Leptos),
+            "rsx" => Some(Framework::Dioxus),
+            "html" => Some(Framework::Yew),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Framework::Leptos => "leptos",
+            Framework::Dioxus => "dioxus",
+            Framework::Yew => "yew",
+        }
+    }
+
+    fn macro_name(self) -> &'static str {
+        match self {
+            Framework::Leptos => "view!",
+            Framework::Dioxus => "rsx!",
+            Framework::Yew => "html!",
+        }
+    }
+}
+
+/// Where a literal was found
+#[derive(Debug, Clone, Copy)]
+struct LiteralContext {
+    framework: Option<Framework>,
+    /// Innermost macro holding the literal, or `LitStr` in ordinary code
+    node_kind: &'static str,
+    /// Class attribute the literal is part of, if any
+    attribute: Option<&'static str>,
+    /// Whether the classes only apply under a condition
+    conditional: bool,
+    /// Whether `{…}` in the literal are placeholders
+    format: bool,
+}
+
+impl LiteralContext {
+    const CODE: Self = Self {
+        framework: None,
+        node_kind: "LitStr",
+        attribute: None,
+        conditional: false,
+        format: false,
+    };
+
+    fn class_attribute(framework: Framework, attribute: &'static str, conditional: bool) -> Self {
+        Self {
+            framework: Some(framework),
+            node_kind: framework.macro_name(),
+            attribute: Some(attribute),
+            conditional,
+            // Dioxus interpolates `{name}` in every string literal
+            format: framework == Framework::Dioxus,
+        }
+    }
+}
+
+/// A string literal that may hold classes
+#[derive(Debug, Clone)]
+struct ClassLiteral {
+    /// Byte range of the literal's contents in the file
+    range: Range<usize>,
+    /// Unescaped value
+    value: String,
+    /// Whether the file text at `range` is `value`, so words can be located
+    verbatim: bool,
+    context: LiteralContext,
+}
+
+impl RustMacroExtractor {
+    /// Create a new Rust macro extractor
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Extract classes from Rust source
+    pub fn extract_classes(&self, content: &str) -> Result<Vec<ExtractedClass>> {
+        let Collector {
+            mut literals,
+            items,
+            ..
+        } = Collector::collect(content)?;
+        literals.sort_by_key(|literal| literal.range.start);
+
+        let lines = LineIndex::new(content);
+        let mut classes = Vec::new();
+        for literal in literals {
+            let context = literal.context;
+            let tokens: Vec<_> = class_tokens(&literal.value)
+                .into_iter()
+                .filter(|(range, _)| {
+                    !(context.format && literal.value[range.clone()].contains(['{', '}']))
+                })
+                .collect();
+            if context.attribute.is_none()
+                && !tokens
+                    .iter()
+                    .all(|(range, _)| looks_like_class(&literal.value[range.clone()]))
+            {
+                continue;
+            }
+
+            let mut metadata = HashMap::new();
+            if let Some(framework) = context.framework {
+                metadata.insert("framework".to_string(), framework.name().to_string());
+            }
+            if let Some(attribute) = context.attribute {
+                metadata.insert("attribute".to_string(), attribute.to_string());
+            }
+            if context.conditional {
+                metadata.insert("conditional".to_string(), "true".to_string());
+            }
+
+            for (range, class_names) in tokens {
+                let byte_range = if literal.verbatim {
+                    literal.range.start + range.start..literal.range.start + range.end
+                } else {
+                    literal.range.clone()
+                };
+                let (line, column) = lines.position(byte_range.start);
+                for class_name in class_names {
+                    classes.push(ExtractedClass {
+                        class_name,
+                        context: ClassContext {
+                            code_context: lines.line_text(byte_range.start).to_string(),
+                            file_type: "Rust".to_string(),
+                            in_string: true,
+                            in_comment: false,
+                            metadata: metadata.clone(),
+                        },
+                        line,
+                        column,
+                        byte_range: byte_range.clone(),
+                        node_kind: Some(context.node_kind.to_string()),
+                        item: innermost_item(&items, byte_range.start),
+                    });
+                }
+            }
+        }
+
+        Ok(classes)
+    }
+
+    /// Byte ranges and names of the functions and methods in Rust source
+    ///
+    /// Lets classes found without parsing, such as candidates, be attributed
+    /// to the item they appear in.
+    pub fn items(&self, content: &str) -> Result<Vec<(Range<usize>, String)>> {
+        Ok(Collector::collect(content)?.items)
+    }
+
+    /// Strings in Rust source built from literal text and runtime values
+    pub(crate) fn dynamic_sites(&self, content: &str) -> Result<Vec<DynamicSite>> {
+        let Collector {
+            mut dynamic, items, ..
+        } = Collector::collect(content)?;
+        dynamic.sort_by_key(|site| site.range.start);
+        for site in &mut dynamic {
+            site.item = innermost_item(&items, site.range.start);
+        }
+        Ok(dynamic)
+    }
+}
+
+/// Length of the byte order mark and shebang line that `syn::parse_file`
+/// skips before parsing
+fn skipped_prefix_len(content: &str) -> usize {
+    let rest = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut skipped = content.len() - rest.len();
+    if rest.starts_with("#!") && !rest[2..].trim_start().starts_with('[') {
+        skipped += rest.find('\n').unwrap_or(rest.len());
+    }
+    skipped
+}
+
+/// Template of a `+` concatenation, with each operand that isn't a string
+/// literal as a hole
+fn concat_template(expr: &syn::ExprBinary) -> String {
+    fn operand(expr: &syn::Expr) -> String {
+        match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(string),
+                ..
+            }) => string.value(),
+            syn::Expr::Binary(binary) if matches!(binary.op, syn::BinOp::Add(_)) => {
+                concat_template(binary)
+            }
+            // `"bg-".to_string() + color`
+            syn::Expr::MethodCall(call)
+                if call.args.is_empty() && matches!(call.receiver.as_ref(), syn::Expr::Lit(_)) =>
+            {
+                operand(&call.receiver)
+            }
+            syn::Expr::Paren(paren) => operand(&paren.expr),
+            syn::Expr::Reference(reference) => operand(&reference.expr),
+            _ => HOLE.to_string(),
+        }
+    }
+    operand(&expr.left) + &operand(&expr.right)
+}
+
+fn is_punct(token: &TokenTree, c: char) -> bool {
+    matches!(token, TokenTree::Punct(punct) if punct.as_char() == c)
+}
+
+/// Whether an attribute name (`id`, `aria-label`, `on:click`) followed by
+/// `=` or `:` starts at `tokens[i]`
+fn is_attribute_start(tokens: &[TokenTree], mut i: usize) -> bool {
+    loop {
+        if !matches!(tokens.get(i), Some(TokenTree::Ident(_))) {
+            return false;
+        }
+        match tokens.get(i + 1) {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == '-' => i += 2,
+            Some(TokenTree::Punct(punct)) => {
+                return matches!(punct.as_char(), '=' | ':') && punct.spacing() == Spacing::Alone
+            }
+            _ => return false,
+        }
+    }
+}
+
+/// Index of the token after an unbraced attribute value starting at
+/// `tokens[start]`: the next attribute, or the `>` or `/>` ending the tag
+fn attribute_value_end(tokens: &[TokenTree], start: usize) -> usize {
+    (start + 1..tokens.len())
+        .find(|&j| {
+            let closes_tag = is_punct(&tokens[j], '>')
+                // Not the end of `->`, `=>` or `>=`
+                && !matches!(&tokens[j - 1], TokenTree::Punct(punct)
+                    if punct.spacing() == Spacing::Joint && punct.as_char() != '/');
+            closes_tag
+                || is_punct(&tokens[j], '/')
+                    && tokens.get(j + 1).is_some_and(|next| is_punct(next, '>'))
+                || is_attribute_start(tokens, j)
+        })
+        .unwrap_or(tokens.len())
+}
+
+/// The name and arguments of a `name!(…)` call starting at `i`
+fn macro_call(tokens: &[TokenTree], i: usize) -> Option<(String, TokenStream)> {
+    match (tokens.get(i)?, tokens.get(i + 1)?, tokens.get(i + 2)?) {
+        (TokenTree::Ident(name), bang, TokenTree::Group(args)) if is_punct(bang, '!') => {
+            Some((name.to_string(), args.stream()))
+        }
+        _ => None,
+    }
+}
+
+/// Collects class literals and named items while walking a parsed file
+struct Collector {
+    /// Offset of the parsed text in the file
+    base: usize,
+    literals: Vec<ClassLiteral>,
+    /// Byte range and name of every function and method
+    items: Vec<(Range<usize>, String)>,
+    /// Type or trait whose methods are being walked
+    owner: Option<String>,
+    /// Format strings and concatenations
+    dynamic: Vec<DynamicSite>,
+    /// Whether the expressions being walked are operands of a concatenation
+    in_concat: bool,
+}
+
+impl<'ast> Visit<'ast> for Collector {
+    fn visit_attribute(&mut self, _attribute: &'ast syn::Attribute) {
+        // Doc comments and other attributes never hold classes
+    }
+
+    fn visit_item_fn(&mut self, item: &'ast syn::ItemFn) {
+        self.push_item(item.span(), item.sig.ident.to_string());
+        syn::visit::visit_item_fn(self, item);
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast syn::ItemImpl) {
+        let owner = match item.self_ty.as_ref() {
+            syn::Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string()),
+            _ => None,
+        };
+        let outer = std::mem::replace(&mut self.owner, owner);
+        syn::visit::visit_item_impl(self, item);
+        self.owner = outer;
+    }
+
+    fn visit_item_trait(&mut self, item: &'ast syn::ItemTrait) {
+        let outer = self.owner.replace(item.ident.to_string());
+        syn::visit::visit_item_trait(self, item);
+        self.owner = outer;
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &'ast syn::ImplItemFn) {
+        self.push_method(item.span(), &item.sig.ident);
+        syn::visit::visit_impl_item_fn(self, item);
+    }
+
+    fn visit_trait_item_fn(&mut self, item: &'ast syn::TraitItemFn) {
+        self.push_method(item.span(), &item.sig.ident);
+        syn::visit::visit_trait_item_fn(self, item);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast syn::Macro) {
+        let Some(name) = mac
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+        else {
+            return;
+        };
+        if let Some(framework) = Framework::from_macro(&name) {
+            self.walk(mac.tokens.clone(), framework);
+        } else if name == "classes" {
+            let context = LiteralContext {
+                node_kind: "classes!",
+                ..LiteralContext::class_attribute(Framework::Yew, "class", false)
+            };
+            self.collect_literals(mac.tokens.clone(), context);
+        } else if matches!(name.as_str(), "format" | "format_args") {
+            if let Some(TokenTree::Literal(literal)) = mac.tokens.clone().into_iter().next() {
+                self.push_format(&literal);
+            }
+        }
+    }
+
+    fn visit_expr_binary(&mut self, expr: &'ast syn::ExprBinary) {
+        let is_concat = matches!(expr.op, syn::BinOp::Add(_));
+        if is_concat && !self.in_concat {
+            let template = concat_template(expr);
+            if template.contains(HOLE) && !template.replace(HOLE, "").is_empty() {
+                let range = expr.span().byte_range();
+                self.dynamic.push(DynamicSite {
+                    range: self.base + range.start..self.base + range.end,
+                    template,
+                    item: None,
+                });
+            }
+        }
+        let outer = std::mem::replace(&mut self.in_concat, is_concat);
+        syn::visit::visit_expr_binary(self, expr);
+        self.in_concat = outer;
+    }
+
+    fn visit_lit_str(&mut self, literal: &'ast syn::LitStr) {
+        self.push(&literal.token(), LiteralContext::CODE);
+    }
+}
+
+impl Collector {
+    /// Parse a file and collect its class literals and items
+    fn collect(content: &str) -> Result<Self> {
+        let file = syn::parse_file(content).map_err(|e| ScannerError::ParseError(e.to_string()))?;
+        let mut collector = Collector {
+            base: skipped_prefix_len(content),
+            literals: Vec::new(),
+            items: Vec::new(),
+            owner: None,
+            dynamic: Vec::new(),
+            in_concat: false,
+        };
+        collector.visit_file(&file);
+        Ok(collector)
+    }
+
+    fn push_item(&mut self, span: proc_macro2::Span, name: String) {
+        let range = span.byte_range();
+        self.items
+            .push((self.base + range.start..self.base + range.end, name));
+    }
+
+    fn push_method(&mut self, span: proc_macro2::Span, name: &syn::Ident) {
+        let name = match &self.owner {
+            Some(owner) => format!("{}::{}", owner, name),
+            None => name.to_string(),
+        };
+        self.push_item(span, name);
+    }
+
+    /// Walk the tokens of a framework macro looking for class attributes
+    fn walk(&mut self, stream: TokenStream, framework: Framework) {
+        let tokens: Vec<TokenTree> = stream.into_iter().collect();
+        let mut i = 0;
+        while i < tokens.len() {
+            i = match &tokens[i] {
+                TokenTree::Ident(ident) if ident == "class" => {
+                    self.class_attribute(&tokens, i, framework)
+                }
+                TokenTree::Ident(_) if macro_call(&tokens, i).is_some() => {
+                    let (name, args) = macro_call(&tokens, i).unwrap_or_default();
+                    match Framework::from_macro(&name) {
+                        Some(nested) => self.walk(args, nested),
+                        None if name == "classes" => self.collect_literals(
+                            args,
+                            LiteralContext {
+                                node_kind: "classes!",
+                                ..LiteralContext::class_attribute(framework, "class", false)
+                            },
+                        ),
+                        None => self.walk(args, framework),
+                    }
+                    i + 3
+                }
+                TokenTree::Group(group) => {
+                    self.walk(group.stream(), framework);
+                    i + 1
+                }
+                _ => i + 1,
+            };
+        }
+    }
+
+    /// Read the class attribute starting at `tokens[i]`, returning the index
+    /// of the first token after it
+    fn class_attribute(&mut self, tokens: &[TokenTree], i: usize, framework: Framework) -> usize {
+        let (Some(separator), Some(value)) = (tokens.get(i + 1), tokens.get(i + 2)) else {
+            return i + 1;
+        };
+
+        if is_punct(separator, '=') {
+            let context =
+                |conditional| LiteralContext::class_attribute(framework, "class", conditional);
+            match value {
+                TokenTree::Literal(literal) => self.push(literal, context(false)),
+                TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+                    // Leptos `class=("name", condition)`: only the name is a class
+                    let names = group
+                        .stream()
+                        .into_iter()
+                        .take_while(|token| !is_punct(token, ','))
+                        .collect();
+                    self.collect_literals(names, context(true));
+                }
+                TokenTree::Group(group) => self.collect_literals(group.stream(), context(true)),
+                _ => {
+                    // Leptos `class=move || if active.get() { "a" } else { "b" }`
+                    let end = attribute_value_end(tokens, i + 2);
+                    self.collect_literals(
+                        tokens[i + 2..end].iter().cloned().collect(),
+                        context(true),
+                    );
+                    return end;
+                }
+            }
+            return i + 3;
+        }
+
+        if !is_punct(separator, ':') {
+            return i + 1;
+        }
+        if framework == Framework::Dioxus {
+            // Dioxus `class: expression,`
+            let value: Vec<TokenTree> = tokens[i + 2..]
+                .iter()
+                .take_while(|token| !is_punct(token, ','))
+                .cloned()
+                .collect();
+            let conditional = !matches!(value.as_slice(), [TokenTree::Literal(_)]);
+            let len = value.len();
+            self.collect_literals(
+                value.into_iter().collect(),
+                LiteralContext::class_attribute(framework, "class", conditional),
+            );
+            return i + 2 + len;
+        }
+
+        // Leptos `class:name=condition`, where the name may contain dashes
+        let Some(end) = (i + 2..tokens.len()).find(|&j| is_punct(&tokens[j], '=')) else {
+            return i + 2;
+        };
+        self.push_directive(&tokens[i + 2..end], framework);
+        end + 1
+    }
+
+    /// Record the class name of a `class:name` directive
+    fn push_directive(&mut self, name: &[TokenTree], framework: Framework) {
+        let (Some(first), Some(last)) = (name.first(), name.last()) else {
+            return;
+        };
+        let start = first.span().byte_range().start;
+        let end = last.span().byte_range().end;
+        let value: String = name.iter().map(|token| token.to_string()).collect();
+        self.literals.push(ClassLiteral {
+            range: self.base + start..self.base + end,
+            // Tokens written apart (`class:a - b`) aren't one class
+            verbatim: value.len() == end - start,
+            value,
+            context: LiteralContext::class_attribute(framework, "class:", true),
+        });
+    }
+
+    /// Record every string literal in a class expression
+    fn collect_literals(&mut self, stream: TokenStream, context: LiteralContext) {
+        let tokens: Vec<TokenTree> = stream.into_iter().collect();
+        let mut i = 0;
+        while i < tokens.len() {
+            if let Some((name, args)) = macro_call(&tokens, i) {
+                let node_kind = match name.as_str() {
+                    "format" | "format_args" => "format!",
+                    "classes" => "classes!",
+                    _ => context.node_kind,
+                };
+                let context = LiteralContext {
+                    node_kind,
+                    format: context.format || node_kind == "format!",
+                    ..context
+                };
+                self.collect_literals(args, context);
+                i += 3;
+                continue;
+            }
+            match &tokens[i] {
+                TokenTree::Literal(literal) => self.push(literal, context),
+                TokenTree::Group(group) => self.collect_literals(group.stream(), context),
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+
+    /// Record a format string that interpolates values
+    fn push_format(&mut self, literal: &Literal) {
+        let syn::Lit::Str(string) = syn::Lit::new(literal.clone()) else {
+            return;
+        };
+        let template = format_template(&string.value());
+        if template.contains(HOLE) {
+            let range = literal.span().byte_range();
+            self.dynamic.push(DynamicSite {
+                range: self.base + range.start..self.base + range.end,
+                template,
+                item: None,
+            });
+        }
+    }
+
+    /// Record a literal if it is a string
+    fn push(&mut self, literal: &Literal, context: LiteralContext) {
+        let syn::Lit::Str(string) = syn::Lit::new(literal.clone()) else {
+            return;
+        };
+        if context.format {
+            self.push_format(literal);
+        }
+        let value = string.value();
+        let span = literal.span().byte_range();
+        let source = literal.to_string();
+
+        // The contents sit between the first and last quote, after any `r#`
+        let contents = source
+            .find('"')
+            .zip(source.rfind('"'))
+            .filter(|(open, close)| open < close)
+            .map(|(open, close)| (open + 1, close));
+        let (range, verbatim) = match contents {
+            Some((open, close)) if source[open..close] == value => {
+                (span.start + open..span.start + close, true)
+            }
+            _ => (span, false),
+        };
+
+        self.literals.push(ClassLiteral {
+            range: self.base + range.start..self.base + range.end,
+            value,
+            verbatim,
+            context,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extract(content: &str) -> Vec<ExtractedClass> {
+        RustMacroExtractor::new().extract_classes(content).unwrap()
+    }
+
+    fn names(classes: &[ExtractedClass]) -> Vec<&str> {
+        classes
+            .iter()
+            .map(|class| class.class_name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_leptos_view_classes() {
+        let content = r##"
+/// Renders `<div class="doc-only">`
+#[component]
+fn Card(selected: ReadSignal<bool>) -> impl IntoView {
+    view! {
+        <div
+            class="p-4 hover:(bg-blue-600 text-white)"
+            class=("ring-2", move || selected.get())
+            class:text-red-500=move || selected.get()
+        >
+            <p class=r#"
+                mt-2
+                text-sm
+            "#>"Hello there"</p>
+        </div>
+    }
+}
+"##;
+        let classes = extract(content);
+        assert_eq!(
+            names(&classes),
+            vec![
+                "p-4",
+                "hover:bg-blue-600",
+                "hover:text-white",
+                "ring-2",
+                "text-red-500",
+                "mt-2",
+                "text-sm"
+            ]
+        );
+
+        for class in &classes {
+            assert_eq!(class.node_kind.as_deref(), Some("view!"));
+            assert_eq!(class.context.metadata["framework"], "leptos");
+        }
+        assert_eq!(
+            &content[classes[1].byte_range.clone()],
+            "hover:(bg-blue-600 text-white)"
+        );
+        assert_eq!(&content[classes[4].byte_range.clone()], "text-red-500");
+        assert_eq!(classes[4].context.metadata["attribute"], "class:");
+        assert_eq!(classes[3].context.metadata["conditional"], "true");
+        assert!(!classes[0].context.metadata.contains_key("conditional"));
+        assert_eq!((classes[6].line, classes[6].column), (13, 17));
+    }
+
+    #[test]
+    fn test_leptos_reactive_class_expressions() {
+        let content = r#"
+#[component]
+fn Tab(active: ReadSignal<bool>) -> impl IntoView {
+    view! {
+        <button
+            class=move || if active.get() { "bg-blue-500 text-white" } else { "bg-gray-100" }
+            aria-label="Tab"
+            on:click=move |_| set_active.set(true)
+        >
+            <span class=move || format!("px-2 {}", size.get()) />
+            <span class=move || match count.get() { 0 => "hidden", _ => "inline" }>"Hi"</span>
+        </button>
+    }
+}
+"#;
+        let classes = extract(content);
+        assert_eq!(
+            names(&classes),
+            vec![
+                "bg-blue-500",
+                "text-white",
+                "bg-gray-100",
+                "px-2",
+                "hidden",
+                "inline"
+            ]
+        );
+        for class in &classes {
+            assert_eq!(class.context.metadata["attribute"], "class");
+            assert_eq!(class.context.metadata["conditional"], "true");
+        }
+    }
+
+    #[test]
+    fn test_dioxus_rsx_classes() {
+        let content = r#"
+fn App(cx: Scope) -> Element {
+    let size = "text-lg";
+    rsx! {
+        div {
+            class: "flex {size} items-center",
+            button { class: if active { "bg-green-500" } else { "bg-gray-200" }, "Save" }
+        }
+    }
+}
+"#;
+        let classes = extract(content);
+        assert_eq!(
+            names(&classes),
+            vec![
+                "text-lg",
+                "flex",
+                "items-center",
+                "bg-green-500",
+                "bg-gray-200"
+            ]
+        );
+        assert_eq!(classes[0].node_kind.as_deref(), Some("LitStr"));
+        assert_eq!(classes[2].context.metadata["framework"], "dioxus");
+        assert_eq!(classes[3].context.metadata["conditional"], "true");
+    }
+
+    #[test]
+    fn test_yew_html_and_classes() {
+        let content = r#"
+fn view(&self) -> Html {
+    let extra = classes!("shadow", self.open.then_some("block"));
+    html! {
+        <div class={classes!("p-2", extra)}>
+            <span class={format!("text-{} font-bold", self.color)}>{ "Hi" }</span>
+        </div>
+    }
+}
+"#;
+        let classes = extract(content);
+        assert_eq!(names(&classes), vec!["shadow", "block", "p-2", "font-bold"]);
+        assert_eq!(classes[0].node_kind.as_deref(), Some("classes!"));
+        assert_eq!(classes[3].node_kind.as_deref(), Some("format!"));
+    }
+
+    #[test]
+    fn test_plain_literals_and_escapes() {
+        let content = "const BUTTON: &str = \"px-4\\tpy-2\";\nfn f() { println!(\"p-4\"); let s = \"Some text\"; }";
+        let classes = extract(content);
+        assert_eq!(names(&classes), vec!["px-4", "py-2"]);
+        // Escapes keep words from being located inside the literal
+        assert_eq!(&content[classes[0].byte_range.clone()], "\"px-4\\tpy-2\"");
+    }
+
+    #[test]
+    fn test_classes_record_enclosing_item() {
+        let content = r#"
+const BASE: &str = "rounded-md";
+
+#[component]
+fn Card() -> impl IntoView {
+    view! { <div class="p-4">"Hi"</div> }
+}
+
+impl Button {
+    fn render(&self) -> Html {
+        let helper = || "m-2";
+        html! { <button class="px-4">{ "Go" }</button> }
+    }
+}
+"#;
+        let items: Vec<_> = extract(content)
+            .into_iter()
+            .map(|class| (class.class_name, class.item))
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                ("rounded-md".to_string(), None),
+                ("p-4".to_string(), Some("Card".to_string())),
+                ("m-2".to_string(), Some("Button::render".to_string())),
+                ("px-4".to_string(), Some("Button::render".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dynamic_sites() {
+        let content = r#"
+fn badge(color: &str, size: &str) -> String {
+    let total = 1 + 2;
+    let label = format!("{} items", total);
+    let text = "text-".to_string() + size + " font-bold";
+    format!("bg-{color}-500 {{x}}")
+}
+
+fn Card() -> Element {
+    rsx! { div { class: "p-{padding} flex" } }
+}
+"#;
+        let sites = RustMacroExtractor::new().dynamic_sites(content).unwrap();
+        let templates: Vec<_> = sites
+            .iter()
+            .map(|site| (site.template.as_str(), site.item.as_deref()))
+            .collect();
+        assert_eq!(
+            templates,
+            vec![
+                ("{} items", Some("badge")),
+                ("text-{} font-bold", Some("badge")),
+                ("bg-{}-500 {x}", Some("badge")),
+                ("p-{} flex", Some("Card")),
+            ]
+        );
+        assert_eq!(&content[sites[2].range.clone()], "\"bg-{color}-500 {{x}}\"");
+    }
+
+    #[test]
+    fn test_unparsable_file_is_an_error() {
+        let result = RustMacroExtractor::new().extract_classes("fn broken( {");
+        assert!(matches!(result, Err(ScannerError::ParseError(_))));
+    }
+}
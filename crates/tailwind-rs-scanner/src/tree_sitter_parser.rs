@@ -2,8 +2,28 @@
 //!
 //! This module provides tree-sitter integration for
 //! accurate AST-based parsing and class extraction.
-
+//!
+//! With the `tree-sitter` feature the Rust, HTML, JavaScript, TypeScript and
+//! TSX grammars are compiled in. Vue and Svelte components are parsed with
+//! the HTML grammar; their `<script>` blocks and bound class expressions
+//! (`:class="…"`, `class={…}`) are parsed again as JavaScript or TypeScript.
+//!
+//! Class candidates come from string and template literals, JSX `className`
+//! and `class` attribute values, and Svelte `class:` directives. Literals
+//! outside a class attribute only count when every word in them looks like a
+//! class, so prose and format strings are skipped. Template literals with
+//! substitutions and `+` concatenations of strings are recorded as dynamic
+//! sites. Without the feature no language is supported and parsing fails
+//! with [`ScannerError::TreeSitterError`].
+
+use crate::class_extractor::{
+    class_tokens, looks_like_class, ClassContext, ExtractedClass, LineIndex,
+};
+use crate::dynamic_classes::DynamicSite;
 use crate::error::{Result, ScannerError};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::Path;
 
 /// Tree-sitter parser for different languages
 #[derive(Debug)]
@@ -26,7 +46,7 @@ pub struct LanguageSupport {
 /// Parse result
 #[derive(Debug, Clone)]
 pub struct ParseResult {
-    /// Parsed AST
+    /// Parsed AST, as an S-expression
     pub ast: String,
     /// Parse errors
     pub errors: Vec<String>,
@@ -34,49 +54,46 @@ pub struct ParseResult {
     pub language: String,
 }
 
+/// A piece of source text that may hold classes
+#[derive(Debug, Clone, PartialEq)]
+struct ClassSpan {
+    /// Byte range of the text in the file
+    range: Range<usize>,
+    /// Kind of the node holding the text
+    node_kind: String,
+    /// Class attribute the text is the value of, if any
+    attribute: Option<String>,
+    /// Function, method or component holding the text, if any
+    item: Option<String>,
+}
+
 impl TreeSitterParser {
     /// Create a new tree-sitter parser
     pub fn new() -> Self {
-        let mut languages = Vec::new();
-
-        // Initialize supported languages
-        languages.push(LanguageSupport {
-            name: "rust".to_string(),
-            extensions: vec!["rs".to_string()],
-            supported: true,
-        });
-
-        languages.push(LanguageSupport {
-            name: "javascript".to_string(),
-            extensions: vec!["js".to_string(), "jsx".to_string()],
-            supported: true,
-        });
-
-        languages.push(LanguageSupport {
-            name: "typescript".to_string(),
-            extensions: vec!["ts".to_string(), "tsx".to_string()],
-            supported: true,
-        });
-
-        languages.push(LanguageSupport {
-            name: "html".to_string(),
-            extensions: vec!["html".to_string(), "htm".to_string()],
-            supported: true,
-        });
+        let languages = [
+            ("rust", &["rs"][..]),
+            ("javascript", &["js", "jsx", "mjs", "cjs"][..]),
+            ("typescript", &["ts", "mts", "cts"][..]),
+            ("tsx", &["tsx"][..]),
+            ("html", &["html", "htm"][..]),
+            ("vue", &["vue"][..]),
+            ("svelte", &["svelte"][..]),
+        ]
+        .into_iter()
+        .map(|(name, extensions)| LanguageSupport {
+            name: name.to_string(),
+            extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+            supported: cfg!(feature = "tree-sitter"),
+        })
+        .collect();
 
         Self { languages }
     }
 
     /// Parse content with tree-sitter
     pub fn parse(&self, content: &str, language: &str) -> Result<ParseResult> {
-        if !self.is_language_supported(language) {
-            return Err(ScannerError::UnsupportedLanguage(language.to_string()));
-        }
-
-        // For now, implement a basic parser that extracts class names
-        // In a full implementation, this would use the tree-sitter crate
-        let errors = Vec::new();
-        let ast = self.generate_ast(content, language);
+        self.check_supported(language)?;
+        let (ast, errors) = syntax::parse(content, language)?;
 
         Ok(ParseResult {
             ast,
@@ -85,141 +102,633 @@ impl TreeSitterParser {
         })
     }
 
-    /// Generate a basic AST representation
-    fn generate_ast(&self, content: &str, language: &str) -> String {
-        match language {
-            "html" => self.parse_html_ast(content),
-            "javascript" | "js" => self.parse_js_ast(content),
-            "typescript" | "ts" => self.parse_ts_ast(content),
-            "rust" => self.parse_rust_ast(content),
-            _ => format!("Basic AST for {}: {}", language, content),
+    /// Extract class candidates from content with tree-sitter
+    pub fn extract_classes(&self, content: &str, language: &str) -> Result<Vec<ExtractedClass>> {
+        self.check_supported(language)?;
+        let spans = syntax::class_spans(content, language)?;
+        let lines = LineIndex::new(content);
+
+        let mut classes = Vec::new();
+        for span in spans {
+            let text = &content[span.range.clone()];
+            let tokens = class_tokens(text);
+            if span.attribute.is_none()
+                && !tokens
+                    .iter()
+                    .all(|(range, _)| looks_like_class(&text[range.clone()]))
+            {
+                continue;
+            }
+
+            for (range, class_names) in tokens {
+                let start = span.range.start + range.start;
+                let (line, column) = lines.position(start);
+
+                let mut metadata = HashMap::new();
+                if let Some(attribute) = &span.attribute {
+                    metadata.insert("attribute".to_string(), attribute.clone());
+                }
+                for class_name in class_names {
+                    classes.push(ExtractedClass {
+                        class_name,
+                        context: ClassContext {
+                            code_context: lines.line_text(start).to_string(),
+                            file_type: language.to_string(),
+                            in_string: true,
+                            in_comment: false,
+                            metadata: metadata.clone(),
+                        },
+                        line,
+                        column,
+                        byte_range: start..span.range.start + range.end,
+                        node_kind: Some(span.node_kind.clone()),
+                        item: span.item.clone(),
+                    });
+                }
+            }
         }
+
+        Ok(classes)
+    }
+
+    /// Strings built from literal text and runtime values, such as template
+    /// literals with substitutions
+    pub(crate) fn dynamic_sites(&self, content: &str, language: &str) -> Result<Vec<DynamicSite>> {
+        self.check_supported(language)?;
+        syntax::dynamic_sites(content, language)
     }
 
-    /// Parse HTML and extract class attributes
-    fn parse_html_ast(&self, content: &str) -> String {
-        let mut ast_nodes = Vec::new();
-        let mut in_class_attr = false;
-        let mut current_class = String::new();
+    /// Get the language to parse a file with, from its extension
+    pub fn language_for_path(&self, path: &Path) -> Option<&str> {
+        let extension = path.extension()?.to_str()?;
+        self.languages
+            .iter()
+            .find(|lang| lang.extensions.iter().any(|ext| ext == extension))
+            .map(|lang| lang.name.as_str())
+    }
 
-        for (i, c) in content.char_indices() {
-            if content[i..].starts_with("class=") {
-                in_class_attr = true;
-                continue;
+    /// Get supported languages
+    pub fn get_supported_languages(&self) -> &[LanguageSupport] {
+        &self.languages
+    }
+
+    /// Check if language is supported
+    pub fn is_language_supported(&self, language: &str) -> bool {
+        self.languages
+            .iter()
+            .any(|lang| lang.name == language && lang.supported)
+    }
+
+    fn check_supported(&self, language: &str) -> Result<()> {
+        if self.is_language_supported(language) {
+            return Ok(());
+        }
+        if self.languages.iter().any(|lang| lang.name == language) {
+            return Err(ScannerError::TreeSitterError(format!(
+                "{} parsing requires the `tree-sitter` feature",
+                language
+            )));
+        }
+        Err(ScannerError::UnsupportedLanguage(language.to_string()))
+    }
+}
+
+impl Default for TreeSitterParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "tree-sitter")]
+mod syntax {
+    use super::ClassSpan;
+    use crate::dynamic_classes::{DynamicSite, HOLE};
+    use crate::error::{Result, ScannerError};
+    use std::ops::Range;
+    use tree_sitter::{Language, Node, Parser, Tree};
+
+    /// Attribute names whose value is a class list
+    const CLASS_ATTRIBUTES: &[&str] = &["class", "className"];
+
+    /// Attribute names whose value is a JavaScript expression producing classes
+    const BOUND_CLASS_ATTRIBUTES: &[&str] = &[":class", "v-bind:class", ":className"];
+
+    fn grammar(language: &str) -> Option<Language> {
+        Some(match language {
+            "rust" => tree_sitter_rust::LANGUAGE.into(),
+            "javascript" => tree_sitter_javascript::LANGUAGE.into(),
+            "typescript" => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            "tsx" => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            "html" | "vue" | "svelte" => tree_sitter_html::LANGUAGE.into(),
+            _ => return None,
+        })
+    }
+
+    fn parse_tree(content: &str, language: &str) -> Result<Tree> {
+        let grammar = grammar(language)
+            .ok_or_else(|| ScannerError::UnsupportedLanguage(language.to_string()))?;
+        let mut parser = Parser::new();
+        parser
+            .set_language(&grammar)
+            .map_err(|e| ScannerError::TreeSitterError(e.to_string()))?;
+        parser
+            .parse(content, None)
+            .ok_or_else(|| ScannerError::TreeSitterError(format!("failed to parse {}", language)))
+    }
+
+    /// Parse content into an S-expression and the positions of syntax errors
+    pub(super) fn parse(content: &str, language: &str) -> Result<(String, Vec<String>)> {
+        let tree = parse_tree(content, language)?;
+        let mut errors = Vec::new();
+        visit(tree.root_node(), &mut |node| {
+            if node.is_error() || node.is_missing() {
+                let position = node.start_position();
+                errors.push(format!(
+                    "syntax error at {}:{}",
+                    position.row + 1,
+                    position.column + 1
+                ));
             }
+            true
+        });
+        Ok((tree.root_node().to_sexp(), errors))
+    }
+
+    /// Find the text spans that may hold classes, in file order
+    pub(super) fn class_spans(content: &str, language: &str) -> Result<Vec<ClassSpan>> {
+        let mut spans = Collector::new(content, 0, language, None).collect()?.spans;
+        spans.sort_by_key(|span| span.range.start);
+        Ok(spans)
+    }
 
-            if in_class_attr {
-                if c == '"' || c == '\'' {
-                    if !current_class.is_empty() {
-                        ast_nodes.push(format!("class: {}", current_class));
-                        current_class.clear();
+    /// Find the strings built at runtime, in file order
+    pub(super) fn dynamic_sites(content: &str, language: &str) -> Result<Vec<DynamicSite>> {
+        let mut dynamic = Collector::new(content, 0, language, None)
+            .collect()?
+            .dynamic;
+        dynamic.sort_by_key(|site| site.range.start);
+        Ok(dynamic)
+    }
+
+    /// Depth-first walk; `f` returns whether to descend into a node
+    fn visit(node: Node, f: &mut impl FnMut(Node) -> bool) {
+        if f(node) {
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                visit(child, f);
+            }
+        }
+    }
+
+    /// The first child of a node with the given kind
+    fn child_of_kind<'tree>(node: Node<'tree>, kind: &str) -> Option<Node<'tree>> {
+        let mut cursor = node.walk();
+        let child = node
+            .children(&mut cursor)
+            .find(|child| child.kind() == kind);
+        child
+    }
+
+    /// Source embedded in a markup file, parsed with another grammar
+    struct Embedded {
+        source: String,
+        /// Byte offset of the source in the file
+        offset: usize,
+        language: &'static str,
+        /// Class attribute the source is the value of, if any
+        attribute: Option<String>,
+    }
+
+    /// Collects class spans of a source, which may be embedded in a file at
+    /// `offset`
+    struct Collector<'a> {
+        source: &'a str,
+        offset: usize,
+        language: &'a str,
+        /// Class attribute the whole source is the value of, if any
+        attribute: Option<String>,
+        spans: Vec<ClassSpan>,
+        /// Template literals and concatenations
+        dynamic: Vec<DynamicSite>,
+    }
+
+    impl<'a> Collector<'a> {
+        fn new(
+            source: &'a str,
+            offset: usize,
+            language: &'a str,
+            attribute: Option<String>,
+        ) -> Self {
+            Self {
+                source,
+                offset,
+                language,
+                attribute,
+                spans: Vec::new(),
+                dynamic: Vec::new(),
+            }
+        }
+
+        fn collect(mut self) -> Result<Self> {
+            let tree = parse_tree(self.source, self.language)?;
+            let root = tree.root_node();
+            match self.language {
+                "rust" => visit(root, &mut |node| self.visit_rust(node)),
+                "html" | "vue" | "svelte" => {
+                    let mut embedded = Vec::new();
+                    visit(root, &mut |node| self.visit_markup(node, &mut embedded));
+                    for source in embedded {
+                        self.collect_embedded(source);
                     }
-                    in_class_attr = false;
-                } else if c != ' ' {
-                    current_class.push(c);
                 }
+                _ => visit(root, &mut |node| self.visit_script(node)),
             }
+            Ok(self)
         }
 
-        if ast_nodes.is_empty() {
-            "HTML AST: No class attributes found".to_string()
-        } else {
-            format!("HTML AST: {}", ast_nodes.join(", "))
+        /// Collect the spans of embedded source, skipping it if it can't be
+        /// parsed
+        fn collect_embedded(&mut self, embedded: Embedded) {
+            let collector = Collector::new(
+                &embedded.source,
+                embedded.offset,
+                embedded.language,
+                embedded.attribute,
+            );
+            if let Ok(mut collector) = collector.collect() {
+                self.spans.append(&mut collector.spans);
+                self.dynamic.append(&mut collector.dynamic);
+            }
+        }
+
+        fn text(&self, node: Node) -> &'a str {
+            &self.source[node.byte_range()]
+        }
+
+        fn push(&mut self, node: Node, node_kind: &str, attribute: Option<String>) {
+            let range = node.byte_range();
+            let item = self.enclosing_item(node);
+            self.push_span(
+                self.offset + range.start..self.offset + range.end,
+                node_kind,
+                attribute,
+                item,
+            );
+        }
+
+        fn push_range(&mut self, range: Range<usize>, node_kind: &str, attribute: Option<String>) {
+            self.push_span(range, node_kind, attribute, None);
+        }
+
+        fn push_span(
+            &mut self,
+            range: Range<usize>,
+            node_kind: &str,
+            attribute: Option<String>,
+            item: Option<String>,
+        ) {
+            if !range.is_empty() {
+                self.spans.push(ClassSpan {
+                    range,
+                    node_kind: node_kind.to_string(),
+                    attribute,
+                    item,
+                });
+            }
         }
-    }
 
-    /// Parse JavaScript and extract string literals that might contain classes
-    fn parse_js_ast(&self, content: &str) -> String {
-        let mut ast_nodes = Vec::new();
-        let mut in_string = false;
-        let mut current_string = String::new();
-        let mut string_delimiter = '"';
+        /// Name of the innermost function, method or component holding a
+        /// node; Rust methods and class methods are prefixed with their type
+        fn enclosing_item(&self, node: Node) -> Option<String> {
+            let mut current = node.parent();
+            while let Some(candidate) = current {
+                current = candidate.parent();
+                let name = match candidate.kind() {
+                    "function_item"
+                    | "function_declaration"
+                    | "generator_function_declaration"
+                    | "method_definition" => candidate.child_by_field_name("name"),
+                    // `const Card = () => …` components
+                    "variable_declarator" => candidate
+                        .child_by_field_name("value")
+                        .filter(|value| {
+                            matches!(
+                                value.kind(),
+                                "arrow_function" | "function_expression" | "function"
+                            )
+                        })
+                        .and(candidate.child_by_field_name("name")),
+                    _ => None,
+                };
+                let Some(name) = name else {
+                    continue;
+                };
+                let owner = self.item_owner(candidate);
+                let name = self.text(name);
+                return Some(match owner {
+                    Some(owner) => format!("{}::{}", owner, name),
+                    None => name.to_string(),
+                });
+            }
+            None
+        }
 
-        for c in content.chars() {
-            if !in_string && (c == '"' || c == '\'') {
-                in_string = true;
-                string_delimiter = c;
-                current_string.clear();
-            } else if in_string {
-                if c == string_delimiter {
-                    // Check if this string contains potential Tailwind classes
-                    if self.looks_like_tailwind_classes(&current_string) {
-                        ast_nodes.push(format!("string: {}", current_string));
+        /// The `impl`, trait or class a method is declared in
+        fn item_owner(&self, item: Node) -> Option<&'a str> {
+            let mut current = item.parent();
+            while let Some(node) = current {
+                let owner = match node.kind() {
+                    "impl_item" => node.child_by_field_name("type"),
+                    "trait_item" | "class_declaration" | "class" => {
+                        node.child_by_field_name("name")
                     }
-                    in_string = false;
-                } else {
-                    current_string.push(c);
+                    "declaration_list" | "class_body" => None,
+                    _ => return None,
+                };
+                if owner.is_some() {
+                    return owner.map(|owner| self.text(owner));
                 }
+                current = node.parent();
             }
+            None
         }
 
-        if ast_nodes.is_empty() {
-            "JS AST: No class strings found".to_string()
-        } else {
-            format!("JS AST: {}", ast_nodes.join(", "))
+        /// String literals, including those inside macro token trees
+        fn visit_rust(&mut self, node: Node) -> bool {
+            if !matches!(node.kind(), "string_literal" | "raw_string_literal") {
+                return true;
+            }
+            let attribute = self.rust_class_attribute(node);
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                if child.kind() == "string_content" {
+                    self.push(child, node.kind(), attribute.clone());
+                }
+            }
+            false
         }
-    }
 
-    /// Parse TypeScript (similar to JavaScript)
-    fn parse_ts_ast(&self, content: &str) -> String {
-        // TypeScript parsing is similar to JavaScript
-        self.parse_js_ast(content)
-    }
+        /// `class="…"` in `view!`/`html!` or `class: "…"` in `rsx!`
+        fn rust_class_attribute(&self, literal: Node) -> Option<String> {
+            let separator = literal.prev_sibling()?;
+            let name = separator.prev_sibling()?;
+            (matches!(separator.kind(), "=" | ":")
+                && name.kind() == "identifier"
+                && self.text(name) == "class")
+                .then(|| "class".to_string())
+        }
+
+        /// String and template literals
+        fn visit_script(&mut self, node: Node) -> bool {
+            if matches!(node.kind(), "template_string" | "binary_expression")
+                && !self.is_operand(node)
+            {
+                self.push_dynamic(node);
+            }
+            if !matches!(node.kind(), "string" | "template_string") {
+                return true;
+            }
+            let attribute = self
+                .jsx_class_attribute(node)
+                .or_else(|| self.attribute.clone());
+            let mut cursor = node.walk();
+            for child in node.children(&mut cursor) {
+                if child.kind() == "string_fragment" {
+                    self.push(child, node.kind(), attribute.clone());
+                }
+            }
+            // Template substitutions may hold more literals
+            node.kind() == "template_string"
+        }
 
-    /// Parse Rust and extract string literals
-    fn parse_rust_ast(&self, content: &str) -> String {
-        let mut ast_nodes = Vec::new();
-        let mut in_string = false;
-        let mut current_string = String::new();
-        let mut string_delimiter = '"';
+        /// Record a template literal or concatenation that interpolates values
+        fn push_dynamic(&mut self, node: Node) {
+            let template = self.template(node);
+            if template.contains(HOLE) && !template.replace(HOLE, "").is_empty() {
+                let range = node.byte_range();
+                self.dynamic.push(DynamicSite {
+                    range: self.offset + range.start..self.offset + range.end,
+                    template,
+                    item: self.enclosing_item(node),
+                });
+            }
+        }
 
-        for c in content.chars() {
-            if !in_string && c == '"' {
-                in_string = true;
-                current_string.clear();
-            } else if in_string {
-                if c == string_delimiter {
-                    // Check if this string contains potential Tailwind classes
-                    if self.looks_like_tailwind_classes(&current_string) {
-                        ast_nodes.push(format!("string: {}", current_string));
+        /// Whether a node is part of a larger concatenation, which is
+        /// recorded as a whole
+        fn is_operand(&self, node: Node) -> bool {
+            let mut parent = node.parent();
+            while let Some(node) = parent.filter(|node| node.kind() == "parenthesized_expression") {
+                parent = node.parent();
+            }
+            parent.is_some_and(|parent| self.is_concatenation(parent))
+        }
+
+        fn is_concatenation(&self, node: Node) -> bool {
+            node.kind() == "binary_expression"
+                && node
+                    .child_by_field_name("operator")
+                    .is_some_and(|operator| self.text(operator) == "+")
+        }
+
+        /// Literal text of an expression, with every value computed at
+        /// runtime as a hole
+        fn template(&self, node: Node) -> String {
+            match node.kind() {
+                "string" | "template_string" => {
+                    let mut cursor = node.walk();
+                    let template = node
+                        .children(&mut cursor)
+                        .filter_map(|child| match child.kind() {
+                            "string_fragment" | "escape_sequence" => Some(self.text(child)),
+                            "template_substitution" => Some(HOLE),
+                            _ => None,
+                        })
+                        .collect();
+                    template
+                }
+                "binary_expression" if self.is_concatenation(node) => {
+                    let (Some(left), Some(right)) = (
+                        node.child_by_field_name("left"),
+                        node.child_by_field_name("right"),
+                    ) else {
+                        return HOLE.to_string();
+                    };
+                    self.template(left) + &self.template(right)
+                }
+                "parenthesized_expression" => node
+                    .named_child(0)
+                    .map_or_else(|| HOLE.to_string(), |inner| self.template(inner)),
+                _ => HOLE.to_string(),
+            }
+        }
+
+        /// The JSX class attribute a literal is part of, if any
+        fn jsx_class_attribute(&self, literal: Node) -> Option<String> {
+            let mut node = literal.parent()?;
+            loop {
+                match node.kind() {
+                    "jsx_attribute" => {
+                        let name = self.text(node.child(0)?);
+                        return CLASS_ATTRIBUTES.contains(&name).then(|| name.to_string());
                     }
-                    in_string = false;
+                    "jsx_opening_element"
+                    | "jsx_self_closing_element"
+                    | "statement_block"
+                    | "program" => return None,
+                    _ => node = node.parent()?,
+                }
+            }
+        }
+
+        /// Class attributes, Svelte `class:` directives and script blocks
+        fn visit_markup(&mut self, node: Node, embedded: &mut Vec<Embedded>) -> bool {
+            match node.kind() {
+                "attribute" => {
+                    self.visit_markup_attribute(node, embedded);
+                    false
+                }
+                "script_element" => {
+                    if let Some(script) = child_of_kind(node, "raw_text") {
+                        embedded.push(Embedded {
+                            source: self.text(script).to_string(),
+                            offset: self.offset + script.start_byte(),
+                            language: self.script_language(node),
+                            attribute: None,
+                        });
+                    }
+                    false
+                }
+                "style_element" => false,
+                _ => true,
+            }
+        }
+
+        fn visit_markup_attribute(&mut self, node: Node, embedded: &mut Vec<Embedded>) {
+            let Some(name) = child_of_kind(node, "attribute_name") else {
+                return;
+            };
+            let name_text = self.text(name);
+            if let Some(directive) = name_text.strip_prefix("class:") {
+                // Svelte `class:name={condition}` toggles the class `name`
+                let start = self.offset + name.start_byte() + "class:".len();
+                self.push_range(
+                    start..start + directive.len(),
+                    name.kind(),
+                    Some("class:".to_string()),
+                );
+                return;
+            }
+
+            let Some(value) = child_of_kind(node, "attribute_value").or_else(|| {
+                child_of_kind(node, "quoted_attribute_value")
+                    .and_then(|quoted| child_of_kind(quoted, "attribute_value"))
+            }) else {
+                return;
+            };
+            if BOUND_CLASS_ATTRIBUTES.contains(&name_text) {
+                // Parenthesized so an object literal parses as an expression
+                embedded.push(Embedded {
+                    source: format!("({})", self.text(value)),
+                    offset: self.offset + value.start_byte() - 1,
+                    language: "javascript",
+                    attribute: Some(name_text.to_string()),
+                });
+            } else if CLASS_ATTRIBUTES.contains(&name_text) {
+                if self.language == "svelte" {
+                    self.push_svelte_class_value(value, name_text, embedded);
                 } else {
-                    current_string.push(c);
+                    self.push(value, value.kind(), Some(name_text.to_string()));
+                }
+            }
+        }
+
+        /// Split a Svelte class value into static text and `{expression}`s
+        fn push_svelte_class_value(
+            &mut self,
+            value: Node,
+            attribute: &str,
+            embedded: &mut Vec<Embedded>,
+        ) {
+            let text = self.text(value);
+            let base = self.offset + value.start_byte();
+            let attribute = Some(attribute.to_string());
+            let mut depth = 0usize;
+            let mut start = 0;
+            for (i, c) in text.char_indices() {
+                match c {
+                    '{' if depth == 0 => {
+                        self.push_range(base + start..base + i, value.kind(), attribute.clone());
+                        depth = 1;
+                        start = i + 1;
+                    }
+                    '{' => depth += 1,
+                    '}' if depth == 1 => {
+                        embedded.push(Embedded {
+                            source: text[start..i].to_string(),
+                            offset: base + start,
+                            language: "javascript",
+                            attribute: attribute.clone(),
+                        });
+                        depth = 0;
+                        start = i + 1;
+                    }
+                    '}' if depth > 1 => depth -= 1,
+                    _ => {}
                 }
             }
+            if depth == 0 {
+                self.push_range(base + start..base + text.len(), value.kind(), attribute);
+            }
         }
 
-        if ast_nodes.is_empty() {
-            "Rust AST: No class strings found".to_string()
-        } else {
-            format!("Rust AST: {}", ast_nodes.join(", "))
+        /// `<script lang="ts">` is TypeScript; other scripts are JavaScript
+        fn script_language(&self, script: Node) -> &'static str {
+            let is_typescript = child_of_kind(script, "start_tag").is_some_and(|tag| {
+                let mut cursor = tag.walk();
+                let typescript = tag.children(&mut cursor).any(|attribute| {
+                    attribute.kind() == "attribute"
+                        && child_of_kind(attribute, "attribute_name")
+                            .is_some_and(|name| self.text(name) == "lang")
+                        && matches!(
+                            self.text(attribute).rsplit('=').next(),
+                            Some("\"ts\"" | "'ts'" | "ts")
+                        )
+                });
+                typescript
+            });
+            if is_typescript {
+                "typescript"
+            } else {
+                "javascript"
+            }
         }
     }
+}
 
-    /// Check if a string looks like it contains Tailwind classes
-    fn looks_like_tailwind_classes(&self, s: &str) -> bool {
-        // Simple heuristic: check for common Tailwind patterns
-        s.contains("bg-")
-            || s.contains("text-")
-            || s.contains("p-")
-            || s.contains("m-")
-            || s.contains("w-")
-            || s.contains("h-")
-            || s.contains("flex")
-            || s.contains("grid")
-            || s.contains("hidden")
-            || s.contains("block")
+#[cfg(not(feature = "tree-sitter"))]
+mod syntax {
+    use super::ClassSpan;
+    use crate::dynamic_classes::DynamicSite;
+    use crate::error::{Result, ScannerError};
+
+    fn unavailable<T>(language: &str) -> Result<T> {
+        Err(ScannerError::TreeSitterError(format!(
+            "{} parsing requires the `tree-sitter` feature",
+            language
+        )))
     }
 
-    /// Get supported languages
-    pub fn get_supported_languages(&self) -> &[LanguageSupport] {
-        &self.languages
+    pub(super) fn parse(_content: &str, language: &str) -> Result<(String, Vec<String>)> {
+        unavailable(language)
     }
 
-    /// Check if language is supported
-    pub fn is_language_supported(&self, language: &str) -> bool {
-        self.languages.iter().any(|lang| lang.name == language)
+    pub(super) fn class_spans(_content: &str, language: &str) -> Result<Vec<ClassSpan>> {
+        unavailable(language)
+    }
+
+    pub(super) fn dynamic_sites(_content: &str, language: &str) -> Result<Vec<DynamicSite>> {
+        unavailable(language)
     }
 }
 
@@ -236,9 +745,15 @@ mod tests {
     #[test]
     fn test_language_support() {
         let parser = TreeSitterParser::new();
-        assert!(parser.is_language_supported("rust"));
-        assert!(parser.is_language_supported("javascript"));
+        let enabled = cfg!(feature = "tree-sitter");
+        assert_eq!(parser.is_language_supported("rust"), enabled);
+        assert_eq!(parser.is_language_supported("javascript"), enabled);
+        assert_eq!(parser.is_language_supported("svelte"), enabled);
         assert!(!parser.is_language_supported("unknown"));
+        assert_eq!(
+            parser.language_for_path(Path::new("src/App.tsx")),
+            Some("tsx")
+        );
     }
 
     #[test]
@@ -247,9 +762,150 @@ mod tests {
         let content = "let class = 'p-4';";
         let result = parser.parse(content, "javascript");
 
+        if !cfg!(feature = "tree-sitter") {
+            assert!(matches!(result, Err(ScannerError::TreeSitterError(_))));
+            return;
+        }
         assert!(result.is_ok());
         let result = result.unwrap();
         assert_eq!(result.language, "javascript");
         assert!(result.errors.is_empty());
+        assert!(result.ast.starts_with("(program"));
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    fn extract(content: &str, language: &str) -> Vec<(String, String, String)> {
+        TreeSitterParser::new()
+            .extract_classes(content, language)
+            .unwrap()
+            .into_iter()
+            .map(|class| {
+                (
+                    class.class_name,
+                    content[class.byte_range].to_string(),
+                    class.node_kind.unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    fn names(classes: &[(String, String, String)]) -> Vec<&str> {
+        classes.iter().map(|(name, _, _)| name.as_str()).collect()
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn test_rust_string_literals() {
+        let content = r##"
+            // class="comment-only"
+            fn view() {
+                println!("Hello world");
+                let raw = r#"flex items-center"#;
+                view! { <div class="p-4 hover:(m-2 underline)">"Hi"</div> }
+            }
+        "##;
+        let classes = extract(content, "rust");
+        assert_eq!(
+            names(&classes),
+            vec![
+                "flex",
+                "items-center",
+                "p-4",
+                "hover:m-2",
+                "hover:underline"
+            ]
+        );
+        assert_eq!(classes[0].2, "raw_string_literal");
+        assert_eq!(classes[3].1, "hover:(m-2 underline)");
+        assert_eq!(classes[3].2, "string_literal");
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn test_jsx_and_template_literals() {
+        let content =
+            "const a = <div className={`p-4 ${on ? 'block' : 'hidden'}`}>Hello there</div>;\n\
+                       const b = 'Not classes here';";
+        let classes = extract(content, "tsx");
+        assert_eq!(names(&classes), vec!["p-4", "block", "hidden"]);
+        assert_eq!(classes[0].2, "template_string");
+        assert_eq!(classes[1].2, "string");
+
+        let parsed = TreeSitterParser::new()
+            .extract_classes(content, "tsx")
+            .unwrap();
+        assert_eq!(parsed[0].context.metadata["attribute"], "className");
+        assert_eq!((parsed[0].line, parsed[0].column), (1, 28));
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn test_enclosing_items() {
+        let items = |content: &str, language: &str| -> Vec<Option<String>> {
+            TreeSitterParser::new()
+                .extract_classes(content, language)
+                .unwrap()
+                .into_iter()
+                .map(|class| class.item)
+                .collect()
+        };
+
+        let tsx = "const Card = () => <div className=\"p-4\" />;\n\
+                   class Modal { render() { return 'fixed'; } }\n\
+                   const top = 'block';";
+        assert_eq!(
+            items(tsx, "tsx"),
+            vec![
+                Some("Card".to_string()),
+                Some("Modal::render".to_string()),
+                None
+            ]
+        );
+
+        let rust = "impl Button { fn view(&self) { let c = \"px-4\"; } }";
+        assert_eq!(items(rust, "rust"), vec![Some("Button::view".to_string())]);
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn test_dynamic_sites() {
+        let content = "function Badge({ tone, size }) {\n\
+                       return <span className={`bg-${tone}-500 ` + (\"text-\" + size)} />;\n\
+                       }\n\
+                       const plain = `p-4`;";
+        let sites = TreeSitterParser::new()
+            .dynamic_sites(content, "javascript")
+            .unwrap();
+        let templates: Vec<_> = sites
+            .iter()
+            .map(|site| (site.template.as_str(), site.item.as_deref()))
+            .collect();
+        assert_eq!(templates, vec![("bg-{}-500 text-{}", Some("Badge"))]);
+        assert!(content[sites[0].range.clone()].starts_with("`bg-${tone}"));
+    }
+
+    #[cfg(feature = "tree-sitter")]
+    #[test]
+    fn test_markup_attributes_and_scripts() {
+        let html =
+            r#"<p class="text-sm font-bold">Some prose</p><script>el.className = "mt-2";</script>"#;
+        assert_eq!(
+            names(&extract(html, "html")),
+            vec!["text-sm", "font-bold", "mt-2"]
+        );
+
+        let vue = r#"<template><div class="p-4" :class="{ 'bg-red-500': error }"></div></template>
+<script lang="ts">const size: string = "text-lg";</script>"#;
+        let classes = extract(vue, "vue");
+        assert_eq!(names(&classes), vec!["p-4", "bg-red-500", "text-lg"]);
+        assert_eq!(classes[0].2, "attribute_value");
+        assert_eq!(classes[1].1, "bg-red-500");
+
+        let svelte = r#"<div class="flex {wide ? 'w-full' : 'w-1/2'}" class:active={on}></div>"#;
+        let classes = extract(svelte, "svelte");
+        assert_eq!(names(&classes), vec!["flex", "w-full", "w-1/2", "active"]);
+        assert_eq!(classes[1].1, "w-full");
+        assert_eq!(classes[3].2, "attribute_name");
     }
 }
@@ -0,0 +1,254 @@
+//! Class usage index
+//!
+//! This module indexes where each class is used across scanned files: the
+//! file, line, column and enclosing item of every occurrence, and the
+//! classes of every file. It answers the reverse queries behind find
+//! usages, usage statistics and dead-class reports, and is updated one file
+//! at a time so a watcher can keep it current.
+
+use crate::class_extractor::ExtractedClass;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// One occurrence of a class in a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassUsage {
+    /// Class name
+    pub class_name: String,
+    /// File the class appears in
+    pub path: PathBuf,
+    /// Line number
+    pub line: usize,
+    /// Column number
+    pub column: usize,
+    /// Byte range of the class in the file
+    pub byte_range: Range<usize>,
+    /// Function, method or component the class appears in, when known
+    pub item: Option<String>,
+}
+
+/// Index of class usages by class and by file
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageIndex {
+    /// Extracted classes of each file, in file order
+    files: BTreeMap<PathBuf, Vec<ExtractedClass>>,
+    /// Files using each class
+    classes: HashMap<String, BTreeSet<PathBuf>>,
+}
+
+impl UsageIndex {
+    /// Create an empty usage index
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace the classes recorded for a file
+    pub fn update_file(&mut self, path: PathBuf, mut classes: Vec<ExtractedClass>) {
+        self.remove_file(&path);
+        classes.sort_by_key(|class| (class.line, class.column));
+        for class in &classes {
+            self.classes
+                .entry(class.class_name.clone())
+                .or_default()
+                .insert(path.clone());
+        }
+        self.files.insert(path, classes);
+    }
+
+    /// Forget a file, returning whether it was indexed
+    pub fn remove_file(&mut self, path: &Path) -> bool {
+        let Some(classes) = self.files.remove(path) else {
+            return false;
+        };
+        for class in classes {
+            if let Some(files) = self.classes.get_mut(&class.class_name) {
+                files.remove(path);
+                if files.is_empty() {
+                    self.classes.remove(&class.class_name);
+                }
+            }
+        }
+        true
+    }
+
+    /// Forget every file
+    pub fn clear(&mut self) {
+        self.files.clear();
+        self.classes.clear();
+    }
+
+    /// Every occurrence of a class, ordered by file and position
+    pub fn usages(&self, class: &str) -> Vec<ClassUsage> {
+        self.files_using(class)
+            .into_iter()
+            .flat_map(|path| self.file_usages(path, |name| name == class))
+            .collect()
+    }
+
+    /// Every occurrence of the classes matching a predicate, such as
+    /// deprecated classes, ordered by file and position
+    pub fn find(&self, predicate: impl Fn(&str) -> bool) -> Vec<ClassUsage> {
+        self.files
+            .keys()
+            .flat_map(|path| self.file_usages(path, &predicate))
+            .collect()
+    }
+
+    /// Files using a class, sorted
+    pub fn files_using(&self, class: &str) -> Vec<&Path> {
+        self.classes
+            .get(class)
+            .map(|files| files.iter().map(PathBuf::as_path).collect())
+            .unwrap_or_default()
+    }
+
+    /// Classes extracted from a file, in file order
+    pub fn file_classes(&self, path: &Path) -> Option<&[ExtractedClass]> {
+        self.files.get(path).map(Vec::as_slice)
+    }
+
+    /// Indexed files, sorted
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.files.keys().map(PathBuf::as_path)
+    }
+
+    /// Used classes, sorted
+    pub fn classes(&self) -> Vec<&str> {
+        let mut classes: Vec<&str> = self.classes.keys().map(String::as_str).collect();
+        classes.sort_unstable();
+        classes
+    }
+
+    /// Number of occurrences of a class
+    pub fn usage_count(&self, class: &str) -> usize {
+        self.files_using(class)
+            .into_iter()
+            .flat_map(|path| &self.files[path])
+            .filter(|extracted| extracted.class_name == class)
+            .count()
+    }
+
+    /// Classes that occur exactly once, sorted
+    pub fn used_once(&self) -> Vec<&str> {
+        self.classes()
+            .into_iter()
+            .filter(|class| self.usage_count(class) == 1)
+            .collect()
+    }
+
+    /// Known classes, such as those of a stylesheet, that no file uses
+    pub fn unused<'a>(&self, known: impl IntoIterator<Item = &'a str>) -> Vec<&'a str> {
+        let mut unused: Vec<&str> = known
+            .into_iter()
+            .filter(|class| !self.classes.contains_key(*class))
+            .collect();
+        unused.sort_unstable();
+        unused.dedup();
+        unused
+    }
+
+    /// Number of indexed files
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Whether no file is indexed
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    fn file_usages<'a>(
+        &'a self,
+        path: &'a Path,
+        predicate: impl Fn(&str) -> bool + 'a,
+    ) -> impl Iterator<Item = ClassUsage> + 'a {
+        self.files[path]
+            .iter()
+            .filter(move |class| predicate(&class.class_name))
+            .map(move |class| ClassUsage {
+                class_name: class.class_name.clone(),
+                path: path.to_path_buf(),
+                line: class.line,
+                column: class.column,
+                byte_range: class.byte_range.clone(),
+                item: class.item.clone(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::class_extractor::ClassContext;
+
+    fn class(name: &str, line: usize, item: Option<&str>) -> ExtractedClass {
+        ExtractedClass {
+            class_name: name.to_string(),
+            context: ClassContext::new(),
+            line,
+            column: 1,
+            byte_range: 0..name.len(),
+            node_kind: None,
+            item: item.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_usage_queries() {
+        let mut index = UsageIndex::new();
+        index.update_file(
+            PathBuf::from("src/card.rs"),
+            vec![
+                class("p-4", 3, Some("Card")),
+                class("shadow", 4, Some("Card")),
+            ],
+        );
+        index.update_file(
+            PathBuf::from("src/app.rs"),
+            vec![class("p-4", 7, Some("App")), class("p-4", 2, None)],
+        );
+
+        let usages = index.usages("p-4");
+        let places: Vec<_> = usages
+            .iter()
+            .map(|usage| {
+                (
+                    usage.path.to_str().unwrap(),
+                    usage.line,
+                    usage.item.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            places,
+            vec![
+                ("src/app.rs", 2, None),
+                ("src/app.rs", 7, Some("App")),
+                ("src/card.rs", 3, Some("Card")),
+            ]
+        );
+        assert_eq!(index.usage_count("p-4"), 3);
+        assert_eq!(index.used_once(), vec!["shadow"]);
+        assert_eq!(index.files_using("shadow"), vec![Path::new("src/card.rs")]);
+        assert_eq!(index.find(|class| class == "shadow").len(), 1);
+        assert_eq!(index.unused(["p-4", "m-2", "flex"]), vec!["flex", "m-2"]);
+    }
+
+    #[test]
+    fn test_incremental_updates() {
+        let mut index = UsageIndex::new();
+        let path = PathBuf::from("src/card.rs");
+        index.update_file(path.clone(), vec![class("p-4", 1, None)]);
+        index.update_file(path.clone(), vec![class("m-2", 1, None)]);
+
+        assert_eq!(index.classes(), vec!["m-2"]);
+        assert!(index.usages("p-4").is_empty());
+
+        assert!(index.remove_file(&path));
+        assert!(!index.remove_file(&path));
+        assert!(index.is_empty());
+        assert!(index.classes().is_empty());
+    }
+}
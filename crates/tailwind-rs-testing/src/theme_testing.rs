@@ -297,8 +297,9 @@ mod tests {
 
     #[test]
     fn test_theme_box_shadow_testing() {
-        let theme = tailwind_rs_core::theme::create_default_theme();
+        let mut theme = tailwind_rs_core::theme::create_default_theme();
         let expected_shadow = BoxShadow::new(0.0, 1.0, 2.0, 0.0, Color::hex("#000000"), false);
+        theme.add_box_shadow("sm", expected_shadow.clone());
 
         let result = test_theme_box_shadow(&theme, "sm", &expected_shadow);
         assert!(result.success);
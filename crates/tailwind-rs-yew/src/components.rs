@@ -193,6 +193,9 @@ pub fn CardBody(props: &CardBodyProps) -> Html {
 }
 
 /// Input component with Tailwind styling
+///
+/// The classes only adjust the input's look; enable the `forms` plugin
+/// (`plugins = ["forms"]`) to normalize the underlying element across browsers.
 #[derive(Properties, PartialEq, Clone)]
 pub struct InputProps {
     #[prop_or_default]